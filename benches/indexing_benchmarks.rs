@@ -0,0 +1,132 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use indoc::formatdoc;
+use std::fs;
+use tempfile::tempdir;
+use ummon::parser::pipeline::parse_files;
+
+/// Number of files in the generated codebase
+const FILE_COUNT: usize = 200;
+
+/// Helper function to generate a small codebase mixing all supported languages
+fn create_codebase(dir: &std::path::Path) -> Vec<String> {
+    let mut files = Vec::with_capacity(FILE_COUNT);
+
+    for i in 0..FILE_COUNT {
+        let (file_name, content) = match i % 4 {
+            0 => (
+                format!("module_{}.rs", i),
+                formatdoc! {r#"
+                    /// A point in 2D space
+                    pub struct Point{i} {{
+                        pub x: f64,
+                        pub y: f64,
+                    }}
+
+                    impl Point{i} {{
+                        /// Create a new point
+                        pub fn new(x: f64, y: f64) -> Self {{
+                            Self {{ x, y }}
+                        }}
+
+                        /// Calculate distance between two points
+                        pub fn distance(&self, other: &Point{i}) -> f64 {{
+                            let dx = self.x - other.x;
+                            let dy = self.y - other.y;
+                            (dx * dx + dy * dy).sqrt()
+                        }}
+                    }}
+
+                    pub fn origin_{i}() -> Point{i} {{
+                        Point{i}::new(0.0, 0.0)
+                    }}
+                "#},
+            ),
+            1 => (
+                format!("module_{}.py", i),
+                formatdoc! {r#"
+                    class Point{i}:
+                        """A point in 2D space"""
+
+                        def __init__(self, x, y):
+                            self.x = x
+                            self.y = y
+
+                        def distance(self, other):
+                            """Calculate distance between two points"""
+                            return ((self.x - other.x) ** 2 + (self.y - other.y) ** 2) ** 0.5
+
+                    def origin_{i}():
+                        return Point{i}(0, 0)
+                "#},
+            ),
+            2 => (
+                format!("module_{}.js", i),
+                formatdoc! {r#"
+                    /** A point in 2D space */
+                    class Point{i} {{
+                        constructor(x, y) {{
+                            this.x = x;
+                            this.y = y;
+                        }}
+
+                        distance(other) {{
+                            return Math.sqrt(Math.pow(this.x - other.x, 2) + Math.pow(this.y - other.y, 2));
+                        }}
+                    }}
+
+                    function origin_{i}() {{
+                        return new Point{i}(0, 0);
+                    }}
+                "#},
+            ),
+            _ => (
+                format!("Point{}.java", i),
+                formatdoc! {r#"
+                    /** A point in 2D space */
+                    public class Point{i} {{
+                        private final double x;
+                        private final double y;
+
+                        public Point{i}(double x, double y) {{
+                            this.x = x;
+                            this.y = y;
+                        }}
+
+                        public double distance(Point{i} other) {{
+                            double dx = x - other.x;
+                            double dy = y - other.y;
+                            return Math.sqrt(dx * dx + dy * dy);
+                        }}
+                    }}
+                "#},
+            ),
+        };
+
+        let file_path = dir.join(file_name);
+        fs::write(&file_path, content).expect("Failed to write temp file");
+        files.push(file_path.to_string_lossy().to_string());
+    }
+
+    files
+}
+
+/// Benchmark parsing a whole codebase with different numbers of worker threads
+pub fn bench_parallel_parsing(c: &mut Criterion) {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let files = create_codebase(temp_dir.path());
+
+    let mut group = c.benchmark_group("indexing_benchmarks");
+    group.sample_size(20);
+
+    // 0 lets the pool use one thread per available CPU
+    for jobs in [1, 2, 4, 0] {
+        group.bench_with_input(BenchmarkId::new("parse_files", jobs), &jobs, |b, &jobs| {
+            b.iter(|| parse_files(&files, jobs).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_parsing);
+criterion_main!(benches);
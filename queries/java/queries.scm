@@ -0,0 +1,125 @@
+; Methods and constructors
+
+(method_declaration
+  name: (identifier) @function.name) @function.definition
+
+((constructor_declaration
+  name: (identifier) @function.name) @function.definition
+  (#set! kind "constructor"))
+
+((method_declaration
+  type: (_) @function.return_type) @function.definition
+  (#not-eq? @function.return_type "void"))
+
+[
+  (method_declaration (modifiers) @function.visibility)
+  (constructor_declaration (modifiers) @function.visibility)
+] @function.definition
+
+[
+  (method_declaration
+    parameters: (formal_parameters
+      [
+        (formal_parameter name: (identifier) @function.parameter)
+        (spread_parameter (variable_declarator name: (identifier) @function.parameter))
+      ]))
+  (constructor_declaration
+    parameters: (formal_parameters
+      [
+        (formal_parameter name: (identifier) @function.parameter)
+        (spread_parameter (variable_declarator name: (identifier) @function.parameter))
+      ]))
+] @function.definition
+
+; Types
+
+(class_declaration
+  name: (identifier) @class.name) @class.definition
+
+((interface_declaration
+  name: (identifier) @class.name) @class.definition
+  (#set! kind "interface"))
+
+((enum_declaration
+  name: (identifier) @class.name) @class.definition
+  (#set! kind "enum"))
+
+((record_declaration
+  name: (identifier) @class.name) @class.definition
+  (#set! kind "struct"))
+
+[
+  (class_declaration (modifiers) @class.visibility)
+  (interface_declaration (modifiers) @class.visibility)
+  (enum_declaration (modifiers) @class.visibility)
+  (record_declaration (modifiers) @class.visibility)
+] @class.definition
+
+(class_declaration
+  superclass: (superclass
+    [
+      (type_identifier) @class.super
+      (generic_type (type_identifier) @class.super)
+      (scoped_type_identifier) @class.super
+    ])) @class.definition
+
+[
+  (class_declaration
+    interfaces: (super_interfaces
+      (type_list
+        [
+          (type_identifier) @class.super
+          (generic_type (type_identifier) @class.super)
+          (scoped_type_identifier) @class.super
+        ])))
+  (interface_declaration
+    (extends_interfaces
+      (type_list
+        [
+          (type_identifier) @class.super
+          (generic_type (type_identifier) @class.super)
+          (scoped_type_identifier) @class.super
+        ])))
+] @class.definition
+
+(field_declaration
+  type: (_) @field.type
+  declarator: (variable_declarator
+    name: (identifier) @field.name)) @field.definition
+
+(field_declaration
+  (modifiers) @field.visibility) @field.definition
+
+(enum_constant
+  name: (identifier) @field.name) @field.definition
+
+; Calls
+
+(method_invocation
+  object: (_)? @call.receiver
+  name: (identifier) @call.callee) @call.expression
+
+; Imports
+
+(package_declaration
+  [
+    (identifier)
+    (scoped_identifier)
+  ] @module.name)
+
+((import_declaration
+  (scoped_identifier
+    scope: (_) @import.module
+    name: (identifier) @import.symbol)) @import.definition
+  (#not-match? @import.definition "\\*"))
+
+(import_declaration
+  [
+    (identifier)
+    (scoped_identifier)
+  ] @import.module
+  (asterisk)) @import.definition
+
+; Names read or written, which resolve to fields
+
+(identifier) @reference.name
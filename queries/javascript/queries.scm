@@ -0,0 +1,158 @@
+; Functions and methods
+
+(function_declaration
+  name: (identifier) @function.name) @function.definition
+
+(method_definition
+  name: (property_identifier) @function.name) @function.definition
+
+((method_definition
+  name: (property_identifier) @function.name) @function.definition
+  (#eq? @function.name "constructor")
+  (#set! kind "constructor"))
+
+; Arrow functions are named after the variable they're assigned to
+((variable_declarator
+  name: (identifier) @function.name
+  value: (arrow_function)) @function.definition
+  (#set! kind "lambda"))
+
+(export_statement
+  declaration: (function_declaration) @function.definition) @function.visibility
+
+[
+  (function_declaration
+    parameters: (formal_parameters
+      [
+        (identifier) @function.parameter
+        (assignment_pattern left: (identifier) @function.parameter)
+        (rest_pattern (identifier) @function.parameter)
+      ]))
+  (method_definition
+    parameters: (formal_parameters
+      [
+        (identifier) @function.parameter
+        (assignment_pattern left: (identifier) @function.parameter)
+        (rest_pattern (identifier) @function.parameter)
+      ]))
+] @function.definition
+
+(variable_declarator
+  value: (arrow_function
+    [
+      parameter: (identifier) @function.parameter
+      parameters: (formal_parameters
+        [
+          (identifier) @function.parameter
+          (assignment_pattern left: (identifier) @function.parameter)
+          (rest_pattern (identifier) @function.parameter)
+        ])
+    ])) @function.definition
+
+; Classes
+
+(class_declaration
+  name: (identifier) @class.name) @class.definition
+
+(class_declaration
+  (class_heritage
+    [
+      (identifier) @class.super
+      (member_expression) @class.super
+    ])) @class.definition
+
+(export_statement
+  declaration: (class_declaration) @class.definition) @class.visibility
+
+(class_body
+  (field_definition
+    property: (property_identifier) @field.name) @field.definition)
+
+; Calls
+
+(call_expression
+  function: (member_expression) @call.callee) @call.expression
+
+; `require()` is an import, below
+((call_expression
+  function: (identifier) @call.callee) @call.expression
+  (#not-eq? @call.callee "require"))
+
+; Imports
+
+(import_statement
+  (import_clause
+    [
+      (identifier) @import.symbol
+      (named_imports
+        [
+          (import_specifier alias: (identifier) @import.symbol)
+          (import_specifier name: (identifier) @import.symbol !alias)
+        ])
+      (namespace_import (identifier) @import.symbol)
+    ])
+  source: (string (string_fragment) @import.module)) @import.definition
+
+(import_statement
+  source: (string (string_fragment) @import.module)) @import.definition
+
+((call_expression
+  function: (identifier) @_require
+  arguments: (arguments
+    (string (string_fragment) @import.module))) @import.definition
+  (#eq? @_require "require"))
+
+; Exports
+
+(export_statement
+  declaration: [
+    (function_declaration name: (identifier) @export.name)
+    (class_declaration name: (identifier) @export.name)
+    (lexical_declaration (variable_declarator name: (identifier) @export.name))
+  ])
+
+; Top-level variables, constants when declared with `const`
+
+(program
+  (lexical_declaration
+    kind: _ @_kind
+    (variable_declarator
+      name: (identifier) @variable.name
+      value: (_)? @variable.value) @variable.definition))
+
+((program
+  (lexical_declaration
+    kind: _ @_kind
+    (variable_declarator) @variable.definition))
+  (#eq? @_kind "const")
+  (#set! kind "constant"))
+
+(program
+  (variable_declaration
+    (variable_declarator
+      name: (identifier) @variable.name
+      value: (_)? @variable.value) @variable.definition))
+
+(program
+  (export_statement
+    "export" @variable.visibility
+    declaration: (lexical_declaration
+      kind: _ @_kind
+      (variable_declarator
+        name: (identifier) @variable.name
+        value: (_)? @variable.value) @variable.definition)))
+
+((program
+  (export_statement
+    declaration: (lexical_declaration
+      kind: _ @_kind
+      (variable_declarator) @variable.definition)))
+  (#eq? @_kind "const")
+  (#set! kind "constant"))
+
+; Names read or written
+
+[
+  (identifier)
+  (shorthand_property_identifier)
+] @reference.name
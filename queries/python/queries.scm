@@ -0,0 +1,94 @@
+; Functions and methods
+
+(function_definition
+  name: (identifier) @function.name) @function.definition
+
+(function_definition
+  return_type: (type) @function.return_type) @function.definition
+
+(function_definition
+  parameters: (parameters
+    [
+      (identifier) @function.parameter
+      (typed_parameter (identifier) @function.parameter)
+      (default_parameter name: (identifier) @function.parameter)
+      (typed_default_parameter name: (identifier) @function.parameter)
+      (list_splat_pattern) @function.parameter
+      (dictionary_splat_pattern) @function.parameter
+    ])) @function.definition
+
+; Classes
+
+(class_definition
+  name: (identifier) @class.name) @class.definition
+
+(class_definition
+  superclasses: (argument_list
+    [
+      (identifier) @class.super
+      (attribute) @class.super
+    ])) @class.definition
+
+; Class attributes, assigned directly in the class body
+
+(class_definition
+  body: (block
+    (expression_statement
+      (assignment
+        left: (identifier) @field.name)) @field.definition))
+
+(class_definition
+  body: (block
+    (expression_statement
+      (assignment
+        left: (identifier)
+        type: (type) @field.type)) @field.definition))
+
+; Calls
+
+(call
+  function: [
+    (identifier)
+    (attribute)
+  ] @call.callee) @call.expression
+
+; Imports
+
+(import_statement
+  name: (dotted_name) @import.module @import.definition)
+
+(import_statement
+  name: (aliased_import
+    name: (dotted_name) @import.module
+    alias: (identifier) @import.symbol) @import.definition)
+
+(import_from_statement
+  module_name: (_) @import.module
+  name: [
+    (dotted_name) @import.symbol
+    (aliased_import alias: (identifier) @import.symbol)
+  ]) @import.definition
+
+(import_from_statement
+  module_name: (_) @import.module
+  (wildcard_import)) @import.definition
+
+; Module-level variables, constants by naming convention
+
+(module
+  (expression_statement
+    (assignment
+      left: (identifier) @variable.name
+      type: (type)? @variable.type
+      right: (_)? @variable.value) @variable.definition))
+
+((module
+  (expression_statement
+    (assignment
+      left: (identifier) @variable.name) @variable.definition))
+  (#match? @variable.name "^[A-Z][A-Z0-9_]*$")
+  (#set! kind "constant"))
+
+; Names read or written
+
+(identifier) @reference.name
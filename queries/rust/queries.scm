@@ -0,0 +1,143 @@
+; Functions and methods
+
+(function_item
+  name: (identifier) @function.name) @function.definition
+
+(function_item
+  (visibility_modifier) @function.visibility) @function.definition
+
+(function_item
+  return_type: (_) @function.return_type) @function.definition
+
+(function_item
+  parameters: (parameters
+    (parameter
+      pattern: (identifier) @function.parameter))) @function.definition
+
+; Types
+
+((struct_item
+  name: (type_identifier) @class.name) @class.definition
+  (#set! kind "struct"))
+
+((enum_item
+  name: (type_identifier) @class.name) @class.definition
+  (#set! kind "enum"))
+
+((union_item
+  name: (type_identifier) @class.name) @class.definition
+  (#set! kind "union"))
+
+((trait_item
+  name: (type_identifier) @class.name) @class.definition
+  (#set! kind "trait"))
+
+[
+  (struct_item (visibility_modifier) @class.visibility)
+  (enum_item (visibility_modifier) @class.visibility)
+  (union_item (visibility_modifier) @class.visibility)
+  (trait_item (visibility_modifier) @class.visibility)
+] @class.definition
+
+(trait_item
+  bounds: (trait_bounds
+    (type_identifier) @class.super)) @class.definition
+
+(field_declaration
+  name: (field_identifier) @field.name
+  type: (_) @field.type) @field.definition
+
+(enum_variant
+  name: (identifier) @field.name) @field.definition
+
+; Methods in `impl` blocks belong to the implementing type
+
+(impl_item
+  type: [
+    (type_identifier) @scope.name
+    (generic_type type: (type_identifier) @scope.name)
+    (scoped_type_identifier name: (type_identifier) @scope.name)
+  ]) @scope.definition
+
+; Calls
+
+(call_expression
+  function: [
+    (identifier)
+    (scoped_identifier)
+    (field_expression)
+  ] @call.callee) @call.expression
+
+(call_expression
+  function: (generic_function
+    function: (_) @call.callee)) @call.expression
+
+; Imports
+
+(use_declaration
+  argument: (identifier) @import.module) @import.definition
+
+(use_declaration
+  argument: (scoped_identifier
+    path: (_) @import.module
+    name: (identifier) @import.symbol)) @import.definition
+
+(use_declaration
+  argument: (scoped_use_list
+    path: (_) @import.module
+    list: (use_list
+      [
+        (identifier) @import.symbol
+        (self) @import.symbol
+      ]))) @import.definition
+
+(use_declaration
+  argument: (use_wildcard
+    (_) @import.module)) @import.definition
+
+(use_declaration
+  argument: (use_as_clause
+    path: (scoped_identifier
+      path: (_) @import.module)
+    alias: (identifier) @import.symbol)) @import.definition
+
+; Constants and statics of modules, but not of traits and impls
+
+((source_file
+  (const_item
+    name: (identifier) @variable.name
+    type: (_) @variable.type
+    value: (_) @variable.value) @variable.definition)
+  (#set! kind "constant"))
+
+((mod_item
+  body: (declaration_list
+    (const_item
+      name: (identifier) @variable.name
+      type: (_) @variable.type
+      value: (_) @variable.value) @variable.definition))
+  (#set! kind "constant"))
+
+((source_file
+  (static_item
+    name: (identifier) @variable.name
+    type: (_) @variable.type
+    value: (_)? @variable.value) @variable.definition)
+  (#set! kind "static"))
+
+((mod_item
+  body: (declaration_list
+    (static_item
+      name: (identifier) @variable.name
+      type: (_) @variable.type
+      value: (_)? @variable.value) @variable.definition))
+  (#set! kind "static"))
+
+[
+  (const_item (visibility_modifier) @variable.visibility)
+  (static_item (visibility_modifier) @variable.visibility)
+] @variable.definition
+
+; Names read or written
+
+(identifier) @reference.name
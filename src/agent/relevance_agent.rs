@@ -2,22 +2,50 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use tracing;
 
+use crate::config::{Config, LlmSettings, RelevanceWeights};
 use crate::db::Database;
 use crate::graph::entity::{Entity, EntityId, EntityType};
 use crate::graph::relationship::RelationshipType;
 use crate::prompt::llm_integration::{get_llm_config, query_llm};
 
+/// Number of files returned by `suggest_relevant_files` and `ummon assist --top`
+pub const DEFAULT_TOP_FILES: usize = 10;
+
 /// Represents a file with relevance to a proposed change
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RelevantFile {
     pub path: String,
     pub relevance_score: f32,
-    pub contributing_entity_ids: Vec<EntityId>,
+    /// Entities behind the file's score, highest scoring first
+    pub contributing_entities: Vec<ContributingEntity>,
+}
+
+/// An entity that contributed to a file's relevance score
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContributingEntity {
+    pub id: EntityId,
+    pub name: String,
+    pub entity_type: EntityType,
+    pub score: f32,
 }
 
 /// Suggests relevant files based on a proposed change
-pub async fn suggest_relevant_files(change: &str, db: &Database) -> Result<Vec<RelevantFile>> {
-    let keywords = extract_keywords(change).await?;
+pub async fn suggest_relevant_files(
+    change: &str,
+    db: &Database,
+    config: &Config,
+) -> Result<Vec<RelevantFile>> {
+    suggest_top_relevant_files(change, db, DEFAULT_TOP_FILES, config).await
+}
+
+/// Suggests up to `top` relevant files based on a proposed change
+pub async fn suggest_top_relevant_files(
+    change: &str,
+    db: &Database,
+    top: usize,
+    config: &Config,
+) -> Result<Vec<RelevantFile>> {
+    let keywords = extract_keywords(change, &config.llm).await?;
     tracing::info!("Extracted keywords: {:?}", keywords);
 
     let seed_entities = search_seed_entities(db, &keywords)?;
@@ -26,18 +54,18 @@ pub async fn suggest_relevant_files(change: &str, db: &Database) -> Result<Vec<R
     let expanded_entities = expand_context(db, &seed_entities)?;
     tracing::info!("Expanded to {} entities", expanded_entities.len());
 
-    let ranked_entities = rank_entities(db, expanded_entities)?;
+    let ranked_entities = rank_entities(db, expanded_entities, config.relevance)?;
     tracing::info!("Ranked {} entities", ranked_entities.len());
 
-    let ranked_files = aggregate_and_rank_files(ranked_entities)?;
+    let ranked_files = aggregate_and_rank_files(ranked_entities, top)?;
     tracing::info!("Ranked {} files", ranked_files.len());
 
     Ok(ranked_files)
 }
 
 /// Extract technical keywords from the proposed change using LLM
-async fn extract_keywords(change: &str) -> Result<Vec<String>> {
-    let llm_config = get_llm_config(None, None);
+async fn extract_keywords(change: &str, llm: &LlmSettings) -> Result<Vec<String>> {
+    let llm_config = get_llm_config(llm);
     let prompt = format!(
         r#"Analyze the following proposed change and extract key technical concepts, entity names, domain terms, and actions as a JSON array of strings.
 
@@ -57,7 +85,14 @@ Return ONLY the JSON array without any explanation, markdown formatting, or othe
         change
     );
 
-    let response = query_llm(&prompt, &llm_config).await?;
+    let response = match query_llm(&prompt, &llm_config).await {
+        Ok(response) => response,
+        Err(e) => {
+            // Still rank files without an LLM by matching the words of the change
+            tracing::warn!("Keyword extraction failed, using plain words: {}", e);
+            return Ok(change.split_whitespace().map(|s| s.to_string()).collect());
+        }
+    };
     let cleaned_response = response.trim().trim_matches(|c| c == '`' || c == '"');
 
     match serde_json::from_str::<Vec<String>>(cleaned_response) {
@@ -136,7 +171,7 @@ fn search_seed_entities(db: &Database, keywords: &[String]) -> Result<Vec<(Box<d
         }
 
         let condition = conditions.join(" OR ");
-        let entities = db.query_entities_by_type(&entity_type, Some(&condition), vec![])?;
+        let entities = db.query_entities_by_type(&entity_type, Some(&condition), vec![], None)?;
 
         for entity in entities {
             let mut score = 0.0;
@@ -226,6 +261,7 @@ fn expand_context(
 fn rank_entities(
     db: &Database,
     entities: Vec<(Box<dyn Entity>, f32)>,
+    weights: RelevanceWeights,
 ) -> Result<Vec<(Box<dyn Entity>, f32)>> {
     let mut ranked_entities = Vec::new();
     let entity_ids: Vec<EntityId> = entities.iter().map(|(e, _)| e.id().clone()).collect();
@@ -257,7 +293,7 @@ fn rank_entities(
 
     for (entity, proximity_score) in entities {
         let centrality = normalized_centrality.get(entity.id()).unwrap_or(&0.0);
-        let final_score = proximity_score * 0.7 + centrality * 0.3;
+        let final_score = proximity_score * weights.proximity + centrality * weights.centrality;
         ranked_entities.push((entity, final_score));
     }
 
@@ -265,9 +301,12 @@ fn rank_entities(
     Ok(ranked_entities)
 }
 
-/// Aggregate entity scores into file-level scores
-fn aggregate_and_rank_files(entities: Vec<(Box<dyn Entity>, f32)>) -> Result<Vec<RelevantFile>> {
-    let mut file_map: std::collections::HashMap<String, (f32, Vec<EntityId>)> =
+/// Aggregate entity scores into file-level scores, keeping the `top` best files
+fn aggregate_and_rank_files(
+    entities: Vec<(Box<dyn Entity>, f32)>,
+    top: usize,
+) -> Result<Vec<RelevantFile>> {
+    let mut file_map: std::collections::HashMap<String, (f32, Vec<ContributingEntity>)> =
         std::collections::HashMap::new();
 
     for (entity, score) in entities {
@@ -277,16 +316,33 @@ fn aggregate_and_rank_files(entities: Vec<(Box<dyn Entity>, f32)>) -> Result<Vec
                 .or_insert((0.0, Vec::new()));
 
             entry.0 = entry.0.max(score);
-            entry.1.push(entity.id().clone());
+
+            // An entity can be reached several ways; keep its best score once
+            match entry.1.iter_mut().find(|c| &c.id == entity.id()) {
+                Some(existing) => existing.score = existing.score.max(score),
+                None => entry.1.push(ContributingEntity {
+                    id: entity.id().clone(),
+                    name: entity.name().to_string(),
+                    entity_type: entity.entity_type(),
+                    score,
+                }),
+            }
         }
     }
 
     let mut files: Vec<RelevantFile> = file_map
         .into_iter()
-        .map(|(path, (score, entity_ids))| RelevantFile {
-            path,
-            relevance_score: score,
-            contributing_entity_ids: entity_ids,
+        .map(|(path, (score, mut contributors))| {
+            contributors.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            RelevantFile {
+                path,
+                relevance_score: score,
+                contributing_entities: contributors,
+            }
         })
         .collect();
 
@@ -296,9 +352,7 @@ fn aggregate_and_rank_files(entities: Vec<(Box<dyn Entity>, f32)>) -> Result<Vec
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
-    if files.len() > 10 {
-        files.truncate(10);
-    }
+    files.truncate(top);
 
     Ok(files)
 }
@@ -357,14 +411,56 @@ mod tests {
             (Box::new(base3) as Box<dyn Entity>, 0.6),
         ];
 
-        let files = aggregate_and_rank_files(entities).unwrap();
+        let files = aggregate_and_rank_files(entities, DEFAULT_TOP_FILES).unwrap();
 
         assert_eq!(files.len(), 2);
         assert_eq!(files[0].path, "file1.rs");
         assert_eq!(files[0].relevance_score, 0.8);
-        assert_eq!(files[0].contributing_entity_ids.len(), 2);
+        assert_eq!(files[0].contributing_entities.len(), 2);
         assert_eq!(files[1].path, "file2.rs");
         assert_eq!(files[1].relevance_score, 0.6);
-        assert_eq!(files[1].contributing_entity_ids.len(), 1);
+        assert_eq!(files[1].contributing_entities.len(), 1);
+
+        // Contributors are reported by name, highest score first
+        let names: Vec<&str> = files[0]
+            .contributing_entities
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["test1", "test2"]);
+        assert_eq!(files[0].contributing_entities[1].score, 0.5);
+    }
+
+    #[test]
+    fn test_aggregate_and_rank_files_top() {
+        let entities = (0..4)
+            .map(|i| {
+                let base = BaseEntity::new(
+                    EntityId::new(&format!("test{}", i)),
+                    format!("test{}", i),
+                    EntityType::Function,
+                    Some(format!("file{}.rs", i)),
+                );
+                (Box::new(base) as Box<dyn Entity>, i as f32 / 10.0)
+            })
+            .collect();
+
+        let mut entities: Vec<(Box<dyn Entity>, f32)> = entities;
+        // The same entity reached twice is listed once with its best score
+        let duplicate = BaseEntity::new(
+            EntityId::new("test3"),
+            "test3".to_string(),
+            EntityType::Function,
+            Some("file3.rs".to_string()),
+        );
+        entities.push((Box::new(duplicate), 0.9));
+
+        let files = aggregate_and_rank_files(entities, 2).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "file3.rs");
+        assert_eq!(files[0].contributing_entities.len(), 1);
+        assert_eq!(files[0].contributing_entities[0].score, 0.9);
+        assert_eq!(files[1].path, "file2.rs");
     }
 }
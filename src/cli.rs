@@ -8,6 +8,11 @@ use clap::{Parser, Subcommand};
     about = "A code analysis tool that builds knowledge graphs from codebases"
 )]
 pub struct Cli {
+    /// Path to the knowledge graph database. Defaults to $UMMON_DB, then to the
+    /// nearest .ummon/ directory in this or a parent directory
+    #[arg(long, global = true, value_name = "PATH")]
+    pub db: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -29,16 +34,28 @@ pub enum Commands {
         enable_domain_extraction: bool,
 
         /// Directory to analyze for domain extraction (defaults to src/)
-        #[arg(long, default_value = "src")]
-        domain_dir: String,
+        #[arg(long)]
+        domain_dir: Option<String>,
 
-        /// LLM provider to use for domain extraction
-        #[arg(long, value_enum, default_value = "openrouter")]
+        /// LLM provider to use for domain extraction (defaults to openrouter)
+        #[arg(long)]
         llm_provider: Option<String>,
 
         /// LLM model to use
         #[arg(long)]
         llm_model: Option<String>,
+
+        /// Number of threads used to parse files (0 uses one per CPU)
+        #[arg(long, short = 'j', default_value_t = 0)]
+        jobs: usize,
+
+        /// Only index files matching this glob (can be repeated)
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Skip files matching this glob (can be repeated)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
     },
 
     /// Query the knowledge graph using Ummon's query language or natural language
@@ -69,25 +86,29 @@ pub enum Commands {
         #[arg(long, short)]
         type_filter: Option<String>,
 
-        /// Filter results by file path pattern
+        /// Filter results by file path (substring, or a SQL LIKE pattern containing '%')
         #[arg(long, short = 'p')]
         path: Option<String>,
 
-        /// Include exact ID matches only
+        /// Match names and paths exactly instead of as patterns
         #[arg(long, short)]
         exact: bool,
 
-        /// Maximum number of results to return
+        /// Maximum number of results to return (0 for no limit)
         #[arg(long, short, default_value = "20")]
         limit: usize,
 
+        /// Number of results to skip, for paging through large result sets
+        #[arg(long, short, default_value = "0")]
+        offset: usize,
+
         /// Skip LLM and only use direct knowledge graph queries
         /// Use this when you want to use the structured query syntax directly
         #[arg(long)]
         no_llm: bool,
 
-        /// LLM provider to use for natural language query translation
-        #[arg(long, value_enum, default_value = "openrouter")]
+        /// LLM provider to use for natural language query translation (defaults to openrouter)
+        #[arg(long)]
         llm_provider: Option<String>,
 
         /// LLM model to use for natural language query translation
@@ -100,12 +121,49 @@ pub enum Commands {
         /// User instruction (e.g., "implement a user registration function")
         instruction: String,
 
-        /// LLM provider to use for assistance
-        #[arg(long, value_enum, default_value = "openrouter")]
+        /// Only suggest relevant files, without asking the LLM for guidance
+        #[arg(long)]
+        suggest_files: bool,
+
+        /// Number of files to suggest
+        #[arg(long, default_value_t = ummon::agent::relevance_agent::DEFAULT_TOP_FILES)]
+        top: usize,
+
+        /// Output format for file suggestions
+        #[arg(long, short, default_value = "text", value_parser=["text", "json"])]
+        format: String,
+
+        /// LLM provider to use for assistance (defaults to openrouter)
+        #[arg(long)]
         llm_provider: Option<String>,
 
         /// LLM model to use
         #[arg(long)]
         llm_model: Option<String>,
     },
+
+    /// Run a Model Context Protocol server over stdio
+    ///
+    /// Reads newline-delimited JSON-RPC requests from stdin and writes responses to stdout,
+    /// exposing the knowledge graph to coding agents as MCP tools.
+    Serve,
+
+    /// Watch a directory and keep the knowledge graph up to date
+    ///
+    /// Performs an incremental index, then re-indexes only the files touched by each
+    /// burst of changes. Files ignored by .gitignore or .ummonignore, or excluded by
+    /// the globs of the last `index`, are skipped.
+    Watch {
+        /// Path to the directory to watch (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: String,
+
+        /// Milliseconds to wait for a burst of changes to settle before re-indexing
+        #[arg(long, default_value_t = 500)]
+        debounce_ms: u64,
+
+        /// Number of threads used to parse files (0 uses one per CPU)
+        #[arg(long, short = 'j', default_value_t = 0)]
+        jobs: usize,
+    },
 }
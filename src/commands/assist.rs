@@ -1,32 +1,41 @@
 use anyhow::Result;
 use colored::Colorize;
 
+use crate::config::Config;
+use crate::db::Database;
 use crate::prompt::context_builder::build_context;
 use crate::prompt::llm_integration::{get_llm_config, query_llm};
-use ummon::agent::relevance_agent::RelevantFile;
+use ummon::agent::relevance_agent::{suggest_top_relevant_files, RelevantFile};
+
+/// Contributing entities listed per file in text output
+const MAX_LISTED_ENTITIES: usize = 5;
+
+/// How the assist command should respond
+pub struct AssistOptions<'a> {
+    /// Only rank relevant files, skipping the LLM advice call
+    pub suggest_files: bool,
+    /// Number of files to suggest
+    pub top: usize,
+    /// Output format for file suggestions (text or json)
+    pub format: &'a str,
+}
 
 pub async fn run(
+    db: &Database,
+    config: &Config,
     instruction: &str,
-    llm_provider: Option<&str>,
-    llm_model: Option<&str>,
+    options: AssistOptions<'_>,
 ) -> Result<()> {
-    println!("{} {}", "AI Assist:".bold().green(), instruction);
-
-    let db = crate::db::get_database("ummon.db")?;
+    if options.suggest_files {
+        let relevant_files =
+            suggest_top_relevant_files(instruction, db, options.top, config).await?;
+        return print_suggestions(instruction, &relevant_files, options.format);
+    }
 
+    println!("{} {}", "AI Assist:".bold().green(), instruction);
     println!("{}", "Finding relevant files...".italic());
 
-    async fn get_relevant_files(
-        query: &str,
-        _db: &crate::db::Database,
-    ) -> Result<Vec<RelevantFile>> {
-        let db_path = "ummon.db";
-        let lib_db = ummon::db::get_database(db_path)?;
-        let files = ummon::agent::relevance_agent::suggest_relevant_files(query, &lib_db).await?;
-        Ok(files)
-    }
-
-    let relevant_files = get_relevant_files(instruction, &db).await?;
+    let relevant_files = suggest_top_relevant_files(instruction, db, options.top, config).await?;
 
     if !relevant_files.is_empty() {
         println!("\n{}", "Suggested files:".bold().underline());
@@ -36,7 +45,7 @@ pub async fn run(
                 i + 1,
                 file.path.bold(),
                 file.relevance_score,
-                file.contributing_entity_ids.len()
+                file.contributing_entities.len()
             );
         });
         println!();
@@ -51,19 +60,60 @@ pub async fn run(
         .join("\n");
 
     let context = if file_context.is_empty() {
-        build_context(&db, instruction)
+        build_context(db, instruction)
     } else {
         format!(
             "{}\n\nRelevant files:\n{}",
-            build_context(&db, instruction),
+            build_context(db, instruction),
             file_context
         )
     };
 
     println!("{}", "Consulting LLM for guidance...".italic());
-    let response = query_llm(&context, &get_llm_config(llm_provider, llm_model)).await?;
+    let response = query_llm(&context, &get_llm_config(&config.llm)).await?;
 
     println!("\n{}\n{}", "LLM suggests:".bold().blue(), response);
 
     Ok(())
 }
+
+/// Print ranked file suggestions with the entities that contributed to each score
+fn print_suggestions(instruction: &str, files: &[RelevantFile], format: &str) -> Result<()> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(files)?);
+        return Ok(());
+    }
+
+    if files.is_empty() {
+        println!("No relevant files found for \"{}\"", instruction);
+        return Ok(());
+    }
+
+    println!(
+        "{}\n",
+        format!("Relevant files for \"{}\":", instruction).bold()
+    );
+    for (i, file) in files.iter().enumerate() {
+        println!(
+            "{}. {} (relevance: {:.2})",
+            i + 1,
+            file.path.bold(),
+            file.relevance_score
+        );
+        for entity in file.contributing_entities.iter().take(MAX_LISTED_ENTITIES) {
+            println!(
+                "   - {} ({}, score: {:.2})",
+                entity.name, entity.entity_type, entity.score
+            );
+        }
+        let hidden = file
+            .contributing_entities
+            .len()
+            .saturating_sub(MAX_LISTED_ENTITIES);
+        if hidden > 0 {
+            println!("   ... and {} more", hidden);
+        }
+    }
+
+    Ok(())
+}
@@ -1,799 +1,1950 @@
 use anyhow::Result;
 use chrono::prelude::*;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use ignore::WalkBuilder;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 use std::time::Instant;
 
+use crate::config::{IndexSettings, LlmSettings};
+use crate::db::{project_root, Database, FileRecord, SymbolRecord};
 use crate::graph::entity::{
-    BaseEntity, Entity, EntityId, EntityType, FunctionEntity, ModuleEntity, TypeEntity,
-    VariableEntity,
+    BaseEntity, Decorator, Entity, EntityId, EntityType, FunctionEntity, Location, ModuleEntity,
+    TypeEntity, VariableEntity,
 };
 use crate::graph::relationship::{Relationship, RelationshipType};
 use crate::graph::KnowledgeGraph;
+use crate::parser::call_resolver::{CallResolution, CallResolver, ResolutionStrategy};
 use crate::parser::domain_model::RelationType;
+use crate::parser::header_merge::{is_header_pair, merge_header_declarations, resolve_include};
+use crate::parser::implicit_interfaces::find_implicit_implementations;
+use crate::parser::js_modules::{self, ExportOrigin, JsModules};
 use crate::parser::language_support::{
-    get_parser_for_file, is_supported_source_file, DomainConcept, FunctionDefinition,
-    TypeDefinition,
+    is_supported_source_file, language_for_file, CallReference, DomainConcept, FunctionDefinition,
+    FunctionKind, ImportDefinition, TypeDefinition, VariableDefinition,
 };
+use crate::parser::pipeline::{parse_files, ParsedFile, ParsedFunction};
+use crate::parser::python_modules;
+use crate::parser::rust_modules::RustModules;
+use crate::parser::type_usage::{field_types, signature_types, TypeRole};
+
+/// Settings that control how changed files are indexed
+pub struct IndexOptions<'a> {
+    /// Number of threads used to parse files, or 0 for one per CPU
+    pub jobs: usize,
+    pub enable_domain_extraction: bool,
+    pub domain_dir: &'a str,
+    /// Which files under the indexed directory are indexed
+    pub filter: &'a SourceFilter,
+    /// LLM used for domain extraction
+    pub llm: &'a LlmSettings,
+}
+
+/// Gitignore-style file listing paths that are never indexed
+pub const IGNORE_FILE: &str = ".ummonignore";
+
+/// Metadata key under which the command-line filter patterns are stored
+const FILTER_PATTERNS_KEY: &str = "filter_patterns";
+
+/// Include and exclude globs given on the command line
+///
+/// They are stored with the index, so later incremental runs and `ummon watch`
+/// select the same files without repeating the flags.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilterPatterns {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl FilterPatterns {
+    /// Load the patterns stored by the last index, if any
+    pub fn load(db: &Database) -> Result<Self> {
+        match db.get_metadata(FILTER_PATTERNS_KEY)? {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        db.set_metadata(FILTER_PATTERNS_KEY, &serde_json::to_string(self)?)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+}
+
+/// Selects the source files to index using the configured globs and languages
+pub struct SourceFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    languages: Vec<String>,
+    patterns: FilterPatterns,
+}
+
+impl SourceFilter {
+    /// Combine the globs from `ummon.toml` with those given on the command line
+    pub fn new(settings: &IndexSettings, patterns: FilterPatterns) -> Result<Self> {
+        let include: Vec<String> = settings
+            .include
+            .iter()
+            .chain(&patterns.include)
+            .cloned()
+            .collect();
+        let exclude: Vec<String> = settings
+            .exclude
+            .iter()
+            .chain(&patterns.exclude)
+            .cloned()
+            .collect();
+
+        Ok(Self {
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(build_glob_set(&include)?)
+            },
+            exclude: build_glob_set(&exclude)?,
+            languages: settings
+                .languages
+                .iter()
+                .map(|l| l.to_lowercase())
+                .collect(),
+            patterns,
+        })
+    }
+
+    /// The command-line patterns this filter was built with
+    pub fn patterns(&self) -> &FilterPatterns {
+        &self.patterns
+    }
+
+    /// Whether a supported source file, given relative to the indexed
+    /// directory, should be indexed
+    pub fn matches(&self, relative_path: &Path) -> bool {
+        let included = self
+            .include
+            .as_ref()
+            .is_none_or(|include| include.is_match(relative_path));
+        let language_enabled = self.languages.is_empty()
+            || language_for_file(relative_path)
+                .is_some_and(|language| self.languages.iter().any(|l| l == language));
+
+        included && language_enabled && !self.exclude.is_match(relative_path)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            Glob::new(pattern)
+                .map_err(|e| anyhow::anyhow!("Invalid glob pattern '{}': {}", pattern, e))?,
+        );
+    }
+    Ok(builder.build()?)
+}
+
+/// Move into the root of the project the database belongs to, and return the
+/// path to index relative to it
+///
+/// Files are stored under paths relative to the project root, so indexing a
+/// subdirectory updates the same entries as indexing the whole project.
+pub fn enter_project_root(db: &Database, path: &str) -> Result<String> {
+    let root = project_root(Path::new(db.path()), &std::env::current_dir()?)?;
+    let relative = path_in_project(&root, Path::new(path))?;
+
+    std::env::set_current_dir(&root)?;
+    Ok(relative)
+}
+
+/// The path to index, relative to the project root, as `.` or `./{path}`
+fn path_in_project(root: &Path, path: &Path) -> Result<String> {
+    let root = std::fs::canonicalize(root)?;
+    let target = std::fs::canonicalize(path)?;
+    let relative = target.strip_prefix(&root).map_err(|_| {
+        anyhow::anyhow!(
+            "{} is outside the project at {}",
+            path.display(),
+            root.display()
+        )
+    })?;
+
+    if relative.as_os_str().is_empty() {
+        Ok(".".to_string())
+    } else {
+        Ok(Path::new(".").join(relative).to_string_lossy().to_string())
+    }
+}
 
 /// Main entry point for the indexing command
 pub async fn run(
+    db: &Database,
     path: &str,
     full_rebuild: bool,
-    enable_domain_extraction: bool,
-    domain_dir: &str,
-    llm_provider: Option<&str>,
-    llm_model: Option<&str>,
+    options: &IndexOptions<'_>,
 ) -> Result<()> {
     tracing::info!("Indexing code at path: {}", path);
     let start_time = Instant::now();
 
-    // Set environment variables for the LLM provider and model if specified
-    if let Some(provider) = llm_provider {
-        std::env::set_var("LLM_PROVIDER", provider);
-    }
+    let current_files = scan_source_files(path, options.filter)?;
+    let indexed_files = db.load_file_records()?;
+    let indexed_count = indexed_files.len();
 
-    if let Some(model) = llm_model {
-        std::env::set_var("LLM_MODEL", model);
-    }
+    // Indexing a subdirectory leaves the rest of the project's files alone
+    let previous_files: HashMap<String, FileRecord> = indexed_files
+        .into_iter()
+        .filter(|(file, _)| Path::new(file).starts_with(path))
+        .collect();
+    let whole_project = previous_files.len() == indexed_count;
 
-    let db = crate::db::get_database("ummon.db")?;
-    let mut kg = KnowledgeGraph::new_with_db(db.clone());
+    // Files the filter no longer accepts show up as deleted below
+    options.filter.patterns().save(db)?;
 
-    let mut function_map: HashMap<String, FunctionDefinition> = HashMap::new();
-    let mut type_map: HashMap<String, TypeDefinition> = HashMap::new();
-    let mut domain_concepts: HashMap<String, DomainConcept> = HashMap::new();
-    let mut indexed_files = HashSet::new();
+    let changes = if full_rebuild || previous_files.is_empty() {
+        if full_rebuild {
+            tracing::info!("Performing full rebuild of the knowledge graph...");
+        } else {
+            tracing::info!("No previous index found, performing full initial index.");
+        }
 
-    if full_rebuild {
-        tracing::info!("Performing full rebuild of the knowledge graph...");
-        kg.purge()?;
+        if whole_project {
+            db.purge_graph()?;
+            FileChanges::diff(&HashMap::new(), &current_files)
+        } else {
+            FileChanges::rebuild(&previous_files, &current_files)
+        }
     } else {
         tracing::info!("Performing incremental update of the knowledge graph...");
-        let last_index_time = db.get_metadata("last_index_time")?;
+        let changes = FileChanges::diff(&previous_files, &current_files);
 
-        if let Some(timestamp) = last_index_time {
-            let modified_files = get_modified_files(path, &timestamp)?;
+        if changes.is_empty() {
+            tracing::info!("No files changed since last index. Nothing to do.");
+            return Ok(());
+        }
 
-            if modified_files.is_empty() {
-                tracing::info!("No files modified since last index. Nothing to do.");
-                return Ok(());
-            }
+        changes
+    };
 
-            tracing::info!("Found {} modified files to update", modified_files.len());
-            kg.prune(&modified_files)?;
+    update_graph(db, &changes, &current_files, options).await?;
 
-            for file in modified_files {
-                indexed_files.insert(file);
-            }
-        } else {
-            tracing::info!("No previous index found, performing full initial index.");
-        }
-    }
+    tracing::info!("Indexing complete in {:.2?}.", start_time.elapsed());
+    tracing::info!("Graph saved to database '{}'.", db.path());
 
-    tracing::info!("Pass 1: Collecting entities...");
-    if indexed_files.is_empty() {
-        index_entities(
-            path,
-            &mut kg,
-            &mut function_map,
-            &mut type_map,
-            &mut domain_concepts,
-            &mut indexed_files,
-        )?;
-    } else {
-        index_specific_entities(
-            &indexed_files,
-            &mut kg,
-            &mut function_map,
-            &mut type_map,
-            &mut domain_concepts,
-        )?;
+    Ok(())
+}
+
+/// Bring the stored graph up to date with a set of file changes
+///
+/// Entities of changed and deleted files are pruned, added and changed files
+/// are parsed and indexed, and files with relationships into the pruned
+/// entities have their relationships resolved again. `current_files` must hold
+/// the records of every added and changed file.
+pub async fn update_graph(
+    db: &Database,
+    changes: &FileChanges,
+    current_files: &HashMap<String, FileRecord>,
+    options: &IndexOptions<'_>,
+) -> Result<()> {
+    let mut kg = KnowledgeGraph::new_with_db(db.clone());
+
+    let mut function_map: HashMap<String, FunctionDefinition> = HashMap::new();
+    let mut type_map: HashMap<String, TypeDefinition> = HashMap::new();
+    let mut domain_concepts: HashMap<String, DomainConcept> = HashMap::new();
+    let mut variables: Vec<SymbolRecord> = Vec::new();
+
+    tracing::info!(
+        "Found {} added, {} changed and {} deleted files",
+        changes.added.len(),
+        changes.changed.len(),
+        changes.deleted.len()
+    );
+
+    // Files calling into changed or deleted files lose those edges when the
+    // stale entities are pruned, so their relationships are resolved again
+    let stale: Vec<String> = changes
+        .changed
+        .iter()
+        .chain(&changes.deleted)
+        .cloned()
+        .collect();
+    let mut dependents: Vec<String> = db
+        .files_with_relationships_into(&stale)?
+        .into_iter()
+        .filter(|file| Path::new(file).is_file())
+        .collect();
+
+    // A Go package spans its whole directory, and which interfaces its structs
+    // satisfy depends on every file in it, so packages are indexed together.
+    // Java packages are too, as their files share the package's module entity.
+    // C/C++ headers are merged with their implementation files, so those are
+    // indexed in pairs.
+    let touched: Vec<&String> = changes.added.iter().chain(&stale).collect();
+    let indexed_files = db.load_file_records()?;
+    dependents.extend(
+        package_siblings(&touched, indexed_files.keys())
+            .into_iter()
+            .chain(header_pairs(&touched, indexed_files.keys()))
+            .filter(|file| !changes.deleted.contains(file) && Path::new(file).is_file()),
+    );
+    dependents.sort();
+    dependents.dedup();
+    if !dependents.is_empty() {
+        tracing::info!(
+            "Re-resolving relationships from {} dependent files",
+            dependents.len()
+        );
     }
 
-    tracing::info!("Pass 2: Building relationships...");
-    if indexed_files.is_empty() {
-        index_relationships(
-            path,
-            &mut kg,
-            &function_map,
-            &type_map,
-            &domain_concepts,
-            &indexed_files,
-        )?;
-    } else {
-        index_specific_relationships(
-            &indexed_files,
-            &mut kg,
-            &function_map,
-            &type_map,
-            &domain_concepts,
-        )?;
+    kg.prune(&stale)?;
+    db.remove_relationships_from_files(&dependents)?;
+    db.remove_file_records(&changes.deleted)?;
+
+    // Files are parsed in parallel but merged in sorted order, so the graph
+    // doesn't depend on how the work was scheduled
+    let files_to_index: Vec<String> = changes
+        .added
+        .iter()
+        .chain(&changes.changed)
+        .chain(&dependents)
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    tracing::info!("Parsing {} files...", files_to_index.len());
+    let mut parsed_files = parse_files(&files_to_index, options.jobs)?;
+
+    // Calls and type names left unresolved, or bound by name alone, may bind
+    // to what the added and changed files define, so the files naming them
+    // are resolved again too
+    let defined: BTreeSet<String> = parsed_files
+        .iter()
+        .filter(|parsed| {
+            changes.added.contains(&parsed.path) || changes.changed.contains(&parsed.path)
+        })
+        .flat_map(defined_names)
+        .collect();
+    let rebinding: Vec<String> = db
+        .files_naming_unresolved(&defined)?
+        .into_iter()
+        .filter(|file| !files_to_index.contains(file) && Path::new(file).is_file())
+        .collect();
+    if !rebinding.is_empty() {
+        tracing::info!(
+            "Re-resolving relationships from {} files naming new definitions",
+            rebinding.len()
+        );
+        db.remove_relationships_from_files(&rebinding)?;
+        parsed_files.extend(parse_files(&rebinding, options.jobs)?);
+        parsed_files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    let resolved_files: Vec<String> = files_to_index
+        .iter()
+        .chain(&rebinding)
+        .chain(&changes.deleted)
+        .cloned()
+        .collect();
+    db.remove_unresolved_names(&resolved_files)?;
+
+    merge_header_declarations(&mut parsed_files);
+    let rust_modules = RustModules::build(&parsed_files);
+
+    // Rust methods are keyed by the type their `impl` block is for, which may
+    // be defined in another module
+    let symbols = db.load_symbols()?;
+    let no_types = BTreeSet::new();
+    let mut known_types: BTreeSet<String> = symbols
+        .iter()
+        .filter_map(|symbol| symbol.id.strip_prefix("type::"))
+        .map(String::from)
+        .collect();
+    for parsed in &parsed_files {
+        let keys = EntityKeys::new(parsed, &rust_modules, &no_types);
+        known_types.extend(parsed.types.iter().map(|type_def| keys.type_key(type_def)));
     }
 
-    tracing::info!("Pass 3: Inferring domain model from source files...");
-    infer_domain_model(
+    tracing::info!("Pass 1: Collecting entities...");
+    let file_keys: Vec<EntityKeys> = parsed_files
+        .iter()
+        .map(|parsed| EntityKeys::new(parsed, &rust_modules, &known_types))
+        .collect();
+    index_entities(
+        &parsed_files,
+        &file_keys,
         &mut kg,
+        &mut function_map,
+        &mut type_map,
         &mut domain_concepts,
-        enable_domain_extraction,
-        domain_dir,
-    )
-    .await?;
+        &mut variables,
+    )?;
+
+    // Calls and references may target entities in files that weren't
+    // re-indexed this run
+    let mut resolver = CallResolver::new(&function_map, &type_map);
+    for symbol in symbols.into_iter().chain(variables) {
+        // Methods and static fields are matched to their type by the name
+        // they were defined with, just as in `CallResolver::new`
+        let containing_type = symbol.containing_type.as_deref();
+        match symbol.entity_type {
+            EntityType::Function | EntityType::Method => {
+                resolver.add_function(&symbol.id, &symbol.name, &symbol.file_path, containing_type)
+            }
+            EntityType::Variable | EntityType::Constant => {
+                resolver.add_variable(&symbol.id, &symbol.name, &symbol.file_path, None)
+            }
+            EntityType::Field => {
+                resolver.add_variable(&symbol.id, &symbol.name, &symbol.file_path, containing_type)
+            }
+            _ => resolver.add_type(&symbol.id, &symbol.name, &symbol.file_path),
+        }
+    }
 
-    let duration = start_time.elapsed();
+    tracing::info!("Pass 2: Building relationships...");
+    let mut unresolved = BTreeSet::new();
+    index_relationships(
+        &parsed_files,
+        &file_keys,
+        &mut kg,
+        &function_map,
+        &resolver,
+        &mut unresolved,
+    )?;
+    index_implicit_interfaces(&parsed_files, &mut kg);
+
+    tracing::info!("Pass 3: Inferring domain model from source files...");
+    infer_domain_model(&mut kg, &mut domain_concepts, options).await?;
 
     let entities: Vec<&dyn Entity> = kg.get_all_entities();
     let relationships = kg.get_all_relationships()?;
     let rel_refs: Vec<&Relationship> = relationships.iter().collect();
 
     db.save_all_in_transaction(&entities, &rel_refs)?;
+    db.save_unresolved_names(&unresolved)?;
+    db.remove_orphaned_placeholders()?;
+
+    let indexed_records: Vec<FileRecord> = changes
+        .added
+        .iter()
+        .chain(&changes.changed)
+        .filter_map(|file| current_files.get(file).cloned())
+        .collect();
+    db.save_file_records(&indexed_records)?;
 
     let now = Utc::now().to_rfc3339();
     db.set_metadata("last_index_time", &now)?;
 
-    let entity_count = kg.get_all_entities().len();
-    let relationship_count = kg.get_relationship_count();
-    let domain_concept_count = kg.get_domain_concepts().len();
-
-    tracing::info!("Indexing complete in {:.2?}.", duration);
     tracing::info!("Knowledge Graph Statistics:");
-    tracing::info!("  - {} entities indexed", entity_count);
-    tracing::info!("  - {} relationships established", relationship_count);
-    tracing::info!("  - {} domain concepts inferred", domain_concept_count);
-    tracing::info!("Graph saved to database 'ummon.db'.");
+    tracing::info!("  - {} entities indexed", kg.get_all_entities().len());
+    tracing::info!(
+        "  - {} relationships established",
+        kg.get_relationship_count()
+    );
+    tracing::info!(
+        "  - {} domain concepts inferred",
+        kg.get_domain_concepts().len()
+    );
 
     Ok(())
 }
 
-/// First pass: Index all entities from code
-fn index_entities(
-    path: &str,
+/// Names of the functions, types and variables a file defines
+fn defined_names(parsed: &ParsedFile) -> impl Iterator<Item = String> + '_ {
+    parsed
+        .functions
+        .iter()
+        .map(|func| &func.definition.name)
+        .chain(parsed.types.iter().map(|type_def| &type_def.name))
+        .chain(parsed.variables.iter().map(|variable| &variable.name))
+        .cloned()
+}
+
+/// Indexed Go and Java files in the same directories, and of the same
+/// language, as the given files
+fn package_siblings<'a>(
+    files: &[&String],
+    indexed_files: impl Iterator<Item = &'a String>,
+) -> Vec<String> {
+    let package_language = |file: &str| {
+        language_for_file(Path::new(file)).filter(|language| matches!(*language, "go" | "java"))
+    };
+    let packages: BTreeSet<(&Path, &str)> = files
+        .iter()
+        .filter_map(|file| Some((Path::new(file.as_str()).parent()?, package_language(file)?)))
+        .collect();
+
+    indexed_files
+        .filter(|file| {
+            let dir = Path::new(file.as_str()).parent();
+            dir.zip(package_language(file))
+                .is_some_and(|package| packages.contains(&package))
+        })
+        .filter(|file| !files.contains(file))
+        .cloned()
+        .collect()
+}
+
+/// Indexed C/C++ headers and implementation files paired with the given files
+fn header_pairs<'a>(
+    files: &[&String],
+    indexed_files: impl Iterator<Item = &'a String>,
+) -> Vec<String> {
+    indexed_files
+        .filter(|indexed| files.iter().any(|file| is_header_pair(file, indexed)))
+        .filter(|indexed| !files.contains(indexed))
+        .cloned()
+        .collect()
+}
+
+/// Create `Implements` relationships for interfaces Go structs satisfy implicitly
+fn index_implicit_interfaces(parsed_files: &[ParsedFile], kg: &mut KnowledgeGraph) {
+    for implementation in find_implicit_implementations(parsed_files) {
+        let metadata = HashMap::from([("resolution".to_string(), "method_set".to_string())]);
+
+        if let Err(e) = kg.create_weighted_relationship(
+            EntityId::new(&format!("type::{}", implementation.struct_key)),
+            EntityId::new(&format!("type::{}", implementation.interface_key)),
+            RelationshipType::Implements,
+            1.0,
+            metadata,
+        ) {
+            tracing::warn!(
+                "Failed to create implicit implements relationship: error: {}",
+                e
+            );
+        }
+    }
+}
+
+/// Create `Calls` relationships for the call sites found in a file.
+///
+/// The source of each edge is the function or method enclosing the call, or the
+/// file's module for calls made at module level. Targets are bound by the
+/// resolver, whose confidence becomes the relationship weight. Calls it can't
+/// bind keep their fully qualified name as an external placeholder target.
+fn index_call_relationships(
     kg: &mut KnowledgeGraph,
-    function_map: &mut HashMap<String, FunctionDefinition>,
-    type_map: &mut HashMap<String, TypeDefinition>,
-    domain_concepts: &mut HashMap<String, DomainConcept>,
-    indexed_files: &mut HashSet<String>,
-) -> Result<()> {
-    let walker = WalkBuilder::new(path).hidden(false).ignore(true).build();
+    parsed: &ParsedFile,
+    keys: &EntityKeys,
+    resolver: &CallResolver,
+    js_modules: &mut JsModules,
+    unresolved: &mut BTreeSet<(String, String)>,
+) {
+    let file_path = &parsed.path;
+    let imports = &parsed.module.imports;
+    let is_javascript = language_for_file(Path::new(file_path)) == Some("javascript");
+    for call in &parsed.calls {
+        let caller = call
+            .caller_name
+            .as_ref()
+            .and_then(|caller| find_caller(&parsed.functions, caller, call.location.as_ref()));
+        let caller_id = match caller {
+            Some(caller) => EntityId::new(&keys.function(caller)),
+            None => EntityId::new(keys.module_id()),
+        };
 
-    for entry in walker {
-        let entry = entry?;
-        let path = entry.path();
+        // Names a JavaScript file imports are bound through the module graph
+        let imported = if is_javascript {
+            resolve_js_import(kg, parsed, js_modules, call)
+        } else {
+            None
+        };
+        let resolution = imported.or_else(|| resolver.resolve(call, file_path, caller, imports));
+        if is_unsettled(resolution.as_ref()) {
+            unresolved.insert((
+                file_path.clone(),
+                unqualified_name(&call.callee_name).to_string(),
+            ));
+        }
+
+        let mut metadata = HashMap::new();
+        let (callee_id, weight) = match resolution {
+            Some(resolution) => {
+                metadata.insert(
+                    "resolution".to_string(),
+                    resolution.strategy.as_str().to_string(),
+                );
+                if !resolution.candidates.is_empty() {
+                    metadata.insert("candidates".to_string(), resolution.candidates.join(","));
+                }
+                (EntityId::new(&resolution.target_id), resolution.confidence)
+            }
+            None => match &call.fully_qualified_name {
+                Some(callee_key) => {
+                    metadata.insert("resolution".to_string(), "unresolved".to_string());
+                    (EntityId::new(callee_key), 0.0)
+                }
+                None => continue,
+            },
+        };
+
+        if let Err(e) = kg.create_weighted_relationship(
+            caller_id,
+            callee_id,
+            RelationshipType::Calls,
+            weight,
+            metadata,
+        ) {
+            tracing::warn!("Failed to create call relationship: error: {}", e);
+        }
+    }
+}
 
-        if !path.is_file() {
+/// Create `References` relationships from functions to the module-level
+/// variables and constants, and the static fields, they read or write.
+///
+/// Edges are weighted by the resolver's confidence and record whether the
+/// function writes the variable as their `access`. Names the resolver can't
+/// bind are most likely locals, so they're skipped rather than kept as
+/// placeholders.
+fn index_variable_references(
+    kg: &mut KnowledgeGraph,
+    parsed: &ParsedFile,
+    keys: &EntityKeys,
+    resolver: &CallResolver,
+) {
+    let file_path = &parsed.path;
+    let imports = &parsed.module.imports;
+    let package_scope = language_for_file(Path::new(file_path)) == Some("go");
+    for reference in &parsed.variable_references {
+        let Some(caller) = find_caller(
+            &parsed.functions,
+            &reference.caller_name,
+            Some(&reference.location),
+        ) else {
+            continue;
+        };
+        let Some(resolution) =
+            resolver.resolve_variable(reference, file_path, Some(caller), imports, package_scope)
+        else {
             continue;
+        };
+
+        let access = if reference.is_write { "write" } else { "read" };
+        let mut metadata = HashMap::from([
+            ("access".to_string(), access.to_string()),
+            (
+                "resolution".to_string(),
+                resolution.strategy.as_str().to_string(),
+            ),
+        ]);
+        if !resolution.candidates.is_empty() {
+            metadata.insert("candidates".to_string(), resolution.candidates.join(","));
         }
 
-        let file_path = path.to_string_lossy().to_string();
+        if let Err(e) = kg.create_weighted_relationship(
+            EntityId::new(&keys.function(caller)),
+            EntityId::new(&resolution.target_id),
+            RelationshipType::References,
+            resolution.confidence,
+            metadata,
+        ) {
+            tracing::warn!("Failed to create reference relationship: error: {}", e);
+        }
+    }
+}
 
-        // Skip if already processed
-        if indexed_files.contains(&file_path) {
-            continue;
+/// Create `Uses` relationships from functions to the types named in their
+/// parameter and return types, and from fields to the types named in theirs.
+///
+/// Type names, including generic arguments, are bound to indexed types the
+/// same way supertypes are. A function naming a type in several places gets
+/// one relationship, whose `role` metadata lists them, e.g. `param,return`.
+/// Names that don't resolve, such as built-in types, are skipped.
+fn index_type_usages(
+    kg: &mut KnowledgeGraph,
+    parsed: &ParsedFile,
+    keys: &EntityKeys,
+    resolver: &CallResolver,
+    unresolved: &mut BTreeSet<(String, String)>,
+) {
+    let functions = parsed.functions.iter().map(|function| {
+        let func = &function.definition;
+        (keys.function(func), signature_types(func))
+    });
+    let fields = parsed.types.iter().flat_map(|type_def| {
+        let key = keys.type_key(type_def);
+        type_def.fields.iter().map(move |field| {
+            (
+                format!("{}::field::{}", key, field.name),
+                field_types(field),
+            )
+        })
+    });
+
+    for (source, usages) in functions.chain(fields) {
+        // Roles and the most confident binding, by target
+        let mut targets: BTreeMap<String, (BTreeSet<TypeRole>, f32, &str)> = BTreeMap::new();
+        for (role, type_name) in usages {
+            let resolution = resolver.resolve_type(type_name, &parsed.path, &parsed.module.imports);
+            if is_unsettled(resolution.as_ref()) {
+                unresolved.insert((parsed.path.clone(), unqualified_name(type_name).to_string()));
+            }
+            let Some(resolution) = resolution else {
+                continue;
+            };
+            let target = targets
+                .entry(resolution.target_id)
+                .or_insert_with(|| (BTreeSet::new(), 0.0, ""));
+            target.0.insert(role);
+            if resolution.confidence > target.1 {
+                target.1 = resolution.confidence;
+                target.2 = resolution.strategy.as_str();
+            }
         }
 
-        // Skip if file has unsupported extension
-        if !is_supported_source_file(path) {
-            tracing::debug!("Skipping unsupported file type: {}", file_path);
-            continue;
+        for (target_id, (roles, weight, resolution)) in targets {
+            let roles: Vec<&str> = roles.into_iter().map(TypeRole::as_str).collect();
+            let metadata = HashMap::from([
+                ("role".to_string(), roles.join(",")),
+                ("resolution".to_string(), resolution.to_string()),
+            ]);
+
+            if let Err(e) = kg.create_weighted_relationship(
+                EntityId::new(&source),
+                EntityId::new(&target_id),
+                RelationshipType::Uses,
+                weight,
+                metadata,
+            ) {
+                tracing::warn!("Failed to create type usage relationship: error: {}", e);
+            }
         }
+    }
+}
 
-        indexed_files.insert(file_path.clone());
+/// Whether a call or type name was left unresolved, or bound by name alone or
+/// ambiguously, so that a definition added elsewhere could bind it differently
+fn is_unsettled(resolution: Option<&CallResolution>) -> bool {
+    resolution.is_none_or(|resolution| {
+        resolution.strategy == ResolutionStrategy::GlobalName || !resolution.candidates.is_empty()
+    })
+}
 
-        if let Ok(Some(mut parser)) = get_parser_for_file(path) {
-            let content = std::fs::read_to_string(path)?;
+/// The name a call or type refers to, without its qualifier or generic
+/// arguments, e.g. `process` for `jobs.process` and `List` for `java.util.List<T>`
+fn unqualified_name(name: &str) -> &str {
+    let name = name.split(['<', '[', '(']).next().unwrap_or(name).trim();
+    name.rsplit([':', '.', '\\', '/']).next().unwrap_or(name)
+}
 
-            // Process file as a module
-            let module_info = parser.parse_modules(&content, &file_path)?;
-            let module_id = EntityId::new(&file_path);
+/// The function a call was made from: the innermost one with the caller's
+/// name enclosing the call, or the last one with that name if the call's
+/// location is unknown
+fn find_caller<'a>(
+    functions: &'a [ParsedFunction],
+    caller_name: &str,
+    location: Option<&Location>,
+) -> Option<&'a FunctionDefinition> {
+    let named = functions
+        .iter()
+        .map(|function| &function.definition)
+        .filter(|function| function.name == caller_name);
+
+    location
+        .and_then(|location| {
+            named
+                .clone()
+                .filter(|function| encloses(&function.location, location))
+                .min_by_key(|function| {
+                    function.location.end.offset - function.location.start.offset
+                })
+        })
+        .or_else(|| named.clone().next_back())
+}
 
-            let module_entity = ModuleEntity {
-                base: BaseEntity::new(
-                    module_id.clone(),
-                    module_info.name.clone(),
-                    EntityType::Module,
-                    Some(file_path.clone()),
-                ),
-                path: file_path.clone(),
-                children: Vec::new(),
-                imports: module_info
-                    .imports
-                    .iter()
-                    .map(|imp| imp.module_name.clone())
-                    .collect(),
-            };
+/// Whether a source range lies within another
+fn encloses(outer: &Location, inner: &Location) -> bool {
+    outer.start.offset <= inner.start.offset && inner.end.offset <= outer.end.offset
+}
+
+/// Entity IDs of the definitions in a parsed file
+///
+/// Functions and types are keyed by their file, as `{file_path}::{name}`,
+/// except in Rust files of a crate's module tree, where they're keyed by
+/// their full Rust path, such as `ummon::graph::KnowledgeGraph::new`.
+/// Type entity IDs carry an extra `type::` prefix either way. Modules are
+/// identified by their file, or by their name where they have one.
+struct EntityKeys<'a> {
+    file_path: &'a str,
+    /// Module path of a Rust file in a crate's module tree, dotted name of a
+    /// Python module in a package, or package of a Java file
+    module_name: Option<String>,
+    /// Whether functions and types are keyed by their Rust path
+    rust_paths: bool,
+    /// Full paths of the inline Rust modules declared in the file, with their locations
+    inline_modules: Vec<(String, &'a Location)>,
+    /// Package-qualified names of the types of a Java file in a package, by
+    /// their simple name, e.g. `com.example.Outer.Inner` for `Inner`
+    java_types: Option<HashMap<String, String>>,
+    /// The file's imports, through which Rust `impl` blocks may name their type
+    imports: &'a [ImportDefinition],
+    /// Keys of the types defined anywhere in the indexed code
+    known_types: &'a BTreeSet<String>,
+}
 
-            // Add module to the knowledge graph
-            kg.add_entity(module_entity)?;
-
-            // Process functions
-            let functions = parser.parse_functions(&content, &file_path)?;
-            for func in functions {
-                let key = format!("{}::{}", func.file_path, func.name);
-                function_map.insert(key.clone(), func.clone());
-
-                // Create entity ID and entity type
-                let entity_id = EntityId::new(&key);
-                let entity_type = match func.kind {
-                    crate::parser::language_support::FunctionKind::Function => EntityType::Function,
-                    crate::parser::language_support::FunctionKind::Method => EntityType::Method,
-                    crate::parser::language_support::FunctionKind::Constructor => {
-                        EntityType::Method
+impl<'a> EntityKeys<'a> {
+    fn new(
+        parsed: &'a ParsedFile,
+        rust_modules: &RustModules,
+        known_types: &'a BTreeSet<String>,
+    ) -> Self {
+        match language_for_file(Path::new(&parsed.path)) {
+            Some("python") => {
+                return Self {
+                    file_path: &parsed.path,
+                    module_name: python_modules::module_name(&parsed.path),
+                    rust_paths: false,
+                    inline_modules: Vec::new(),
+                    java_types: None,
+                    imports: &parsed.module.imports,
+                    known_types,
+                };
+            }
+            Some("java") => {
+                let package = java_package(parsed);
+                let java_types = package.map(|package| {
+                    // Outer types are listed before the types nested in them
+                    let mut types = HashMap::new();
+                    for type_def in &parsed.types {
+                        let scope = match &type_def.containing_entity_name {
+                            Some(outer) => types
+                                .get(outer)
+                                .cloned()
+                                .unwrap_or_else(|| format!("{}.{}", package, outer)),
+                            None => package.to_string(),
+                        };
+                        types.insert(
+                            type_def.name.clone(),
+                            format!("{}.{}", scope, type_def.name),
+                        );
                     }
-                    _ => EntityType::Function,
+                    types
+                });
+                return Self {
+                    file_path: &parsed.path,
+                    module_name: package.map(String::from),
+                    rust_paths: false,
+                    inline_modules: Vec::new(),
+                    java_types,
+                    imports: &parsed.module.imports,
+                    known_types,
                 };
+            }
+            _ => {}
+        }
 
-                // Extract documentation if available
-                let doc = parser.extract_documentation(&content, &func.location)?;
+        let module_path = rust_modules.module_path(&parsed.path);
+        let inline_modules = module_path
+            .map(|module_path| {
+                parsed
+                    .module
+                    .submodules
+                    .iter()
+                    .filter(|submodule| submodule.is_inline)
+                    .map(|submodule| {
+                        (
+                            format!("{}::{}", module_path, submodule.name),
+                            &submodule.location,
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            file_path: &parsed.path,
+            module_name: module_path.map(String::from),
+            rust_paths: module_path.is_some(),
+            inline_modules,
+            java_types: None,
+            imports: &parsed.module.imports,
+            known_types,
+        }
+    }
 
-                let mut base = BaseEntity::new(
-                    entity_id.clone(),
-                    func.name.clone(),
-                    entity_type,
-                    Some(func.file_path.clone()),
-                );
+    /// ID of the file's module entity
+    fn module_id(&self) -> &str {
+        self.module_name.as_deref().unwrap_or(self.file_path)
+    }
 
-                base.location = Some(func.location.clone());
-                base.documentation = doc;
-                base.containing_entity = func
-                    .containing_type
-                    .as_ref()
-                    .map(|t| EntityId::new(&format!("type::{}", t)));
+    /// Path of the innermost Rust module a definition is in, or the file
+    fn scope(&self, location: &Location) -> &str {
+        self.inline_modules
+            .iter()
+            .filter(|(_, module)| encloses(module, location))
+            .max_by_key(|(_, module)| module.start.offset)
+            .map_or(self.module_id(), |(path, _)| path.as_str())
+    }
 
-                let function_entity = FunctionEntity {
-                    base,
-                    parameters: func.parameters.clone(),
-                    return_type: None,
-                    visibility: func.visibility.clone(),
-                    is_async: false,
-                    is_static: false,
-                    is_constructor: func.kind
-                        == crate::parser::language_support::FunctionKind::Constructor,
-                    is_abstract: false,
-                };
+    fn function(&self, func: &FunctionDefinition) -> String {
+        if self.java_types.is_some() {
+            let scope = match func.containing_type.as_deref() {
+                Some(type_name) => self.java_type(type_name),
+                None => self.module_id().to_string(),
+            };
+            return format!("{}.{}", scope, func.name);
+        }
+        if !self.rust_paths {
+            return match func.containing_type.as_deref() {
+                Some(type_name) => format!("{}::{}::{}", func.file_path, type_name, func.name),
+                None => format!("{}::{}", func.file_path, func.name),
+            };
+        }
+        match self.method_type(func) {
+            Some(type_key) => format!("{}::{}", type_key, func.name),
+            None => format!("{}::{}", self.scope(&func.location), func.name),
+        }
+    }
 
-                kg.add_entity(function_entity)?;
-            }
+    fn variable(&self, variable: &VariableDefinition) -> String {
+        if self.rust_paths {
+            format!("{}::{}", self.scope(&variable.location), variable.name)
+        } else {
+            format!("{}::{}", variable.file_path, variable.name)
+        }
+    }
 
-            // Process types (classes, structs, etc.)
-            let types = parser.parse_types(&content, &file_path)?;
-            for type_def in types {
-                let key = format!("{}::{}", type_def.file_path, type_def.name);
-                type_map.insert(key.clone(), type_def.clone());
-
-                // Create entity ID and entity type
-                let entity_id = EntityId::new(&format!("type::{}", key));
-                let entity_type = match type_def.kind {
-                    crate::parser::language_support::TypeKind::Class => EntityType::Class,
-                    crate::parser::language_support::TypeKind::Struct => EntityType::Struct,
-                    crate::parser::language_support::TypeKind::Interface => EntityType::Interface,
-                    crate::parser::language_support::TypeKind::Trait => EntityType::Trait,
-                    crate::parser::language_support::TypeKind::Enum => EntityType::Enum,
-                    _ => EntityType::Type,
-                };
+    fn type_key(&self, type_def: &TypeDefinition) -> String {
+        if self.java_types.is_some() {
+            return self.java_type(&type_def.name);
+        }
+        if self.rust_paths {
+            format!("{}::{}", self.scope(&type_def.location), type_def.name)
+        } else {
+            format!("{}::{}", type_def.file_path, type_def.name)
+        }
+    }
 
-                let mut base = BaseEntity::new(
-                    entity_id.clone(),
-                    type_def.name.clone(),
-                    entity_type,
-                    Some(type_def.file_path.clone()),
-                );
+    /// Package-qualified name of a type defined in a Java file
+    fn java_type(&self, type_name: &str) -> String {
+        self.java_types
+            .as_ref()
+            .and_then(|types| types.get(type_name).cloned())
+            .unwrap_or_else(|| format!("{}.{}", self.module_id(), type_name))
+    }
+
+    /// Key of the type a Rust method is implemented for
+    ///
+    /// The type is looked up at the path its `impl` block names, directly or
+    /// through a `use` declaration, or else assumed to be defined in the same
+    /// module as the `impl` block.
+    fn method_type(&self, func: &FunctionDefinition) -> Option<String> {
+        if !self.rust_paths || func.kind != FunctionKind::Method {
+            return None;
+        }
+        let type_name = func.containing_type.as_deref()?;
+        let scope = self.scope(&func.location);
+        let path = type_name.split('<').next().unwrap_or(type_name).trim();
+
+        let first = path.split("::").next().unwrap_or(path);
+        let imported = self
+            .imports
+            .iter()
+            .filter(|import| import.imported_symbols.iter().any(|symbol| symbol == first))
+            .map(|import| format!("{}::{}", import.module_name, path));
+
+        std::iter::once(path.to_string())
+            .chain(imported)
+            .flat_map(|written: String| rust_path_candidates(self, scope, &written))
+            .find(|candidate| self.known_types.contains(candidate))
+            .or_else(|| Some(format!("{}::{}", scope, rust_type_name(type_name))))
+    }
+}
 
-                base.location = Some(type_def.location.clone());
-                base.documentation = type_def.documentation.clone();
+/// The name of a Rust type as written in an `impl` block, without its path
+/// or generic arguments, e.g. `Graph` for `crate::graph::Graph<T>`
+fn rust_type_name(type_name: &str) -> &str {
+    let type_name = type_name.split('<').next().unwrap_or(type_name).trim();
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
 
-                let type_entity = TypeEntity {
-                    base,
-                    fields: type_def
-                        .fields
-                        .iter()
-                        .map(|f| EntityId::new(&format!("field::{}::{}", key, f.name)))
-                        .collect(),
-                    methods: type_def
-                        .methods
-                        .iter()
-                        .map(|m| EntityId::new(&format!("method::{}::{}", key, m)))
-                        .collect(),
-                    supertypes: type_def
-                        .super_types
-                        .iter()
-                        .map(|s| EntityId::new(&format!("type::{}", s)))
-                        .collect(),
-                    visibility: type_def.visibility.clone(),
-                    is_abstract: false,
-                };
+/// The parsed files whose methods may belong to types defined in a file: the
+/// file itself, its paired C/C++ header or implementation file, and the other
+/// files of its Go package
+fn member_files<'a>(
+    parsed_files: &'a [ParsedFile],
+    file_path: &'a str,
+) -> impl Iterator<Item = &'a ParsedFile> {
+    let is_go = |file: &str| language_for_file(Path::new(file)) == Some("go");
+    let same_package = move |other: &str| {
+        is_go(file_path)
+            && is_go(other)
+            && Path::new(other).parent() == Path::new(file_path).parent()
+    };
+    let own_file = parsed_files
+        .iter()
+        .filter(move |other| other.path == file_path);
+    let others = parsed_files.iter().filter(move |other| {
+        other.path != file_path
+            && (is_header_pair(file_path, &other.path) || same_package(&other.path))
+    });
+    own_file.chain(others)
+}
 
-                kg.add_entity(type_entity)?;
+/// The package a Java file declares, which its module definition is named
+/// after; files in the default package are named after themselves
+fn java_package(parsed: &ParsedFile) -> Option<&str> {
+    let file_name = Path::new(&parsed.path).file_name()?.to_str()?;
+    (parsed.module.name != file_name).then_some(parsed.module.name.as_str())
+}
 
-                // Add fields as entities
-                for field in &type_def.fields {
-                    let field_id = EntityId::new(&format!("{}::field::{}", key, field.name));
+/// Walk the source tree below `path`, respecting ignore files and
+/// `.ummonignore`, and list every supported file the filter accepts
+///
+/// Filter globs match paths relative to `root`, the project root.
+pub fn walk_source_files(root: &str, path: &str, filter: &SourceFilter) -> Result<Vec<String>> {
+    let walker = WalkBuilder::new(path)
+        .hidden(false)
+        .ignore(true)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .build();
+    let mut files = Vec::new();
 
-                    let mut base = BaseEntity::new(
-                        field_id.clone(),
-                        field.name.clone(),
-                        EntityType::Field,
-                        Some(type_def.file_path.clone()),
-                    );
+    for entry in walker {
+        let entry = entry?;
+        let file = entry.path();
+        let relative = file.strip_prefix(root).unwrap_or(file);
 
-                    base.location = Some(field.location.clone());
-                    base.containing_entity = Some(entity_id.clone());
+        if file.is_file() && is_supported_source_file(file) && filter.matches(relative) {
+            files.push(file.to_string_lossy().to_string());
+        }
+    }
 
-                    let var_entity = VariableEntity {
-                        base,
-                        type_annotation: field.type_annotation.clone(),
-                        visibility: field.visibility.clone(),
-                        is_const: false,
-                        is_static: field.is_static,
-                    };
+    Ok(files)
+}
 
-                    kg.add_entity(var_entity)?;
-                }
-            }
+/// Record the content hash of a source file
+pub fn file_record(file_path: &str) -> Result<FileRecord> {
+    let path = Path::new(file_path);
+    let content = std::fs::read(path)?;
+
+    Ok(FileRecord {
+        path: file_path.to_string(),
+        hash: format!("{:x}", Sha256::digest(&content)),
+        size: content.len() as u64,
+        language: language_for_file(path).map(String::from),
+    })
+}
+
+/// Walk the source tree and record the content hash of every supported file
+fn scan_source_files(path: &str, filter: &SourceFilter) -> Result<HashMap<String, FileRecord>> {
+    walk_source_files(".", path, filter)?
+        .into_iter()
+        .map(|file| Ok((file.clone(), file_record(&file)?)))
+        .collect()
+}
 
-            // Extract domain concepts
-            let concepts = parser.infer_domain_concepts(&content, &file_path)?;
-            for concept in concepts {
-                domain_concepts.insert(concept.name.clone(), concept);
+/// Differences between the indexed files and the files in the working tree
+#[derive(Debug, Default, PartialEq)]
+pub struct FileChanges {
+    pub added: Vec<String>,
+    pub changed: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+impl FileChanges {
+    /// Compare file records by content hash, so files whose modification time
+    /// changed without their contents changing are left alone
+    pub fn diff(
+        previous: &HashMap<String, FileRecord>,
+        current: &HashMap<String, FileRecord>,
+    ) -> Self {
+        let mut changes = FileChanges::default();
+
+        for (path, record) in current {
+            match previous.get(path) {
+                None => changes.added.push(path.clone()),
+                Some(old) if old.hash != record.hash => changes.changed.push(path.clone()),
+                Some(_) => {}
             }
         }
+
+        changes.deleted = previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned()
+            .collect();
+
+        changes.added.sort();
+        changes.changed.sort();
+        changes.deleted.sort();
+        changes
     }
 
-    Ok(())
+    /// Treat every file that is still present as changed, so all of them are
+    /// indexed again without touching the graph outside these files
+    pub fn rebuild(
+        previous: &HashMap<String, FileRecord>,
+        current: &HashMap<String, FileRecord>,
+    ) -> Self {
+        let mut changes = FileChanges::diff(previous, current);
+        changes.changed = current
+            .keys()
+            .filter(|path| previous.contains_key(*path))
+            .cloned()
+            .collect();
+        changes.changed.sort();
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.deleted.is_empty()
+    }
 }
 
-/// Second pass: Build relationships between entities
-fn index_relationships(
-    path: &str,
+/// Add the entities of parsed files to the graph, in the order the files were given
+fn index_entities(
+    parsed_files: &[ParsedFile],
+    file_keys: &[EntityKeys],
     kg: &mut KnowledgeGraph,
-    _function_map: &HashMap<String, FunctionDefinition>,
-    type_map: &HashMap<String, TypeDefinition>,
-    _domain_concepts: &HashMap<String, DomainConcept>,
-    indexed_files: &HashSet<String>,
+    function_map: &mut HashMap<String, FunctionDefinition>,
+    type_map: &mut HashMap<String, TypeDefinition>,
+    domain_concepts: &mut HashMap<String, DomainConcept>,
+    variables: &mut Vec<SymbolRecord>,
 ) -> Result<()> {
-    let walker = WalkBuilder::new(path).hidden(false).ignore(true).build();
-
-    for entry in walker {
-        let entry = entry?;
-        let path = entry.path();
+    for (parsed, keys) in parsed_files.iter().zip(file_keys) {
+        let file_path = &parsed.path;
+        let module_info = &parsed.module;
+        let module_id = EntityId::new(keys.module_id());
+
+        // Named modules are named after the last segment of their name
+        let module_name = match &keys.module_name {
+            Some(name) => name.rsplit([':', '.']).next().unwrap_or(name),
+            None => &module_info.name,
+        };
+        let module_entity = ModuleEntity {
+            base: BaseEntity::new(
+                module_id.clone(),
+                module_name.to_string(),
+                EntityType::Module,
+                Some(file_path.clone()),
+            ),
+            path: file_path.clone(),
+            children: Vec::new(),
+            imports: module_info
+                .imports
+                .iter()
+                .map(|imp| imp.module_name.clone())
+                .collect(),
+        };
 
-        if !path.is_file() {
-            continue;
-        }
+        kg.add_entity(module_entity)?;
 
-        let file_path = path.to_string_lossy().to_string();
+        for (path, location) in &keys.inline_modules {
+            let name = path.rsplit("::").next().unwrap_or(path);
+            let mut base = BaseEntity::new(
+                EntityId::new(path),
+                name.to_string(),
+                EntityType::Module,
+                Some(file_path.clone()),
+            );
+            base.location = Some((*location).clone());
 
-        // Skip if not previously indexed
-        if !indexed_files.contains(&file_path) {
-            continue;
+            kg.add_entity(ModuleEntity {
+                base,
+                path: file_path.clone(),
+                children: Vec::new(),
+                imports: Vec::new(),
+            })?;
         }
 
-        // Skip if file has unsupported extension
-        if !is_supported_source_file(path) {
-            continue;
-        }
+        for ParsedFunction {
+            definition: func,
+            documentation,
+        } in &parsed.functions
+        {
+            let key = keys.function(func);
+            function_map.insert(key.clone(), func.clone());
+
+            let entity_id = EntityId::new(&key);
+            let entity_type = match func.kind {
+                crate::parser::language_support::FunctionKind::Function => EntityType::Function,
+                crate::parser::language_support::FunctionKind::Method => EntityType::Method,
+                crate::parser::language_support::FunctionKind::Constructor => EntityType::Method,
+                _ => EntityType::Function,
+            };
 
-        if let Ok(Some(mut parser)) = get_parser_for_file(path) {
-            let content = std::fs::read_to_string(path)?;
+            let mut base = BaseEntity::new(
+                entity_id.clone(),
+                func.name.clone(),
+                entity_type,
+                Some(func.file_path.clone()),
+            );
+
+            base.location = Some(func.location.clone());
+            base.documentation = documentation.clone();
+            base.containing_entity = if keys.rust_paths {
+                keys.method_type(func)
+                    .map(|type_key| EntityId::new(&format!("type::{}", type_key)))
+            } else if keys.java_types.is_some() {
+                func.containing_type
+                    .as_ref()
+                    .map(|t| EntityId::new(&format!("type::{}", keys.java_type(t))))
+            } else {
+                func.containing_type.as_ref().map(|t| {
+                    let type_file = member_files(parsed_files, file_path)
+                        .find(|other| other.types.iter().any(|type_def| type_def.name == *t))
+                        .map_or(file_path.as_str(), |other| other.path.as_str());
+                    EntityId::new(&format!("type::{}::{}", type_file, t))
+                })
+            };
 
-            // Process calls between functions
-            let calls = parser.parse_calls(&content, &file_path)?;
-            for call in calls {
-                // Add relationship between entities if they exist
-                // Create a more tolerant implementation that will try to make relationships even if exact IDs don't match
+            let function_entity = FunctionEntity {
+                base,
+                parameters: func.parameters.clone(),
+                return_type: func.return_type.clone(),
+                visibility: func.visibility.clone(),
+                is_async: false,
+                is_static: false,
+                is_constructor: func.kind
+                    == crate::parser::language_support::FunctionKind::Constructor,
+                is_abstract: false,
+                decorators: func.decorators.clone(),
+                containing_type: func.containing_type.clone(),
+            };
 
-                // First try with the callee function name being used in the calling file
-                let caller_id = EntityId::new(&format!("{}::{}", file_path, call.callee_name));
+            kg.add_entity(function_entity)?;
+        }
 
-                if let Some(callee_key) = &call.fully_qualified_name {
-                    // For the target, try to find the most likely target entity
-                    let callee_id = EntityId::new(callee_key);
+        for type_def in &parsed.types {
+            let key = keys.type_key(type_def);
+            type_map.insert(key.clone(), type_def.clone());
+
+            let entity_id = EntityId::new(&format!("type::{}", key));
+            let entity_type = match type_def.kind {
+                crate::parser::language_support::TypeKind::Class => EntityType::Class,
+                crate::parser::language_support::TypeKind::Struct => EntityType::Struct,
+                crate::parser::language_support::TypeKind::Interface => EntityType::Interface,
+                crate::parser::language_support::TypeKind::Trait => EntityType::Trait,
+                crate::parser::language_support::TypeKind::Enum => EntityType::Enum,
+                _ => EntityType::Type,
+            };
 
-                    if let Err(e) =
-                        kg.create_relationship(caller_id, callee_id, RelationshipType::Calls)
-                    {
-                        tracing::warn!("Failed to create call relationship: error: {}", e);
-                    }
-                }
-            }
+            let mut base = BaseEntity::new(
+                entity_id.clone(),
+                type_def.name.clone(),
+                entity_type,
+                Some(type_def.file_path.clone()),
+            );
+
+            base.location = Some(type_def.location.clone());
+            base.documentation = type_def.documentation.clone();
+
+            let type_entity = TypeEntity {
+                base,
+                fields: type_def
+                    .fields
+                    .iter()
+                    .map(|f| EntityId::new(&format!("field::{}::{}", key, f.name)))
+                    .collect(),
+                methods: type_def
+                    .methods
+                    .iter()
+                    .map(|m| EntityId::new(&format!("method::{}::{}", key, m)))
+                    .collect(),
+                supertypes: type_def
+                    .super_types
+                    .iter()
+                    .map(|s| EntityId::new(&format!("type::{}", s)))
+                    .collect(),
+                visibility: type_def.visibility.clone(),
+                is_abstract: false,
+                decorators: type_def.decorators.clone(),
+            };
 
-            // Create type inheritance/implementation relationships
-            for (key, type_def) in type_map {
-                if type_def.file_path == file_path {
-                    let type_id = EntityId::new(key);
-
-                    // Create relationships to super types
-                    for super_type in &type_def.super_types {
-                        let super_id = EntityId::new(super_type);
-
-                        // Determine if this is inheritance or implementation
-                        let rel_type = match type_def.kind {
-                            crate::parser::language_support::TypeKind::Class => {
-                                RelationshipType::Inherits
-                            }
-                            crate::parser::language_support::TypeKind::Struct => {
-                                RelationshipType::Inherits
-                            }
-                            _ => RelationshipType::Implements,
-                        };
+            kg.add_entity(type_entity)?;
 
-                        if let Err(e) = kg.create_relationship(type_id.clone(), super_id, rel_type)
-                        {
-                            tracing::warn!(
-                                "Failed to create inheritance relationship: error: {}",
-                                e
-                            );
-                        }
-                    }
+            for field in &type_def.fields {
+                let field_id = EntityId::new(&format!("{}::field::{}", key, field.name));
 
-                    // Create containment relationships to methods
-                    for method in &type_def.methods {
-                        let method_id = EntityId::new(&format!("{}::{}", key, method));
-                        if let Err(e) = kg.create_relationship(
-                            type_id.clone(),
-                            method_id,
-                            RelationshipType::Contains,
-                        ) {
-                            tracing::warn!(
-                                "Failed to create contains relationship (method): error: {}",
-                                e
-                            );
-                        }
-                    }
+                let mut base = BaseEntity::new(
+                    field_id.clone(),
+                    field.name.clone(),
+                    EntityType::Field,
+                    Some(type_def.file_path.clone()),
+                );
 
-                    // Create containment relationships to fields
-                    for field in &type_def.fields {
-                        let field_id = EntityId::new(&format!("{}::field::{}", key, field.name));
-                        if let Err(e) = kg.create_relationship(
-                            type_id.clone(),
-                            field_id,
-                            RelationshipType::Contains,
-                        ) {
-                            tracing::warn!(
-                                "Failed to create contains relationship (field): error: {}",
-                                e
-                            );
-                        }
-                    }
+                base.location = Some(field.location.clone());
+                base.containing_entity = Some(entity_id.clone());
+
+                // Static fields can be referenced from outside their type's instances
+                if field.is_static {
+                    variables.push(SymbolRecord {
+                        id: field_id.as_str().to_string(),
+                        name: field.name.clone(),
+                        entity_type: EntityType::Field,
+                        file_path: type_def.file_path.clone(),
+                        containing_type: Some(type_def.name.clone()),
+                    });
                 }
-            }
 
-            // Process the module's imports
-            let module_id = EntityId::new(&file_path);
-            let module_info = parser.parse_modules(&content, &file_path)?;
+                let var_entity = VariableEntity {
+                    base,
+                    type_annotation: field.type_annotation.clone(),
+                    initializer: field.default_value.clone(),
+                    visibility: field.visibility.clone(),
+                    is_const: false,
+                    is_static: field.is_static,
+                    decorators: field
+                        .annotations
+                        .iter()
+                        .map(|annotation| Decorator::parse(annotation))
+                        .collect(),
+                    containing_type: Some(type_def.name.clone()),
+                };
 
-            for import in &module_info.imports {
-                let imported_module_id = EntityId::new(&import.module_name);
-                if let Err(e) = kg.create_relationship(
-                    module_id.clone(),
-                    imported_module_id,
-                    RelationshipType::Imports,
-                ) {
-                    tracing::warn!("Failed to create imports relationship: error: {}", e);
-                }
+                kg.add_entity(var_entity)?;
             }
         }
+
+        for variable in &parsed.variables {
+            let key = keys.variable(variable);
+            let entity_type = if variable.is_const {
+                EntityType::Constant
+            } else {
+                EntityType::Variable
+            };
+            variables.push(SymbolRecord {
+                id: key.clone(),
+                name: variable.name.clone(),
+                entity_type: entity_type.clone(),
+                file_path: variable.file_path.clone(),
+                containing_type: None,
+            });
+
+            let mut base = BaseEntity::new(
+                EntityId::new(&key),
+                variable.name.clone(),
+                entity_type,
+                Some(variable.file_path.clone()),
+            );
+            base.location = Some(variable.location.clone());
+            base.documentation = variable.documentation.clone();
+            base.containing_entity = Some(EntityId::new(keys.scope(&variable.location)));
+
+            kg.add_entity(VariableEntity {
+                base,
+                type_annotation: variable.type_annotation.clone(),
+                initializer: variable.initializer.clone(),
+                visibility: variable.visibility.clone(),
+                is_const: variable.is_const,
+                is_static: variable.is_static,
+                decorators: Vec::new(),
+                containing_type: None,
+            })?;
+        }
+
+        for concept in &parsed.domain_concepts {
+            domain_concepts.insert(concept.name.clone(), concept.clone());
+        }
     }
 
     Ok(())
 }
 
-/// Get files that have been modified since the last indexing
-fn get_modified_files(path: &str, last_index_time: &str) -> Result<Vec<String>> {
-    let last_index_datetime = DateTime::parse_from_rfc3339(last_index_time)
-        .map_err(|e| anyhow::anyhow!("Failed to parse last index time: {}", e))?;
+/// Build relationships for parsed files, in the order the files were given
+fn index_relationships(
+    parsed_files: &[ParsedFile],
+    file_keys: &[EntityKeys],
+    kg: &mut KnowledgeGraph,
+    function_map: &HashMap<String, FunctionDefinition>,
+    resolver: &CallResolver,
+    unresolved: &mut BTreeSet<(String, String)>,
+) -> Result<()> {
+    let mut js_modules = JsModules::new(parsed_files);
+    for (parsed, keys) in parsed_files.iter().zip(file_keys) {
+        let file_path = &parsed.path;
+        let imports = &parsed.module.imports;
+        index_call_relationships(kg, parsed, keys, resolver, &mut js_modules, unresolved);
+        index_variable_references(kg, parsed, keys, resolver);
+        index_type_usages(kg, parsed, keys, resolver, unresolved);
+
+        for type_def in &parsed.types {
+            let key = keys.type_key(type_def);
+            let type_id = EntityId::new(&format!("type::{}", key));
+
+            let inheritance = match type_def.kind {
+                crate::parser::language_support::TypeKind::Class
+                | crate::parser::language_support::TypeKind::Struct => RelationshipType::Inherits,
+                _ => RelationshipType::Implements,
+            };
+            let super_types = type_def
+                .super_types
+                .iter()
+                .map(|super_type| (super_type, inheritance.clone()))
+                .chain(
+                    type_def
+                        .mixins
+                        .iter()
+                        .map(|mixin| (mixin, RelationshipType::Implements)),
+                );
 
-    let last_index_utc = last_index_datetime.with_timezone(&Utc);
-    let walker = WalkBuilder::new(path).hidden(false).ignore(true).build();
-    let mut modified_files = Vec::new();
+            for (super_type, rel_type) in super_types {
+                let resolution = resolver.resolve_type(super_type, file_path, imports);
+                if is_unsettled(resolution.as_ref()) {
+                    unresolved
+                        .insert((file_path.clone(), unqualified_name(super_type).to_string()));
+                }
 
-    for entry in walker {
-        let entry = entry?;
-        let path = entry.path();
+                let mut metadata = HashMap::new();
+                let (super_id, weight) = match resolution {
+                    Some(resolution) => {
+                        metadata.insert(
+                            "resolution".to_string(),
+                            resolution.strategy.as_str().to_string(),
+                        );
+                        (EntityId::new(&resolution.target_id), resolution.confidence)
+                    }
+                    None => {
+                        metadata.insert("resolution".to_string(), "unresolved".to_string());
+                        (EntityId::new(super_type), 0.0)
+                    }
+                };
+
+                if let Err(e) = kg.create_weighted_relationship(
+                    type_id.clone(),
+                    super_id,
+                    rel_type,
+                    weight,
+                    metadata,
+                ) {
+                    tracing::warn!("Failed to create inheritance relationship: error: {}", e);
+                }
+            }
 
-        if !path.is_file() || !is_supported_source_file(path) {
+            // Rust methods are linked to their types from the `impl` blocks
+            // below. Java methods are keyed by their type, and other methods
+            // belong to the type their `containing_type` names, which may be
+            // defined in a header or another file of the Go package.
+            let method_keys: Vec<String> = if keys.rust_paths {
+                Vec::new()
+            } else if keys.java_types.is_some() {
+                type_def
+                    .methods
+                    .iter()
+                    .map(|method| format!("{}.{}", key, method))
+                    .filter(|method_key| function_map.contains_key(method_key))
+                    .collect()
+            } else {
+                member_files(parsed_files, &type_def.file_path)
+                    .flat_map(|other| &other.functions)
+                    .map(|func| &func.definition)
+                    .filter(|func| func.containing_type.as_deref() == Some(&type_def.name))
+                    .map(|func| keys.function(func))
+                    .collect()
+            };
+            for method_key in method_keys {
+                let method_id = EntityId::new(&method_key);
+                if let Err(e) =
+                    kg.create_relationship(type_id.clone(), method_id, RelationshipType::Contains)
+                {
+                    tracing::warn!(
+                        "Failed to create contains relationship (method): error: {}",
+                        e
+                    );
+                }
+            }
+
+            for field in &type_def.fields {
+                let field_id = EntityId::new(&format!("{}::field::{}", key, field.name));
+                if let Err(e) =
+                    kg.create_relationship(type_id.clone(), field_id, RelationshipType::Contains)
+                {
+                    tracing::warn!(
+                        "Failed to create contains relationship (field): error: {}",
+                        e
+                    );
+                }
+            }
+        }
+
+        if keys.rust_paths {
+            index_rust_relationships(kg, parsed, keys, resolver)?;
+            continue;
+        }
+        if language_for_file(Path::new(file_path)) == Some("python") {
+            index_python_imports(kg, parsed, keys)?;
+            continue;
+        }
+        if keys.java_types.is_some() {
+            index_java_relationships(kg, parsed, keys)?;
+            continue;
+        }
+        if matches!(
+            language_for_file(Path::new(file_path)),
+            Some("javascript" | "typescript")
+        ) {
+            index_js_imports(kg, parsed, &mut js_modules)?;
             continue;
         }
 
-        if let Ok(metadata) = std::fs::metadata(path) {
-            if let Ok(modified) = metadata.modified() {
-                let modified_time = DateTime::<Utc>::from(modified);
+        let module_id = EntityId::new(file_path);
 
-                if modified_time > last_index_utc {
-                    modified_files.push(path.to_string_lossy().to_string());
-                }
+        for import in imports {
+            // Quoted C/C++ includes name a file relative to the including one
+            let imported_module_id = match resolve_include(file_path, import) {
+                Some(header) if kg.has_entity(&EntityId::new(&header))? => EntityId::new(&header),
+                _ => EntityId::new(&import.module_name),
+            };
+            if let Err(e) = kg.create_relationship(
+                module_id.clone(),
+                imported_module_id,
+                RelationshipType::Imports,
+            ) {
+                tracing::warn!("Failed to create imports relationship: error: {}", e);
             }
         }
     }
 
-    Ok(modified_files)
+    Ok(())
 }
 
-/// Index entities from a specific set of files
-fn index_specific_entities(
-    file_paths: &HashSet<String>,
+/// Build the relationships particular to a Rust file in a crate's module tree:
+/// its methods, `impl Trait for Type` blocks, `mod` declarations and `use`
+/// declarations
+fn index_rust_relationships(
     kg: &mut KnowledgeGraph,
-    function_map: &mut HashMap<String, FunctionDefinition>,
-    type_map: &mut HashMap<String, TypeDefinition>,
-    domain_concepts: &mut HashMap<String, DomainConcept>,
+    parsed: &ParsedFile,
+    keys: &EntityKeys,
+    resolver: &CallResolver,
 ) -> Result<()> {
-    for file_path in file_paths {
-        let path = Path::new(file_path);
-
-        if !path.is_file() || !is_supported_source_file(path) {
+    let file_path = &parsed.path;
+    let imports = &parsed.module.imports;
+
+    // A type's methods may be implemented in another module than the type
+    for ParsedFunction {
+        definition: func, ..
+    } in &parsed.functions
+    {
+        let (Some(type_key), Some(type_name)) =
+            (keys.method_type(func), func.containing_type.as_deref())
+        else {
             continue;
-        }
-
-        if let Ok(Some(mut parser)) = get_parser_for_file(path) {
-            let content = std::fs::read_to_string(path)?;
-
-            let module_info = parser.parse_modules(&content, file_path)?;
-            let module_id = EntityId::new(file_path);
-
-            let module_entity = ModuleEntity {
-                base: BaseEntity::new(
-                    module_id.clone(),
-                    module_info.name.clone(),
-                    EntityType::Module,
-                    Some(file_path.clone()),
-                ),
-                path: file_path.clone(),
-                children: Vec::new(),
-                imports: module_info
-                    .imports
-                    .iter()
-                    .map(|imp| imp.module_name.clone())
-                    .collect(),
-            };
-
-            kg.add_entity(module_entity)?;
-
-            let functions = parser.parse_functions(&content, file_path)?;
-            for func in functions {
-                let key = format!("{}::{}", func.file_path, func.name);
-                function_map.insert(key.clone(), func.clone());
+        };
+        let local_id = EntityId::new(&format!("type::{}", type_key));
+        let type_id = if kg.has_entity(&local_id)? {
+            local_id
+        } else {
+            match resolver.resolve_type(type_name, file_path, imports) {
+                Some(resolution) => EntityId::new(&resolution.target_id),
+                None => continue,
+            }
+        };
 
-                let entity_id = EntityId::new(&key);
-                let entity_type = match func.kind {
-                    crate::parser::language_support::FunctionKind::Function => EntityType::Function,
-                    crate::parser::language_support::FunctionKind::Method => EntityType::Method,
-                    crate::parser::language_support::FunctionKind::Constructor => {
-                        EntityType::Method
-                    }
-                    _ => EntityType::Function,
-                };
+        let method_id = EntityId::new(&keys.function(func));
+        if let Err(e) = kg.create_relationship(type_id, method_id, RelationshipType::Contains) {
+            tracing::warn!(
+                "Failed to create contains relationship (method): error: {}",
+                e
+            );
+        }
+    }
 
-                let doc = parser.extract_documentation(&content, &func.location)?;
+    for implementation in &parsed.implementations {
+        let scope = keys.scope(&implementation.location);
+        let resolve = |kg: &KnowledgeGraph, type_name: &str| {
+            resolve_rust_type(kg, keys, scope, type_name).map(|resolved| {
+                resolved.or_else(|| {
+                    resolver
+                        .resolve_type(type_name, file_path, imports)
+                        .map(|resolution| {
+                            (
+                                EntityId::new(&resolution.target_id),
+                                resolution.strategy.as_str(),
+                                resolution.confidence,
+                            )
+                        })
+                })
+            })
+        };
 
-                let mut base = BaseEntity::new(
-                    entity_id.clone(),
-                    func.name.clone(),
-                    entity_type,
-                    Some(func.file_path.clone()),
-                );
+        let Some((type_id, _, _)) = resolve(kg, &implementation.type_name)? else {
+            continue;
+        };
+        let (trait_id, strategy, weight) = resolve(kg, &implementation.trait_name)?
+            .unwrap_or_else(|| (EntityId::new(&implementation.trait_name), "unresolved", 0.0));
+        let metadata = HashMap::from([("resolution".to_string(), strategy.to_string())]);
+
+        if let Err(e) = kg.create_weighted_relationship(
+            type_id,
+            trait_id,
+            RelationshipType::Implements,
+            weight,
+            metadata,
+        ) {
+            tracing::warn!("Failed to create implements relationship: error: {}", e);
+        }
+    }
 
-                base.location = Some(func.location.clone());
-                base.documentation = doc;
-                base.containing_entity = func
-                    .containing_type
-                    .as_ref()
-                    .map(|t| EntityId::new(&format!("type::{}", t)));
+    // Modules contain the modules they declare, if those were indexed
+    for submodule in &parsed.module.submodules {
+        let child = format!("{}::{}", keys.module_id(), submodule.name);
+        let parent = child
+            .rsplit_once("::")
+            .map_or(keys.module_id(), |(parent, _)| parent);
+        let child_id = EntityId::new(&child);
+        if !kg.has_entity(&child_id)? {
+            continue;
+        }
+        if let Err(e) =
+            kg.create_relationship(EntityId::new(parent), child_id, RelationshipType::Contains)
+        {
+            tracing::warn!(
+                "Failed to create contains relationship (module): error: {}",
+                e
+            );
+        }
+    }
 
-                let function_entity = FunctionEntity {
-                    base,
-                    parameters: func.parameters.clone(),
-                    return_type: None,
-                    visibility: func.visibility.clone(),
-                    is_async: false,
-                    is_static: false,
-                    is_constructor: func.kind
-                        == crate::parser::language_support::FunctionKind::Constructor,
-                    is_abstract: false,
-                };
+    for import in imports {
+        let scope = keys.scope(&import.location);
+        let mut targets = Vec::new();
+        for symbol in &import.imported_symbols {
+            let path = match symbol.as_str() {
+                "*" | "self" => import.module_name.clone(),
+                symbol => format!("{}::{}", import.module_name, symbol),
+            };
+            targets.extend(resolve_rust_path(kg, keys, scope, &path)?);
+        }
+        if import.imported_symbols.is_empty() {
+            targets.extend(resolve_rust_path(kg, keys, scope, &import.module_name)?);
+        }
+        // Imports of external crates keep the module name as a placeholder
+        if targets.is_empty() {
+            targets.push(
+                resolve_rust_path(kg, keys, scope, &import.module_name)?
+                    .unwrap_or_else(|| EntityId::new(&import.module_name)),
+            );
+        }
 
-                kg.add_entity(function_entity)?;
+        for target in targets {
+            if let Err(e) =
+                kg.create_relationship(EntityId::new(scope), target, RelationshipType::Imports)
+            {
+                tracing::warn!("Failed to create imports relationship: error: {}", e);
             }
+        }
+    }
 
-            let types = parser.parse_types(&content, file_path)?;
-            for type_def in types {
-                let key = format!("{}::{}", type_def.file_path, type_def.name);
-                type_map.insert(key.clone(), type_def.clone());
-
-                let entity_id = EntityId::new(&format!("type::{}", key));
-                let entity_type = match type_def.kind {
-                    crate::parser::language_support::TypeKind::Class => EntityType::Class,
-                    crate::parser::language_support::TypeKind::Struct => EntityType::Struct,
-                    crate::parser::language_support::TypeKind::Interface => EntityType::Interface,
-                    crate::parser::language_support::TypeKind::Trait => EntityType::Trait,
-                    crate::parser::language_support::TypeKind::Enum => EntityType::Enum,
-                    _ => EntityType::Type,
-                };
-
-                let mut base = BaseEntity::new(
-                    entity_id.clone(),
-                    type_def.name.clone(),
-                    entity_type,
-                    Some(type_def.file_path.clone()),
-                );
-
-                base.location = Some(type_def.location.clone());
-                base.documentation = type_def.documentation.clone();
-
-                let type_entity = TypeEntity {
-                    base,
-                    fields: type_def
-                        .fields
-                        .iter()
-                        .map(|f| EntityId::new(&format!("field::{}::{}", key, f.name)))
-                        .collect(),
-                    methods: type_def
-                        .methods
-                        .iter()
-                        .map(|m| EntityId::new(&format!("method::{}::{}", key, m)))
-                        .collect(),
-                    supertypes: type_def
-                        .super_types
-                        .iter()
-                        .map(|s| EntityId::new(&format!("type::{}", s)))
-                        .collect(),
-                    visibility: type_def.visibility.clone(),
-                    is_abstract: false,
+    Ok(())
+}
+/// Create `Imports` relationships for a Python file's imports
+///
+/// Each imported name is linked to the submodule, function or class it names
+/// in the imported module, or else to the module itself. Modules that aren't
+/// part of the indexed code keep their name as an external placeholder.
+fn index_python_imports(
+    kg: &mut KnowledgeGraph,
+    parsed: &ParsedFile,
+    keys: &EntityKeys,
+) -> Result<()> {
+    let module_id = EntityId::new(keys.module_id());
+    for import in &parsed.module.imports {
+        let mut targets = Vec::new();
+        if let Some(module_file) = python_modules::resolve_import(&parsed.path, &import.module_name)
+        {
+            for symbol in import.imported_symbols.iter().filter(|s| *s != "*") {
+                let candidates = match python_modules::submodule_file(&module_file, symbol) {
+                    Some(submodule) => vec![python_modules::module_id(&submodule)],
+                    None => vec![
+                        format!("{}::{}", module_file, symbol),
+                        format!("type::{}::{}", module_file, symbol),
+                    ],
                 };
+                for candidate in candidates {
+                    let id = EntityId::new(&candidate);
+                    if kg.has_entity(&id)? {
+                        targets.push(id);
+                        break;
+                    }
+                }
+            }
 
-                kg.add_entity(type_entity)?;
-
-                for field in &type_def.fields {
-                    let field_id = EntityId::new(&format!("{}::field::{}", key, field.name));
+            let imported_module = EntityId::new(&python_modules::module_id(&module_file));
+            if targets.is_empty() && kg.has_entity(&imported_module)? {
+                targets.push(imported_module);
+            }
+        }
+        if targets.is_empty() {
+            targets.push(EntityId::new(&import.module_name));
+        }
 
-                    let mut base = BaseEntity::new(
-                        field_id.clone(),
-                        field.name.clone(),
-                        EntityType::Field,
-                        Some(type_def.file_path.clone()),
-                    );
+        for target in targets {
+            if let Err(e) =
+                kg.create_relationship(module_id.clone(), target, RelationshipType::Imports)
+            {
+                tracing::warn!("Failed to create imports relationship: error: {}", e);
+            }
+        }
+    }
 
-                    base.location = Some(field.location.clone());
-                    base.containing_entity = Some(entity_id.clone());
+    Ok(())
+}
 
-                    let var_entity = VariableEntity {
-                        base,
-                        type_annotation: field.type_annotation.clone(),
-                        visibility: field.visibility.clone(),
-                        is_const: false,
-                        is_static: field.is_static,
-                    };
+/// Build the relationships particular to a Java file in a package: its
+/// package containing its top-level types, and its imports
+///
+/// Imports point at the class, static method or static field they name, and
+/// wildcard imports at the package or at the class whose static members they
+/// import. Classes outside the indexed code, such as those of the JDK, keep
+/// their name as an external placeholder.
+fn index_java_relationships(
+    kg: &mut KnowledgeGraph,
+    parsed: &ParsedFile,
+    keys: &EntityKeys,
+) -> Result<()> {
+    let package_id = EntityId::new(keys.module_id());
+    for type_def in &parsed.types {
+        if type_def.containing_entity_name.is_some() {
+            continue;
+        }
+        let type_id = EntityId::new(&format!("type::{}", keys.type_key(type_def)));
+        if let Err(e) =
+            kg.create_relationship(package_id.clone(), type_id, RelationshipType::Contains)
+        {
+            tracing::warn!(
+                "Failed to create contains relationship (type): error: {}",
+                e
+            );
+        }
+    }
 
-                    kg.add_entity(var_entity)?;
+    for import in &parsed.module.imports {
+        let candidates = match import.module_name.strip_suffix(".*") {
+            Some(name) => vec![name.to_string(), format!("type::{}", name)],
+            None => {
+                let name = &import.module_name;
+                let mut candidates = vec![format!("type::{}", name), name.clone()];
+                if let Some((owner, member)) = name.rsplit_once('.') {
+                    candidates.push(format!("{}::field::{}", owner, member));
                 }
+                candidates
             }
+        };
 
-            let concepts = parser.infer_domain_concepts(&content, file_path)?;
-            for concept in concepts {
-                domain_concepts.insert(concept.name.clone(), concept);
+        let mut target = EntityId::new(&import.module_name);
+        for candidate in candidates {
+            let id = EntityId::new(&candidate);
+            if kg.has_entity(&id)? {
+                target = id;
+                break;
             }
         }
+
+        if let Err(e) =
+            kg.create_relationship(package_id.clone(), target, RelationshipType::Imports)
+        {
+            tracing::warn!("Failed to create imports relationship: error: {}", e);
+        }
     }
 
     Ok(())
 }
 
-/// Build relationships between entities for specific files
-fn index_specific_relationships(
-    file_paths: &HashSet<String>,
+/// Create `Imports` relationships for a JavaScript file's imports, `require`
+/// calls and re-exports
+///
+/// Each module is linked to the file it resolves to, and each name imported
+/// from it to the function, class or module defining it, following re-exports
+/// through other modules. Modules that aren't part of the indexed code keep
+/// their name as an external placeholder. TypeScript imports are resolved to
+/// files the same way.
+fn index_js_imports(
     kg: &mut KnowledgeGraph,
-    _function_map: &HashMap<String, FunctionDefinition>,
-    type_map: &HashMap<String, TypeDefinition>,
-    _domain_concepts: &HashMap<String, DomainConcept>,
+    parsed: &ParsedFile,
+    js_modules: &mut JsModules,
 ) -> Result<()> {
-    for file_path in file_paths {
-        let path = Path::new(file_path);
+    let module_id = EntityId::new(&parsed.path);
+    let mut targets = Vec::new();
+
+    for import in &parsed.module.imports {
+        let target = match js_modules::resolve_import(&parsed.path, &import.module_name) {
+            Some(file) if kg.has_entity(&EntityId::new(&file))? => file,
+            _ => import.module_name.clone(),
+        };
+        targets.push(EntityId::new(&target));
+    }
 
-        if !path.is_file() || !is_supported_source_file(path) {
+    for binding in &parsed.module.bindings {
+        let Some(module) = &binding.module_name else {
+            continue;
+        };
+        if binding.target == "*" {
             continue;
         }
+        let Some(module_file) = js_modules::resolve_import(&parsed.path, module) else {
+            continue;
+        };
 
-        if let Ok(Some(mut parser)) = get_parser_for_file(path) {
-            let content = std::fs::read_to_string(path)?;
-
-            let calls = parser.parse_calls(&content, file_path)?;
-            for call in calls {
-                let caller_id = EntityId::new(&format!("{}::{}", file_path, call.callee_name));
-
-                if let Some(callee_key) = &call.fully_qualified_name {
-                    let callee_id = EntityId::new(callee_key);
-
-                    if let Err(e) =
-                        kg.create_relationship(caller_id, callee_id, RelationshipType::Calls)
-                    {
-                        tracing::warn!("Failed to create call relationship: error: {}", e);
-                    }
-                }
+        let origin = js_modules.find_export(&module_file, &binding.target);
+        let candidates = match origin {
+            Some(ExportOrigin::Module(file)) => vec![file],
+            Some(ExportOrigin::Definition { file, name }) => js_definition_ids(&file, &name),
+            // Modules without `export` statements may still define the name
+            None => js_definition_ids(&module_file, &binding.target),
+        };
+        for candidate in candidates {
+            let id = EntityId::new(&candidate);
+            if kg.has_entity(&id)? {
+                targets.push(id);
+                break;
             }
+        }
+    }
 
-            for (key, type_def) in type_map {
-                if type_def.file_path == *file_path {
-                    let type_id = EntityId::new(key);
+    for target in targets {
+        if let Err(e) = kg.create_relationship(module_id.clone(), target, RelationshipType::Imports)
+        {
+            tracing::warn!("Failed to create imports relationship: error: {}", e);
+        }
+    }
 
-                    for super_type in &type_def.super_types {
-                        let super_id = EntityId::new(super_type);
+    Ok(())
+}
 
-                        let rel_type = match type_def.kind {
-                            crate::parser::language_support::TypeKind::Class
-                            | crate::parser::language_support::TypeKind::Struct => {
-                                RelationshipType::Inherits
-                            }
-                            _ => RelationshipType::Implements,
-                        };
+/// Bind a call in a JavaScript file to the function or class an import brings
+/// in under the called name, following re-exports to where it's defined
+///
+/// Both `helper()` after `import { helper } from './lib'` and `lib.helper()`
+/// after `import * as lib from './lib'` are bound this way.
+fn resolve_js_import(
+    kg: &KnowledgeGraph,
+    parsed: &ParsedFile,
+    js_modules: &mut JsModules,
+    call: &CallReference,
+) -> Option<CallResolution> {
+    let written = call
+        .fully_qualified_name
+        .as_deref()
+        .unwrap_or(&call.callee_name);
+    let (local, member) = match written.split_once('.') {
+        Some((local, member)) => (local, Some(member)),
+        None => (written, None),
+    };
+    let binding = parsed
+        .module
+        .bindings
+        .iter()
+        .find(|binding| !binding.is_export && binding.name == local)?;
+    let name = match (binding.target.as_str(), member) {
+        ("*", Some(member)) if !member.contains('.') => member,
+        (target, None) if target != "*" => target,
+        _ => return None,
+    };
 
-                        if let Err(e) = kg.create_relationship(type_id.clone(), super_id, rel_type)
-                        {
-                            tracing::warn!(
-                                "Failed to create inheritance relationship: error: {}",
-                                e
-                            );
-                        }
-                    }
+    let module_file = js_modules::resolve_import(&parsed.path, binding.module_name.as_deref()?)?;
+    let candidates = match js_modules.find_export(&module_file, name) {
+        Some(ExportOrigin::Definition { file, name }) => js_definition_ids(&file, &name),
+        Some(ExportOrigin::Module(_)) => return None,
+        // Modules without `export` statements may still define the name
+        None => js_definition_ids(&module_file, name),
+    };
+    let target_id = candidates
+        .into_iter()
+        .find(|id| kg.has_entity(&EntityId::new(id)).unwrap_or(false))?;
+
+    let strategy = ResolutionStrategy::Import;
+    Some(CallResolution {
+        target_id,
+        confidence: strategy.confidence(),
+        strategy,
+        candidates: Vec::new(),
+    })
+}
 
-                    for method in &type_def.methods {
-                        let method_id = EntityId::new(&format!("{}::{}", key, method));
-                        if let Err(e) = kg.create_relationship(
-                            type_id.clone(),
-                            method_id,
-                            RelationshipType::Contains,
-                        ) {
-                            tracing::warn!(
-                                "Failed to create contains relationship (method): error: {}",
-                                e
-                            );
-                        }
-                    }
+/// IDs an entity defined under a name in a JavaScript file may have: a
+/// function, an arrow function assigned to a variable, or a class
+fn js_definition_ids(file: &str, name: &str) -> Vec<String> {
+    vec![
+        format!("{}::{}", file, name),
+        format!("{}::arrow_{}", file, name),
+        format!("type::{}::{}", file, name),
+    ]
+}
 
-                    for field in &type_def.fields {
-                        let field_id = EntityId::new(&format!("{}::field::{}", key, field.name));
-                        if let Err(e) = kg.create_relationship(
-                            type_id.clone(),
-                            field_id,
-                            RelationshipType::Contains,
-                        ) {
-                            tracing::warn!(
-                                "Failed to create contains relationship (field): error: {}",
-                                e
-                            );
-                        }
-                    }
-                }
+/// Bind a Rust type named by its path, such as `Graph<T>` or
+/// `crate::graph::Graph`, to a type entity defined at that path
+fn resolve_rust_type(
+    kg: &KnowledgeGraph,
+    keys: &EntityKeys,
+    scope: &str,
+    type_name: &str,
+) -> Result<Option<(EntityId, &'static str, f32)>> {
+    let path = type_name.split('<').next().unwrap_or(type_name).trim();
+    for candidate in rust_path_candidates(keys, scope, path) {
+        let type_id = EntityId::new(&format!("type::{}", candidate));
+        if kg.has_entity(&type_id)? {
+            return Ok(Some((type_id, "path", 1.0)));
+        }
+    }
+    Ok(None)
+}
+
+/// Bind a path named in a Rust `use` declaration to the module, function or
+/// type entity defined at that path
+fn resolve_rust_path(
+    kg: &KnowledgeGraph,
+    keys: &EntityKeys,
+    scope: &str,
+    path: &str,
+) -> Result<Option<EntityId>> {
+    for candidate in rust_path_candidates(keys, scope, path) {
+        for id in [candidate.clone(), format!("type::{}", candidate)] {
+            let id = EntityId::new(&id);
+            if kg.has_entity(&id)? {
+                return Ok(Some(id));
             }
+        }
+    }
+    Ok(None)
+}
 
-            let module_id = EntityId::new(file_path);
-            let module_info = parser.parse_modules(&content, file_path)?;
+/// The full paths a Rust path used in a module may refer to, most likely first
+///
+/// `crate::`, `self::` and `super::` are relative to the crate and the module
+/// the path is used in. Other paths start either in that module or at the
+/// root of a crate.
+fn rust_path_candidates(keys: &EntityKeys, scope: &str, path: &str) -> Vec<String> {
+    let join = |base: &str, rest: &[&str]| {
+        std::iter::once(base)
+            .chain(rest.iter().copied())
+            .collect::<Vec<_>>()
+            .join("::")
+    };
+    let segments: Vec<&str> = path.split("::").collect();
 
-            for import in &module_info.imports {
-                let imported_module_id = EntityId::new(&import.module_name);
-                if let Err(e) = kg.create_relationship(
-                    module_id.clone(),
-                    imported_module_id,
-                    RelationshipType::Imports,
-                ) {
-                    tracing::warn!("Failed to create imports relationship: error: {}", e);
-                }
+    match segments[0] {
+        "crate" => {
+            let crate_name = keys.module_id().split("::").next().unwrap_or_default();
+            vec![join(crate_name, &segments[1..])]
+        }
+        "self" => vec![join(scope, &segments[1..])],
+        "super" => {
+            let supers = segments.iter().take_while(|s| **s == "super").count();
+            let mut module: Vec<&str> = scope.split("::").collect();
+            if supers >= module.len() {
+                return Vec::new();
             }
+            module.truncate(module.len() - supers);
+            vec![join(&module.join("::"), &segments[supers..])]
         }
+        _ => vec![join(scope, &segments), path.to_string()],
     }
-
-    Ok(())
 }
 
 /// Third pass: Infer domain model from code entities
 async fn infer_domain_model(
     kg: &mut KnowledgeGraph,
     domain_concepts: &mut HashMap<String, DomainConcept>,
-    enable_domain_extraction: bool,
-    domain_dir: &str,
+    options: &IndexOptions<'_>,
 ) -> Result<()> {
     use crate::graph::entity::{BaseEntity, DomainConceptEntity, EntityId, EntityType};
     use crate::graph::relationship::RelationshipType;
     use crate::parser::domain_model::DomainModelBuilder;
     use crate::prompt::domain_extraction::LlmModelExtractor;
+    use crate::prompt::llm_integration::get_llm_config;
 
     // Combine all extracted domain concepts into the graph (from language parsers)
     for (name, concept) in domain_concepts {
@@ -834,13 +1985,14 @@ async fn infer_domain_model(
     // Use LLM to extract domain entities from important files
     tracing::info!("Using LLM to extract domain models...");
 
-    if enable_domain_extraction {
+    if options.enable_domain_extraction {
         use std::collections::HashSet;
         use std::fs;
 
         use crate::parser::domain_model::EntityType as DomainEntityType;
 
-        let extractor = LlmModelExtractor::new();
+        let extractor = LlmModelExtractor::new(get_llm_config(options.llm));
+        let domain_dir = options.domain_dir;
         let mut processed_files = HashSet::new();
         let mut domain_entity_count = 0;
 
@@ -854,6 +2006,7 @@ async fn infer_domain_model(
             .ignore(true) // Respect .gitignore files
             .git_global(true) // Use global git ignore files
             .git_exclude(true) // Use git exclude files
+            .add_custom_ignore_filename(IGNORE_FILE)
             .build();
 
         for entry in walker {
@@ -1042,3 +2195,891 @@ async fn infer_domain_model(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(files: &[(&str, &str)]) -> HashMap<String, FileRecord> {
+        files
+            .iter()
+            .map(|(path, hash)| {
+                (
+                    path.to_string(),
+                    FileRecord {
+                        path: path.to_string(),
+                        hash: hash.to_string(),
+                        size: 0,
+                        language: None,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Write files below `root` and bring the graph in `db` up to date with them
+    async fn index_files(db: &Database, root: &Path, files: &[(&str, &str)]) {
+        for (file, content) in files {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let filter =
+            SourceFilter::new(&IndexSettings::default(), FilterPatterns::default()).unwrap();
+        let llm = LlmSettings::default();
+        let options = IndexOptions {
+            jobs: 1,
+            enable_domain_extraction: false,
+            domain_dir: "src",
+            filter: &filter,
+            llm: &llm,
+        };
+        let current = scan_source_files(root.to_str().unwrap(), &filter).unwrap();
+        let changes = FileChanges::diff(&db.load_file_records().unwrap(), &current);
+        update_graph(db, &changes, &current, &options)
+            .await
+            .unwrap();
+    }
+
+    /// The relationships of a type as `(source, target)` pairs, sorted and
+    /// with `root` cut from the IDs
+    fn edges(db: &Database, root: &Path, rel_type: RelationshipType) -> Vec<(String, String)> {
+        let root = format!("{}/", root.display());
+        let mut edges: Vec<_> = db
+            .load_relationships_by_type(&rel_type)
+            .unwrap()
+            .into_iter()
+            .map(|rel| {
+                (
+                    rel.source_id.as_str().replace(&root, ""),
+                    rel.target_id.as_str().replace(&root, ""),
+                )
+            })
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    /// A relationship as `(source, target, type, weight, metadata)`
+    type Edge = (String, String, String, String, BTreeMap<String, String>);
+
+    /// Every entity ID and every relationship with its weight and metadata,
+    /// sorted, to compare two graphs
+    fn graph(db: &Database) -> (Vec<String>, Vec<Edge>) {
+        let mut relationships: Vec<_> = db
+            .load_relationships()
+            .unwrap()
+            .into_iter()
+            .map(|rel| {
+                let metadata: BTreeMap<_, _> = rel.metadata.into_iter().collect();
+                (
+                    rel.source_id.as_str().to_string(),
+                    rel.target_id.as_str().to_string(),
+                    rel.relationship_type.to_string(),
+                    rel.weight.to_string(),
+                    metadata,
+                )
+            })
+            .collect();
+        relationships.sort();
+        let mut entities: Vec<_> = db
+            .load_entities()
+            .unwrap()
+            .iter()
+            .map(|entity| entity.id().as_str().to_string())
+            .collect();
+        entities.sort();
+        (entities, relationships)
+    }
+
+    #[test]
+    fn test_source_filter() {
+        let filter = SourceFilter::new(
+            &IndexSettings {
+                include: vec!["src/**".to_string()],
+                exclude: vec!["**/generated/**".to_string()],
+                languages: vec!["Rust".to_string(), "python".to_string()],
+                domain_dir: None,
+                queries: None,
+            },
+            FilterPatterns {
+                include: vec!["lib/**".to_string()],
+                exclude: vec!["**/test/**".to_string()],
+            },
+        )
+        .unwrap();
+
+        assert!(filter.matches(Path::new("src/lib.rs")));
+        assert!(filter.matches(Path::new("src/app/main.py")));
+        assert!(!filter.matches(Path::new("test/fixture.rs")));
+        assert!(!filter.matches(Path::new("src/generated/proto.rs")));
+        assert!(!filter.matches(Path::new("src/app.js")));
+        assert!(filter.matches(Path::new("lib/util.rs")));
+        assert!(!filter.matches(Path::new("lib/test/util_test.rs")));
+
+        let everything =
+            SourceFilter::new(&IndexSettings::default(), FilterPatterns::default()).unwrap();
+        assert!(everything.matches(Path::new("test/fixture.js")));
+    }
+
+    #[test]
+    fn test_walk_honours_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "src/lib.rs",
+            "src/gen/api.rs",
+            "vendor/dep.rs",
+            "test/fixture.py",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        std::fs::write(root.join(IGNORE_FILE), "vendor/\n").unwrap();
+
+        let db = Database::new(root.join("ummon.db")).unwrap();
+        assert_eq!(
+            FilterPatterns::load(&db).unwrap(),
+            FilterPatterns::default()
+        );
+
+        let patterns = FilterPatterns {
+            include: Vec::new(),
+            exclude: vec!["test/**".to_string(), "**/gen/**".to_string()],
+        };
+        patterns.save(&db).unwrap();
+        let stored = FilterPatterns::load(&db).unwrap();
+        assert_eq!(stored, patterns);
+
+        let filter = SourceFilter::new(&IndexSettings::default(), stored).unwrap();
+        let root_path = root.to_string_lossy().to_string();
+        let files = walk_source_files(&root_path, &root_path, &filter).unwrap();
+        assert_eq!(
+            files,
+            vec![root.join("src/lib.rs").to_string_lossy().to_string()]
+        );
+    }
+
+    #[test]
+    fn test_package_siblings() {
+        let indexed: Vec<String> = [
+            "svc/a.go",
+            "svc/b.go",
+            "svc/c.py",
+            "svc/Handler.java",
+            "svc/sub/d.go",
+            "lib/e.go",
+            "web/App.java",
+            "web/Routes.java",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect();
+        let changed = "svc/a.go".to_string();
+        let script = "lib/run.py".to_string();
+        let class = "web/App.java".to_string();
+
+        let siblings = package_siblings(&[&changed, &script, &class], indexed.iter());
+        assert_eq!(siblings, vec!["svc/b.go", "web/Routes.java"]);
+    }
+
+    #[test]
+    fn test_header_pairs() {
+        let indexed: Vec<String> = [
+            "include/server.hpp",
+            "src/server.cpp",
+            "src/server.rs",
+            "src/client.cpp",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect();
+        let changed = "src/server.cpp".to_string();
+
+        let pairs = header_pairs(&[&changed], indexed.iter());
+        assert_eq!(pairs, vec!["include/server.hpp"]);
+    }
+
+    #[test]
+    fn test_rust_path_candidates() {
+        let keys = EntityKeys {
+            file_path: "src/graph/entity.rs",
+            module_name: Some("app::graph::entity".to_string()),
+            rust_paths: true,
+            inline_modules: Vec::new(),
+            java_types: None,
+            imports: &[],
+            known_types: &BTreeSet::new(),
+        };
+        let scope = "app::graph::entity::tests";
+
+        assert_eq!(
+            rust_path_candidates(&keys, scope, "crate::db::Database"),
+            vec!["app::db::Database"]
+        );
+        assert_eq!(
+            rust_path_candidates(&keys, scope, "super::Entity"),
+            vec!["app::graph::entity::Entity"]
+        );
+        assert_eq!(
+            rust_path_candidates(&keys, scope, "super::super::KnowledgeGraph"),
+            vec!["app::graph::KnowledgeGraph"]
+        );
+        assert_eq!(
+            rust_path_candidates(&keys, scope, "self::fixtures"),
+            vec!["app::graph::entity::tests::fixtures"]
+        );
+        assert_eq!(
+            rust_path_candidates(&keys, scope, "serde::Serialize"),
+            vec![
+                "app::graph::entity::tests::serde::Serialize",
+                "serde::Serialize"
+            ]
+        );
+        assert!(rust_path_candidates(&keys, "app", "super::Entity").is_empty());
+    }
+
+    #[test]
+    fn test_file_changes_diff() {
+        let previous = records(&[("kept.rs", "1"), ("edited.rs", "2"), ("removed.rs", "3")]);
+        let current = records(&[("kept.rs", "1"), ("edited.rs", "4"), ("new.rs", "5")]);
+
+        let changes = FileChanges::diff(&previous, &current);
+        assert_eq!(changes.added, vec!["new.rs"]);
+        assert_eq!(changes.changed, vec!["edited.rs"]);
+        assert_eq!(changes.deleted, vec!["removed.rs"]);
+
+        assert!(FileChanges::diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_file_changes_rebuild() {
+        let previous = records(&[("kept.rs", "1"), ("edited.rs", "2"), ("removed.rs", "3")]);
+        let current = records(&[("kept.rs", "1"), ("edited.rs", "4"), ("new.rs", "5")]);
+
+        let changes = FileChanges::rebuild(&previous, &current);
+        assert_eq!(changes.added, vec!["new.rs"]);
+        assert_eq!(changes.changed, vec!["edited.rs", "kept.rs"]);
+        assert_eq!(changes.deleted, vec!["removed.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_with_database_outside_the_tree() {
+        let project = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(project.path()).unwrap();
+        let src = root.join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            root.join(crate::config::CONFIG_FILE),
+            "database = \"graph/ummon.db\"\n",
+        )
+        .unwrap();
+
+        // `database` in ummon.toml and `--db` both name a file called ummon.db
+        let configured = crate::config::Config::from_file(&root.join(crate::config::CONFIG_FILE))
+            .unwrap()
+            .database
+            .unwrap();
+        let explicit = elsewhere.path().join(crate::db::DATABASE_FILE);
+        for db_path in [configured, explicit] {
+            std::fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+            let db = Database::new(&db_path).unwrap();
+            assert_eq!(project_root(&db_path, &src).unwrap(), root);
+            assert_eq!(path_in_project(&root, &src).unwrap(), "./src");
+
+            index_files(&db, &root, &[("src/app.py", "def run():\n    pass\n")]).await;
+            let files: Vec<_> = db.load_file_records().unwrap().into_keys().collect();
+            assert_eq!(files, [format!("{}/src/app.py", root.display())]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_methods_belong_to_their_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                (
+                    "shapes/shapes.go",
+                    indoc::indoc! {"
+                        package shapes
+
+                        type Shape interface { Area() float64 }
+                        type Circle struct{ R float64 }
+                        type Square struct{ S float64 }
+
+                        func (c Circle) Area() float64 { return 3 * c.R * c.R }
+                    "},
+                ),
+                (
+                    "shapes/square.go",
+                    indoc::indoc! {"
+                        package shapes
+
+                        func (s *Square) Area() float64 { return s.S * s.S }
+                    "},
+                ),
+                (
+                    "app.py",
+                    indoc::indoc! {"
+                        class Invoice:
+                            def total(self): pass
+
+                        class Order:
+                            def total(self): pass
+                    "},
+                ),
+            ],
+        )
+        .await;
+
+        let methods: Vec<_> = edges(&db, dir.path(), RelationshipType::Contains)
+            .into_iter()
+            .filter(|(_, method)| !method.contains("::field::"))
+            .collect();
+        assert_eq!(
+            methods,
+            [
+                ("type::app.py::Invoice", "app.py::Invoice::total"),
+                ("type::app.py::Order", "app.py::Order::total"),
+                (
+                    "type::shapes/shapes.go::Circle",
+                    "shapes/shapes.go::Circle::Area"
+                ),
+                (
+                    "type::shapes/shapes.go::Square",
+                    "shapes/square.go::Square::Area"
+                ),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+
+        let area = db
+            .load_entity(&EntityId::new(&format!(
+                "{}/shapes/square.go::Square::Area",
+                dir.path().display()
+            )))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            area.containing_entity().map(|id| id.as_str().to_string()),
+            Some(format!(
+                "type::{}/shapes/shapes.go::Square",
+                dir.path().display()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incremental_index_matches_full_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let caller = ("app.py", "def main():\n    process()\n    jobs.cleanup()\n");
+        let callee = (
+            "jobs.py",
+            "def process():\n    pass\n\ndef cleanup():\n    pass\n",
+        );
+
+        let incremental = Database::new(root.join("incremental.db")).unwrap();
+        index_files(&incremental, root, &[caller]).await;
+        index_files(&incremental, root, &[callee]).await;
+
+        let full = Database::new(root.join("full.db")).unwrap();
+        index_files(&full, root, &[]).await;
+
+        assert_eq!(graph(&incremental), graph(&full));
+        assert_eq!(
+            edges(&incremental, root, RelationshipType::Calls),
+            [
+                ("app.py::main".to_string(), "jobs.py::cleanup".to_string()),
+                ("app.py::main".to_string(), "jobs.py::process".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unresolved_targets_are_external() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[(
+                "app.py",
+                "import json\n\ndef run(data):\n    return json.dumps(data)\n",
+            )],
+        )
+        .await;
+
+        let names = |entity_type: EntityType| {
+            let mut names: Vec<_> = db
+                .query_entities_by_type(&entity_type, None, vec![], None)
+                .unwrap()
+                .iter()
+                .map(|entity| entity.name().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(EntityType::Function), ["run"]);
+        assert_eq!(names(EntityType::External), ["json", "json.dumps"]);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_index_after_no_op_edit_matches_full_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // `a.rs` is indexed before the placeholder for `FromStr` is created
+        let level = indoc::indoc! {"
+            pub struct Level;
+
+            impl std::str::FromStr for Level {
+                type Err = ();
+                fn from_str(_: &str) -> Result<Self, ()> { Ok(Level) }
+            }
+
+            mod tests {
+                fn write() { std::fs::write(\"x\", \"y\").unwrap(); }
+                fn t() { write(); }
+            }
+        "};
+        let files = [
+            ("Cargo.toml", "[package]\nname = \"r\"\n"),
+            ("src/lib.rs", "pub mod a;\npub mod db;\npub mod x;\n"),
+            ("src/db.rs", "pub fn open() {}\n"),
+            ("src/x.rs", level),
+            (
+                "src/a.rs",
+                "use std::str::FromStr;\nuse crate::db;\n\npub fn run() { db::open(); }\n",
+            ),
+        ];
+
+        let incremental = Database::new(root.join("incremental.db")).unwrap();
+        index_files(&incremental, root, &files).await;
+        index_files(
+            &incremental,
+            root,
+            &[("src/db.rs", "pub fn open() {}\n// x\n")],
+        )
+        .await;
+
+        let full = Database::new(root.join("full.db")).unwrap();
+        index_files(&full, root, &[]).await;
+
+        assert_eq!(graph(&incremental), graph(&full));
+    }
+
+    #[tokio::test]
+    async fn test_trait_impl_methods_are_keyed_by_their_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                ("Cargo.toml", "[package]\nname = \"shop\"\n"),
+                ("src/lib.rs", "pub mod billing;\npub mod models;\n"),
+                ("src/models/mod.rs", "pub mod order;\n"),
+                ("src/models/order.rs", "pub struct Order;\n"),
+                (
+                    "src/billing.rs",
+                    indoc::indoc! {"
+                        use crate::models::order::Order;
+
+                        pub trait Total {
+                            fn total(&self) -> u32;
+                        }
+
+                        impl Total for Order {
+                            fn total(&self) -> u32 { 0 }
+                        }
+                    "},
+                ),
+            ],
+        )
+        .await;
+
+        assert!(
+            edges(&db, dir.path(), RelationshipType::Contains).contains(&(
+                "type::shop::models::order::Order".to_string(),
+                "shop::models::order::Order::total".to_string()
+            ))
+        );
+        let total = db
+            .load_entity(&EntityId::new("shop::models::order::Order::total"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            total.containing_entity().map(EntityId::as_str),
+            Some("type::shop::models::order::Order")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inline_module_functions_call_within_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        let tests = "mod tests {\n    fn write() {}\n    fn t() { write(); }\n}\n";
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                ("Cargo.toml", "[package]\nname = \"r\"\n"),
+                ("src/lib.rs", "pub mod m;\n"),
+                ("src/m/mod.rs", "pub mod x;\npub mod y;\n"),
+                ("src/m/x.rs", tests),
+                ("src/m/y.rs", tests),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            edges(&db, dir.path(), RelationshipType::Calls),
+            [
+                ("r::m::x::tests::t", "r::m::x::tests::write"),
+                ("r::m::y::tests::t", "r::m::y::tests::write"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_functions_use_their_signature_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[(
+                "shipping.py",
+                indoc::indoc! {"
+                    class Order:
+                        pass
+
+                    class Receipt:
+                        pass
+
+                    def ship(order: Order) -> Receipt:
+                        pass
+
+                    def reship(order: Order) -> Order:
+                        pass
+                "},
+            )],
+        )
+        .await;
+
+        let root = format!("{}/", dir.path().display());
+        let mut uses: Vec<_> = db
+            .load_relationships_by_type(&RelationshipType::Uses)
+            .unwrap()
+            .into_iter()
+            .map(|rel| {
+                (
+                    rel.source_id.as_str().replace(&root, ""),
+                    rel.target_id.as_str().replace(&root, ""),
+                    rel.metadata["role"].clone(),
+                )
+            })
+            .collect();
+        uses.sort();
+        assert_eq!(
+            uses,
+            [
+                (
+                    "shipping.py::reship",
+                    "type::shipping.py::Order",
+                    "param,return"
+                ),
+                ("shipping.py::ship", "type::shipping.py::Order", "param"),
+                ("shipping.py::ship", "type::shipping.py::Receipt", "return"),
+            ]
+            .map(|(a, b, c)| (a.to_string(), b.to_string(), c.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_functions_reference_module_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                ("config.py", "TIMEOUT = 30\n"),
+                (
+                    "app.py",
+                    indoc::indoc! {"
+                        from config import TIMEOUT
+
+                        count = 0
+
+                        def tick(step):
+                            global count
+                            count = count + step
+                            return TIMEOUT
+                    "},
+                ),
+            ],
+        )
+        .await;
+
+        let root = format!("{}/", dir.path().display());
+        let mut references: Vec<_> = db
+            .load_relationships_by_type(&RelationshipType::References)
+            .unwrap()
+            .into_iter()
+            .map(|rel| {
+                (
+                    rel.source_id.as_str().replace(&root, ""),
+                    rel.target_id.as_str().replace(&root, ""),
+                    rel.metadata["access"].clone(),
+                    rel.metadata["resolution"].clone(),
+                )
+            })
+            .collect();
+        references.sort();
+        assert_eq!(
+            references,
+            [
+                ("app.py::tick", "app.py::count", "write", "same_file"),
+                ("app.py::tick", "config.py::TIMEOUT", "read", "import"),
+            ]
+            .map(|(a, b, c, d)| (
+                a.to_string(),
+                b.to_string(),
+                c.to_string(),
+                d.to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_python_imports_point_at_what_they_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                ("app/__init__.py", ""),
+                ("app/models.py", "class User:\n    pass\n"),
+                ("app/api/__init__.py", ""),
+                ("app/api/views.py", "def index():\n    pass\n"),
+                (
+                    "app/api/routes.py",
+                    indoc::indoc! {"
+                        from ..models import User
+                        from . import views
+                        import app.models as m
+                        import requests
+                    "},
+                ),
+            ],
+        )
+        .await;
+
+        let imports: Vec<_> = edges(&db, dir.path(), RelationshipType::Imports)
+            .into_iter()
+            .filter(|(module, _)| module == "app.api.routes")
+            .collect();
+        assert_eq!(
+            imports,
+            [
+                ("app.api.routes", "app.api.views"),
+                ("app.api.routes", "app.models"),
+                ("app.api.routes", "requests"),
+                ("app.api.routes", "type::app/models.py::User"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_javascript_imports_follow_re_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                ("lib/helper.js", "export function helper() {}\n"),
+                ("lib/index.js", "export { helper } from './helper';\n"),
+                ("legacy.js", "function helper() {}\n"),
+                (
+                    "app.js",
+                    indoc::indoc! {"
+                        import { helper } from './lib';
+                        const fs = require('fs');
+
+                        function main() {
+                            helper();
+                        }
+                    "},
+                ),
+            ],
+        )
+        .await;
+
+        let imports: Vec<_> = edges(&db, dir.path(), RelationshipType::Imports)
+            .into_iter()
+            .filter(|(module, _)| module == "app.js")
+            .collect();
+        assert_eq!(
+            imports,
+            [
+                ("app.js", "fs"),
+                ("app.js", "lib/helper.js::helper"),
+                ("app.js", "lib/index.js"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+
+        // Neither `require` nor the helper defined elsewhere is called
+        let calls = db
+            .load_relationships_by_type(&RelationshipType::Calls)
+            .unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(
+            calls[0].target_id.as_str(),
+            format!("{}/lib/helper.js::helper", dir.path().display())
+        );
+        assert_eq!(calls[0].metadata["resolution"], "import");
+    }
+
+    #[tokio::test]
+    async fn test_typescript_imports_point_at_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                ("src/b.ts", "export class B {}\n"),
+                ("src/components/index.tsx", "export const View = 1;\n"),
+                (
+                    "src/a.ts",
+                    indoc::indoc! {"
+                        import { B } from './b';
+                        import { View } from './components';
+                        import React from 'react';
+                    "},
+                ),
+            ],
+        )
+        .await;
+
+        let imports: Vec<_> = edges(&db, dir.path(), RelationshipType::Imports)
+            .into_iter()
+            .filter(|(module, _)| module == "src/a.ts")
+            .collect();
+        assert_eq!(
+            imports,
+            [
+                ("src/a.ts", "react"),
+                ("src/a.ts", "src/b.ts"),
+                ("src/a.ts", "src/components/index.tsx"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cpp_includes_point_at_headers() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                ("src/util/log.h", "void log_line(const char* line);\n"),
+                ("src/net/socket.hpp", "class Socket {};\n"),
+                (
+                    "src/net/server.cpp",
+                    indoc::indoc! {r#"
+                        #include "socket.hpp"
+                        #include "../util/log.h"
+                        #include "config.h"
+                        #include <vector>
+
+                        void serve() {}
+                    "#},
+                ),
+            ],
+        )
+        .await;
+
+        let imports: Vec<_> = edges(&db, dir.path(), RelationshipType::Imports)
+            .into_iter()
+            .filter(|(module, _)| module == "src/net/server.cpp")
+            .collect();
+        assert_eq!(
+            imports,
+            [
+                ("src/net/server.cpp", "config.h"),
+                ("src/net/server.cpp", "src/net/socket.hpp"),
+                ("src/net/server.cpp", "src/util/log.h"),
+                ("src/net/server.cpp", "vector"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_java_imports_point_at_classes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("ummon.db")).unwrap();
+        index_files(
+            &db,
+            dir.path(),
+            &[
+                (
+                    "src/com/example/model/User.java",
+                    "package com.example.model;\n\npublic class User {}\n",
+                ),
+                (
+                    "src/com/example/util/Strings.java",
+                    indoc::indoc! {"
+                        package com.example.util;
+
+                        public class Strings {
+                            public static String capitalize(String s) { return s; }
+                        }
+                    "},
+                ),
+                (
+                    "src/com/example/web/UserController.java",
+                    indoc::indoc! {"
+                        package com.example.web;
+
+                        import com.example.model.User;
+                        import static com.example.util.Strings.capitalize;
+                        import java.util.List;
+
+                        public class UserController {}
+                    "},
+                ),
+            ],
+        )
+        .await;
+
+        let imports: Vec<_> = edges(&db, dir.path(), RelationshipType::Imports)
+            .into_iter()
+            .filter(|(module, _)| module == "com.example.web")
+            .collect();
+        assert_eq!(
+            imports,
+            [
+                ("com.example.web", "com.example.util.Strings.capitalize"),
+                ("com.example.web", "java.util.List"),
+                ("com.example.web", "type::com.example.model.User"),
+            ]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod assist;
 pub mod index;
 pub mod query;
+pub mod serve;
+pub mod watch;
@@ -0,0 +1,13 @@
+use anyhow::Result;
+use ummon::config::Config;
+use ummon::db::Database;
+use ummon::mcp::McpServer;
+
+/// Runs the MCP server on stdin/stdout until the client disconnects
+pub async fn run(db: &Database, config: &Config) -> Result<()> {
+    tracing::info!("Starting MCP server");
+
+    let server = McpServer::new(db.clone()).with_config(config.clone());
+
+    server.run_stdio().await
+}
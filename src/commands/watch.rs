@@ -0,0 +1,122 @@
+use anyhow::Result;
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::commands::index::{self, FileChanges, FilterPatterns, IndexOptions, SourceFilter};
+use crate::config::Config;
+use crate::db::{Database, FileRecord};
+use crate::parser::language_support::is_supported_source_file;
+
+/// Main entry point for the watch command
+///
+/// Brings the graph up to date, then re-indexes the files touched by each
+/// debounced burst of file system events until interrupted.
+pub async fn run(
+    db: &Database,
+    config: &Config,
+    path: &str,
+    debounce_ms: u64,
+    jobs: usize,
+) -> Result<()> {
+    let filter = SourceFilter::new(&config.index, FilterPatterns::load(db)?)?;
+    let options = IndexOptions {
+        jobs,
+        enable_domain_extraction: false,
+        domain_dir: config.index.domain_dir(),
+        filter: &filter,
+        llm: &config.llm,
+    };
+
+    // Catch up with changes made while nothing was watching
+    index::run(db, path, false, &options).await?;
+
+    let root = std::fs::canonicalize(path)?;
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        move |result: DebounceEventResult| {
+            let _ = tx.send(result);
+        },
+    )?;
+    debouncer.watcher().watch(&root, RecursiveMode::Recursive)?;
+
+    tracing::info!("Watching {} for changes. Press Ctrl-C to stop.", path);
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("Stopping watcher.");
+                break;
+            }
+            result = rx.recv() => match result {
+                Some(Ok(events)) => {
+                    // Events carry absolute paths; the graph is keyed by paths
+                    // relative to the project root, which is the working directory
+                    let changed: BTreeSet<String> = events
+                        .iter()
+                        .filter_map(|event| event.path.strip_prefix(&root).ok())
+                        .map(|relative| Path::new(path).join(relative).to_string_lossy().to_string())
+                        .collect();
+
+                    if let Err(e) = sync_paths(db, path, &changed, &options).await {
+                        tracing::error!("Failed to update the knowledge graph: {}", e);
+                    }
+                }
+                Some(Err(e)) => tracing::warn!("File watcher error: {}", e),
+                None => break,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-index the source files at or below the changed paths
+async fn sync_paths(
+    db: &Database,
+    path: &str,
+    changed: &BTreeSet<String>,
+    options: &IndexOptions<'_>,
+) -> Result<()> {
+    // Writes to other files, including the database itself, are of no interest
+    let changed: Vec<&Path> = changed
+        .iter()
+        .map(Path::new)
+        .filter(|p| !p.is_file() || is_supported_source_file(p))
+        .collect();
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    // An event on a directory stands for every file below it
+    let affected = |file: &str| changed.iter().any(|p| Path::new(file).starts_with(p));
+
+    let previous_files: HashMap<String, FileRecord> = db
+        .load_file_records()?
+        .into_iter()
+        .filter(|(file, _)| affected(file))
+        .collect();
+
+    // Walking the tree applies the same ignore rules as `ummon index`, but is
+    // only needed when something indexable may have appeared
+    let current_files: HashMap<String, FileRecord> = if changed.iter().any(|p| p.exists()) {
+        index::walk_source_files(".", path, options.filter)?
+            .into_iter()
+            .filter(|file| affected(file))
+            .map(|file| Ok((file.clone(), index::file_record(&file)?)))
+            .collect::<Result<_>>()?
+    } else {
+        HashMap::new()
+    };
+
+    let changes = FileChanges::diff(&previous_files, &current_files);
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    index::update_graph(db, &changes, &current_files, options).await
+}
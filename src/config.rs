@@ -0,0 +1,220 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file
+pub const CONFIG_FILE: &str = "ummon.toml";
+
+/// Default directory analyzed by LLM domain extraction
+pub const DEFAULT_DOMAIN_DIR: &str = "src";
+
+/// Project configuration, usually loaded from `ummon.toml`
+///
+/// Settings are layered in increasing order of precedence: built-in defaults,
+/// `ummon.toml`, environment variables, and finally command-line flags, which
+/// the CLI applies on top of the loaded configuration.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Path to the knowledge graph database, relative to the configuration file
+    pub database: Option<PathBuf>,
+    pub index: IndexSettings,
+    pub llm: LlmSettings,
+    pub relevance: RelevanceWeights,
+}
+
+/// Which files are indexed, and where domain extraction looks
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexSettings {
+    /// Glob patterns a file must match to be indexed; all files if empty
+    pub include: Vec<String>,
+    /// Glob patterns excluding files from the index
+    pub exclude: Vec<String>,
+    /// Languages to index, e.g. `["rust", "python"]`; all supported languages if empty
+    pub languages: Vec<String>,
+    /// Directory analyzed by LLM domain extraction
+    pub domain_dir: Option<String>,
+    /// Directory of tree-sitter query packs, relative to the configuration file
+    pub queries: Option<PathBuf>,
+}
+
+impl IndexSettings {
+    /// Directory analyzed by LLM domain extraction, `src` unless configured
+    pub fn domain_dir(&self) -> &str {
+        self.domain_dir.as_deref().unwrap_or(DEFAULT_DOMAIN_DIR)
+    }
+}
+
+/// LLM settings; anything left unset falls back to the provider's defaults
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmSettings {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub endpoint: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+}
+
+impl LlmSettings {
+    /// Override the provider and model with values given on the command line
+    pub fn with_overrides(mut self, provider: Option<String>, model: Option<String>) -> Self {
+        if provider.is_some() {
+            self.provider = provider;
+        }
+        if model.is_some() {
+            self.model = model;
+        }
+        self
+    }
+}
+
+/// Weights combining an entity's proximity to the seed entities with its
+/// centrality when the relevance agent ranks files
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RelevanceWeights {
+    pub proximity: f32,
+    pub centrality: f32,
+}
+
+impl Default for RelevanceWeights {
+    fn default() -> Self {
+        Self {
+            proximity: 0.7,
+            centrality: 0.3,
+        }
+    }
+}
+
+impl Config {
+    /// Load the configuration for the project containing the current directory
+    ///
+    /// The nearest `ummon.toml` in the current directory or its parents is
+    /// read if there is one, then environment variables are applied over it.
+    pub fn load() -> Result<Self> {
+        let cwd = std::env::current_dir()?;
+        let mut config = match find_config_file(&cwd) {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+
+        config.apply_env(|name| std::env::var(name).ok());
+        Ok(config)
+    }
+
+    /// Read a configuration file, resolving relative paths against its directory
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config: Config = toml::from_str(&content)
+            .with_context(|| format!("Invalid configuration in {}", path.display()))?;
+
+        if let Some(dir) = path.parent() {
+            config.database = config.database.map(|db| dir.join(db));
+            config.index.queries = config.index.queries.map(|queries| dir.join(queries));
+        }
+
+        tracing::debug!("Loaded configuration from {}", path.display());
+        Ok(config)
+    }
+
+    /// Override settings with the environment variables that are set
+    ///
+    /// `UMMON_DB`, `LLM_PROVIDER`, `LLM_MODEL`, `LLM_ENDPOINT` and
+    /// `DOMAIN_EXTRACTION_DIR` are recognized; empty values are ignored.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+
+        if let Some(db) = var(crate::db::DATABASE_ENV_VAR) {
+            self.database = Some(PathBuf::from(db));
+        }
+        if let Some(dir) = var("DOMAIN_EXTRACTION_DIR") {
+            self.index.domain_dir = Some(dir);
+        }
+        if let Some(provider) = var("LLM_PROVIDER") {
+            self.llm.provider = Some(provider);
+        }
+        if let Some(model) = var("LLM_MODEL") {
+            self.llm.model = Some(model);
+        }
+        if let Some(endpoint) = var("LLM_ENDPOINT") {
+            self.llm.endpoint = Some(endpoint);
+        }
+    }
+}
+
+/// Find the nearest `ummon.toml` in `start` or its parents
+fn find_config_file(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_config_precedence() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(CONFIG_FILE);
+        std::fs::write(
+            &config_path,
+            indoc::indoc! {r#"
+                database = "graph/ummon.db"
+
+                [index]
+                exclude = ["vendor/**"]
+                languages = ["rust"]
+                queries = "queries"
+
+                [llm]
+                provider = "ollama"
+                model = "llama3"
+                temperature = 0.5
+
+                [relevance]
+                proximity = 0.6
+                centrality = 0.4
+            "#},
+        )
+        .unwrap();
+
+        let nested = dir.path().join("src");
+        std::fs::create_dir(&nested).unwrap();
+        assert_eq!(find_config_file(&nested), Some(config_path.clone()));
+
+        let mut config = Config::from_file(&config_path).unwrap();
+        assert_eq!(config.database, Some(dir.path().join("graph/ummon.db")));
+        assert_eq!(config.index.exclude, vec!["vendor/**"]);
+        assert_eq!(config.index.queries, Some(dir.path().join("queries")));
+        assert_eq!(config.index.domain_dir(), DEFAULT_DOMAIN_DIR);
+        assert_eq!(config.llm.temperature, Some(0.5));
+        assert_eq!(config.relevance.proximity, 0.6);
+
+        // The environment overrides the file...
+        let env: HashMap<&str, &str> = [("LLM_MODEL", "codellama"), ("LLM_PROVIDER", "")].into();
+        config.apply_env(|name| env.get(name).map(|v| v.to_string()));
+        assert_eq!(config.llm.provider.as_deref(), Some("ollama"));
+        assert_eq!(config.llm.model.as_deref(), Some("codellama"));
+
+        // ...and command-line flags override both
+        let llm = config.llm.with_overrides(Some("openai".to_string()), None);
+        assert_eq!(llm.provider.as_deref(), Some("openai"));
+        assert_eq!(llm.model.as_deref(), Some("codellama"));
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join(CONFIG_FILE);
+        std::fs::write(&config_path, "[llm]\nprovder = \"openai\"\n").unwrap();
+
+        assert!(Config::from_file(&config_path).is_err());
+    }
+}
@@ -4,8 +4,8 @@ use log::{debug, error, info};
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::params;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
 use crate::graph::entity::{
     BaseEntity, DomainConceptEntity, DomainConceptEntityData, Entity, EntityId, EntityType,
@@ -14,11 +14,138 @@ use crate::graph::entity::{
 };
 use crate::graph::relationship::{Relationship, RelationshipId, RelationshipType};
 
+/// Environment variable naming the database file, used when `--db` isn't given
+pub const DATABASE_ENV_VAR: &str = "UMMON_DB";
+
+/// Directory marking a project root, which holds the project's database
+pub const PROJECT_DIR: &str = ".ummon";
+
+/// File name of the knowledge graph database
+pub const DATABASE_FILE: &str = "ummon.db";
+
 /// Get a database instance - this is a convenience method that just calls Database::new
 pub fn get_database(path: &str) -> Result<Database> {
     Database::new(path)
 }
 
+/// Open the project's database, creating its directory if needed
+///
+/// See [`resolve_database_path`] for how the location is chosen.
+pub fn open_database(explicit: Option<&Path>) -> Result<Database> {
+    let path = resolve_database_path(explicit)?;
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Indexing moves into the project root, so connections opened later
+    // must not depend on the working directory
+    Database::new(std::path::absolute(path)?)
+}
+
+/// The directory the file paths stored in a database are relative to
+///
+/// A database inside a `.ummon/` directory belongs to the directory holding
+/// it. Otherwise the root is the nearest directory from `start` upwards with
+/// an `ummon.toml`, a `.ummon/` directory or the legacy `ummon.db` in use,
+/// falling back to `start` itself, so a database stored elsewhere with
+/// `--db`, `UMMON_DB` or `database` doesn't move the root.
+pub fn project_root(db_path: &Path, start: &Path) -> Result<PathBuf> {
+    let db_path = std::path::absolute(db_path)?;
+    let start = std::path::absolute(start)?;
+
+    if let Some(parent) = db_path.parent() {
+        if parent.file_name() == Some(PROJECT_DIR.as_ref()) {
+            if let Some(root) = parent.parent() {
+                return Ok(root.to_path_buf());
+            }
+        }
+    }
+
+    let root = start.ancestors().find(|dir| {
+        dir.join(crate::config::CONFIG_FILE).is_file()
+            || dir.join(PROJECT_DIR).is_dir()
+            || dir.join(DATABASE_FILE) == db_path
+    });
+    Ok(root.unwrap_or(&start).to_path_buf())
+}
+
+/// Work out where the knowledge graph database lives
+///
+/// In order of precedence: an explicit path (the `--db` flag), the `UMMON_DB`
+/// environment variable, then the nearest `.ummon/` directory (or legacy
+/// `ummon.db` file) found walking up from the current directory. Without any
+/// of these, a new `.ummon/` directory in the current directory is used.
+pub fn resolve_database_path(explicit: Option<&Path>) -> Result<PathBuf> {
+    if let Some(path) = explicit {
+        return Ok(path.to_path_buf());
+    }
+
+    if let Some(path) = std::env::var_os(DATABASE_ENV_VAR).filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+
+    let cwd = std::env::current_dir()?;
+    Ok(discover_database(&cwd).unwrap_or_else(|| cwd.join(PROJECT_DIR).join(DATABASE_FILE)))
+}
+
+/// Find the database of the project containing `start`, like git finds `.git`
+fn discover_database(start: &Path) -> Option<PathBuf> {
+    start.ancestors().find_map(|dir| {
+        let project_dir = dir.join(PROJECT_DIR);
+        if project_dir.is_dir() {
+            return Some(project_dir.join(DATABASE_FILE));
+        }
+
+        let legacy = dir.join(DATABASE_FILE);
+        legacy.is_file().then_some(legacy)
+    })
+}
+
+/// A window into a larger result set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pagination {
+    /// Maximum number of rows to return, or all remaining rows if None
+    pub limit: Option<usize>,
+    /// Number of rows to skip
+    pub offset: usize,
+}
+
+impl Pagination {
+    pub fn new(limit: Option<usize>, offset: usize) -> Self {
+        Self { limit, offset }
+    }
+
+    /// Offset of the following page, if rows remain beyond this one
+    pub fn next_offset(&self, total: usize) -> Option<usize> {
+        let end = self.offset + self.limit?;
+        (end < total).then_some(end)
+    }
+}
+
+/// An indexed source file, used to detect changes between index runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRecord {
+    pub path: String,
+    /// Hex-encoded SHA-256 of the file contents
+    pub hash: String,
+    /// Size of the file in bytes
+    pub size: u64,
+    pub language: Option<String>,
+}
+
+/// A function, method, type or variable stored in the graph that call sites
+/// and references can resolve to
+#[derive(Debug, Clone)]
+pub struct SymbolRecord {
+    pub id: String,
+    pub name: String,
+    pub entity_type: EntityType,
+    pub file_path: String,
+    /// Name of the type a method or static field belongs to, as written where it's defined
+    pub containing_type: Option<String>,
+}
+
 /// Database wrapper for managing the SQLite knowledge graph storage with connection pooling
 pub struct Database {
     pool: Pool<SqliteConnectionManager>,
@@ -49,8 +176,10 @@ impl Database {
         let db_path = path.as_ref().to_string_lossy().to_string();
         info!("Initializing database connection pool at {}", db_path);
 
-        // Configure SQLite connection
-        let manager = SqliteConnectionManager::file(&path);
+        // Configure SQLite connection. Write-ahead logging lets other processes
+        // keep reading the graph while an indexer or watcher is writing to it
+        let manager = SqliteConnectionManager::file(&path)
+            .with_init(|conn| conn.execute_batch("PRAGMA journal_mode = WAL;"));
 
         // Build a connection pool with a max size of 10 connections
         let pool = Pool::builder().max_size(10).build(manager).map_err(|e| {
@@ -66,6 +195,11 @@ impl Database {
         Ok(db)
     }
 
+    /// Path of the database file
+    pub fn path(&self) -> &str {
+        &self.db_path
+    }
+
     /// Get a connection from the pool
     pub fn get_connection(&self) -> Result<r2d2::PooledConnection<SqliteConnectionManager>> {
         self.pool.get().map_err(|e| {
@@ -115,6 +249,19 @@ impl Database {
                 value TEXT
             );
             
+            CREATE TABLE IF NOT EXISTS files (
+                path TEXT PRIMARY KEY,
+                hash TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                language TEXT
+            );
+            
+            CREATE TABLE IF NOT EXISTS unresolved_names (
+                file_path TEXT NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (file_path, name)
+            );
+            
             CREATE INDEX IF NOT EXISTS idx_entity_name ON entities(name);
             CREATE INDEX IF NOT EXISTS idx_entity_type ON entities(entity_type);
             CREATE INDEX IF NOT EXISTS idx_entity_file_path ON entities(file_path);
@@ -126,11 +273,22 @@ impl Database {
             -- Add additional indexes to optimize common queries
             CREATE INDEX IF NOT EXISTS idx_entity_containing_entity ON entities(containing_entity);
             CREATE INDEX IF NOT EXISTS idx_entity_name_type ON entities(name, entity_type);
-            
-            -- Initialize version if needed (using OR IGNORE to avoid errors if already exists)
-            INSERT OR IGNORE INTO schema_version (version) VALUES (1);
+            CREATE INDEX IF NOT EXISTS idx_unresolved_name ON unresolved_names(name);
         "#})?;
 
+        // Initialize version if needed. Checking first keeps opening an existing
+        // database read-only, so it doesn't wait on a concurrent writer
+        let has_version: bool =
+            conn.query_row("SELECT EXISTS(SELECT 1 FROM schema_version)", [], |row| {
+                row.get(0)
+            })?;
+        if !has_version {
+            conn.execute(
+                "INSERT OR IGNORE INTO schema_version (version) VALUES (1)",
+                [],
+            )?;
+        }
+
         debug!("Database schema initialized successfully");
         Ok(())
     }
@@ -183,11 +341,18 @@ impl Database {
                 } else {
                     None
                 },
-                entity.metadata().get("documentation").map(|s| s.as_str()),
                 entity
-                    .metadata()
-                    .get("containing_entity")
-                    .map(|s| s.as_str()),
+                    .documentation()
+                    .or_else(|| { entity.metadata().get("documentation").map(|s| s.as_str()) }),
+                entity
+                    .containing_entity()
+                    .map(|id| id.as_str())
+                    .or_else(|| {
+                        entity
+                            .metadata()
+                            .get("containing_entity")
+                            .map(|s| s.as_str())
+                    }),
                 entity_data,
             ],
         ) {
@@ -311,6 +476,8 @@ impl Database {
                         is_static: data.is_static,
                         is_constructor: data.is_constructor,
                         is_abstract: data.is_abstract,
+                        decorators: data.decorators,
+                        containing_type: data.containing_type,
                     }),
                     Err(e) => {
                         error!(
@@ -327,6 +494,8 @@ impl Database {
                             is_static: default_data.is_static,
                             is_constructor: default_data.is_constructor,
                             is_abstract: default_data.is_abstract,
+                            decorators: default_data.decorators,
+                            containing_type: default_data.containing_type,
                         })
                     }
                 }
@@ -345,6 +514,7 @@ impl Database {
                         supertypes: data.supertypes,
                         visibility: data.visibility,
                         is_abstract: data.is_abstract,
+                        decorators: data.decorators,
                     }),
                     Err(e) => {
                         error!("Failed to parse TypeEntityData for entity {}: {}, using default values", 
@@ -357,6 +527,7 @@ impl Database {
                             supertypes: default_data.supertypes,
                             visibility: default_data.visibility,
                             is_abstract: default_data.is_abstract,
+                            decorators: default_data.decorators,
                         })
                     }
                 }
@@ -387,9 +558,12 @@ impl Database {
                     Ok(data) => Box::new(VariableEntity {
                         base,
                         type_annotation: data.type_annotation,
+                        initializer: data.initializer,
                         visibility: data.visibility,
                         is_const: data.is_const,
                         is_static: data.is_static,
+                        decorators: data.decorators,
+                        containing_type: data.containing_type,
                     }),
                     Err(e) => {
                         error!(
@@ -400,9 +574,12 @@ impl Database {
                         Box::new(VariableEntity {
                             base,
                             type_annotation: default_data.type_annotation,
+                            initializer: default_data.initializer,
                             visibility: default_data.visibility,
                             is_const: default_data.is_const,
                             is_static: default_data.is_static,
+                            decorators: default_data.decorators,
+                            containing_type: default_data.containing_type,
                         })
                     }
                 }
@@ -673,6 +850,215 @@ impl Database {
         Ok(())
     }
 
+    /// Load the records of all indexed files, keyed by path
+    pub fn load_file_records(&self) -> Result<HashMap<String, FileRecord>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare("SELECT path, hash, size, language FROM files")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(FileRecord {
+                path: row.get(0)?,
+                hash: row.get(1)?,
+                size: row.get::<_, i64>(2)? as u64,
+                language: row.get(3)?,
+            })
+        })?;
+
+        let mut records = HashMap::new();
+        for record in rows {
+            let record = record?;
+            records.insert(record.path.clone(), record);
+        }
+
+        debug!(
+            "Loaded {} file records from {}",
+            records.len(),
+            self.db_path
+        );
+        Ok(records)
+    }
+
+    /// Insert or update the records of indexed files in a single transaction
+    pub fn save_file_records(&self, records: &[FileRecord]) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+
+        for record in records {
+            tx.execute(
+                "INSERT OR REPLACE INTO files (path, hash, size, language) VALUES (?, ?, ?, ?)",
+                params![
+                    record.path,
+                    record.hash,
+                    record.size as i64,
+                    record.language
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        debug!("Saved {} file records", records.len());
+        Ok(())
+    }
+
+    /// Remove the records of files that are no longer indexed
+    pub fn remove_file_records(&self, file_paths: &[String]) -> Result<()> {
+        if file_paths.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.get_connection()?;
+        let placeholders = file_paths.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        conn.execute(
+            &format!("DELETE FROM files WHERE path IN ({})", placeholders),
+            rusqlite::params_from_iter(file_paths.iter()),
+        )?;
+        Ok(())
+    }
+
+    /// Find the files, other than the given ones, containing the source of a
+    /// relationship into an entity defined in one of the given files
+    pub fn files_with_relationships_into(&self, file_paths: &[String]) -> Result<Vec<String>> {
+        if file_paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.get_connection()?;
+        let placeholders = file_paths.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!(
+            "SELECT DISTINCT s.file_path FROM relationships r
+             JOIN entities s ON r.source_id = s.id
+             JOIN entities t ON r.target_id = t.id
+             WHERE t.file_path IN ({0}) AND s.file_path IS NOT NULL
+               AND s.file_path NOT IN ({0})
+             ORDER BY s.file_path",
+            placeholders
+        );
+
+        let params: Vec<&String> = file_paths.iter().chain(file_paths.iter()).collect();
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| row.get(0))?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<String>>>()?)
+    }
+
+    /// Record names that calls, supertypes and type annotations in a file
+    /// couldn't bind to a single definition, as `(file path, name)` pairs
+    pub fn save_unresolved_names(&self, names: &BTreeSet<(String, String)>) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO unresolved_names (file_path, name) VALUES (?1, ?2)",
+            )?;
+            for (file_path, name) in names {
+                stmt.execute(params![file_path, name])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Find the files with a call, supertype or type annotation that couldn't
+    /// be bound to a single definition of one of the given names
+    pub fn files_naming_unresolved(&self, names: &BTreeSet<String>) -> Result<Vec<String>> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.get_connection()?;
+        let placeholders = names.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!(
+            "SELECT DISTINCT file_path FROM unresolved_names WHERE name IN ({})
+             ORDER BY file_path",
+            placeholders
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(names.iter()), |row| row.get(0))?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<String>>>()?)
+    }
+
+    /// Forget the unresolved names recorded for the given files
+    pub fn remove_unresolved_names(&self, file_paths: &[String]) -> Result<()> {
+        if file_paths.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.get_connection()?;
+        let placeholders = file_paths.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        conn.execute(
+            &format!(
+                "DELETE FROM unresolved_names WHERE file_path IN ({})",
+                placeholders
+            ),
+            rusqlite::params_from_iter(file_paths.iter()),
+        )?;
+        Ok(())
+    }
+
+    /// Remove the relationships whose source is an entity defined in one of the given files,
+    /// leaving the entities and the relationships pointing at them in place
+    pub fn remove_relationships_from_files(&self, file_paths: &[String]) -> Result<()> {
+        if file_paths.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.get_connection()?;
+        let placeholders = file_paths.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        let sql = format!(
+            "DELETE FROM relationships WHERE source_id IN
+             (SELECT id FROM entities WHERE file_path IN ({}))",
+            placeholders
+        );
+        let removed = conn.execute(&sql, rusqlite::params_from_iter(file_paths.iter()))?;
+
+        debug!(
+            "Removed {} relationships from {} files",
+            removed,
+            file_paths.len()
+        );
+        Ok(())
+    }
+
+    /// Load the functions, methods, types, variables, constants and static
+    /// fields defined in indexed files
+    pub fn load_symbols(&self) -> Result<Vec<SymbolRecord>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, entity_type, file_path, json_extract(data, '$.containing_type')
+             FROM entities
+             WHERE file_path IS NOT NULL
+               AND (entity_type IN ('Function', 'Method', 'Class', 'Struct', 'Interface',
+                                    'Trait', 'Enum', 'Type', 'Variable', 'Constant')
+                    OR (entity_type = 'Field' AND json_extract(data, '$.is_static')))",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SymbolRecord {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                entity_type: parse_entity_type(&row.get::<_, String>(2)?),
+                file_path: row.get(3)?,
+                containing_type: row.get(4)?,
+            })
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Check whether an entity of the indexed code is stored in the database
+    ///
+    /// Placeholders for external targets don't count, so binding a name
+    /// doesn't depend on which placeholders earlier runs happened to create.
+    pub fn entity_exists(&self, id: &EntityId) -> Result<bool> {
+        let conn = self.get_connection()?;
+        let exists = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM entities WHERE id = ?1 AND entity_type != ?2)",
+            params![id.as_str(), EntityType::External.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
     /// Remove entities and relationships associated with specified files
     pub fn remove_entities_and_relationships_by_files(&self, file_paths: &[String]) -> Result<()> {
         if file_paths.is_empty() {
@@ -732,6 +1118,22 @@ impl Database {
         Ok(())
     }
 
+    /// Remove the external entities standing in for unresolved targets that
+    /// no relationship points at any more
+    pub fn remove_orphaned_placeholders(&self) -> Result<()> {
+        let conn = self.get_connection()?;
+        let removed = conn.execute(
+            "DELETE FROM entities
+             WHERE entity_type = ?1
+               AND NOT EXISTS (SELECT 1 FROM relationships WHERE target_id = entities.id)
+               AND NOT EXISTS (SELECT 1 FROM relationships WHERE source_id = entities.id)",
+            params![EntityType::External.to_string()],
+        )?;
+
+        debug!("Removed {} orphaned placeholders", removed);
+        Ok(())
+    }
+
     /// Purge all entities and relationships from the graph
     pub fn purge_graph(&self) -> Result<()> {
         let mut conn = self.get_connection()?;
@@ -743,17 +1145,23 @@ impl Database {
         // Delete all entities
         tx.execute("DELETE FROM entities", [])?;
 
+        // Forget which files were indexed so they are all indexed again
+        tx.execute("DELETE FROM files", [])?;
+        tx.execute("DELETE FROM unresolved_names", [])?;
+
         tx.commit()?;
         debug!("Purged all entities and relationships from the graph");
         Ok(())
     }
 
-    /// Query entities based on entity type and optional condition with parameters
+    /// Query entities based on entity type and optional condition with parameters.
+    /// When a page is given, results are ordered by ID so consecutive pages are stable.
     pub fn query_entities_by_type(
         &self,
         entity_type: &EntityType,
         condition: Option<&str>,
         params: Vec<Box<dyn rusqlite::types::ToSql>>,
+        page: Option<Pagination>,
     ) -> Result<Vec<Box<dyn Entity>>> {
         debug!(
             "Querying entities of type {:?} from {} with parameterized condition",
@@ -773,6 +1181,16 @@ impl Database {
             sql.push_str(cond);
         }
 
+        // Add paging - numeric values only, so no parameters needed
+        if let Some(page) = page {
+            sql.push_str(" ORDER BY id");
+            match page.limit {
+                Some(limit) => sql.push_str(&format!(" LIMIT {}", limit)),
+                None => sql.push_str(" LIMIT -1"),
+            }
+            sql.push_str(&format!(" OFFSET {}", page.offset));
+        }
+
         // Create a vector with entity_type as the first parameter
         let entity_type_str = entity_type.to_string();
 
@@ -788,6 +1206,31 @@ impl Database {
         self.load_entities_with_query(&sql, &all_params)
     }
 
+    /// Count entities matching an entity type and optional condition with parameters
+    pub fn count_entities_by_type(
+        &self,
+        entity_type: &EntityType,
+        condition: Option<&str>,
+        params: Vec<Box<dyn rusqlite::types::ToSql>>,
+    ) -> Result<usize> {
+        let mut sql = String::from("SELECT COUNT(*) FROM entities WHERE entity_type = ?");
+
+        if let Some(cond) = condition {
+            sql.push_str(" AND ");
+            sql.push_str(cond);
+        }
+
+        let entity_type_str = entity_type.to_string();
+        let mut all_params: Vec<&dyn rusqlite::types::ToSql> = vec![&entity_type_str];
+        for param in &params {
+            all_params.push(param.as_ref());
+        }
+
+        let conn = self.get_connection()?;
+        let count: i64 = conn.query_row(&sql, all_params.as_slice(), |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
     /// Find paths between entities using recursive CTEs in SQLite
     pub fn find_paths(
         &self,
@@ -808,11 +1251,7 @@ impl Database {
         let conn = self.get_connection()?;
 
         // Define direction condition based on parameter
-        let direction_condition = match direction {
-            "outbound" => "r.source_id = t.id",
-            "inbound" => "r.target_id = t.id",
-            _ => "(r.source_id = t.id OR r.target_id = t.id)", // both directions
-        };
+        let direction_condition = direction_join_condition(direction);
 
         // Define relationship type, max depth, and target filters with parameter placeholders
         let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
@@ -950,11 +1389,18 @@ impl Database {
                     } else {
                         None
                     },
-                    entity.metadata().get("documentation").map(|s| s.as_str()),
                     entity
-                        .metadata()
-                        .get("containing_entity")
-                        .map(|s| s.as_str()),
+                        .documentation()
+                        .or_else(|| { entity.metadata().get("documentation").map(|s| s.as_str()) }),
+                    entity
+                        .containing_entity()
+                        .map(|id| id.as_str())
+                        .or_else(|| {
+                            entity
+                                .metadata()
+                                .get("containing_entity")
+                                .map(|s| s.as_str())
+                        }),
                     entity_data,
                 ],
             ) {
@@ -1037,6 +1483,16 @@ impl Database {
     }
 }
 
+/// Join condition between a relationship `r` and a traversal row `t` for a
+/// direction of "outbound", "inbound" or anything else for both directions
+pub fn direction_join_condition(direction: &str) -> &'static str {
+    match direction {
+        "outbound" => "r.source_id = t.id",
+        "inbound" => "r.target_id = t.id",
+        _ => "(r.source_id = t.id OR r.target_id = t.id)", // both directions
+    }
+}
+
 /// Parse entity type from string representation
 pub fn parse_entity_type(type_str: &str) -> EntityType {
     match type_str {
@@ -1054,6 +1510,7 @@ pub fn parse_entity_type(type_str: &str) -> EntityType {
         "Constant" => EntityType::Constant,
         "DomainConcept" => EntityType::DomainConcept,
         "Type" => EntityType::Type,
+        "External" => EntityType::External,
         _ => {
             if type_str.starts_with("Other") {
                 // Extract the content between parentheses for Other type
@@ -1125,6 +1582,56 @@ mod tests {
         assert!(db_path.exists());
     }
 
+    #[test]
+    fn test_discover_database() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let nested = root.join("src").join("deep");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(discover_database(&nested), None);
+
+        // A legacy database file at the root is still found
+        std::fs::write(root.join(DATABASE_FILE), "").unwrap();
+        assert_eq!(discover_database(&nested), Some(root.join(DATABASE_FILE)));
+
+        // A `.ummon/` directory takes precedence over it
+        std::fs::create_dir(root.join(PROJECT_DIR)).unwrap();
+        let expected = root.join(PROJECT_DIR).join(DATABASE_FILE);
+        assert_eq!(discover_database(&nested), Some(expected.clone()));
+        assert_eq!(discover_database(root), Some(expected));
+
+        assert_eq!(
+            resolve_database_path(Some(Path::new("graph.db"))).unwrap(),
+            PathBuf::from("graph.db")
+        );
+    }
+
+    #[test]
+    fn test_project_root() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let nested = root.join("src").join("deep");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let project_db = root.join(PROJECT_DIR).join(DATABASE_FILE);
+        assert_eq!(project_root(&project_db, &nested).unwrap(), root);
+
+        // Without a project marker, the start directory is the root
+        let elsewhere = Path::new("/tmp/other").join(DATABASE_FILE);
+        assert_eq!(project_root(&elsewhere, &nested).unwrap(), nested);
+
+        // The legacy database in use marks its directory
+        let legacy = root.join(DATABASE_FILE);
+        assert_eq!(project_root(&legacy, &nested).unwrap(), root);
+
+        // A database named `ummon.db` stored elsewhere doesn't move the root
+        std::fs::write(root.join(crate::config::CONFIG_FILE), "").unwrap();
+        let configured = root.join("graph").join(DATABASE_FILE);
+        assert_eq!(project_root(&configured, &nested).unwrap(), root);
+        assert_eq!(project_root(&elsewhere, &nested).unwrap(), root);
+    }
+
     #[test]
     fn test_save_and_load_entity() {
         let dir = tempdir().unwrap();
@@ -1149,6 +1656,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         // Save entity
@@ -1247,6 +1756,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let class_id = EntityId::new("class::test");
@@ -1264,6 +1775,7 @@ mod tests {
             supertypes: vec![],
             visibility: Visibility::Public,
             is_abstract: false,
+            decorators: Vec::new(),
         };
 
         // Save entities
@@ -1346,6 +1858,8 @@ mod tests {
                     is_static: false,
                     is_constructor: false,
                     is_abstract: false,
+                    decorators: Vec::new(),
+                    containing_type: None,
                 };
 
                 // Save the entity
@@ -1374,6 +1888,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reads_during_write() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let writer = Database::new(&db_path).unwrap();
+        writer.set_metadata("version", "1").unwrap();
+
+        let conn = writer.get_connection().unwrap();
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+
+        // A second handle stands in for another process querying the graph
+        conn.execute_batch("BEGIN IMMEDIATE; UPDATE metadata SET value = '2';")
+            .unwrap();
+        let reader = Database::new(&db_path).unwrap();
+        assert_eq!(
+            reader.get_metadata("version").unwrap().as_deref(),
+            Some("1")
+        );
+
+        conn.execute_batch("COMMIT;").unwrap();
+        assert_eq!(
+            reader.get_metadata("version").unwrap().as_deref(),
+            Some("2")
+        );
+    }
+
     #[test]
     fn test_transaction_integrity() {
         // Test transaction integrity - all entities should be saved or none
@@ -1403,6 +1946,8 @@ mod tests {
                 is_static: false,
                 is_constructor: false,
                 is_abstract: false,
+                decorators: Vec::new(),
+                containing_type: None,
             };
 
             entities.push(Box::new(function));
@@ -1431,4 +1976,109 @@ mod tests {
         assert_eq!(loaded_entities.len(), 5);
         assert_eq!(loaded_relationships.len(), 1);
     }
+
+    #[test]
+    fn test_query_entities_by_type_paged() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).unwrap();
+
+        for i in 0..5 {
+            let base = BaseEntity::new(
+                EntityId::new(&format!("func{}", i)),
+                format!("function_{}", i),
+                EntityType::Function,
+                Some("test.rs".to_string()),
+            );
+            db.save_entity(&base).unwrap();
+        }
+
+        let page = Pagination::new(Some(2), 2);
+        let entities = db
+            .query_entities_by_type(&EntityType::Function, None, vec![], Some(page))
+            .unwrap();
+        let ids: Vec<&str> = entities.iter().map(|e| e.id().as_str()).collect();
+        assert_eq!(ids, vec!["func2", "func3"]);
+
+        let total = db
+            .count_entities_by_type(&EntityType::Function, None, vec![])
+            .unwrap();
+        assert_eq!(total, 5);
+        assert_eq!(page.next_offset(total), Some(4));
+        assert_eq!(Pagination::new(Some(2), 4).next_offset(total), None);
+        assert_eq!(Pagination::new(None, 0).next_offset(total), None);
+
+        let filtered = db
+            .count_entities_by_type(
+                &EntityType::Function,
+                Some("name LIKE ?"),
+                vec![Box::new("%_4".to_string())],
+            )
+            .unwrap();
+        assert_eq!(filtered, 1);
+    }
+
+    #[test]
+    fn test_file_records() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).unwrap();
+
+        let record = |path: &str, hash: &str| FileRecord {
+            path: path.to_string(),
+            hash: hash.to_string(),
+            size: 42,
+            language: Some("rust".to_string()),
+        };
+        db.save_file_records(&[record("a.rs", "1"), record("b.rs", "2")])
+            .unwrap();
+        db.save_file_records(&[record("a.rs", "3")]).unwrap();
+
+        let records = db.load_file_records().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records["a.rs"], record("a.rs", "3"));
+
+        db.remove_file_records(&["b.rs".to_string()]).unwrap();
+        assert!(!db.load_file_records().unwrap().contains_key("b.rs"));
+
+        db.purge_graph().unwrap();
+        assert!(db.load_file_records().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_relationships_across_files() {
+        let dir = tempdir().unwrap();
+        let db = Database::new(dir.path().join("test.db")).unwrap();
+
+        for (id, file) in [("caller", "a.rs"), ("callee", "b.rs"), ("helper", "b.rs")] {
+            let base = BaseEntity::new(
+                EntityId::new(id),
+                id.to_string(),
+                EntityType::Function,
+                Some(file.to_string()),
+            );
+            db.save_entity(&base).unwrap();
+        }
+        for (source, target) in [("caller", "callee"), ("callee", "helper")] {
+            let (source, target) = (EntityId::new(source), EntityId::new(target));
+            let id = Relationship::generate_id(&source, &target, &RelationshipType::Calls);
+            db.save_relationship(&Relationship::new(
+                id,
+                source,
+                target,
+                RelationshipType::Calls,
+            ))
+            .unwrap();
+        }
+
+        let dependents = db
+            .files_with_relationships_into(&["b.rs".to_string()])
+            .unwrap();
+        assert_eq!(dependents, vec!["a.rs".to_string()]);
+
+        db.remove_relationships_from_files(&["a.rs".to_string()])
+            .unwrap();
+        let remaining = db.load_relationships().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].source_id.as_str(), "callee");
+        assert!(db.entity_exists(&EntityId::new("caller")).unwrap());
+    }
 }
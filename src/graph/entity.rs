@@ -45,6 +45,68 @@ pub struct Parameter {
     pub default_value: Option<String>,
 }
 
+/// Decorator or annotation applied to a function, type or field, e.g. `@app.route("/users")`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Decorator {
+    /// Name as written, without arguments, e.g. `app.route`
+    #[serde(default)]
+    pub name: String,
+    /// Arguments as written, e.g. `"/users"` or `methods=["GET"]`
+    #[serde(default)]
+    pub arguments: Vec<String>,
+}
+
+impl Decorator {
+    /// Split an annotation written as source text, such as `@Column(name = "id")`
+    /// or `#[serde(default)]`, into its name and arguments
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let text = text
+            .strip_prefix("#[")
+            .and_then(|attribute| attribute.strip_suffix(']'))
+            .or_else(|| text.strip_prefix('@'))
+            .unwrap_or(text)
+            .trim();
+
+        let Some((name, rest)) = text.split_once('(') else {
+            return Self {
+                name: text.to_string(),
+                arguments: Vec::new(),
+            };
+        };
+        let inner = rest.strip_suffix(')').unwrap_or(rest);
+
+        // Split on commas outside nested brackets and strings
+        let mut arguments = Vec::new();
+        let mut depth = 0usize;
+        let mut quote = None;
+        let mut current = String::new();
+        for c in inner.chars() {
+            match (quote, c) {
+                (Some(q), _) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '(' | '[' | '{') => depth += 1,
+                (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+                (None, ',') if depth == 0 => {
+                    arguments.push(std::mem::take(&mut current).trim().to_string());
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(c);
+        }
+        if !current.trim().is_empty() {
+            arguments.push(current.trim().to_string());
+        }
+
+        Self {
+            name: name.trim().to_string(),
+            arguments,
+        }
+    }
+}
+
 /// Unique identifier for an entity
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct EntityId(pub String);
@@ -82,6 +144,8 @@ pub enum EntityType {
     Constant,
     DomainConcept,
     Type,
+    /// A call, import or type target outside the indexed code
+    External,
     Other(String),
 }
 
@@ -102,6 +166,7 @@ impl std::fmt::Display for EntityType {
             EntityType::Constant => write!(f, "Constant"),
             EntityType::DomainConcept => write!(f, "DomainConcept"),
             EntityType::Type => write!(f, "Type"),
+            EntityType::External => write!(f, "External"),
             EntityType::Other(s) => write!(f, "Other({})", s),
         }
     }
@@ -125,6 +190,16 @@ pub trait Entity {
     #[allow(dead_code)]
     fn metadata_mut(&mut self) -> &mut HashMap<String, String>;
 
+    /// Documentation attached to the entity
+    fn documentation(&self) -> Option<&str> {
+        None
+    }
+
+    /// ID of the entity containing this one, such as a method's type
+    fn containing_entity(&self) -> Option<&EntityId> {
+        None
+    }
+
     /// Serialize the entity data to a string for database storage
     /// Default implementation provides empty JSON object
     ///
@@ -200,6 +275,14 @@ impl Entity for BaseEntity {
         &mut self.metadata
     }
 
+    fn documentation(&self) -> Option<&str> {
+        self.documentation.as_deref()
+    }
+
+    fn containing_entity(&self) -> Option<&EntityId> {
+        self.containing_entity.as_ref()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -216,6 +299,9 @@ pub struct FunctionEntity {
     pub is_static: bool,
     pub is_constructor: bool,
     pub is_abstract: bool,
+    pub decorators: Vec<Decorator>,
+    /// Name of the type the function is a method of, as written where it's defined
+    pub containing_type: Option<String>,
 }
 
 /// Serializable data for function entities
@@ -235,6 +321,10 @@ pub struct FunctionEntityData {
     pub is_constructor: bool,
     #[serde(default)]
     pub is_abstract: bool,
+    #[serde(default)]
+    pub decorators: Vec<Decorator>,
+    #[serde(default)]
+    pub containing_type: Option<String>,
 }
 
 impl Entity for FunctionEntity {
@@ -266,6 +356,14 @@ impl Entity for FunctionEntity {
         &mut self.base.metadata
     }
 
+    fn documentation(&self) -> Option<&str> {
+        self.base.documentation.as_deref()
+    }
+
+    fn containing_entity(&self) -> Option<&EntityId> {
+        self.base.containing_entity.as_ref()
+    }
+
     fn serialize_data(&self) -> anyhow::Result<String> {
         let data = FunctionEntityData {
             parameters: self.parameters.clone(),
@@ -275,6 +373,8 @@ impl Entity for FunctionEntity {
             is_static: self.is_static,
             is_constructor: self.is_constructor,
             is_abstract: self.is_abstract,
+            decorators: self.decorators.clone(),
+            containing_type: self.containing_type.clone(),
         };
         serde_json::to_string(&data).map_err(Into::into)
     }
@@ -293,6 +393,7 @@ pub struct TypeEntity {
     pub supertypes: Vec<EntityId>,
     pub visibility: Visibility,
     pub is_abstract: bool,
+    pub decorators: Vec<Decorator>,
 }
 
 /// Serializable data for type entities
@@ -308,6 +409,8 @@ pub struct TypeEntityData {
     pub visibility: Visibility,
     #[serde(default)]
     pub is_abstract: bool,
+    #[serde(default)]
+    pub decorators: Vec<Decorator>,
 }
 
 impl Entity for TypeEntity {
@@ -339,6 +442,14 @@ impl Entity for TypeEntity {
         &mut self.base.metadata
     }
 
+    fn documentation(&self) -> Option<&str> {
+        self.base.documentation.as_deref()
+    }
+
+    fn containing_entity(&self) -> Option<&EntityId> {
+        self.base.containing_entity.as_ref()
+    }
+
     fn serialize_data(&self) -> anyhow::Result<String> {
         let data = TypeEntityData {
             fields: self.fields.clone(),
@@ -346,6 +457,7 @@ impl Entity for TypeEntity {
             supertypes: self.supertypes.clone(),
             visibility: self.visibility.clone(),
             is_abstract: self.is_abstract,
+            decorators: self.decorators.clone(),
         };
         serde_json::to_string(&data).map_err(Into::into)
     }
@@ -404,6 +516,14 @@ impl Entity for ModuleEntity {
         &mut self.base.metadata
     }
 
+    fn documentation(&self) -> Option<&str> {
+        self.base.documentation.as_deref()
+    }
+
+    fn containing_entity(&self) -> Option<&EntityId> {
+        self.base.containing_entity.as_ref()
+    }
+
     fn serialize_data(&self) -> anyhow::Result<String> {
         let data = ModuleEntityData {
             path: self.path.clone(),
@@ -423,9 +543,14 @@ impl Entity for ModuleEntity {
 pub struct VariableEntity {
     pub base: BaseEntity,
     pub type_annotation: Option<String>,
+    /// Source text of the initial or default value
+    pub initializer: Option<String>,
     pub visibility: Visibility,
     pub is_const: bool,
     pub is_static: bool,
+    pub decorators: Vec<Decorator>,
+    /// Name of the type the field belongs to, as written where it's declared
+    pub containing_type: Option<String>,
 }
 
 /// Serializable data for variable entities
@@ -434,11 +559,17 @@ pub struct VariableEntityData {
     #[serde(default)]
     pub type_annotation: Option<String>,
     #[serde(default)]
+    pub initializer: Option<String>,
+    #[serde(default)]
     pub visibility: Visibility,
     #[serde(default)]
     pub is_const: bool,
     #[serde(default)]
     pub is_static: bool,
+    #[serde(default)]
+    pub decorators: Vec<Decorator>,
+    #[serde(default)]
+    pub containing_type: Option<String>,
 }
 
 impl Entity for VariableEntity {
@@ -470,12 +601,23 @@ impl Entity for VariableEntity {
         &mut self.base.metadata
     }
 
+    fn documentation(&self) -> Option<&str> {
+        self.base.documentation.as_deref()
+    }
+
+    fn containing_entity(&self) -> Option<&EntityId> {
+        self.base.containing_entity.as_ref()
+    }
+
     fn serialize_data(&self) -> anyhow::Result<String> {
         let data = VariableEntityData {
             type_annotation: self.type_annotation.clone(),
+            initializer: self.initializer.clone(),
             visibility: self.visibility.clone(),
             is_const: self.is_const,
             is_static: self.is_static,
+            decorators: self.decorators.clone(),
+            containing_type: self.containing_type.clone(),
         };
         serde_json::to_string(&data).map_err(Into::into)
     }
@@ -538,6 +680,14 @@ impl Entity for DomainConceptEntity {
         &mut self.base.metadata
     }
 
+    fn documentation(&self) -> Option<&str> {
+        self.base.documentation.as_deref()
+    }
+
+    fn containing_entity(&self) -> Option<&EntityId> {
+        self.base.containing_entity.as_ref()
+    }
+
     fn serialize_data(&self) -> anyhow::Result<String> {
         let data = DomainConceptEntityData {
             attributes: self.attributes.clone(),
@@ -563,6 +713,23 @@ mod tests {
         assert_eq!(id.as_str(), "test::id");
     }
 
+    #[test]
+    fn test_decorator_parse() {
+        let column = Decorator::parse(r#"@Column(name = "user_id", length = max(1, 2))"#);
+        assert_eq!(column.name, "Column");
+        assert_eq!(
+            column.arguments,
+            vec![r#"name = "user_id""#, "length = max(1, 2)"]
+        );
+
+        let attribute = Decorator::parse("#[serde(default)]");
+        assert_eq!(attribute.name, "serde");
+        assert_eq!(attribute.arguments, vec!["default"]);
+
+        assert_eq!(Decorator::parse("@Autowired").name, "Autowired");
+        assert!(Decorator::parse("Key").arguments.is_empty());
+    }
+
     #[test]
     fn test_base_entity() {
         // Create a base entity
@@ -617,6 +784,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         // Check entity properties
@@ -656,6 +825,7 @@ mod tests {
             supertypes: vec![supertype_id],
             visibility: Visibility::Public,
             is_abstract: false,
+            decorators: Vec::new(),
         };
 
         // Check entity properties
@@ -711,15 +881,19 @@ mod tests {
         let variable = VariableEntity {
             base,
             type_annotation: Some("i32".to_string()),
+            initializer: Some("42".to_string()),
             visibility: Visibility::Private,
             is_const: true,
             is_static: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         // Check entity properties
         assert_eq!(variable.name(), "test_var");
         assert!(matches!(variable.entity_type(), EntityType::Variable));
         assert_eq!(variable.type_annotation, Some("i32".to_string()));
+        assert_eq!(variable.initializer.as_deref(), Some("42"));
         assert!(matches!(variable.visibility, Visibility::Private));
         assert!(variable.is_const);
         assert!(!variable.is_static);
@@ -784,6 +958,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         assert_eq!(function.file_path(), Some(&"function.rs".to_string()));
@@ -820,6 +996,7 @@ mod tests {
             EntityType::Constant,
             EntityType::DomainConcept,
             EntityType::Type,
+            EntityType::External,
             EntityType::Other("CustomType".to_string()),
         ];
 
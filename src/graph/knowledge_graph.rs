@@ -172,8 +172,8 @@ impl KnowledgeGraph {
 
         let metadata_values: Vec<String> = entity
             .metadata()
-            .iter()
-            .map(|(_key, v)| v.to_lowercase())
+            .values()
+            .map(|v| v.to_lowercase())
             .collect();
 
         self.entities.insert(id.clone(), Box::new(storage));
@@ -294,8 +294,8 @@ impl KnowledgeGraph {
         let metadata_values: Vec<String> = storage_ref
             .as_entity()
             .metadata()
-            .iter()
-            .map(|(_key, v)| v.to_lowercase())
+            .values()
+            .map(|v| v.to_lowercase())
             .collect();
 
         self.entities.insert(id.clone(), Box::new(storage));
@@ -365,19 +365,36 @@ impl KnowledgeGraph {
     /// Tries to locate both source and target entities in the graph.
     /// If the source entity doesn't exist, the relationship creation fails.
     /// If the target entity doesn't exist, it may be an external reference (like a standard library function),
-    /// so we create a placeholder BaseEntity to represent it.
+    /// so we create a placeholder `External` entity to represent it.
     pub fn create_relationship(
         &mut self,
         source_id: EntityId,
         target_id: EntityId,
         rel_type: RelationshipType,
+    ) -> Result<()> {
+        self.create_weighted_relationship(source_id, target_id, rel_type, 1.0, HashMap::new())
+    }
+
+    /// Create and add a relationship carrying a weight and metadata
+    ///
+    /// Behaves like `create_relationship`; the weight typically records how
+    /// confident the indexer is that the relationship is real.
+    pub fn create_weighted_relationship(
+        &mut self,
+        source_id: EntityId,
+        target_id: EntityId,
+        rel_type: RelationshipType,
+        weight: f32,
+        metadata: HashMap<String, String>,
     ) -> Result<()> {
         self.get_entity_result(&source_id)?;
 
-        // Check if target entity exists, if not, create a placeholder
-        if self.get_entity(&target_id).is_none() {
+        // Check if target entity exists, if not, create a placeholder. During an
+        // incremental update the target may live in a file that wasn't re-indexed.
+        if !self.has_entity(&target_id)? {
             // It might be a standard library or external reference
-            // Create a placeholder BaseEntity for the target
+            // Create a placeholder BaseEntity for the target, typed apart from
+            // indexed code so it doesn't show up as a function or module
             let target_name = target_id
                 .as_str()
                 .split("::")
@@ -386,8 +403,8 @@ impl KnowledgeGraph {
             let base_entity = crate::graph::entity::BaseEntity::new(
                 target_id.clone(),
                 target_name.to_string(),
-                crate::graph::entity::EntityType::Function, // Assume it's a function for now
-                None,                                       // No file path for external entities
+                crate::graph::entity::EntityType::External,
+                None, // No file path for external entities
             );
 
             // Add the placeholder entity to the graph
@@ -396,7 +413,9 @@ impl KnowledgeGraph {
 
         // Generate relationship ID
         let rel_id = Relationship::generate_id(&source_id, &target_id, &rel_type);
-        let relationship = Relationship::new(rel_id, source_id, target_id, rel_type);
+        let mut relationship = Relationship::new(rel_id, source_id, target_id, rel_type);
+        relationship.weight = weight;
+        relationship.metadata = metadata;
 
         // Add the relationship
         self.add_relationship(relationship);
@@ -404,6 +423,23 @@ impl KnowledgeGraph {
         Ok(())
     }
 
+    /// Whether an entity is saved in the graph's database
+    fn is_stored(&self, id: &EntityId) -> Result<bool> {
+        match &self.database {
+            Some(db) => db.entity_exists(id),
+            None => Ok(false),
+        }
+    }
+
+    /// Whether an entity of the indexed code was added to the graph or is
+    /// saved in its database; placeholders for external targets don't count
+    pub fn has_entity(&self, id: &EntityId) -> Result<bool> {
+        match self.get_entity(id) {
+            Some(entity) => Ok(entity.entity_type() != EntityType::External),
+            None => self.is_stored(id),
+        }
+    }
+
     /// Get relationships by source entity
     pub fn get_outgoing_relationships(&self, source_id: &EntityId) -> Vec<&Relationship> {
         self.relationship_store
@@ -586,6 +622,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let result = kg.add_entity(function);
@@ -635,6 +673,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let boxed_entity: Box<dyn Entity> = Box::new(function);
@@ -688,6 +728,7 @@ mod tests {
             supertypes: vec![supertype_id.clone()],
             visibility: Visibility::Public,
             is_abstract: true,
+            decorators: Vec::new(),
         };
 
         let boxed_entity: Box<dyn Entity> = Box::new(type_entity);
@@ -761,6 +802,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let id2 = EntityId::new("function2");
@@ -780,6 +823,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function1).unwrap();
@@ -823,6 +868,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let id2 = EntityId::new("concept1");
@@ -877,6 +924,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let id_b = EntityId::new("B");
@@ -896,6 +945,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let id_c = EntityId::new("C");
@@ -915,6 +966,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function_a).unwrap();
@@ -985,6 +1038,7 @@ mod tests {
             supertypes: vec![],
             visibility: Visibility::Public,
             is_abstract: false,
+            decorators: Vec::new(),
         };
 
         kg.add_entity(code_entity).unwrap();
@@ -1018,6 +1072,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function1).unwrap();
@@ -1038,6 +1094,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function2).unwrap();
@@ -1067,6 +1125,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function_a).unwrap();
@@ -1110,6 +1170,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let id_b = EntityId::new("B");
@@ -1129,6 +1191,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function_a).unwrap();
@@ -1169,6 +1233,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let func2_id = EntityId::new("func2");
@@ -1188,6 +1254,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let class_id = EntityId::new("class");
@@ -1205,6 +1273,7 @@ mod tests {
             supertypes: vec![],
             visibility: Visibility::Public,
             is_abstract: false,
+            decorators: Vec::new(),
         };
 
         kg.add_entity(function1).unwrap();
@@ -1260,6 +1329,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let base_b = BaseEntity::new(
@@ -1277,6 +1348,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let base_c = BaseEntity::new(
@@ -1294,6 +1367,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         let base_d = BaseEntity::new(
@@ -1311,6 +1386,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function_a).unwrap();
@@ -1412,6 +1489,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         kg.add_entity(function).unwrap();
@@ -1455,6 +1534,8 @@ mod tests {
                 is_static: false,
                 is_constructor: false,
                 is_abstract: false,
+                decorators: Vec::new(),
+                containing_type: None,
             };
 
             kg.add_entity(function).unwrap();
@@ -1,7 +1,9 @@
 // Expose modules as public for use by other crates
 pub mod agent;
+pub mod config;
 pub mod db;
 pub mod graph;
+pub mod mcp;
 pub mod parser;
 pub mod prompt;
 pub mod query;
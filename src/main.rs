@@ -1,10 +1,7 @@
 mod cli;
 mod commands;
-mod db;
-mod graph;
-mod parser;
-mod prompt;
-mod query;
+
+use ummon::{config, db, graph, parser, prompt, query};
 
 use anyhow::Result;
 use clap::Parser;
@@ -23,6 +20,22 @@ async fn main() -> Result<()> {
 
     let cli = cli::Cli::parse();
 
+    // Command-line flags take precedence over the environment and ummon.toml
+    let mut config = config::Config::load()?;
+    if let Some(db_path) = cli.db {
+        config.database = Some(db_path.into());
+    }
+
+    // Query packs must be in place before any parser is created
+    if let Some(queries) = &config.index.queries {
+        let count = parser::language_support::query_pack::register_query_packs(queries)?;
+        tracing::debug!("Loaded {} query packs from {}", count, queries.display());
+    }
+
+    // Every command shares one handle to the project's database
+    let db = db::open_database(config.database.as_deref())?;
+    tracing::debug!("Using database at {}", db.path());
+
     match cli.command {
         cli::Commands::Index {
             path,
@@ -31,46 +44,88 @@ async fn main() -> Result<()> {
             domain_dir,
             llm_provider,
             llm_model,
+            jobs,
+            include,
+            exclude,
         } => {
-            commands::index::run(
-                &path,
-                full,
+            let llm = config.llm.with_overrides(llm_provider, llm_model);
+            let path = commands::index::enter_project_root(&db, &path)?;
+
+            // Without globs on the command line, an incremental run keeps the
+            // ones the graph was built with
+            let mut patterns = commands::index::FilterPatterns { include, exclude };
+            if patterns.is_empty() && !full {
+                patterns = commands::index::FilterPatterns::load(&db)?;
+                if !patterns.is_empty() {
+                    tracing::info!(
+                        "Using include {:?} and exclude {:?} patterns from the last index",
+                        patterns.include,
+                        patterns.exclude
+                    );
+                }
+            }
+            let filter = commands::index::SourceFilter::new(&config.index, patterns)?;
+            let options = commands::index::IndexOptions {
+                jobs,
                 enable_domain_extraction,
-                &domain_dir,
-                llm_provider.as_deref(),
-                llm_model.as_deref(),
-            )
-            .await?
+                domain_dir: domain_dir
+                    .as_deref()
+                    .unwrap_or_else(|| config.index.domain_dir()),
+                filter: &filter,
+                llm: &llm,
+            };
+
+            commands::index::run(&db, &path, full, &options).await?
         }
         cli::Commands::Query {
             query,
             format,
+            type_filter,
+            path,
+            exact,
             no_llm,
             limit,
+            offset,
             llm_provider,
             llm_model,
-            ..
         } => {
-            // Use the natural flag as the opposite of no_llm
-            let natural = !no_llm;
-
-            commands::query::run(
-                &query,
-                &format,
-                natural,
+            let options = query::QueryOptions {
+                format,
+                // Use the natural flag as the opposite of no_llm
+                natural: !no_llm,
+                filters: query::QueryFilters::new(type_filter.as_deref(), path.as_deref(), exact)?,
+                llm: config.llm.with_overrides(llm_provider, llm_model),
                 limit,
-                llm_provider.as_deref(),
-                llm_model.as_deref(),
-            )
-            .await?
+                offset,
+            };
+
+            commands::query::run(&db, &query, options).await?
         }
         cli::Commands::Assist {
             instruction,
+            suggest_files,
+            top,
+            format,
             llm_provider,
             llm_model,
         } => {
-            commands::assist::run(&instruction, llm_provider.as_deref(), llm_model.as_deref())
-                .await?
+            config.llm = config.llm.with_overrides(llm_provider, llm_model);
+            let options = commands::assist::AssistOptions {
+                suggest_files,
+                top,
+                format: &format,
+            };
+
+            commands::assist::run(&db, &config, &instruction, options).await?
+        }
+        cli::Commands::Serve => commands::serve::run(&db, &config).await?,
+        cli::Commands::Watch {
+            path,
+            debounce_ms,
+            jobs,
+        } => {
+            let path = commands::index::enter_project_root(&db, &path)?;
+            commands::watch::run(&db, &config, &path, debounce_ms, jobs).await?
         }
     }
 
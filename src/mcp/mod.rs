@@ -0,0 +1,548 @@
+//! Model Context Protocol server exposing the knowledge graph over stdio JSON-RPC.
+//!
+//! Messages are newline-delimited JSON-RPC 2.0 objects read from stdin; responses are
+//! written to stdout one per line. All logging goes to stderr so it never corrupts the
+//! protocol stream.
+
+use anyhow::{anyhow, Result};
+use rusqlite::types::ToSql;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::agent::relevance_agent::suggest_relevant_files;
+use crate::config::Config;
+use crate::db::{parse_entity_type, parse_relationship_type, Database, Pagination};
+use crate::graph::entity::{Entity, EntityId, EntityType};
+use crate::query::{parse_query, DbQueryExecutor};
+
+/// Protocol revision advertised during `initialize`
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Default number of results returned by the search tool
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Entity types considered by free-text search
+const SEARCHABLE_TYPES: [EntityType; 12] = [
+    EntityType::Function,
+    EntityType::Method,
+    EntityType::Class,
+    EntityType::Interface,
+    EntityType::Trait,
+    EntityType::Struct,
+    EntityType::Enum,
+    EntityType::Type,
+    EntityType::Module,
+    EntityType::Variable,
+    EntityType::Constant,
+    EntityType::DomainConcept,
+];
+
+// Standard JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// MCP server answering tool calls against a knowledge graph database
+pub struct McpServer {
+    db: Database,
+    config: Config,
+}
+
+impl McpServer {
+    pub fn new(db: Database) -> Self {
+        Self {
+            db,
+            config: Config::default(),
+        }
+    }
+
+    /// Use the project's LLM and relevance settings when answering tool calls
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Serve requests from stdin until it is closed
+    pub async fn run_stdio(&self) -> Result<()> {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+
+        tracing::info!("MCP server listening on stdio");
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(response) = self.handle_message(&line).await {
+                let mut payload = serde_json::to_string(&response)?;
+                payload.push('\n');
+                stdout.write_all(payload.as_bytes()).await?;
+                stdout.flush().await?;
+            }
+        }
+
+        tracing::info!("stdin closed, shutting down MCP server");
+        Ok(())
+    }
+
+    /// Handle a single raw JSON-RPC message, returning the response to send (if any)
+    pub async fn handle_message(&self, message: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(message) {
+            Ok(value) => value,
+            Err(e) => {
+                return Some(error_response(
+                    Value::Null,
+                    PARSE_ERROR,
+                    &format!("Parse error: {}", e),
+                ))
+            }
+        };
+
+        let id = request.get("id").cloned();
+        let method = match request.get("method").and_then(Value::as_str) {
+            Some(method) => method,
+            None => {
+                return Some(error_response(
+                    id.unwrap_or(Value::Null),
+                    INVALID_REQUEST,
+                    "Request is missing a method",
+                ))
+            }
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        tracing::debug!("Received MCP request: {}", method);
+
+        // Notifications carry no id and never get a response
+        let id = match id {
+            Some(id) => id,
+            None => {
+                tracing::debug!("Ignoring notification: {}", method);
+                return None;
+            }
+        };
+
+        let result = match method {
+            "initialize" => Ok(self.initialize()),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params).await,
+            _ => {
+                return Some(error_response(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Method not found: {}", method),
+                ))
+            }
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, INVALID_PARAMS, &e.to_string()),
+        })
+    }
+
+    fn initialize(&self) -> Value {
+        json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": {
+                "name": "ummon",
+                "version": env!("CARGO_PKG_VERSION"),
+            }
+        })
+    }
+
+    /// Dispatch a `tools/call` request. Tool failures are reported in the result
+    /// with `isError` set, as the protocol expects; only malformed calls are errors.
+    async fn call_tool(&self, params: &Value) -> Result<Value> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("tools/call requires a tool name"))?;
+        let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+
+        let outcome = match name {
+            "search_code" => self.search_code(&arguments),
+            "find_paths" => self.find_paths(&arguments),
+            "suggest_relevant_files" => self.suggest_relevant_files(&arguments).await,
+            _ => return Err(anyhow!("Unknown tool: {}", name)),
+        };
+
+        Ok(match outcome {
+            Ok(value) => json!({
+                "content": [{ "type": "text", "text": serde_json::to_string_pretty(&value)? }],
+                "isError": false,
+            }),
+            Err(e) => {
+                tracing::warn!("Tool '{}' failed: {}", name, e);
+                json!({
+                    "content": [{ "type": "text", "text": e.to_string() }],
+                    "isError": true,
+                })
+            }
+        })
+    }
+
+    /// Search entities with the query language, falling back to keyword matching
+    fn search_code(&self, arguments: &Value) -> Result<Value> {
+        let query = string_arg(arguments, "query")?;
+        let limit = arguments
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_SEARCH_LIMIT);
+
+        let offset = arguments.get("offset").and_then(Value::as_u64).unwrap_or(0) as usize;
+        let page = Pagination::new(Some(limit), offset);
+
+        let (entities, total) = match parse_query(query) {
+            Ok(parsed) => {
+                let page = DbQueryExecutor::new(&self.db).execute_page(parsed, page)?;
+                (page.entities, page.total)
+            }
+            Err(_) => {
+                let matches = self.keyword_search(query)?;
+                let total = matches.len();
+                (
+                    matches.into_iter().skip(offset).take(limit).collect(),
+                    total,
+                )
+            }
+        };
+
+        let results: Vec<Value> = entities
+            .iter()
+            .map(|e| entity_to_json(e.as_ref()))
+            .collect();
+
+        Ok(json!({
+            "total": total,
+            "next_offset": page.next_offset(total),
+            "results": results,
+        }))
+    }
+
+    /// Match every whitespace-separated term against entity names and documentation
+    fn keyword_search(&self, query: &str) -> Result<Vec<Box<dyn Entity>>> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|t| format!("%{}%", t))
+            .collect();
+
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let condition = format!(
+            "({})",
+            terms
+                .iter()
+                .map(|_| "name LIKE ? OR documentation LIKE ?")
+                .collect::<Vec<_>>()
+                .join(" OR ")
+        );
+
+        let mut results = Vec::new();
+        for entity_type in SEARCHABLE_TYPES.iter() {
+            let params: Vec<Box<dyn ToSql>> = terms
+                .iter()
+                .flat_map(|t| [Box::new(t.clone()) as Box<dyn ToSql>, Box::new(t.clone())])
+                .collect();
+            results.extend(self.db.query_entities_by_type(
+                entity_type,
+                Some(&condition),
+                params,
+                None,
+            )?);
+        }
+
+        Ok(results)
+    }
+
+    /// Traverse relationships outward from an entity
+    fn find_paths(&self, arguments: &Value) -> Result<Value> {
+        let from = EntityId::new(string_arg(arguments, "from")?);
+        let to = arguments
+            .get("to")
+            .and_then(Value::as_str)
+            .map(EntityId::new);
+        let target_type = arguments
+            .get("target_type")
+            .and_then(Value::as_str)
+            .map(parse_entity_type);
+        let relationship = arguments
+            .get("relationship")
+            .and_then(Value::as_str)
+            .map(parse_relationship_type);
+        let max_depth = arguments
+            .get("max_depth")
+            .and_then(Value::as_u64)
+            .unwrap_or(3) as usize;
+        let direction = arguments
+            .get("direction")
+            .and_then(Value::as_str)
+            .unwrap_or("outbound");
+
+        if self.db.load_entity(&from)?.is_none() {
+            return Err(anyhow!("Entity not found: {}", from));
+        }
+
+        let paths = self.db.find_paths(
+            &from,
+            to.as_ref(),
+            target_type.as_ref(),
+            relationship.as_ref(),
+            max_depth,
+            direction,
+        )?;
+
+        let mut results = Vec::new();
+        for (entity_id, depth) in paths {
+            if depth == 0 {
+                continue;
+            }
+            if let Some(entity) = self.db.load_entity(&entity_id)? {
+                let mut value = entity_to_json(entity.as_ref());
+                value["depth"] = json!(depth);
+                results.push(value);
+            }
+        }
+
+        Ok(json!({ "from": from.as_str(), "results": results }))
+    }
+
+    /// Rank files relevant to a described change
+    async fn suggest_relevant_files(&self, arguments: &Value) -> Result<Value> {
+        let change = string_arg(arguments, "change")?;
+        let files = suggest_relevant_files(change, &self.db, &self.config).await?;
+        Ok(serde_json::to_value(files)?)
+    }
+}
+
+/// JSON schema descriptions of the tools this server exposes
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "search_code",
+            "description": "Search the code knowledge graph. Accepts Ummon query syntax (e.g. \"select functions where name like 'auth%'\") or plain keywords matched against entity names and documentation.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Structured query or keywords" },
+                    "limit": { "type": "integer", "description": "Maximum number of results" },
+                    "offset": { "type": "integer", "description": "Number of results to skip, for paging" }
+                },
+                "required": ["query"]
+            }
+        },
+        {
+            "name": "find_paths",
+            "description": "Find entities reachable from an entity by following relationships.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "from": { "type": "string", "description": "Starting entity ID" },
+                    "to": { "type": "string", "description": "Only return this target entity ID" },
+                    "target_type": { "type": "string", "description": "Only return entities of this type (e.g. Function, Class)" },
+                    "relationship": { "type": "string", "description": "Only follow this relationship type (e.g. Calls, Contains)" },
+                    "max_depth": { "type": "integer", "description": "Maximum traversal depth (default 3)" },
+                    "direction": { "type": "string", "enum": ["outbound", "inbound", "both"] }
+                },
+                "required": ["from"]
+            }
+        },
+        {
+            "name": "suggest_relevant_files",
+            "description": "Suggest files relevant to a proposed change, ranked by graph proximity and centrality.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "change": { "type": "string", "description": "Description of the proposed change" }
+                },
+                "required": ["change"]
+            }
+        }
+    ])
+}
+
+fn entity_to_json(entity: &dyn Entity) -> Value {
+    let mut value = json!({
+        "id": entity.id().as_str(),
+        "name": entity.name(),
+        "type": entity.entity_type().to_string(),
+    });
+    if let Some(path) = entity.path() {
+        value["file_path"] = json!(path);
+    }
+    if let Some(location) = entity.location() {
+        value["line"] = json!(location.start.line + 1);
+    }
+    value
+}
+
+fn string_arg<'v>(arguments: &'v Value, key: &str) -> Result<&'v str> {
+    arguments
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Missing required string argument '{}'", key))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::get_database;
+    use crate::graph::entity::{BaseEntity, FunctionEntity, Visibility};
+    use crate::graph::relationship::{Relationship, RelationshipId, RelationshipType};
+    use tempfile::{tempdir, TempDir};
+
+    fn create_test_server() -> (McpServer, TempDir) {
+        let dir = tempdir().unwrap();
+        let db = get_database(dir.path().join("test.db").to_str().unwrap()).unwrap();
+
+        for (id, name) in [("func1", "open_connection"), ("func2", "close_pool")] {
+            let function = FunctionEntity {
+                base: BaseEntity::new(
+                    EntityId::new(id),
+                    name.to_string(),
+                    EntityType::Function,
+                    Some("src/db.rs".to_string()),
+                ),
+                parameters: vec![],
+                return_type: None,
+                visibility: Visibility::Public,
+                is_async: false,
+                is_static: false,
+                is_constructor: false,
+                is_abstract: false,
+                decorators: Vec::new(),
+                containing_type: None,
+            };
+            db.save_entity(&function).unwrap();
+        }
+
+        db.save_relationship(&Relationship::new(
+            RelationshipId::new("calls_rel"),
+            EntityId::new("func1"),
+            EntityId::new("func2"),
+            RelationshipType::Calls,
+        ))
+        .unwrap();
+
+        (McpServer::new(db), dir)
+    }
+
+    fn tool_text(response: &Value) -> Value {
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize_and_list_tools() {
+        let (server, _dir) = create_test_server();
+
+        let init = server
+            .handle_message(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#)
+            .await
+            .unwrap();
+        assert_eq!(init["id"], 1);
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert_eq!(init["result"]["serverInfo"]["name"], "ummon");
+
+        let list = server
+            .handle_message(r#"{"jsonrpc":"2.0","id":2,"method":"tools/list","params":{}}"#)
+            .await
+            .unwrap();
+        let names: Vec<&str> = list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["search_code", "find_paths", "suggest_relevant_files"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notifications_and_errors() {
+        let (server, _dir) = create_test_server();
+
+        let notification = server
+            .handle_message(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+            .await;
+        assert!(notification.is_none());
+
+        let unknown = server
+            .handle_message(r#"{"jsonrpc":"2.0","id":3,"method":"resources/list"}"#)
+            .await
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+
+        let garbage = server.handle_message("not json").await.unwrap();
+        assert_eq!(garbage["error"]["code"], PARSE_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_search_code_tool() {
+        let (server, _dir) = create_test_server();
+
+        // Free text falls back to keyword matching
+        let response = server
+            .handle_message(
+                r#"{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"search_code","arguments":{"query":"database connection"}}}"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["isError"], false);
+        let body = tool_text(&response);
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["results"][0]["name"], "open_connection");
+
+        // Structured queries go through the query executor
+        let response = server
+            .handle_message(
+                r#"{"jsonrpc":"2.0","id":5,"method":"tools/call","params":{"name":"search_code","arguments":{"query":"select functions","limit":1}}}"#,
+            )
+            .await
+            .unwrap();
+        let body = tool_text(&response);
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["next_offset"], 1);
+        assert_eq!(body["results"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_paths_tool() {
+        let (server, _dir) = create_test_server();
+
+        let response = server
+            .handle_message(
+                r#"{"jsonrpc":"2.0","id":6,"method":"tools/call","params":{"name":"find_paths","arguments":{"from":"func1","relationship":"Calls"}}}"#,
+            )
+            .await
+            .unwrap();
+        let body = tool_text(&response);
+        assert_eq!(body["results"][0]["id"], "func2");
+        assert_eq!(body["results"][0]["depth"], 1);
+
+        let missing = server
+            .handle_message(
+                r#"{"jsonrpc":"2.0","id":7,"method":"tools/call","params":{"name":"find_paths","arguments":{"from":"nope"}}}"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(missing["result"]["isError"], true);
+    }
+}
@@ -0,0 +1,924 @@
+use std::collections::HashMap;
+
+use std::path::Path;
+
+use crate::parser::language_support::{
+    CallReference, FunctionDefinition, ImportDefinition, TypeDefinition, VariableReference,
+};
+
+/// Largest number of equally likely targets a call may be bound to by name alone.
+/// Names shared by more entities than this (`new`, `get`, ...) are left unresolved.
+pub const MAX_AMBIGUOUS_CANDIDATES: usize = 5;
+
+/// Receivers that refer to the caller's own type
+const SELF_RECEIVERS: &[&str] = &["self", "Self", "this", "cls"];
+
+/// How a call site was bound to its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ResolutionStrategy {
+    /// Matched by name anywhere in the indexed code
+    GlobalName,
+    /// The call's qualifier names the file or module defining the target
+    Qualifier,
+    /// The target's name or module is imported by the calling file
+    Import,
+    /// The target is defined in another file of the calling file's package
+    Package,
+    /// The target is defined in the calling file
+    SameFile,
+    /// The call's qualifier names the type defining the target
+    QualifiedType,
+    /// The target is a method of the caller's own type
+    ContainingType,
+}
+
+impl ResolutionStrategy {
+    /// Confidence that a call resolved this way is bound to the right target
+    pub fn confidence(self) -> f32 {
+        match self {
+            ResolutionStrategy::ContainingType => 0.95,
+            ResolutionStrategy::QualifiedType => 0.9,
+            ResolutionStrategy::SameFile => 0.9,
+            ResolutionStrategy::Package => 0.85,
+            ResolutionStrategy::Import => 0.8,
+            ResolutionStrategy::Qualifier => 0.7,
+            ResolutionStrategy::GlobalName => 0.5,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResolutionStrategy::ContainingType => "containing_type",
+            ResolutionStrategy::QualifiedType => "qualified_type",
+            ResolutionStrategy::SameFile => "same_file",
+            ResolutionStrategy::Package => "package",
+            ResolutionStrategy::Import => "import",
+            ResolutionStrategy::Qualifier => "qualifier",
+            ResolutionStrategy::GlobalName => "global_name",
+        }
+    }
+}
+
+/// The entity a call site was bound to
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallResolution {
+    /// Entity ID of the most likely target
+    pub target_id: String,
+    /// Confidence in the binding, between 0 and 1
+    pub confidence: f32,
+    pub strategy: ResolutionStrategy,
+    /// All equally likely targets, including the chosen one, when the call is ambiguous
+    pub candidates: Vec<String>,
+}
+
+/// What a call is made on: a type or module path (`Graph::new`, `Math.max`)
+/// or a value whose type is unknown (`graph.add`)
+#[derive(Debug, Clone, Copy)]
+struct Qualifier<'a> {
+    text: &'a str,
+    is_path: bool,
+}
+
+/// A function, method, type or variable that a call or reference could target
+#[derive(Debug, Clone)]
+struct Candidate {
+    id: String,
+    file_path: String,
+    containing_type: Option<String>,
+}
+
+/// Binds call sites to the functions, methods and types they most likely target
+///
+/// Candidates are looked up by name across every indexed file and ranked using
+/// the call's qualifier, the caller's containing type and the calling file's imports.
+#[derive(Debug, Default)]
+pub struct CallResolver {
+    functions: HashMap<String, Vec<Candidate>>,
+    types: HashMap<String, Vec<Candidate>>,
+    variables: HashMap<String, Vec<Candidate>>,
+}
+
+impl CallResolver {
+    /// Build a resolver over the functions and types collected during indexing
+    ///
+    /// Both maps are keyed by entity ID, such as `"{file_path}::{name}"` for a
+    /// function; type entity IDs carry an extra `type::` prefix.
+    pub fn new(
+        function_map: &HashMap<String, FunctionDefinition>,
+        type_map: &HashMap<String, TypeDefinition>,
+    ) -> Self {
+        let mut resolver = Self::default();
+
+        for (key, func) in function_map {
+            resolver.add_function(
+                key,
+                &func.name,
+                &func.file_path,
+                func.containing_type.as_deref(),
+            );
+        }
+
+        for (key, type_def) in type_map {
+            resolver.add_type(
+                &format!("type::{}", key),
+                &type_def.name,
+                &type_def.file_path,
+            );
+        }
+
+        resolver
+    }
+
+    /// Add a function or method entity as a possible call target
+    pub fn add_function(
+        &mut self,
+        id: &str,
+        name: &str,
+        file_path: &str,
+        containing_type: Option<&str>,
+    ) {
+        let candidate = Candidate {
+            id: id.to_string(),
+            file_path: file_path.to_string(),
+            containing_type: containing_type.map(base_type_name),
+        };
+        insert_candidate(
+            self.functions.entry(name.to_string()).or_default(),
+            candidate,
+        );
+    }
+
+    /// Add a type entity as a possible target of constructor calls
+    pub fn add_type(&mut self, id: &str, name: &str, file_path: &str) {
+        let candidate = Candidate {
+            id: id.to_string(),
+            file_path: file_path.to_string(),
+            containing_type: None,
+        };
+        insert_candidate(self.types.entry(name.to_string()).or_default(), candidate);
+    }
+
+    /// Add a module-level variable or constant, or a static field of a type,
+    /// as a possible target of references
+    pub fn add_variable(
+        &mut self,
+        id: &str,
+        name: &str,
+        file_path: &str,
+        containing_type: Option<&str>,
+    ) {
+        let candidate = Candidate {
+            id: id.to_string(),
+            file_path: file_path.to_string(),
+            containing_type: containing_type.map(base_type_name),
+        };
+        insert_candidate(
+            self.variables.entry(name.to_string()).or_default(),
+            candidate,
+        );
+    }
+
+    /// Bind a call site to its most likely target
+    ///
+    /// # Arguments
+    /// * `call` - The call site to resolve
+    /// * `file_path` - File containing the call
+    /// * `caller` - Definition of the function making the call, if known
+    /// * `imports` - Imports of the calling file
+    ///
+    /// # Returns
+    /// * `Option<CallResolution>` - The chosen target, or None if nothing plausible was found
+    pub fn resolve(
+        &self,
+        call: &CallReference,
+        file_path: &str,
+        caller: Option<&FunctionDefinition>,
+        imports: &[ImportDefinition],
+    ) -> Option<CallResolution> {
+        let (qualifier, name) = split_call_path(call);
+
+        // Calls to a type's name are constructor calls in most languages
+        let candidates = self.functions.get(name).or_else(|| self.types.get(name))?;
+
+        let caller_type = caller
+            .and_then(|c| c.containing_type.as_deref())
+            .map(base_type_name);
+
+        choose_candidate(
+            candidates,
+            qualifier,
+            name,
+            file_path,
+            caller_type.as_deref(),
+            imports,
+        )
+    }
+
+    /// Bind a type named in a declaration, such as a superclass, an implemented
+    /// interface or an embedded struct, to the type it most likely refers to
+    ///
+    /// # Arguments
+    /// * `type_name` - The type as written, e.g. `Base`, `io.Reader` or `Store<T>`
+    /// * `file_path` - File containing the declaration
+    /// * `imports` - Imports of that file
+    pub fn resolve_type(
+        &self,
+        type_name: &str,
+        file_path: &str,
+        imports: &[ImportDefinition],
+    ) -> Option<CallResolution> {
+        let path = base_type_name(type_name.trim_start_matches(['*', '&']));
+        let path = path.split('[').next().unwrap_or(&path);
+        let reference = CallReference::with_details(
+            last_segment(path).to_string(),
+            Some(path.to_string()),
+            None,
+            None,
+            Vec::new(),
+        );
+        let (qualifier, name) = split_call_path(&reference);
+
+        choose_candidate(
+            self.types.get(name)?,
+            qualifier,
+            name,
+            file_path,
+            None,
+            imports,
+        )
+    }
+
+    /// Bind a name read or written inside a function to the variable,
+    /// constant or static field it most likely refers to
+    ///
+    /// Unlike calls, references aren't bound by name alone: most names in a
+    /// function are locals, so a variable defined elsewhere must be in scope
+    /// through the file, an import, the caller's type or a qualifier.
+    ///
+    /// # Arguments
+    /// * `reference` - The name and what it's accessed on
+    /// * `file_path` - File containing the reference
+    /// * `caller` - Definition of the function making the reference, if known
+    /// * `imports` - Imports of that file
+    /// * `package_scope` - Whether the file's language shares names across
+    ///   the files of a directory, as Go packages do
+    pub fn resolve_variable(
+        &self,
+        reference: &VariableReference,
+        file_path: &str,
+        caller: Option<&FunctionDefinition>,
+        imports: &[ImportDefinition],
+        package_scope: bool,
+    ) -> Option<CallResolution> {
+        let name = reference.name.as_str();
+        let candidates = self.variables.get(name)?;
+
+        // Parameters shadow variables of the same name
+        if reference.qualifier.is_none()
+            && caller.is_some_and(|c| c.parameters.iter().any(|p| p.name == name))
+        {
+            return None;
+        }
+
+        let path = match reference.qualifier.as_deref() {
+            Some(qualifier) if qualifier.contains("::") => format!("{}::{}", qualifier, name),
+            Some(qualifier) => format!("{}.{}", qualifier, name),
+            None => name.to_string(),
+        };
+        let call =
+            CallReference::with_details(name.to_string(), Some(path), None, None, Vec::new());
+        let (qualifier, _) = split_call_path(&call);
+
+        let caller_type = caller
+            .and_then(|c| c.containing_type.as_deref())
+            .map(base_type_name);
+
+        // A bare name only refers to the static fields of the caller's own type
+        let in_scope: Vec<Candidate> = candidates
+            .iter()
+            .filter(|candidate| {
+                qualifier.is_some()
+                    || candidate.containing_type.is_none()
+                    || candidate.containing_type == caller_type
+            })
+            .cloned()
+            .collect();
+
+        let resolution = choose_candidate(
+            &in_scope,
+            qualifier,
+            name,
+            file_path,
+            caller_type.as_deref(),
+            imports,
+        )
+        .filter(|resolution| resolution.strategy != ResolutionStrategy::GlobalName);
+        if resolution.is_some() || !package_scope || qualifier.is_some() {
+            return resolution;
+        }
+
+        let package = Path::new(file_path).parent();
+        let tied: Vec<&Candidate> = in_scope
+            .iter()
+            .filter(|candidate| {
+                candidate.containing_type.is_none()
+                    && Path::new(&candidate.file_path).parent() == package
+            })
+            .collect();
+        (!tied.is_empty()).then(|| bind(ResolutionStrategy::Package, tied))
+    }
+}
+
+/// Pick the best ranked of the candidates sharing a name, splitting the
+/// confidence between equally likely ones
+fn choose_candidate(
+    candidates: &[Candidate],
+    qualifier: Option<Qualifier>,
+    name: &str,
+    file_path: &str,
+    caller_type: Option<&str>,
+    imports: &[ImportDefinition],
+) -> Option<CallResolution> {
+    let ranked: Vec<(ResolutionStrategy, &Candidate)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            rank_candidate(candidate, qualifier, name, file_path, caller_type, imports)
+                .map(|strategy| (strategy, candidate))
+        })
+        .collect();
+
+    let best = ranked.iter().map(|(strategy, _)| *strategy).max()?;
+    let mut tied: Vec<&Candidate> = ranked
+        .iter()
+        .filter(|(strategy, _)| *strategy == best)
+        .map(|(_, candidate)| *candidate)
+        .collect();
+
+    if best == ResolutionStrategy::GlobalName && tied.len() > MAX_AMBIGUOUS_CANDIDATES {
+        return None;
+    }
+
+    // Prefer the candidate closest to the calling file in the directory tree
+    tied.sort_by_key(|candidate| {
+        std::cmp::Reverse(common_prefix_len(&candidate.file_path, file_path))
+    });
+
+    Some(bind(best, tied))
+}
+
+/// Bind to the first of equally likely candidates, splitting the strategy's
+/// confidence between them
+fn bind(strategy: ResolutionStrategy, tied: Vec<&Candidate>) -> CallResolution {
+    CallResolution {
+        target_id: tied[0].id.clone(),
+        confidence: strategy.confidence() / tied.len() as f32,
+        strategy,
+        candidates: if tied.len() > 1 {
+            tied.iter().map(|candidate| candidate.id.clone()).collect()
+        } else {
+            Vec::new()
+        },
+    }
+}
+
+/// Insert a candidate keeping the list sorted by ID, so resolution doesn't
+/// depend on the order entities were added in
+fn insert_candidate(candidates: &mut Vec<Candidate>, candidate: Candidate) {
+    match candidates.binary_search_by(|c| c.id.cmp(&candidate.id)) {
+        Ok(index) => candidates[index] = candidate,
+        Err(index) => candidates.insert(index, candidate),
+    }
+}
+
+/// Rank how a candidate could be the target of a call, or None if it can't be
+fn rank_candidate(
+    candidate: &Candidate,
+    qualifier: Option<Qualifier>,
+    name: &str,
+    file_path: &str,
+    caller_type: Option<&str>,
+    imports: &[ImportDefinition],
+) -> Option<ResolutionStrategy> {
+    let same_file = candidate.file_path == file_path;
+    let is_method = candidate.containing_type.is_some();
+    let on_caller_type =
+        caller_type.is_some() && candidate.containing_type.as_deref() == caller_type;
+    let imported = imports
+        .iter()
+        .any(|import| import_matches(import, name, qualifier, &candidate.file_path));
+
+    let Some(qualifier) = qualifier else {
+        // A bare call: a local function, a method of the caller's own type or an import
+        return if same_file {
+            Some(ResolutionStrategy::SameFile)
+        } else if on_caller_type {
+            Some(ResolutionStrategy::ContainingType)
+        } else if imported {
+            Some(ResolutionStrategy::Import)
+        } else {
+            (!is_method).then_some(ResolutionStrategy::GlobalName)
+        };
+    };
+
+    if SELF_RECEIVERS.contains(&qualifier.text) {
+        // Methods on a type we can't see, such as inherited ones, fall back to the name
+        return if on_caller_type {
+            Some(ResolutionStrategy::ContainingType)
+        } else if qualifier.is_path && qualifier.text == "self" {
+            (same_file && !is_method).then_some(ResolutionStrategy::SameFile)
+        } else {
+            is_method.then_some(ResolutionStrategy::GlobalName)
+        };
+    }
+
+    let last = last_segment(qualifier.text);
+
+    if candidate.containing_type.as_deref() == Some(last) {
+        Some(ResolutionStrategy::QualifiedType)
+    } else if imported {
+        Some(ResolutionStrategy::Import)
+    } else if file_stem(&candidate.file_path) == Some(last) {
+        Some(ResolutionStrategy::Qualifier)
+    } else if qualifier.is_path {
+        // Only a path naming the calling file's own module keeps the call local
+        (same_file && !is_method && module_matches_file(qualifier.text, file_path))
+            .then_some(ResolutionStrategy::SameFile)
+    } else {
+        is_method.then_some(ResolutionStrategy::GlobalName)
+    }
+}
+
+/// Split a call into its qualifier and the called name
+fn split_call_path(call: &CallReference) -> (Option<Qualifier<'_>>, &str) {
+    let path = call
+        .fully_qualified_name
+        .as_deref()
+        .unwrap_or(&call.callee_name);
+
+    // `::` separates Rust, C++ and Ruby paths, `\` PHP namespaces
+    if let Some((text, name)) = path
+        .rsplit_once("::")
+        .or_else(|| path.rsplit_once('\\'))
+        .filter(|(text, _)| !text.is_empty())
+    {
+        return (
+            Some(Qualifier {
+                text,
+                is_path: true,
+            }),
+            name,
+        );
+    }
+
+    match path.rsplit_once('.').filter(|(text, _)| !text.is_empty()) {
+        Some((text, name)) => (
+            Some(Qualifier {
+                text,
+                is_path: last_segment(text).starts_with(char::is_uppercase),
+            }),
+            name,
+        ),
+        None => (None, last_segment(&call.callee_name)),
+    }
+}
+
+/// Whether an import brings a candidate into scope for a call
+fn import_matches(
+    import: &ImportDefinition,
+    name: &str,
+    qualifier: Option<Qualifier>,
+    candidate_path: &str,
+) -> bool {
+    let symbol_imported = import.imported_symbols.iter().any(|symbol| {
+        symbol == "*" || symbol == name || qualifier.is_some_and(|q| last_segment(q.text) == symbol)
+    });
+
+    symbol_imported && module_matches_file(&import.module_name, candidate_path)
+}
+
+/// Whether a module path such as `crate::db`, `pkg.utils`, `./utils` or `App\Models` names a file
+fn module_matches_file(module_name: &str, file_path: &str) -> bool {
+    let module = module_name
+        .trim_start_matches(['.', '/', '@', '\\'])
+        .trim_start_matches("crate::")
+        .trim_start_matches("self::")
+        .trim_start_matches("super::");
+    let segments: Vec<&str> = module
+        .split([':', '.', '/', '\\'])
+        .filter(|s| !s.is_empty() && *s != "*")
+        .collect();
+
+    let path = file_path.replace('\\', "/");
+    let path = path
+        .rsplit_once('.')
+        .map_or(path.as_str(), |(stem, _)| stem);
+
+    // The module path may name the file itself or a symbol inside it
+    (1..=segments.len()).rev().any(|len| {
+        let module_path = segments[..len].join("/");
+        ["", "/mod", "/index", "/__init__"].iter().any(|suffix| {
+            let expected = format!("{}{}", module_path, suffix);
+            path == expected || path.ends_with(&format!("/{}", expected))
+        })
+    })
+}
+
+/// Strip generic arguments from a type name, e.g. `Graph<T>` becomes `Graph`
+fn base_type_name(type_name: &str) -> String {
+    type_name
+        .split('<')
+        .next()
+        .unwrap_or(type_name)
+        .trim()
+        .to_string()
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit([':', '.', '\\']).next().unwrap_or(path)
+}
+
+fn file_stem(file_path: &str) -> Option<&str> {
+    Path::new(file_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+}
+
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.split('/')
+        .zip(b.split('/'))
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::language_support::{
+        FunctionKind, Location, Parameter, Position, Visibility,
+    };
+
+    fn location() -> Location {
+        let position = Position {
+            line: 0,
+            column: 0,
+            offset: 0,
+        };
+        Location {
+            start: position.clone(),
+            end: position,
+        }
+    }
+
+    fn function(file_path: &str, name: &str, containing_type: Option<&str>) -> FunctionDefinition {
+        FunctionDefinition {
+            name: name.to_string(),
+            file_path: file_path.to_string(),
+            kind: if containing_type.is_some() {
+                FunctionKind::Method
+            } else {
+                FunctionKind::Function
+            },
+            visibility: Visibility::Public,
+            location: location(),
+            containing_type: containing_type.map(String::from),
+            parameters: Vec::new(),
+            return_type: None,
+            containing_entity_name: containing_type.map(String::from),
+            decorators: Vec::new(),
+        }
+    }
+
+    fn resolver(functions: &[FunctionDefinition]) -> CallResolver {
+        let function_map = functions
+            .iter()
+            .map(|f| (format!("{}::{}", f.file_path, f.name), f.clone()))
+            .collect();
+        CallResolver::new(&function_map, &HashMap::new())
+    }
+
+    fn call(callee_name: &str, fully_qualified_name: Option<&str>) -> CallReference {
+        CallReference::with_details(
+            callee_name.to_string(),
+            fully_qualified_name.map(String::from),
+            None,
+            None,
+            Vec::new(),
+        )
+    }
+
+    fn import(module_name: &str, symbols: &[&str]) -> ImportDefinition {
+        ImportDefinition {
+            module_name: module_name.to_string(),
+            imported_symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            location: location(),
+            is_relative: false,
+        }
+    }
+
+    #[test]
+    fn test_resolve_prefers_same_file() {
+        let resolver = resolver(&[
+            function("./src/a.rs", "helper", None),
+            function("./src/b.rs", "helper", None),
+        ]);
+
+        let resolution = resolver
+            .resolve(&call("helper", Some("helper")), "./src/a.rs", None, &[])
+            .unwrap();
+        assert_eq!(resolution.target_id, "./src/a.rs::helper");
+        assert_eq!(resolution.strategy, ResolutionStrategy::SameFile);
+        assert!(resolution.candidates.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_path_qualified_call_outside_the_file() {
+        let resolver = resolver(&[function("./src/a.rs", "write", None)]);
+
+        assert!(resolver
+            .resolve(
+                &call("write", Some("std::fs::write")),
+                "./src/a.rs",
+                None,
+                &[]
+            )
+            .is_none());
+
+        let own_module = resolver
+            .resolve(&call("write", Some("self::write")), "./src/a.rs", None, &[])
+            .unwrap();
+        assert_eq!(own_module.strategy, ResolutionStrategy::SameFile);
+    }
+
+    #[test]
+    fn test_resolve_uses_imports_across_files() {
+        let resolver = resolver(&[
+            function("./src/db.rs", "get_database", None),
+            function("./src/other/db_utils.rs", "get_database", None),
+        ]);
+        let imports = [import("crate::db", &["get_database"])];
+
+        let resolution = resolver
+            .resolve(
+                &call("get_database", Some("get_database")),
+                "./src/main.rs",
+                None,
+                &imports,
+            )
+            .unwrap();
+        assert_eq!(resolution.target_id, "./src/db.rs::get_database");
+        assert_eq!(resolution.strategy, ResolutionStrategy::Import);
+        assert_eq!(resolution.confidence, 0.8);
+    }
+
+    #[test]
+    fn test_resolve_method_on_containing_type() {
+        let caller = function("./app.py", "run", Some("Service"));
+        let resolver = resolver(&[
+            caller.clone(),
+            function("./app.py", "helper", Some("Service")),
+            function("./other.py", "helper", Some("Other")),
+        ]);
+
+        let resolution = resolver
+            .resolve(
+                &call("self.helper", Some("self.helper")),
+                "./app.py",
+                Some(&caller),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(resolution.target_id, "./app.py::helper");
+        assert_eq!(resolution.strategy, ResolutionStrategy::ContainingType);
+    }
+
+    #[test]
+    fn test_resolve_qualified_type() {
+        let resolver = resolver(&[
+            function("./src/a.rs", "new", Some("Parser")),
+            function("./src/b.rs", "new", Some("Graph")),
+        ]);
+
+        let resolution = resolver
+            .resolve(&call("new", Some("Graph::new")), "./src/main.rs", None, &[])
+            .unwrap();
+        assert_eq!(resolution.target_id, "./src/b.rs::new");
+        assert_eq!(resolution.strategy, ResolutionStrategy::QualifiedType);
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_records_candidates() {
+        let resolver = resolver(&[
+            function("./src/a/util.rs", "helper", None),
+            function("./lib/b/util.rs", "helper", None),
+        ]);
+
+        let resolution = resolver
+            .resolve(
+                &call("helper", Some("helper")),
+                "./src/a/main.rs",
+                None,
+                &[],
+            )
+            .unwrap();
+        assert_eq!(resolution.strategy, ResolutionStrategy::GlobalName);
+        assert_eq!(resolution.target_id, "./src/a/util.rs::helper");
+        assert_eq!(resolution.candidates.len(), 2);
+        assert_eq!(resolution.confidence, 0.25);
+    }
+
+    #[test]
+    fn test_resolve_unknown_and_overloaded_names() {
+        let functions: Vec<_> = (0..=MAX_AMBIGUOUS_CANDIDATES)
+            .map(|i| function(&format!("./src/m{}.rs", i), "new", None))
+            .collect();
+        let resolver = resolver(&functions);
+
+        assert!(resolver
+            .resolve(&call("missing", None), "./src/main.rs", None, &[])
+            .is_none());
+        assert!(resolver
+            .resolve(&call("new", None), "./src/main.rs", None, &[])
+            .is_none());
+    }
+
+    #[test]
+    fn test_resolve_type() {
+        let mut resolver = CallResolver::default();
+        resolver.add_type("type::./web/base.ts::Base", "Base", "./web/base.ts");
+        resolver.add_type("type::./web/app.ts::Base", "Base", "./web/app.ts");
+        resolver.add_type("type::./store/store.go::Store", "Store", "./store/store.go");
+
+        let resolution = resolver
+            .resolve_type("Base<T>", "./web/app.ts", &[])
+            .unwrap();
+        assert_eq!(resolution.target_id, "type::./web/app.ts::Base");
+        assert_eq!(resolution.strategy, ResolutionStrategy::SameFile);
+
+        let imports = [import("../store", &["store"])];
+        let resolution = resolver
+            .resolve_type("*store.Store", "./api/server.go", &imports)
+            .unwrap();
+        assert_eq!(resolution.target_id, "type::./store/store.go::Store");
+        assert_eq!(resolution.strategy, ResolutionStrategy::Import);
+
+        assert!(resolver
+            .resolve_type("io.Reader", "./api/server.go", &[])
+            .is_none());
+
+        resolver.add_type(
+            "type::./src/Models/User.php::User",
+            "User",
+            "./src/Models/User.php",
+        );
+        let imports = [import("Models\\User", &["User"])];
+        let resolution = resolver
+            .resolve_type("User", "./src/Http/Controller.php", &imports)
+            .unwrap();
+        assert_eq!(resolution.target_id, "type::./src/Models/User.php::User");
+        assert_eq!(resolution.strategy, ResolutionStrategy::Import);
+    }
+
+    fn reference(name: &str, qualifier: Option<&str>) -> VariableReference {
+        VariableReference {
+            name: name.to_string(),
+            qualifier: qualifier.map(String::from),
+            caller_name: "run".to_string(),
+            location: location(),
+            is_write: false,
+        }
+    }
+
+    #[test]
+    fn test_resolve_variable() {
+        let mut resolver = CallResolver::default();
+        resolver.add_variable(
+            "./app/config.py::TIMEOUT",
+            "TIMEOUT",
+            "./app/config.py",
+            None,
+        );
+        resolver.add_variable("./app/main.py::count", "count", "./app/main.py", None);
+        resolver.add_variable(
+            "Config::field::MAX",
+            "MAX",
+            "./src/Config.java",
+            Some("Config"),
+        );
+        resolver.add_variable(
+            "./pkg/limits.go::MaxSize",
+            "MaxSize",
+            "./pkg/limits.go",
+            None,
+        );
+        let caller = function("./app/main.py", "run", None);
+
+        let same_file = resolver
+            .resolve_variable(
+                &reference("count", None),
+                "./app/main.py",
+                Some(&caller),
+                &[],
+                false,
+            )
+            .unwrap();
+        assert_eq!(same_file.target_id, "./app/main.py::count");
+        assert_eq!(same_file.strategy, ResolutionStrategy::SameFile);
+
+        // Names used elsewhere without an import are most likely locals
+        let imports = [import("app.config", &["TIMEOUT"])];
+        assert!(resolver
+            .resolve_variable(
+                &reference("TIMEOUT", None),
+                "./app/main.py",
+                None,
+                &[],
+                false
+            )
+            .is_none());
+        let imported = resolver
+            .resolve_variable(
+                &reference("TIMEOUT", None),
+                "./app/main.py",
+                None,
+                &imports,
+                false,
+            )
+            .unwrap();
+        assert_eq!(imported.strategy, ResolutionStrategy::Import);
+
+        let qualified = resolver
+            .resolve_variable(
+                &reference("MAX", Some("Config")),
+                "./src/App.java",
+                None,
+                &[],
+                false,
+            )
+            .unwrap();
+        assert_eq!(qualified.target_id, "Config::field::MAX");
+        assert_eq!(qualified.strategy, ResolutionStrategy::QualifiedType);
+        assert!(resolver
+            .resolve_variable(&reference("MAX", None), "./src/App.java", None, &[], false)
+            .is_none());
+
+        let package = resolver
+            .resolve_variable(
+                &reference("MaxSize", None),
+                "./pkg/server.go",
+                None,
+                &[],
+                true,
+            )
+            .unwrap();
+        assert_eq!(package.strategy, ResolutionStrategy::Package);
+        assert!(resolver
+            .resolve_variable(
+                &reference("MaxSize", None),
+                "./cmd/main.go",
+                None,
+                &[],
+                true
+            )
+            .is_none());
+
+        // Parameters shadow variables of the same name
+        let mut shadowing = caller.clone();
+        shadowing.parameters.push(Parameter {
+            name: "count".to_string(),
+            ..Default::default()
+        });
+        assert!(resolver
+            .resolve_variable(
+                &reference("count", None),
+                "./app/main.py",
+                Some(&shadowing),
+                &[],
+                false
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_module_matches_file() {
+        assert!(module_matches_file("crate::db", "./src/db.rs"));
+        assert!(module_matches_file(
+            "crate::graph::entity",
+            "./src/graph/entity.rs"
+        ));
+        assert!(module_matches_file("crate::graph", "./src/graph/mod.rs"));
+        assert!(module_matches_file("./utils", "./web/utils.js"));
+        assert!(module_matches_file(
+            "pkg.models",
+            "./pkg/models/__init__.py"
+        ));
+        assert!(module_matches_file(
+            "com.example.Service",
+            "./src/com/example/Service.java"
+        ));
+        assert!(module_matches_file(
+            "App\\Models\\User",
+            "./src/App/Models/User.php"
+        ));
+        assert!(!module_matches_file("crate::db", "./src/db_utils.rs"));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::parser::js_modules::normalize;
+use crate::parser::language_support::cpp::is_header;
+use crate::parser::language_support::{language_for_file, ImportDefinition};
+use crate::parser::pipeline::ParsedFile;
+
+fn is_c_family(file_path: &str) -> bool {
+    matches!(language_for_file(Path::new(file_path)), Some("c" | "cpp"))
+}
+
+/// Whether one file is a C/C++ header and the other an implementation file
+/// sharing its name, such as `include/server.hpp` and `src/server.cpp`
+pub fn is_header_pair(a: &str, b: &str) -> bool {
+    is_c_family(a)
+        && is_c_family(b)
+        && is_header(a) != is_header(b)
+        && Path::new(a).file_stem() == Path::new(b).file_stem()
+}
+
+/// The file a quoted C/C++ include names relative to the including file,
+/// such as `./src/net/socket.hpp` for `#include "socket.hpp"` in
+/// `./src/net/server.cpp`, if it exists
+///
+/// Angle-bracket includes, and quoted ones not found there, are searched on
+/// the compiler's include path, which isn't known.
+pub fn resolve_include(file_path: &str, include: &ImportDefinition) -> Option<String> {
+    if !include.is_relative || !is_c_family(file_path) {
+        return None;
+    }
+    let dir = Path::new(file_path).parent()?;
+    let path = normalize(&dir.join(&include.module_name));
+    path.is_file().then(|| path.to_string_lossy().to_string())
+}
+
+/// Merge functions declared in C/C++ headers into their definitions
+///
+/// A function declared in `server.h` and defined in `server.c` would otherwise
+/// be two entities. The header's declaration is dropped in favour of the
+/// definition, which inherits the header's documentation if it has none of
+/// its own. Functions only declared, such as those of a library that isn't
+/// indexed, stay in the header.
+pub fn merge_header_declarations(parsed_files: &mut [ParsedFile]) {
+    // (file stem, containing type, name) of every function defined outside a header
+    let mut definitions: HashMap<(String, Option<String>, String), (usize, usize)> = HashMap::new();
+    for (file_index, parsed) in parsed_files.iter().enumerate() {
+        if !is_c_family(&parsed.path) || is_header(&parsed.path) {
+            continue;
+        }
+        let Some(stem) = file_stem(&parsed.path) else {
+            continue;
+        };
+
+        for (function_index, func) in parsed.functions.iter().enumerate() {
+            let definition = &func.definition;
+            definitions
+                .entry((
+                    stem.clone(),
+                    definition.containing_type.clone(),
+                    definition.name.clone(),
+                ))
+                .or_insert((file_index, function_index));
+        }
+    }
+
+    let mut inherited_docs = Vec::new();
+    for parsed in parsed_files.iter_mut() {
+        if !is_c_family(&parsed.path) || !is_header(&parsed.path) {
+            continue;
+        }
+        let Some(stem) = file_stem(&parsed.path) else {
+            continue;
+        };
+
+        let header_path = parsed.path.clone();
+        parsed.functions.retain(|func| {
+            let key = (
+                stem.clone(),
+                func.definition.containing_type.clone(),
+                func.definition.name.clone(),
+            );
+            let Some(&(file_index, function_index)) = definitions.get(&key) else {
+                return true;
+            };
+
+            tracing::debug!(
+                "Merging declaration of '{}' in {} into its definition",
+                func.definition.name,
+                header_path
+            );
+            if let Some(doc) = &func.documentation {
+                inherited_docs.push((file_index, function_index, doc.clone()));
+            }
+            false
+        });
+    }
+
+    for (file_index, function_index, doc) in inherited_docs {
+        let documentation = &mut parsed_files[file_index].functions[function_index].documentation;
+        if documentation.is_none() {
+            *documentation = Some(doc);
+        }
+    }
+}
+
+fn file_stem(file_path: &str) -> Option<String> {
+    Path::new(file_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::pipeline::parse_files;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_merge_header_declarations() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("include")).unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+
+        fs::write(
+            dir.path().join("include/server.hpp"),
+            indoc::indoc! {r#"
+                #pragma once
+
+                /// Starts listening.
+                int start(int port);
+
+                /// Provided by the system.
+                int listen(int fd);
+
+                class Server {
+                public:
+                    void run();
+                    int size() const { return 0; }
+                };
+            "#},
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("src/server.cpp"),
+            indoc::indoc! {r#"
+                #include "server.hpp"
+
+                int start(int port) { return listen(port); }
+
+                void Server::run() { start(80); }
+            "#},
+        )
+        .unwrap();
+
+        let files: Vec<String> = ["include/server.hpp", "src/server.cpp"]
+            .iter()
+            .map(|f| dir.path().join(f).to_string_lossy().to_string())
+            .collect();
+        assert!(is_header_pair(&files[0], &files[1]));
+        assert!(!is_header_pair(&files[1], &files[1]));
+
+        let mut parsed = parse_files(&files, 1).unwrap();
+        merge_header_declarations(&mut parsed);
+
+        let names = |file: &ParsedFile| -> Vec<String> {
+            file.functions
+                .iter()
+                .map(|f| f.definition.name.clone())
+                .collect()
+        };
+        assert_eq!(names(&parsed[0]), vec!["listen", "size"]);
+        assert_eq!(names(&parsed[1]), vec!["start", "run"]);
+        assert_eq!(
+            parsed[1].functions[0].documentation.as_deref(),
+            Some("Starts listening.")
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::parser::language_support::{language_for_file, TypeDefinition, TypeKind};
+use crate::parser::pipeline::ParsedFile;
+
+/// A Go struct whose method set covers an interface of its package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImplicitImplementation {
+    /// Type map key (`"{file_path}::{name}"`) of the struct
+    pub struct_key: String,
+    /// Type map key of the interface
+    pub interface_key: String,
+}
+
+/// Types and methods declared across the files of one Go package
+#[derive(Default)]
+struct Package<'a> {
+    types: BTreeMap<&'a str, &'a TypeDefinition>,
+    methods: HashMap<&'a str, BTreeSet<&'a str>>,
+}
+
+/// Find the interfaces Go structs satisfy without declaring it
+///
+/// A Go package is a directory, so structs and interfaces are matched within
+/// the directory they're declared in. A struct's method set includes methods
+/// promoted from the types it embeds, and an interface requires the methods
+/// of the interfaces it embeds. Interfaces embedding a type from another
+/// package can't be checked and are skipped, as are empty interfaces, which
+/// every type satisfies.
+///
+/// # Returns
+/// * `Vec<ImplicitImplementation>` - Matches ordered by struct, then interface
+pub fn find_implicit_implementations(parsed_files: &[ParsedFile]) -> Vec<ImplicitImplementation> {
+    let mut packages: BTreeMap<&Path, Package> = BTreeMap::new();
+
+    for parsed in parsed_files {
+        let path = Path::new(&parsed.path);
+        if language_for_file(path) != Some("go") {
+            continue;
+        }
+
+        let package = packages
+            .entry(path.parent().unwrap_or(Path::new("")))
+            .or_default();
+        for type_def in &parsed.types {
+            package.types.insert(&type_def.name, type_def);
+        }
+        for func in &parsed.functions {
+            if let Some(receiver) = &func.definition.containing_type {
+                package
+                    .methods
+                    .entry(receiver)
+                    .or_default()
+                    .insert(&func.definition.name);
+            }
+        }
+    }
+
+    let mut implementations = Vec::new();
+    for package in packages.values() {
+        let interfaces: Vec<(&TypeDefinition, BTreeSet<&str>)> = package
+            .types
+            .values()
+            .filter(|t| t.kind == TypeKind::Interface)
+            .filter_map(|t| Some((*t, package.required_methods(t, &mut BTreeSet::new())?)))
+            .filter(|(_, required)| !required.is_empty())
+            .collect();
+
+        for struct_def in package
+            .types
+            .values()
+            .filter(|t| t.kind == TypeKind::Struct)
+        {
+            let method_set = package.method_set(struct_def, &mut BTreeSet::new());
+
+            for (interface, required) in &interfaces {
+                if required.is_subset(&method_set) {
+                    implementations.push(ImplicitImplementation {
+                        struct_key: type_key(struct_def),
+                        interface_key: type_key(interface),
+                    });
+                }
+            }
+        }
+    }
+
+    implementations
+}
+
+impl<'a> Package<'a> {
+    /// Methods of a type, including those promoted from embedded types
+    fn method_set(
+        &self,
+        type_def: &'a TypeDefinition,
+        visited: &mut BTreeSet<&'a str>,
+    ) -> BTreeSet<&'a str> {
+        let mut methods = BTreeSet::new();
+        if !visited.insert(&type_def.name) {
+            return methods;
+        }
+
+        if let Some(own) = self.methods.get(type_def.name.as_str()) {
+            methods.extend(own);
+        }
+        if type_def.kind == TypeKind::Interface {
+            methods.extend(type_def.methods.iter().map(String::as_str));
+        }
+
+        for embedded in &type_def.super_types {
+            if let Some(embedded_def) = self.types.get(embedded_type_name(embedded)) {
+                methods.extend(self.method_set(embedded_def, visited));
+            }
+        }
+
+        methods
+    }
+
+    /// Methods an interface requires, or None if some can't be known
+    fn required_methods(
+        &self,
+        interface: &'a TypeDefinition,
+        visited: &mut BTreeSet<&'a str>,
+    ) -> Option<BTreeSet<&'a str>> {
+        let mut methods: BTreeSet<&str> = interface.methods.iter().map(String::as_str).collect();
+        if !visited.insert(&interface.name) {
+            return Some(methods);
+        }
+
+        for embedded in &interface.super_types {
+            let embedded_def = self
+                .types
+                .get(embedded_type_name(embedded))
+                .filter(|t| t.kind == TypeKind::Interface)?;
+            methods.extend(self.required_methods(embedded_def, visited)?);
+        }
+
+        Some(methods)
+    }
+}
+
+/// The name an embedded type is declared with in its package, e.g. `Base` for `*Base[T]`
+fn embedded_type_name(embedded: &str) -> &str {
+    let name = embedded.trim_start_matches('*');
+    name.split('[').next().unwrap_or(name).trim()
+}
+
+fn type_key(type_def: &TypeDefinition) -> String {
+    format!("{}::{}", type_def.file_path, type_def.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::pipeline::parse_files;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_implicit_implementations() {
+        let dir = tempdir().unwrap();
+        let store = dir.path().join("store");
+        let other = dir.path().join("other");
+        fs::create_dir(&store).unwrap();
+        fs::create_dir(&other).unwrap();
+
+        fs::write(
+            store.join("interfaces.go"),
+            indoc::indoc! {r#"
+                package store
+
+                type Reader interface { Get(id string) string }
+                type Writer interface { Put(id, value string) }
+                type ReadWriter interface {
+                    Reader
+                    Writer
+                }
+                type Closer interface { io.Closer }
+                type Any interface {}
+            "#},
+        )
+        .unwrap();
+        fs::write(
+            store.join("memory.go"),
+            indoc::indoc! {r#"
+                package store
+
+                type base struct{}
+                func (b *base) Get(id string) string { return "" }
+
+                type Memory struct {
+                    *base
+                }
+                func (m Memory) Put(id, value string) {}
+
+                type ReadOnly struct{ base }
+            "#},
+        )
+        .unwrap();
+        // Same method, different package
+        fs::write(
+            other.join("cache.go"),
+            "package other\n\ntype Cache struct{}\nfunc (c Cache) Get(id string) string { return \"\" }\n",
+        )
+        .unwrap();
+
+        let files: Vec<String> = ["store/interfaces.go", "store/memory.go", "other/cache.go"]
+            .iter()
+            .map(|f| dir.path().join(f).to_string_lossy().to_string())
+            .collect();
+        let parsed = parse_files(&files, 1).unwrap();
+
+        let key = |file: &str, name: &str| format!("{}::{}", dir.path().join(file).display(), name);
+        let found: Vec<(String, String)> = find_implicit_implementations(&parsed)
+            .into_iter()
+            .map(|i| (i.struct_key, i.interface_key))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (
+                    key("store/memory.go", "Memory"),
+                    key("store/interfaces.go", "ReadWriter")
+                ),
+                (
+                    key("store/memory.go", "Memory"),
+                    key("store/interfaces.go", "Reader")
+                ),
+                (
+                    key("store/memory.go", "Memory"),
+                    key("store/interfaces.go", "Writer")
+                ),
+                (
+                    key("store/memory.go", "ReadOnly"),
+                    key("store/interfaces.go", "Reader")
+                ),
+                (
+                    key("store/memory.go", "base"),
+                    key("store/interfaces.go", "Reader")
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,457 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::parser::language_support::{language_for_file, with_thread_parser, BindingDefinition};
+use crate::parser::pipeline::ParsedFile;
+
+/// Extensions tried, in order, for a module path written without one;
+/// TypeScript files import each other the same way
+const EXTENSIONS: &[&str] = &["js", "mjs", "cjs", "jsx", "ts", "tsx"];
+
+/// `package.json` export conditions followed, in order of preference
+const CONDITIONS: &[&str] = &["import", "require", "node", "default"];
+
+/// Where an export of a JavaScript module is defined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportOrigin {
+    /// A function, class or variable of a file
+    Definition { file: String, name: String },
+    /// A whole module, exported as a namespace with `export * as ns from './m'`
+    Module(String),
+}
+
+/// The imports and exports of JavaScript files, for following re-exports to
+/// the module that defines a name
+#[derive(Debug, Default)]
+pub struct JsModules {
+    /// Names bound by each file's imports and exports
+    bindings: HashMap<String, Vec<BindingDefinition>>,
+}
+
+impl JsModules {
+    /// Collect the bindings of the parsed JavaScript files
+    ///
+    /// Other files reached through re-exports, as happens during an
+    /// incremental update, are read from disk when needed.
+    pub fn new(parsed_files: &[ParsedFile]) -> Self {
+        let bindings = parsed_files
+            .iter()
+            .filter(|parsed| language_for_file(Path::new(&parsed.path)) == Some("javascript"))
+            .map(|parsed| (parsed.path.clone(), parsed.module.bindings.clone()))
+            .collect();
+        Self { bindings }
+    }
+
+    /// Where the export `name` of a file is defined, following re-exports
+    /// such as `export { a as b } from './m'` and `export * from './m'`
+    pub fn find_export(&mut self, file: &str, name: &str) -> Option<ExportOrigin> {
+        self.find_export_from(file, name, &mut HashSet::new())
+    }
+
+    fn find_export_from(
+        &mut self,
+        file: &str,
+        name: &str,
+        visited: &mut HashSet<(String, String)>,
+    ) -> Option<ExportOrigin> {
+        if !visited.insert((file.to_string(), name.to_string())) {
+            return None;
+        }
+
+        let bindings = self.bindings(file).to_vec();
+        let exports = bindings.iter().filter(|binding| binding.is_export);
+        if let Some(binding) = exports.clone().find(|binding| binding.name == name) {
+            return match &binding.module_name {
+                Some(module) => self.follow(file, module, &binding.target, visited),
+                None => self.find_local(file, &binding.target, &bindings, visited),
+            };
+        }
+
+        // `export *` passes on every name except the default export
+        if name == "default" {
+            return None;
+        }
+        exports
+            .filter(|binding| binding.name == "*")
+            .filter_map(|binding| binding.module_name.as_deref())
+            .find_map(|module| self.follow(file, module, name, visited))
+    }
+
+    /// The origin of a name defined or imported by a file
+    fn find_local(
+        &mut self,
+        file: &str,
+        name: &str,
+        bindings: &[BindingDefinition],
+        visited: &mut HashSet<(String, String)>,
+    ) -> Option<ExportOrigin> {
+        let import = bindings.iter().find(|binding| {
+            !binding.is_export && binding.name == name && binding.module_name.is_some()
+        });
+        match import.and_then(|binding| Some((binding.module_name.as_deref()?, binding))) {
+            Some((module, binding)) => self.follow(file, module, &binding.target, visited),
+            None => Some(ExportOrigin::Definition {
+                file: file.to_string(),
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// The origin of the export `target` of a module imported by a file
+    fn follow(
+        &mut self,
+        file: &str,
+        module: &str,
+        target: &str,
+        visited: &mut HashSet<(String, String)>,
+    ) -> Option<ExportOrigin> {
+        let module_file = resolve_import(file, module)?;
+        if target == "*" {
+            return Some(ExportOrigin::Module(module_file));
+        }
+        self.find_export_from(&module_file, target, visited)
+    }
+
+    /// The bindings of a file, read from disk if it wasn't parsed
+    fn bindings(&mut self, file: &str) -> &[BindingDefinition] {
+        self.bindings
+            .entry(file.to_string())
+            .or_insert_with(|| read_bindings(Path::new(file)))
+    }
+}
+
+/// Read the imports and exports of a file that wasn't parsed
+fn read_bindings(file: &Path) -> Vec<BindingDefinition> {
+    let file_path = file.to_string_lossy();
+    let module = std::fs::read_to_string(file)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| {
+            with_thread_parser(file, |parser| parser.parse_modules(&content, &file_path))
+                .transpose()
+        });
+
+    match module {
+        Ok(module) => module.map(|module| module.bindings).unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("Failed to read exports of {}: {}", file_path, e);
+            Vec::new()
+        }
+    }
+}
+
+/// The file a module imported by a JavaScript file resolves to, following
+/// Node's rules, if it exists
+///
+/// Paths such as `./utils` or `../lib` name a file, tried as written and then
+/// with each JavaScript and TypeScript extension, or else a directory, loaded through the
+/// `main` of its `package.json` or its `index` file. Bare specifiers such as
+/// `lodash/fp` are looked up in the `node_modules` directories above the
+/// importing file, through the package's `exports` map when it has one.
+/// Built-in modules such as `fs` aren't found.
+pub fn resolve_import(file_path: &str, specifier: &str) -> Option<String> {
+    let dir = Path::new(file_path).parent()?;
+
+    let is_path = specifier == "."
+        || specifier == ".."
+        || ["./", "../", "/"]
+            .iter()
+            .any(|prefix| specifier.starts_with(prefix));
+    let resolved = if is_path {
+        load_path(&normalize(&dir.join(specifier)))
+    } else {
+        load_package(dir, specifier)
+    };
+
+    resolved.map(|path| path.to_string_lossy().to_string())
+}
+
+/// Load a module path as a file, then as a directory
+fn load_path(path: &Path) -> Option<PathBuf> {
+    load_file(path).or_else(|| load_directory(path))
+}
+
+/// The file at a path, or the path with a JavaScript or TypeScript extension appended
+fn load_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    EXTENSIONS
+        .iter()
+        .map(|ext| {
+            let mut candidate = path.as_os_str().to_owned();
+            candidate.push(format!(".{}", ext));
+            PathBuf::from(candidate)
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+/// The file a directory is loaded from: its `package.json` `main`, or `index`
+fn load_directory(dir: &Path) -> Option<PathBuf> {
+    let main = read_package(dir)
+        .and_then(|package| package.get("main")?.as_str().map(String::from))
+        .and_then(|main| {
+            let main = normalize(&dir.join(main));
+            load_file(&main).or_else(|| load_index(&main))
+        });
+    main.or_else(|| load_index(dir))
+}
+
+fn load_index(dir: &Path) -> Option<PathBuf> {
+    load_file(&dir.join("index"))
+}
+
+/// Load a bare specifier, `package` or `package/subpath`, from the nearest
+/// `node_modules` directory holding the package
+fn load_package(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    if specifier.starts_with("node:") {
+        return None;
+    }
+
+    // Scoped packages such as `@scope/name` take two segments
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let mut parts = specifier.splitn(segments + 1, '/');
+    let name: Vec<&str> = parts.by_ref().take(segments).collect();
+    let subpath = parts.next();
+
+    dir.ancestors()
+        .map(|ancestor| ancestor.join("node_modules").join(name.join("/")))
+        .filter(|package_dir| package_dir.is_dir())
+        .find_map(|package_dir| {
+            let exports = read_package(&package_dir)
+                .and_then(|mut package| package.get_mut("exports").map(Value::take));
+            match exports {
+                Some(exports) => {
+                    let key = subpath.map_or(".".to_string(), |subpath| format!("./{}", subpath));
+                    let target = exports_target(&exports, &key)?;
+                    let path = normalize(&package_dir.join(target));
+                    path.is_file().then_some(path)
+                }
+                None => match subpath {
+                    Some(subpath) => load_path(&package_dir.join(subpath)),
+                    None => load_directory(&package_dir),
+                },
+            }
+        })
+}
+
+/// The file a subpath such as `.` or `./feature` maps to in a `package.json`
+/// `exports` field
+fn exports_target(exports: &Value, subpath: &str) -> Option<String> {
+    let Value::Object(map) = exports else {
+        // A string or array is the package's main export
+        return (subpath == ".")
+            .then(|| condition_target(exports))
+            .flatten();
+    };
+    if !map.keys().any(|key| key.starts_with('.')) {
+        // An object of conditions is also the main export
+        return (subpath == ".")
+            .then(|| condition_target(exports))
+            .flatten();
+    }
+
+    if let Some(target) = map.get(subpath) {
+        return condition_target(target);
+    }
+
+    // Patterns such as `"./features/*": "./src/features/*.js"`
+    map.iter().find_map(|(key, target)| {
+        let (prefix, suffix) = key.split_once('*')?;
+        let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        condition_target(target).map(|target| target.replace('*', matched))
+    })
+}
+
+/// The path an export target stands for, choosing among conditions such as
+/// `import` and `require`
+fn condition_target(target: &Value) -> Option<String> {
+    match target {
+        Value::String(path) => Some(path.clone()),
+        Value::Array(targets) => targets.iter().find_map(condition_target),
+        Value::Object(conditions) => CONDITIONS
+            .iter()
+            .filter_map(|condition| conditions.get(*condition))
+            .find_map(condition_target),
+        _ => None,
+    }
+}
+
+fn read_package(dir: &Path) -> Option<Value> {
+    let manifest = dir.join("package.json");
+    let content = std::fs::read_to_string(&manifest).ok()?;
+    serde_json::from_str(&content)
+        .map_err(|e| tracing::warn!("Invalid {}: {}", manifest.display(), e))
+        .ok()
+}
+
+/// Remove `.` and `..` segments from a path without touching the file
+/// system, keeping a leading `./` as source file paths have
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            Component::CurDir if normalized.as_os_str().is_empty() => normalized.push("."),
+            Component::CurDir => {}
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::pipeline::parse_files;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, files: &[(&str, &str)]) {
+        for (file, content) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_resolve_import() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            &[
+                ("src/app.js", ""),
+                ("src/utils.js", ""),
+                ("src/config.json", ""),
+                ("src/components/index.js", ""),
+                ("src/lib/package.json", r#"{ "main": "./dist/lib" }"#),
+                ("src/lib/dist/lib.cjs", ""),
+                ("node_modules/left-pad/package.json", "{}"),
+                ("node_modules/left-pad/index.js", ""),
+                (
+                    "node_modules/@acme/ui/package.json",
+                    r#"{
+                        "exports": {
+                            ".": { "import": "./esm/index.mjs", "require": "./cjs/index.js" },
+                            "./button": "./esm/button.mjs",
+                            "./icons/*": "./esm/icons/*.mjs"
+                        }
+                    }"#,
+                ),
+                ("node_modules/@acme/ui/esm/index.mjs", ""),
+                ("node_modules/@acme/ui/esm/button.mjs", ""),
+                ("node_modules/@acme/ui/esm/icons/star.mjs", ""),
+            ],
+        );
+        let path = |file: &str| root.join(file).to_string_lossy().to_string();
+        let app = path("src/app.js");
+
+        assert_eq!(resolve_import(&app, "./utils"), Some(path("src/utils.js")));
+        assert_eq!(
+            resolve_import(&app, "./utils.js"),
+            Some(path("src/utils.js"))
+        );
+        assert_eq!(
+            resolve_import(&app, "./components"),
+            Some(path("src/components/index.js"))
+        );
+        assert_eq!(
+            resolve_import(&app, "./lib"),
+            Some(path("src/lib/dist/lib.cjs"))
+        );
+        assert_eq!(
+            resolve_import(&path("src/components/index.js"), "../utils"),
+            Some(path("src/utils.js"))
+        );
+        assert_eq!(
+            resolve_import(&app, "left-pad"),
+            Some(path("node_modules/left-pad/index.js"))
+        );
+        assert_eq!(
+            resolve_import(&app, "@acme/ui"),
+            Some(path("node_modules/@acme/ui/esm/index.mjs"))
+        );
+        assert_eq!(
+            resolve_import(&app, "@acme/ui/button"),
+            Some(path("node_modules/@acme/ui/esm/button.mjs"))
+        );
+        assert_eq!(
+            resolve_import(&app, "@acme/ui/icons/star"),
+            Some(path("node_modules/@acme/ui/esm/icons/star.mjs"))
+        );
+        // Subpaths not in `exports` aren't reachable
+        assert_eq!(resolve_import(&app, "@acme/ui/esm/button.mjs"), None);
+        assert_eq!(resolve_import(&app, "fs"), None);
+        assert_eq!(resolve_import(&app, "./missing"), None);
+    }
+
+    #[test]
+    fn test_normalize_keeps_leading_dot() {
+        assert_eq!(
+            normalize(Path::new("./src/components/../utils")),
+            PathBuf::from("./src/utils")
+        );
+        assert_eq!(
+            normalize(Path::new("../lib/./a")),
+            PathBuf::from("../lib/a")
+        );
+    }
+
+    #[test]
+    fn test_find_export_through_reexports() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            &[
+                (
+                    "src/index.js",
+                    "export { default as Button } from './button';\nexport * from './forms';\nexport * as icons from './icons';\n",
+                ),
+                ("src/button.js", "export default function Button() {}\n"),
+                (
+                    "src/forms/index.js",
+                    "import { Field } from './field';\nexport { Field as Input };\n",
+                ),
+                ("src/forms/field.js", "export class Field {}\n"),
+                ("src/icons.js", "export const star = '*';\n"),
+            ],
+        );
+        let path = |file: &str| root.join(file).to_string_lossy().to_string();
+
+        // Only the barrel is parsed; the modules it re-exports are read from disk
+        let parsed = parse_files(&[path("src/index.js")], 1).unwrap();
+        let mut modules = JsModules::new(&parsed);
+        let index = path("src/index.js");
+
+        assert_eq!(
+            modules.find_export(&index, "Button"),
+            Some(ExportOrigin::Definition {
+                file: path("src/button.js"),
+                name: "Button".to_string()
+            })
+        );
+        assert_eq!(
+            modules.find_export(&index, "Input"),
+            Some(ExportOrigin::Definition {
+                file: path("src/forms/field.js"),
+                name: "Field".to_string()
+            })
+        );
+        assert_eq!(
+            modules.find_export(&index, "icons"),
+            Some(ExportOrigin::Module(path("src/icons.js")))
+        );
+        assert_eq!(modules.find_export(&index, "Missing"), None);
+    }
+}
@@ -0,0 +1,1406 @@
+use super::*;
+use super::{node_to_location, traverse_node};
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::{Node, Parser, Tree};
+
+/// Extensions of C and C++ header files
+pub const HEADER_EXTENSIONS: &[&str] = &["h", "hpp"];
+
+/// Parser for C and C++ source and header files
+///
+/// `.c` files are parsed with the C grammar and everything else, headers
+/// included, with the C++ grammar, which accepts nearly all C declarations.
+pub struct CppParser {
+    c_parser: Parser,
+    cpp_parser: Parser,
+}
+
+impl Default for CppParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CppParser {
+    /// Creates a new C/C++ parser
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of the C/C++ parser
+    ///
+    /// # Panics
+    /// This function will panic if the tree-sitter C or C++ language cannot be loaded.
+    /// This should only happen in case of a build/linking issue with the tree-sitter library.
+    pub fn new() -> Self {
+        Self::try_new()
+            .expect("Failed to load C/C++ grammars - this is a build configuration error")
+    }
+
+    /// Creates a new C/C++ parser with error handling
+    ///
+    /// # Returns
+    /// * `Result<Self>` - A new instance of the C/C++ parser or an error
+    pub fn try_new() -> Result<Self> {
+        let mut c_parser = Parser::new();
+        c_parser
+            .set_language(&tree_sitter_c::LANGUAGE.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load C grammar: {}", e))?;
+
+        let mut cpp_parser = Parser::new();
+        cpp_parser
+            .set_language(&tree_sitter_cpp::LANGUAGE.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load C++ grammar: {}", e))?;
+
+        Ok(Self {
+            c_parser,
+            cpp_parser,
+        })
+    }
+
+    /// Parse a file with the grammar matching its extension
+    fn parse_tree(&mut self, content: &str, file_path: &str, what: &str) -> Result<Tree> {
+        let parser = if extension(file_path) == Some("c") {
+            &mut self.c_parser
+        } else {
+            &mut self.cpp_parser
+        };
+
+        parser.parse(content, None).ok_or_else(|| {
+            let filename = Path::new(file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            anyhow::anyhow!(
+                "Failed to parse C/C++ {} in file '{}' ({}B). The file may contain syntax errors.",
+                what,
+                filename,
+                content.len()
+            )
+        })
+    }
+
+    fn extract_parameters(&self, declarator: Node, content: &str) -> Vec<Parameter> {
+        let mut params = Vec::new();
+
+        let Some(param_list) = declarator.child_by_field_name("parameters") else {
+            return params;
+        };
+
+        let mut cursor = param_list.walk();
+        for param_node in param_list.children(&mut cursor) {
+            match param_node.kind() {
+                "parameter_declaration"
+                | "optional_parameter_declaration"
+                | "variadic_parameter_declaration" => {}
+                // C varargs
+                "..." | "variadic_parameter" => {
+                    params.push(Parameter {
+                        name: "...".to_string(),
+                        type_annotation: None,
+                        default_value: None,
+                    });
+                    continue;
+                }
+                _ => continue,
+            }
+
+            let Ok(text) = param_node.utf8_text(content.as_bytes()) else {
+                continue;
+            };
+            let default_value = param_node
+                .child_by_field_name("default_value")
+                .and_then(|v| v.utf8_text(content.as_bytes()).ok())
+                .map(String::from);
+
+            let Some(name_node) = param_node
+                .child_by_field_name("declarator")
+                .and_then(declared_identifier)
+            else {
+                // `(void)` declares that there are no parameters at all
+                if text.trim() != "void" {
+                    params.push(Parameter {
+                        name: "_".to_string(),
+                        type_annotation: Some(text.trim().to_string()),
+                        default_value,
+                    });
+                }
+                continue;
+            };
+
+            // The type is everything but the name and any default value, e.g.
+            // `const char *` for `const char *s`
+            let start = param_node.start_byte();
+            let type_end = param_node
+                .child_by_field_name("default_value")
+                .and_then(|_| param_node.child_by_field_name("declarator"))
+                .map_or(param_node.end_byte(), |d| d.end_byte());
+            let type_annotation = format!(
+                "{}{}",
+                &content[start..name_node.start_byte()],
+                &content[name_node.end_byte()..type_end]
+            )
+            .trim()
+            .to_string();
+
+            params.push(Parameter {
+                name: name_node
+                    .utf8_text(content.as_bytes())
+                    .unwrap_or("_")
+                    .to_string(),
+                type_annotation: Some(type_annotation),
+                default_value,
+            });
+        }
+
+        params
+    }
+
+    /// Builds a definition for a function definition, or for a prototype when
+    /// `include_prototypes` is set
+    ///
+    /// Functions defined or declared inside a class body, and those defined out
+    /// of line with a qualified name such as `Server::run`, are methods of that
+    /// class.
+    fn extract_function_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+        include_prototypes: bool,
+    ) -> Option<FunctionDefinition> {
+        match node.kind() {
+            "function_definition" => {}
+            "declaration" if include_prototypes && is_namespace_scope(node) => {}
+            _ => return None,
+        }
+
+        let declarator = function_declarator(node.child_by_field_name("declarator")?)?;
+        let (name, scope) =
+            declarator_name(declarator.child_by_field_name("declarator")?, content)?;
+
+        let class = enclosing_class(node, content);
+        let containing_type = class.clone().or(scope);
+        let kind = match &containing_type {
+            Some(type_name) if *type_name == name => FunctionKind::Constructor,
+            Some(_) => FunctionKind::Method,
+            None => FunctionKind::Function,
+        };
+
+        let visibility = if class.is_some() {
+            member_visibility(member_node(node))
+        } else if is_static(node, content) {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        };
+
+        Some(FunctionDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility,
+            location: node_to_location(node),
+            containing_entity_name: containing_type
+                .clone()
+                .or_else(|| enclosing_namespace(node, content)),
+            containing_type,
+            parameters: self.extract_parameters(declarator, content),
+            // Constructors and destructors have no return type
+            return_type: node
+                .child_by_field_name("type")
+                .and_then(|type_node| type_node.utf8_text(content.as_bytes()).ok())
+                .filter(|type_text| *type_text != "void")
+                .map(String::from),
+            decorators: Vec::new(),
+        })
+    }
+
+    fn extract_call_name(&self, node: Node, content: &str) -> Option<(String, Option<String>)> {
+        match node.kind() {
+            "identifier" => {
+                let name = node.utf8_text(content.as_bytes()).ok()?.to_string();
+                Some((name.clone(), Some(name)))
+            }
+            // `obj.method()` and `ptr->method()` alike
+            "field_expression" => {
+                let argument = node.child_by_field_name("argument")?;
+                let field = node.child_by_field_name("field")?;
+
+                let argument_text = argument.utf8_text(content.as_bytes()).ok()?;
+                let field_text = field.utf8_text(content.as_bytes()).ok()?;
+
+                Some((
+                    field_text.to_string(),
+                    Some(format!("{}.{}", argument_text, field_text)),
+                ))
+            }
+            "qualified_identifier" => {
+                let path = node.utf8_text(content.as_bytes()).ok()?;
+                let (name, _) = declarator_name(node, content)?;
+                Some((name, Some(path.to_string())))
+            }
+            // Explicit template arguments, e.g. `make<int>()`
+            "template_function" => {
+                self.extract_call_name(node.child_by_field_name("name")?, content)
+            }
+            _ => None,
+        }
+    }
+
+    fn extract_generic_parameters(&self, node: Node, content: &str) -> Vec<GenericParameter> {
+        let Some(type_params) = node
+            .parent()
+            .filter(|parent| parent.kind() == "template_declaration")
+            .and_then(|template| template.child_by_field_name("parameters"))
+        else {
+            return Vec::new();
+        };
+
+        let text = |n: Node| n.utf8_text(content.as_bytes()).ok().map(String::from);
+
+        let mut params = Vec::new();
+        let mut cursor = type_params.walk();
+        for param_node in type_params.named_children(&mut cursor) {
+            let param = match param_node.kind() {
+                "type_parameter_declaration" | "variadic_type_parameter_declaration" => {
+                    GenericParameter {
+                        name: param_node.named_child(0).and_then(text).unwrap_or_default(),
+                        ..Default::default()
+                    }
+                }
+                "optional_type_parameter_declaration" => GenericParameter {
+                    name: param_node
+                        .child_by_field_name("name")
+                        .and_then(text)
+                        .unwrap_or_default(),
+                    bounds: Vec::new(),
+                    default_type: param_node
+                        .child_by_field_name("default_type")
+                        .and_then(text),
+                },
+                // Non-type parameters such as `int N = 4`, bounded by their type
+                "parameter_declaration" | "optional_parameter_declaration" => GenericParameter {
+                    name: param_node
+                        .child_by_field_name("declarator")
+                        .and_then(declared_identifier)
+                        .and_then(text)
+                        .unwrap_or_default(),
+                    bounds: param_node
+                        .child_by_field_name("type")
+                        .and_then(text)
+                        .into_iter()
+                        .collect(),
+                    default_type: param_node
+                        .child_by_field_name("default_value")
+                        .and_then(text),
+                },
+                _ => continue,
+            };
+
+            if !param.name.is_empty() {
+                params.push(param);
+            }
+        }
+
+        params
+    }
+
+    /// Fields and method names declared in a class, struct or union body
+    fn extract_members(&self, body: Node, content: &str) -> (Vec<FieldDefinition>, Vec<String>) {
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let declaration = if member.kind() == "template_declaration" {
+                let mut template_cursor = member.walk();
+                let inner = member.named_children(&mut template_cursor).last();
+                match inner {
+                    Some(inner) => inner,
+                    None => continue,
+                }
+            } else {
+                member
+            };
+
+            if !matches!(
+                declaration.kind(),
+                "field_declaration" | "function_definition" | "declaration"
+            ) {
+                continue;
+            }
+
+            let mut declarator_cursor = declaration.walk();
+            let declarators: Vec<Node> = declaration
+                .children_by_field_name("declarator", &mut declarator_cursor)
+                .collect();
+
+            // Member functions, whether declared or defined in the body
+            if let Some((name, _)) = declarators
+                .first()
+                .and_then(|d| function_declarator(*d))
+                .and_then(|d| d.child_by_field_name("declarator"))
+                .and_then(|d| declarator_name(d, content))
+            {
+                methods.push(name);
+                continue;
+            }
+
+            if declaration.kind() != "field_declaration" {
+                continue;
+            }
+
+            let type_annotation = declaration
+                .child_by_field_name("type")
+                .and_then(|t| t.utf8_text(content.as_bytes()).ok())
+                .map(String::from);
+            let default_value = declaration
+                .child_by_field_name("default_value")
+                .and_then(|v| v.utf8_text(content.as_bytes()).ok())
+                .map(String::from);
+
+            // `int n, *p;` declares several fields sharing one type
+            for declarator in declarators {
+                let Some(name) = declared_identifier(declarator)
+                    .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                else {
+                    continue;
+                };
+
+                fields.push(FieldDefinition {
+                    name: name.to_string(),
+                    type_annotation: type_annotation.clone(),
+                    visibility: member_visibility(member),
+                    location: node_to_location(declaration),
+                    is_static: is_static(declaration, content),
+                    default_value: default_value.clone(),
+                    ..Default::default()
+                });
+            }
+        }
+
+        (fields, methods)
+    }
+
+    fn extract_enumerators(&self, body: Node, content: &str) -> Vec<FieldDefinition> {
+        let mut cursor = body.walk();
+        body.named_children(&mut cursor)
+            .filter(|n| n.kind() == "enumerator")
+            .filter_map(|enumerator| {
+                Some(FieldDefinition {
+                    name: enumerator
+                        .child_by_field_name("name")?
+                        .utf8_text(content.as_bytes())
+                        .ok()?
+                        .to_string(),
+                    visibility: Visibility::Public,
+                    location: node_to_location(enumerator),
+                    is_static: true,
+                    default_value: enumerator
+                        .child_by_field_name("value")
+                        .and_then(|v| v.utf8_text(content.as_bytes()).ok())
+                        .map(String::from),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn extract_type_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<TypeDefinition> {
+        let kind = match node.kind() {
+            "class_specifier" => TypeKind::Class,
+            "struct_specifier" => TypeKind::Struct,
+            "union_specifier" => TypeKind::Union,
+            "enum_specifier" => TypeKind::Enum,
+            _ => return None,
+        };
+
+        // Only definitions have a body; `struct S *next` merely names the type
+        let body = node.child_by_field_name("body")?;
+
+        // `typedef struct { ... } Name;` names an anonymous struct
+        let name_node = node.child_by_field_name("name").or_else(|| {
+            node.parent()
+                .filter(|parent| parent.kind() == "type_definition")
+                .and_then(|parent| parent.child_by_field_name("declarator"))
+        })?;
+        let name = name_node.utf8_text(content.as_bytes()).ok()?.to_string();
+
+        let (fields, methods) = if kind == TypeKind::Enum {
+            (self.extract_enumerators(body, content), Vec::new())
+        } else {
+            self.extract_members(body, content)
+        };
+
+        let mut super_types = Vec::new();
+        let mut cursor = node.walk();
+        if let Some(bases) = node
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "base_class_clause")
+        {
+            let mut base_cursor = bases.walk();
+            super_types.extend(
+                bases
+                    .named_children(&mut base_cursor)
+                    .filter(|n| {
+                        matches!(
+                            n.kind(),
+                            "type_identifier" | "qualified_identifier" | "template_type"
+                        )
+                    })
+                    .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+                    .map(String::from),
+            );
+        }
+
+        // Doc comments belong to the whole declaration, including any
+        // `template <...>` or `typedef` in front of the type
+        let declaration = node
+            .parent()
+            .filter(|parent| matches!(parent.kind(), "template_declaration" | "type_definition"))
+            .unwrap_or(node);
+
+        let visibility = match enclosing_class(node, content) {
+            Some(_) => member_visibility(member_node(node)),
+            None => Visibility::Public,
+        };
+
+        Some(TypeDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility,
+            location: node_to_location(node),
+            super_types,
+            fields,
+            methods,
+            documentation: leading_comments(content, declaration.start_position().row),
+            containing_entity_name: enclosing_class(node, content)
+                .or_else(|| enclosing_namespace(node, content)),
+            generic_params: self.extract_generic_parameters(node, content),
+            mixins: Vec::new(),
+            decorators: Vec::new(),
+        })
+    }
+
+    fn collect_functions(
+        &self,
+        root: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Vec<FunctionDefinition> {
+        let include_prototypes = is_header(file_path);
+
+        let mut functions: Vec<FunctionDefinition> = Vec::new();
+        traverse_node(root, &mut |node| {
+            let Some(func) =
+                self.extract_function_details(node, content, file_path, include_prototypes)
+            else {
+                return;
+            };
+
+            // A header may declare a function before defining it inline
+            let existing = functions
+                .iter()
+                .position(|f| f.name == func.name && f.containing_type == func.containing_type);
+            match existing {
+                Some(index) if node.kind() == "function_definition" => functions[index] = func,
+                Some(_) => {}
+                None => functions.push(func),
+            }
+        });
+
+        functions
+    }
+
+    fn collect_types(&self, root: Node, content: &str, file_path: &str) -> Vec<TypeDefinition> {
+        let mut types = Vec::new();
+        traverse_node(root, &mut |node| {
+            if let Some(type_def) = self.extract_type_details(node, content, file_path) {
+                types.push(type_def);
+            }
+        });
+        types
+    }
+}
+
+fn extension(file_path: &str) -> Option<&str> {
+    Path::new(file_path).extension().and_then(|e| e.to_str())
+}
+
+/// Whether a file is a C or C++ header
+pub fn is_header(file_path: &str) -> bool {
+    extension(file_path).is_some_and(|ext| HEADER_EXTENSIONS.contains(&ext))
+}
+
+/// The function declarator of a declaration, looking through the pointer and
+/// reference declarators of its return type
+///
+/// Function pointers, whose name is parenthesized, have none.
+fn function_declarator(declarator: Node) -> Option<Node> {
+    match declarator.kind() {
+        "function_declarator" => Some(declarator),
+        "pointer_declarator" | "reference_declarator" => {
+            function_declarator(declarator.child_by_field_name("declarator").or_else(|| {
+                declarator.named_child(declarator.named_child_count().checked_sub(1)?)
+            })?)
+        }
+        _ => None,
+    }
+}
+
+/// The identifier a declarator declares, e.g. `s` in `*s` or `items` in `items[3]`
+fn declared_identifier(declarator: Node) -> Option<Node> {
+    match declarator.kind() {
+        "identifier" | "field_identifier" | "type_identifier" => Some(declarator),
+        "pointer_declarator"
+        | "reference_declarator"
+        | "array_declarator"
+        | "parenthesized_declarator"
+        | "init_declarator" => {
+            declared_identifier(declarator.child_by_field_name("declarator").or_else(|| {
+                declarator.named_child(declarator.named_child_count().checked_sub(1)?)
+            })?)
+        }
+        _ => None,
+    }
+}
+
+/// Name of a declared function and the scope qualifying it, e.g. `("run", Some("Server"))`
+/// for `Server::run` or `ns::Server<T>::run`
+fn declarator_name(node: Node, content: &str) -> Option<(String, Option<String>)> {
+    match node.kind() {
+        "identifier" | "field_identifier" | "destructor_name" | "operator_name" => {
+            Some((node.utf8_text(content.as_bytes()).ok()?.to_string(), None))
+        }
+        "template_function" => declarator_name(node.child_by_field_name("name")?, content),
+        "qualified_identifier" => {
+            let scope = node.child_by_field_name("scope").and_then(|scope| {
+                let scope = match scope.kind() {
+                    "template_type" => scope.child_by_field_name("name")?,
+                    _ => scope,
+                };
+                scope.utf8_text(content.as_bytes()).ok().map(String::from)
+            });
+            // `a::B::run` nests to the right, so the innermost scope is the closest
+            let (name, inner_scope) = declarator_name(node.child_by_field_name("name")?, content)?;
+            Some((name, inner_scope.or(scope)))
+        }
+        _ => None,
+    }
+}
+
+/// The direct child of a class body a member belongs to, looking through `template <...>`
+fn member_node(node: Node) -> Node {
+    node.parent()
+        .filter(|parent| parent.kind() == "template_declaration")
+        .unwrap_or(node)
+}
+
+/// Name of the class, struct or union whose body declares a node
+fn enclosing_class(node: Node, content: &str) -> Option<String> {
+    let body = member_node(node)
+        .parent()
+        .filter(|parent| parent.kind() == "field_declaration_list")?;
+    let class = body.parent()?;
+
+    class
+        .child_by_field_name("name")
+        .or_else(|| {
+            class
+                .parent()
+                .filter(|parent| parent.kind() == "type_definition")
+                .and_then(|parent| parent.child_by_field_name("declarator"))
+        })?
+        .utf8_text(content.as_bytes())
+        .ok()
+        .map(String::from)
+}
+
+/// Qualified name of the namespaces enclosing a node, e.g. `net::http`
+fn enclosing_namespace(node: Node, content: &str) -> Option<String> {
+    let mut names: Vec<&str> = std::iter::successors(node.parent(), |n| n.parent())
+        .filter(|n| n.kind() == "namespace_definition")
+        .filter_map(|n| n.child_by_field_name("name"))
+        .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+        .collect();
+
+    if names.is_empty() {
+        return None;
+    }
+
+    names.reverse();
+    Some(names.join("::"))
+}
+
+/// Whether a declaration sits directly in a file, namespace or `extern "C"` block
+fn is_namespace_scope(node: Node) -> bool {
+    member_node(node)
+        .parent()
+        .is_some_and(|parent| matches!(parent.kind(), "translation_unit" | "declaration_list"))
+}
+
+/// Whether a declaration sits in a file or namespace, looking through
+/// conditional compilation blocks such as include guards
+fn is_file_scope(node: Node) -> bool {
+    std::iter::successors(node.parent(), |n| n.parent())
+        .find(|n| !n.kind().starts_with("preproc_"))
+        .is_some_and(|parent| matches!(parent.kind(), "translation_unit" | "declaration_list"))
+}
+
+fn is_static(node: Node, content: &str) -> bool {
+    let mut cursor = node.walk();
+    let is_static = node
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "storage_class_specifier")
+        .any(|c| c.utf8_text(content.as_bytes()) == Ok("static"));
+    is_static
+}
+
+/// Visibility of a class member from the closest access specifier above it
+///
+/// Members of a `class` are private until one is given, and members of a
+/// `struct` or `union` public.
+fn member_visibility(member: Node) -> Visibility {
+    let access = std::iter::successors(member.prev_named_sibling(), |n| n.prev_named_sibling())
+        .find(|n| n.kind() == "access_specifier");
+
+    let Some(access) = access else {
+        let is_class = member
+            .parent()
+            .and_then(|body| body.parent())
+            .is_some_and(|class| class.kind() == "class_specifier");
+        return if is_class {
+            Visibility::Private
+        } else {
+            Visibility::Public
+        };
+    };
+
+    match access.child(0).map(|keyword| keyword.kind()) {
+        Some("private") => Visibility::Private,
+        Some("protected") => Visibility::Protected,
+        _ => Visibility::Public,
+    }
+}
+
+/// The namespace a file declares, e.g. `net::http` for `namespace net { namespace http {`
+///
+/// Declarations outside it, such as `main`, are ignored, but a file opening
+/// differently named namespaces has none. Include guards are looked through.
+fn file_namespace(root: Node, content: &str) -> Option<String> {
+    let mut names = Vec::new();
+    let mut scope = root;
+
+    loop {
+        let mut cursor = scope.walk();
+        let children: Vec<Node> = scope.named_children(&mut cursor).collect();
+        let namespaces: Vec<(Node, &str)> = children
+            .iter()
+            .filter(|n| n.kind() == "namespace_definition")
+            .filter_map(|n| {
+                let name = n.child_by_field_name("name")?;
+                Some((*n, name.utf8_text(content.as_bytes()).ok()?))
+            })
+            .collect();
+
+        let Some((namespace, name)) = namespaces.first() else {
+            let guards: Vec<&Node> = children
+                .iter()
+                .filter(|n| n.kind() == "preproc_ifdef")
+                .collect();
+            match guards.as_slice() {
+                [guard] => {
+                    scope = **guard;
+                    continue;
+                }
+                _ => break,
+            }
+        };
+        if namespaces.iter().any(|(_, other)| other != name) {
+            break;
+        }
+
+        names.push(*name);
+        match (namespaces.len(), namespace.child_by_field_name("body")) {
+            (1, Some(body)) => scope = body,
+            _ => break,
+        }
+    }
+
+    (!names.is_empty()).then(|| names.join("::"))
+}
+
+/// The comment block directly above a line, without its markers
+///
+/// Both `//` line comments and `/* */` block comments are recognized, and a
+/// `template <...>` line between the comment and the declaration is skipped.
+fn leading_comments(content: &str, line: usize) -> Option<String> {
+    let mut lines: Vec<&str> = content.lines().take(line).map(str::trim).collect();
+    if lines.last().is_some_and(|l| l.starts_with("template")) {
+        lines.pop();
+    }
+
+    let mut doc_lines = Vec::new();
+    if lines.last().is_some_and(|l| l.ends_with("*/")) {
+        while let Some(l) = lines.pop() {
+            let is_start = l.starts_with("/*");
+            let text = l
+                .trim_start_matches("/**")
+                .trim_start_matches("/*")
+                .trim_end_matches("*/")
+                .trim_start_matches('*')
+                .trim();
+            doc_lines.push(text);
+            if is_start {
+                break;
+            }
+        }
+        while doc_lines.first().is_some_and(|l| l.is_empty()) {
+            doc_lines.remove(0);
+        }
+        while doc_lines.last().is_some_and(|l| l.is_empty()) {
+            doc_lines.pop();
+        }
+    } else {
+        doc_lines.extend(
+            lines
+                .iter()
+                .rev()
+                .take_while(|l| l.starts_with("//"))
+                .map(|l| l.trim_start_matches('/').trim_start_matches('!').trim()),
+        );
+    }
+
+    if doc_lines.is_empty() {
+        return None;
+    }
+
+    doc_lines.reverse();
+    Some(doc_lines.join("\n"))
+}
+
+impl LanguageParser for CppParser {
+    /// Determines if this parser can handle a given file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to check
+    ///
+    /// # Returns
+    /// * `bool` - True if this parser can handle the file, false otherwise
+    fn can_handle(&self, file_path: &Path) -> bool {
+        matches!(language_for_file(file_path), Some("c" | "cpp"))
+    }
+
+    /// Parses C and C++ functions and methods from the source code.
+    ///
+    /// Headers also report the prototypes they declare, so functions
+    /// implemented elsewhere, such as in a native library, are still known.
+    /// Prototypes in source files only forward-declare and are skipped.
+    ///
+    /// # Arguments
+    /// * `content` - The C/C++ source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<FunctionDefinition>>` - List of extracted function definitions or an error
+    fn parse_functions(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<FunctionDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code")?;
+        let root_node = tree.root_node();
+
+        tracing::debug!(
+            "Parsed C/C++ file '{}' ({} bytes) - AST has {} nodes",
+            file_path,
+            content.len(),
+            root_node.child_count()
+        );
+
+        Ok(self.collect_functions(root_node, content, file_path))
+    }
+
+    /// Parses function and method calls from C/C++ source code
+    ///
+    /// # Arguments
+    /// * `content` - The C/C++ source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<CallReference>>` - List of extracted function call references or an error
+    fn parse_calls(&mut self, content: &str, file_path: &str) -> Result<Vec<CallReference>> {
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code for function calls")?;
+
+        tracing::debug!(
+            "Parsing function calls from C/C++ file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut calls = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if node.kind() != "call_expression" {
+                return;
+            }
+
+            let Some((name, full_path)) = node
+                .child_by_field_name("function")
+                .and_then(|function| self.extract_call_name(function, content))
+            else {
+                return;
+            };
+
+            calls.push(
+                CallReference::with_details(
+                    name,
+                    full_path,
+                    Some(node_to_location(node)),
+                    Some(file_path.to_string()),
+                    Vec::new(),
+                )
+                .with_caller(enclosing_function_name(node, |n| {
+                    self.extract_function_details(n, content, file_path, false)
+                        .map(|func| func.name)
+                })),
+            );
+        });
+
+        Ok(calls)
+    }
+
+    /// Parses classes, structs, unions and enums
+    ///
+    /// Base classes are reported as `super_types`, and `methods` lists the
+    /// member functions declared in the body, wherever they are defined.
+    ///
+    /// # Arguments
+    /// * `content` - The C/C++ source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<TypeDefinition>>` - List of extracted type definitions or an error
+    fn parse_types(&mut self, content: &str, file_path: &str) -> Result<Vec<TypeDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "types")?;
+
+        tracing::debug!(
+            "Parsing types from C/C++ file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        Ok(self.collect_types(tree.root_node(), content, file_path))
+    }
+
+    /// Parses the namespace and `#include` directives of a C/C++ file
+    ///
+    /// The module is named after the namespace the file declares, e.g.
+    /// `net::http`, or the file name if it has none. Each include imports
+    /// everything the included file declares; quoted includes are relative to
+    /// the including file, angle-bracket ones are searched on the include path.
+    ///
+    /// # Arguments
+    /// * `content` - The C/C++ source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<ModuleDefinition>` - Module definition or an error
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            tracing::debug!(
+                "Empty C/C++ file content for module parsing: '{}'",
+                file_path
+            );
+            return Ok(module_def);
+        }
+
+        let tree = self.parse_tree(content, file_path, "module")?;
+        let root_node = tree.root_node();
+
+        if let Some(namespace) = file_namespace(root_node, content) {
+            module_def.name = namespace;
+        }
+
+        traverse_node(root_node, &mut |node| {
+            if node.kind() != "preproc_include" {
+                return;
+            }
+
+            let Some(path) = node.child_by_field_name("path") else {
+                return;
+            };
+            let Ok(text) = path.utf8_text(content.as_bytes()) else {
+                return;
+            };
+
+            module_def.imports.push(ImportDefinition {
+                module_name: text.trim_matches(['"', '<', '>']).to_string(),
+                imported_symbols: vec!["*".to_string()],
+                location: node_to_location(node),
+                is_relative: path.kind() == "string_literal",
+            });
+        });
+
+        // Everything declared outside a class with external linkage
+        module_def.exports.extend(
+            self.collect_functions(root_node, content, file_path)
+                .into_iter()
+                .filter(|f| f.containing_type.is_none() && f.visibility == Visibility::Public)
+                .map(|f| f.name),
+        );
+        module_def.exports.extend(
+            self.collect_types(root_node, content, file_path)
+                .into_iter()
+                .filter(|t| t.visibility == Visibility::Public)
+                .map(|t| t.name),
+        );
+
+        Ok(module_def)
+    }
+
+    /// Extracts the comment block directly above a declaration
+    fn extract_documentation(&self, content: &str, location: &Location) -> Result<Option<String>> {
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(leading_comments(content, location.start.line))
+    }
+
+    /// Parses variables defined at file or namespace scope, leaving out
+    /// `extern` declarations of variables defined elsewhere, and object-like
+    /// `#define` macros, which are constants
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variables")?;
+        let text = |node: Node| node.utf8_text(content.as_bytes()).ok().map(String::from);
+
+        let mut variables = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| match node.kind() {
+            "preproc_def" => {
+                // Macros without a value are flags, such as include guards
+                let Some(name) = node.child_by_field_name("name").and_then(text) else {
+                    return;
+                };
+                let Some(value) = node
+                    .child_by_field_name("value")
+                    .and_then(text)
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                else {
+                    return;
+                };
+                variables.push(VariableDefinition {
+                    name,
+                    file_path: file_path.to_string(),
+                    type_annotation: None,
+                    initializer: Some(value),
+                    visibility: Visibility::Public,
+                    location: node_to_location(node),
+                    is_const: true,
+                    is_static: false,
+                    documentation: None,
+                });
+            }
+            "declaration" if is_file_scope(node) => {
+                let mut cursor = node.walk();
+                let specifiers: Vec<String> = node
+                    .children(&mut cursor)
+                    .filter(|c| matches!(c.kind(), "storage_class_specifier" | "type_qualifier"))
+                    .filter_map(text)
+                    .collect();
+                if specifiers.iter().any(|s| s == "extern") {
+                    return;
+                }
+                let is_const = specifiers.iter().any(|s| s == "const" || s == "constexpr");
+                let is_static = is_static(node, content);
+
+                let mut cursor = node.walk();
+                for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                    let target = match declarator.kind() {
+                        "init_declarator" => declarator.child_by_field_name("declarator"),
+                        _ => Some(declarator),
+                    };
+                    if target.is_none_or(|target| function_declarator(target).is_some()) {
+                        continue;
+                    }
+                    let Some(name) = declared_identifier(declarator)
+                        .filter(|name| name.kind() == "identifier")
+                        .and_then(text)
+                    else {
+                        continue;
+                    };
+
+                    variables.push(VariableDefinition {
+                        name,
+                        file_path: file_path.to_string(),
+                        type_annotation: node.child_by_field_name("type").and_then(text),
+                        initializer: (declarator.kind() == "init_declarator")
+                            .then(|| declarator.child_by_field_name("value"))
+                            .flatten()
+                            .and_then(text),
+                        // Static variables have internal linkage
+                        visibility: if is_static {
+                            Visibility::Private
+                        } else {
+                            Visibility::Public
+                        },
+                        location: node_to_location(node),
+                        is_const,
+                        is_static,
+                        documentation: None,
+                    });
+                }
+            }
+            _ => {}
+        });
+
+        Ok(variables)
+    }
+
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variable references")?;
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier"],
+            |n| {
+                self.extract_function_details(n, content, file_path, false)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
+        Box::new(CppParser::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_c_functions() -> Result<()> {
+        let mut parser = CppParser::new();
+        let content = indoc! {r#"
+            #include <stdio.h>
+            #include "util.h"
+
+            static int helper(void);
+
+            /* Adds two numbers. */
+            static int add(int a, int b) { return a + b; }
+
+            int *make(const char *name, ...) {
+                printf("%s", name);
+                return helper();
+            }
+
+            int (*callback)(int);
+        "#};
+
+        let functions = parser.parse_functions(content, "math.c")?;
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["add", "make"]);
+
+        let add = &functions[0];
+        assert_eq!(add.kind, FunctionKind::Function);
+        assert_eq!(add.visibility, Visibility::Private);
+        assert_eq!(
+            parser.extract_documentation(content, &add.location)?,
+            Some("Adds two numbers.".to_string())
+        );
+
+        let params: Vec<(&str, Option<&str>)> = functions[1]
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(params, vec![("name", Some("const char *")), ("...", None)]);
+        assert_eq!(functions[1].visibility, Visibility::Public);
+
+        let calls = parser.parse_calls(content, "math.c")?;
+        let calls: Vec<(&str, Option<&str>)> = calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.caller_name.as_deref()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![("printf", Some("make")), ("helper", Some("make"))]
+        );
+
+        let module = parser.parse_modules(content, "math.c")?;
+        assert_eq!(module.name, "math.c");
+        let imports: Vec<(&str, bool)> = module
+            .imports
+            .iter()
+            .map(|i| (i.module_name.as_str(), i.is_relative))
+            .collect();
+        assert_eq!(imports, vec![("stdio.h", false), ("util.h", true)]);
+        assert_eq!(module.exports, vec!["make"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_c_variables() -> Result<()> {
+        let mut parser = CppParser::new();
+        let content = indoc! {r#"
+            #ifndef CONFIG_H
+            #define CONFIG_H
+            #define BUFFER_SIZE 4096
+
+            extern int verbose;
+            static const char *prefix = "log";
+            int counter = 0, *cursor;
+            int compute(int x);
+
+            void tick(void) {
+                int local = BUFFER_SIZE;
+                counter++;
+            }
+            #endif
+        "#};
+
+        let variables = parser.parse_variables(content, "config.c")?;
+        let summary: Vec<_> = variables
+            .iter()
+            .map(|v| {
+                (
+                    v.name.as_str(),
+                    v.type_annotation.as_deref(),
+                    v.initializer.as_deref(),
+                    v.is_const,
+                    v.is_static,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("BUFFER_SIZE", None, Some("4096"), true, false),
+                ("prefix", Some("char"), Some(r#""log""#), true, true),
+                ("counter", Some("int"), Some("0"), false, false),
+                ("cursor", Some("int"), None, false, false),
+            ]
+        );
+        assert_eq!(variables[1].visibility, Visibility::Private);
+
+        let references = parser.parse_variable_references(content, "config.c")?;
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+        assert!(find("counter").is_write);
+        assert!(!find("BUFFER_SIZE").is_write);
+        assert_eq!(find("BUFFER_SIZE").caller_name, "tick");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_cpp_classes() -> Result<()> {
+        let mut parser = CppParser::new();
+        let content = indoc! {r#"
+            #ifndef SERVER_HPP
+            #define SERVER_HPP
+
+            namespace net { namespace http {
+
+            /// Serves requests.
+            template <typename T, int N = 4>
+            class Server : public Base, private virtual ns::Handler<T> {
+            public:
+                explicit Server(int port);
+                virtual void run() = 0;
+                int size() const { return count; }
+                static int instances;
+            private:
+                int count, *slots;
+            };
+
+            struct Point { int x = 0; };
+            enum class Color { Red, Green = 2 };
+            int serve(Server<int> &server);
+
+            }}
+
+            #endif
+        "#};
+
+        let types = parser.parse_types(content, "server.hpp")?;
+        let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Server", "Point", "Color"]);
+
+        let server = &types[0];
+        assert_eq!(server.kind, TypeKind::Class);
+        assert_eq!(server.super_types, vec!["Base", "ns::Handler<T>"]);
+        assert_eq!(server.methods, vec!["Server", "run", "size"]);
+        assert_eq!(server.documentation.as_deref(), Some("Serves requests."));
+        assert_eq!(server.containing_entity_name.as_deref(), Some("net::http"));
+        let generics: Vec<(&str, Option<&str>)> = server
+            .generic_params
+            .iter()
+            .map(|g| (g.name.as_str(), g.default_type.as_deref()))
+            .collect();
+        assert_eq!(generics, vec![("T", None), ("N", Some("4"))]);
+
+        let fields: Vec<(&str, &Visibility, bool)> = server
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), &f.visibility, f.is_static))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("instances", &Visibility::Public, true),
+                ("count", &Visibility::Private, false),
+                ("slots", &Visibility::Private, false),
+            ]
+        );
+        assert_eq!(types[1].fields[0].default_value.as_deref(), Some("0"));
+        assert_eq!(types[2].kind, TypeKind::Enum);
+        assert_eq!(types[2].fields.len(), 2);
+
+        // Headers report prototypes as well as inline definitions
+        let functions = parser.parse_functions(content, "server.hpp")?;
+        let functions: Vec<(&str, &FunctionKind, Option<&str>)> = functions
+            .iter()
+            .map(|f| (f.name.as_str(), &f.kind, f.containing_type.as_deref()))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![
+                ("size", &FunctionKind::Method, Some("Server")),
+                ("serve", &FunctionKind::Function, None),
+            ]
+        );
+
+        let module = parser.parse_modules(content, "server.hpp")?;
+        assert_eq!(module.name, "net::http");
+        assert_eq!(module.exports, vec!["serve", "Server", "Point", "Color"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_out_of_line_methods() -> Result<()> {
+        let mut parser = CppParser::new();
+        let content = indoc! {r#"
+            #include "server.hpp"
+
+            namespace net::http {
+
+            Server::Server(int port) : count(port) { init(); }
+
+            template <typename T>
+            void Server<T>::run() {
+                this->step();
+                client->send(make<int>());
+                util::log("run");
+            }
+
+            }
+
+            int main() { return 0; }
+        "#};
+
+        let functions = parser.parse_functions(content, "server.cpp")?;
+        let functions: Vec<(&str, &FunctionKind, Option<&str>)> = functions
+            .iter()
+            .map(|f| (f.name.as_str(), &f.kind, f.containing_type.as_deref()))
+            .collect();
+        assert_eq!(
+            functions,
+            vec![
+                ("Server", &FunctionKind::Constructor, Some("Server")),
+                ("run", &FunctionKind::Method, Some("Server")),
+                ("main", &FunctionKind::Function, None),
+            ]
+        );
+
+        let calls = parser.parse_calls(content, "server.cpp")?;
+        let calls: Vec<(&str, Option<&str>)> = calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.fully_qualified_name.as_deref()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("init", Some("init")),
+                ("step", Some("this.step")),
+                ("send", Some("client.send")),
+                ("make", Some("make")),
+                ("log", Some("util::log")),
+            ]
+        );
+
+        let module = parser.parse_modules(content, "server.cpp")?;
+        assert_eq!(module.name, "net::http");
+        assert_eq!(module.exports, vec!["main"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cpp_parser_empty_content() -> Result<()> {
+        let mut parser = CppParser::new();
+
+        assert!(parser.parse_functions("", "empty.cpp")?.is_empty());
+        assert!(parser.parse_types("", "empty.h")?.is_empty());
+        assert!(parser.parse_calls("", "empty.c")?.is_empty());
+
+        let module = parser.parse_modules("", "empty.hpp")?;
+        assert!(module.imports.is_empty());
+
+        Ok(())
+    }
+}
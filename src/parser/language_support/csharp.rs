@@ -0,0 +1,1232 @@
+use super::*;
+use super::{node_to_location, traverse_node};
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::{Node, Parser, Tree};
+
+/// Declarations that introduce a C# type
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "struct_declaration",
+    "record_declaration",
+    "enum_declaration",
+];
+
+pub struct CSharpParser {
+    parser: Parser,
+}
+
+impl Default for CSharpParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CSharpParser {
+    /// Creates a new C# parser
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of the C# parser
+    ///
+    /// # Panics
+    /// This function will panic if the tree-sitter C# language cannot be loaded.
+    /// This should only happen in case of a build/linking issue with the tree-sitter library.
+    pub fn new() -> Self {
+        Self::try_new().expect("Failed to load C# grammar - this is a build configuration error")
+    }
+
+    /// Creates a new C# parser with error handling
+    ///
+    /// # Returns
+    /// * `Result<Self>` - A new instance of the C# parser or an error
+    pub fn try_new() -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_c_sharp::LANGUAGE.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load C# grammar: {}", e))?;
+        Ok(Self { parser })
+    }
+
+    fn parse_tree(&mut self, content: &str, file_path: &str, what: &str) -> Result<Tree> {
+        self.parser.parse(content, None).ok_or_else(|| {
+            let filename = Path::new(file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            anyhow::anyhow!(
+                "Failed to parse C# {} in file '{}' ({}B). The file may contain syntax errors.",
+                what,
+                filename,
+                content.len()
+            )
+        })
+    }
+
+    fn extract_parameters(&self, param_list: Node, content: &str) -> Vec<Parameter> {
+        let text = |n: Node| n.utf8_text(content.as_bytes()).ok().map(String::from);
+
+        let mut params = Vec::new();
+        let mut cursor = param_list.walk();
+        for param_node in param_list.named_children(&mut cursor) {
+            if param_node.kind() != "parameter" {
+                continue;
+            }
+
+            let Some(name) = param_node.child_by_field_name("name").and_then(text) else {
+                continue;
+            };
+            // The default value is the last child, following `=`
+            let default_value = param_node
+                .named_child(param_node.named_child_count().saturating_sub(1))
+                .filter(|last| last.prev_sibling().is_some_and(|prev| prev.kind() == "="))
+                .and_then(text);
+
+            params.push(Parameter {
+                name,
+                type_annotation: param_node.child_by_field_name("type").and_then(text),
+                default_value,
+            });
+        }
+
+        // A `params` array is written directly into the list
+        if let Some(name) = param_list.child_by_field_name("name").and_then(text) {
+            params.push(Parameter {
+                name,
+                type_annotation: param_list
+                    .child_by_field_name("type")
+                    .and_then(text)
+                    .map(|t| format!("params {}", t)),
+                default_value: None,
+            });
+        }
+
+        params
+    }
+
+    fn extract_function_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<FunctionDefinition> {
+        let containing_type = enclosing_type(node, content);
+
+        let (kind, name) = match node.kind() {
+            "method_declaration" => (FunctionKind::Method, text_of(node, "name", content)?),
+            "constructor_declaration" => {
+                (FunctionKind::Constructor, text_of(node, "name", content)?)
+            }
+            "destructor_declaration" => (
+                FunctionKind::Method,
+                format!("~{}", text_of(node, "name", content)?),
+            ),
+            "local_function_statement" => (FunctionKind::Function, text_of(node, "name", content)?),
+            _ => return None,
+        };
+
+        let (containing_type, visibility) = if kind == FunctionKind::Function {
+            (None, Visibility::Private)
+        } else {
+            let visibility = member_visibility(node, content);
+            (containing_type, visibility)
+        };
+
+        Some(FunctionDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility,
+            location: node_to_location(node),
+            containing_entity_name: containing_type
+                .clone()
+                .or_else(|| enclosing_namespace(node, content)),
+            containing_type,
+            parameters: node
+                .child_by_field_name("parameters")
+                .map(|params| self.extract_parameters(params, content))
+                .unwrap_or_default(),
+            // Methods name their return type `returns`, local functions `type`
+            return_type: node
+                .child_by_field_name("returns")
+                .or_else(|| node.child_by_field_name("type"))
+                .and_then(|type_node| type_node.utf8_text(content.as_bytes()).ok())
+                .filter(|type_text| *type_text != "void")
+                .map(String::from),
+            decorators: decorators(node, content),
+        })
+    }
+
+    fn extract_call_name(&self, node: Node, content: &str) -> Option<(String, Option<String>)> {
+        match node.kind() {
+            "identifier" => {
+                let name = node.utf8_text(content.as_bytes()).ok()?.to_string();
+                Some((name.clone(), Some(name)))
+            }
+            // Explicit type arguments, e.g. `Parse<int>(text)`
+            "generic_name" => self.extract_call_name(node.named_child(0)?, content),
+            "member_access_expression" => {
+                let name_node = node.child_by_field_name("name")?;
+                let (name, _) = self.extract_call_name(name_node, content)?;
+
+                // `this` and `base` aren't nodes of their own, so the receiver
+                // is read from the source text
+                let receiver = content
+                    .get(node.start_byte()..name_node.start_byte())?
+                    .trim()
+                    .trim_end_matches('.')
+                    .trim();
+                let full_path = if receiver.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", receiver, name)
+                };
+
+                Some((name, Some(full_path)))
+            }
+            _ => None,
+        }
+    }
+
+    fn extract_generic_parameters(&self, node: Node, content: &str) -> Vec<GenericParameter> {
+        let mut params = Vec::new();
+
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "type_parameter_list" => {
+                    let mut param_cursor = child.walk();
+                    params.extend(
+                        child
+                            .named_children(&mut param_cursor)
+                            .filter_map(|param| text_of(param, "name", content))
+                            .map(|name| GenericParameter {
+                                name,
+                                ..Default::default()
+                            }),
+                    );
+                }
+                // `where T : class, IComparable<T>`
+                "type_parameter_constraints_clause" => {
+                    let mut constraint_cursor = child.walk();
+                    let mut parts = child.named_children(&mut constraint_cursor);
+                    let Some(target) = parts
+                        .next()
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                    else {
+                        continue;
+                    };
+                    let bounds: Vec<String> = parts
+                        .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+                        .map(String::from)
+                        .collect();
+
+                    if let Some(param) = params.iter_mut().find(|p| p.name == target) {
+                        param.bounds.extend(bounds);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        params
+    }
+
+    /// Fields, properties and method names declared in a type body
+    fn extract_members(
+        &self,
+        body: Node,
+        content: &str,
+        default_visibility: Visibility,
+    ) -> (Vec<FieldDefinition>, Vec<String>) {
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let modifiers = modifiers(member, content);
+            let visibility =
+                visibility_from_modifiers(&modifiers).unwrap_or(default_visibility.clone());
+            let is_static = modifiers.contains(&"static") || modifiers.contains(&"const");
+
+            match member.kind() {
+                "method_declaration" | "constructor_declaration" => {
+                    methods.extend(text_of(member, "name", content));
+                }
+                "destructor_declaration" => {
+                    methods.extend(text_of(member, "name", content).map(|n| format!("~{}", n)));
+                }
+                "field_declaration" => {
+                    let mut declaration_cursor = member.walk();
+                    let Some(declaration) = member
+                        .named_children(&mut declaration_cursor)
+                        .find(|n| n.kind() == "variable_declaration")
+                    else {
+                        continue;
+                    };
+                    let type_annotation = text_of(declaration, "type", content);
+
+                    // `int x, y = 1;` declares several fields sharing one type
+                    let mut declarator_cursor = declaration.walk();
+                    for declarator in declaration
+                        .named_children(&mut declarator_cursor)
+                        .filter(|n| n.kind() == "variable_declarator")
+                    {
+                        let Some(name) = text_of(declarator, "name", content) else {
+                            continue;
+                        };
+                        let default_value = declarator
+                            .named_child(declarator.named_child_count().saturating_sub(1))
+                            .filter(|value| {
+                                Some(value.id())
+                                    != declarator.child_by_field_name("name").map(|n| n.id())
+                            })
+                            .and_then(|value| value.utf8_text(content.as_bytes()).ok())
+                            .map(String::from);
+
+                        fields.push(FieldDefinition {
+                            name,
+                            type_annotation: type_annotation.clone(),
+                            visibility: visibility.clone(),
+                            location: node_to_location(declarator),
+                            is_static,
+                            default_value,
+                            is_optional: type_annotation
+                                .as_deref()
+                                .is_some_and(|t| t.ends_with('?')),
+                            annotations: attributes(member, content),
+                            documentation: doc_comments(content, member.start_position().row),
+                        });
+                    }
+                }
+                "property_declaration" => {
+                    let Some(name) = text_of(member, "name", content) else {
+                        continue;
+                    };
+                    let type_annotation = text_of(member, "type", content);
+                    // Expression-bodied properties, `=> expr`, are computed, not initialized
+                    let default_value = member
+                        .child_by_field_name("value")
+                        .filter(|value| value.kind() != "arrow_expression_clause")
+                        .and_then(|value| value.utf8_text(content.as_bytes()).ok())
+                        .map(String::from);
+
+                    fields.push(FieldDefinition {
+                        name,
+                        is_optional: type_annotation.as_deref().is_some_and(|t| t.ends_with('?')),
+                        type_annotation,
+                        visibility,
+                        location: node_to_location(member),
+                        is_static,
+                        default_value,
+                        annotations: attributes(member, content),
+                        documentation: doc_comments(content, member.start_position().row),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        (fields, methods)
+    }
+
+    /// Properties declared by a record's primary constructor, e.g. `record Point(int X, int Y)`
+    fn extract_record_parameters(&self, node: Node, content: &str) -> Vec<FieldDefinition> {
+        let mut cursor = node.walk();
+        let Some(param_list) = node
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "parameter_list")
+        else {
+            return Vec::new();
+        };
+
+        let mut param_cursor = param_list.walk();
+        param_list
+            .named_children(&mut param_cursor)
+            .filter(|n| n.kind() == "parameter")
+            .filter_map(|param| {
+                Some(FieldDefinition {
+                    name: text_of(param, "name", content)?,
+                    type_annotation: text_of(param, "type", content),
+                    visibility: Visibility::Public,
+                    location: node_to_location(param),
+                    annotations: attributes(param, content),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn extract_enum_members(&self, body: Node, content: &str) -> Vec<FieldDefinition> {
+        let mut cursor = body.walk();
+        body.named_children(&mut cursor)
+            .filter(|n| n.kind() == "enum_member_declaration")
+            .filter_map(|member| {
+                Some(FieldDefinition {
+                    name: text_of(member, "name", content)?,
+                    visibility: Visibility::Public,
+                    location: node_to_location(member),
+                    is_static: true,
+                    default_value: text_of(member, "value", content),
+                    annotations: attributes(member, content),
+                    documentation: doc_comments(content, member.start_position().row),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn extract_type_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<TypeDefinition> {
+        let kind = match node.kind() {
+            "class_declaration" => TypeKind::Class,
+            "interface_declaration" => TypeKind::Interface,
+            "struct_declaration" => TypeKind::Struct,
+            "enum_declaration" => TypeKind::Enum,
+            "record_declaration" => {
+                let mut cursor = node.walk();
+                let is_struct = node.children(&mut cursor).any(|n| n.kind() == "struct");
+                if is_struct {
+                    TypeKind::Struct
+                } else {
+                    TypeKind::Class
+                }
+            }
+            _ => return None,
+        };
+        let name = text_of(node, "name", content)?;
+
+        // Interface members are public unless stated otherwise, class and struct members private
+        let member_default = if kind == TypeKind::Interface {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        };
+
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+        if node.kind() == "record_declaration" {
+            fields.extend(self.extract_record_parameters(node, content));
+        }
+        if let Some(body) = node.child_by_field_name("body") {
+            if kind == TypeKind::Enum {
+                fields.extend(self.extract_enum_members(body, content));
+            } else {
+                let (members, member_methods) = self.extract_members(body, content, member_default);
+                fields.extend(members);
+                methods = member_methods;
+            }
+        }
+
+        let mut super_types = Vec::new();
+        let mut cursor = node.walk();
+        if let Some(bases) = node
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "base_list")
+        {
+            let mut base_cursor = bases.walk();
+            for base in bases.named_children(&mut base_cursor) {
+                // `record Manager(string Name) : Person(Name)` passes arguments to its base
+                let base = match base.kind() {
+                    "primary_constructor_base_type" => match base.named_child(0) {
+                        Some(base_type) => base_type,
+                        None => continue,
+                    },
+                    "argument_list" => continue,
+                    _ => base,
+                };
+                super_types.extend(base.utf8_text(content.as_bytes()).ok().map(String::from));
+            }
+        }
+
+        let visibility = visibility_from_modifiers(&modifiers(node, content)).unwrap_or(
+            // Nested types default to private, top-level ones to internal
+            if enclosing_type(node, content).is_some() {
+                Visibility::Private
+            } else {
+                Visibility::Internal
+            },
+        );
+
+        Some(TypeDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility,
+            location: node_to_location(node),
+            super_types,
+            fields,
+            methods,
+            documentation: doc_comments(content, node.start_position().row),
+            containing_entity_name: enclosing_type(node, content)
+                .or_else(|| enclosing_namespace(node, content)),
+            generic_params: self.extract_generic_parameters(node, content),
+            mixins: Vec::new(),
+            decorators: decorators(node, content),
+        })
+    }
+}
+
+fn text_of(node: Node, field: &str, content: &str) -> Option<String> {
+    node.child_by_field_name(field)?
+        .utf8_text(content.as_bytes())
+        .ok()
+        .map(String::from)
+}
+
+fn modifiers<'a>(node: Node, content: &'a str) -> Vec<&'a str> {
+    let mut cursor = node.walk();
+    let modifiers = node
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == "modifier")
+        .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+        .collect();
+    modifiers
+}
+
+/// The attributes applied to a declaration, e.g. `Table("orders")` for `[Table("orders")]`
+fn attributes(node: Node, content: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    let lists: Vec<Node> = node
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == "attribute_list")
+        .collect();
+
+    lists
+        .into_iter()
+        .flat_map(|list| {
+            let mut list_cursor = list.walk();
+            list.named_children(&mut list_cursor)
+                .filter(|n| n.kind() == "attribute")
+                .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The attributes applied to a type or method, split into their name and
+/// arguments, e.g. `Route` with the argument `"api/users"` for `[Route("api/users")]`
+fn decorators(node: Node, content: &str) -> Vec<Decorator> {
+    attributes(node, content)
+        .iter()
+        .map(|attribute| Decorator::parse(attribute))
+        .collect()
+}
+
+/// Visibility given by access modifiers, if there are any
+fn visibility_from_modifiers(modifiers: &[&str]) -> Option<Visibility> {
+    let has = |m: &str| modifiers.contains(&m);
+
+    if has("public") {
+        Some(Visibility::Public)
+    } else if has("private") {
+        // `private protected` is narrower than either
+        Some(Visibility::Private)
+    } else if has("protected") {
+        Some(Visibility::Protected)
+    } else if has("internal") {
+        Some(Visibility::Internal)
+    } else {
+        None
+    }
+}
+
+/// Visibility of a member from its modifiers, or its declaring type's default
+fn member_visibility(node: Node, content: &str) -> Visibility {
+    visibility_from_modifiers(&modifiers(node, content)).unwrap_or_else(|| {
+        let in_interface = node
+            .parent()
+            .and_then(|body| body.parent())
+            .is_some_and(|parent| parent.kind() == "interface_declaration");
+        if in_interface {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        }
+    })
+}
+
+/// Name of the type whose body declares a node
+fn enclosing_type(node: Node, content: &str) -> Option<String> {
+    let body = node
+        .parent()
+        .filter(|parent| parent.kind() == "declaration_list")?;
+    let declaration = body
+        .parent()
+        .filter(|parent| TYPE_DECLARATIONS.contains(&parent.kind()))?;
+    text_of(declaration, "name", content)
+}
+
+/// Qualified name of the namespace a node is declared in, block-scoped or file-scoped
+fn enclosing_namespace(node: Node, content: &str) -> Option<String> {
+    let mut names: Vec<String> = std::iter::successors(node.parent(), |n| n.parent())
+        .filter(|n| n.kind() == "namespace_declaration")
+        .filter_map(|n| text_of(n, "name", content))
+        .collect();
+
+    if names.is_empty() {
+        let root = std::iter::successors(Some(node), |n| n.parent()).last()?;
+        let mut cursor = root.walk();
+        let file_scoped = root
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "file_scoped_namespace_declaration");
+        return file_scoped.and_then(|namespace| text_of(namespace, "name", content));
+    }
+
+    names.reverse();
+    Some(names.join("."))
+}
+
+/// The `///` XML documentation comment directly above a line, without its
+/// markers or `<summary>` tags
+fn doc_comments(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().take(line).collect();
+    let mut doc_lines: Vec<&str> = lines
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with("///"))
+        .map(|l| {
+            l.trim_start_matches("///")
+                .trim()
+                .trim_start_matches("<summary>")
+                .trim_end_matches("</summary>")
+                .trim()
+        })
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    if doc_lines.is_empty() {
+        return None;
+    }
+
+    doc_lines.reverse();
+    Some(doc_lines.join("\n"))
+}
+
+impl LanguageParser for CSharpParser {
+    /// Determines if this parser can handle a given file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to check
+    ///
+    /// # Returns
+    /// * `bool` - True if this parser can handle the file, false otherwise
+    fn can_handle(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| ext == "cs")
+    }
+
+    /// Parses C# methods, constructors, finalizers and local functions
+    ///
+    /// # Arguments
+    /// * `content` - The C# source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<FunctionDefinition>>` - List of extracted function definitions or an error
+    fn parse_functions(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<FunctionDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code")?;
+        let root_node = tree.root_node();
+
+        tracing::debug!(
+            "Parsed C# file '{}' ({} bytes) - AST has {} nodes",
+            file_path,
+            content.len(),
+            root_node.child_count()
+        );
+
+        let mut functions = Vec::new();
+        traverse_node(root_node, &mut |node| {
+            if let Some(func) = self.extract_function_details(node, content, file_path) {
+                functions.push(func);
+            }
+        });
+
+        Ok(functions)
+    }
+
+    /// Parses method invocations from C# source code
+    ///
+    /// # Arguments
+    /// * `content` - The C# source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<CallReference>>` - List of extracted function call references or an error
+    fn parse_calls(&mut self, content: &str, file_path: &str) -> Result<Vec<CallReference>> {
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code for method invocations")?;
+
+        tracing::debug!(
+            "Parsing method invocations from C# file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut calls = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if node.kind() != "invocation_expression" {
+                return;
+            }
+
+            let Some((name, full_path)) = node
+                .child_by_field_name("function")
+                .and_then(|function| self.extract_call_name(function, content))
+            else {
+                return;
+            };
+
+            calls.push(
+                CallReference::with_details(
+                    name,
+                    full_path,
+                    Some(node_to_location(node)),
+                    Some(file_path.to_string()),
+                    Vec::new(),
+                )
+                .with_caller(enclosing_function_name(node, |n| {
+                    self.extract_function_details(n, content, file_path)
+                        .map(|func| func.name)
+                })),
+            );
+        });
+
+        Ok(calls)
+    }
+
+    /// Parses C# classes, interfaces, structs, records and enums
+    ///
+    /// Properties are reported as fields, as are the positional parameters of
+    /// records. Attributes become decorators of types and annotations of fields.
+    ///
+    /// # Arguments
+    /// * `content` - The C# source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<TypeDefinition>>` - List of extracted type definitions or an error
+    fn parse_types(&mut self, content: &str, file_path: &str) -> Result<Vec<TypeDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "types")?;
+
+        tracing::debug!(
+            "Parsing types from C# file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut types = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if let Some(type_def) = self.extract_type_details(node, content, file_path) {
+                types.push(type_def);
+            }
+        });
+
+        Ok(types)
+    }
+
+    /// Parses the namespace and `using` directives of a C# file
+    ///
+    /// The module is named after the file's first namespace. A `using`
+    /// directive imports every type of a namespace, or every static member of
+    /// a type for `using static`; an alias imports the aliased name.
+    ///
+    /// # Arguments
+    /// * `content` - The C# source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<ModuleDefinition>` - Module definition or an error
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            tracing::debug!("Empty C# file content for module parsing: '{}'", file_path);
+            return Ok(module_def);
+        }
+
+        let tree = self.parse_tree(content, file_path, "module")?;
+        let root_node = tree.root_node();
+
+        let mut namespace_found = false;
+        traverse_node(root_node, &mut |node| match node.kind() {
+            "namespace_declaration" | "file_scoped_namespace_declaration" if !namespace_found => {
+                if let Some(name) = text_of(node, "name", content) {
+                    module_def.name = name;
+                    namespace_found = true;
+                }
+            }
+            "using_directive" => {
+                // The imported namespace or type is the directive's last named child,
+                // following the alias of `using IO = System.IO;`
+                let Some(target) = node
+                    .named_child(node.named_child_count().saturating_sub(1))
+                    .and_then(|target| target.utf8_text(content.as_bytes()).ok())
+                else {
+                    return;
+                };
+
+                // Both a namespace and `using static` bring all of their members into scope
+                let imported_symbols = match text_of(node, "name", content) {
+                    Some(alias) => vec![alias],
+                    None => vec!["*".to_string()],
+                };
+
+                module_def.imports.push(ImportDefinition {
+                    module_name: target.to_string(),
+                    imported_symbols,
+                    location: node_to_location(node),
+                    is_relative: false,
+                });
+            }
+            kind if TYPE_DECLARATIONS.contains(&kind)
+                && modifiers(node, content).contains(&"public") =>
+            {
+                module_def.exports.extend(text_of(node, "name", content));
+            }
+            _ => {}
+        });
+
+        Ok(module_def)
+    }
+
+    /// Extracts the `///` documentation comment directly above a declaration
+    fn extract_documentation(&self, content: &str, location: &Location) -> Result<Option<String>> {
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(doc_comments(content, location.start.line))
+    }
+
+    /// Parses the names methods read or write. C# has no variables outside
+    /// types, so these refer to fields and properties, such as `const` fields.
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variable references")?;
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
+        Box::new(CSharpParser::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_csharp_basic_parsing() -> Result<()> {
+        let mut parser = CSharpParser::new();
+        let content = indoc! {r#"
+            namespace Shop.Orders
+            {
+                public class OrderService
+                {
+                    /// <summary>
+                    /// Places an order.
+                    /// </summary>
+                    public Order Place(string sku, int quantity = 1, params string[] tags)
+                    {
+                        return Build(sku);
+                    }
+
+                    OrderService(int capacity) { }
+
+                    ~OrderService() { }
+
+                    protected internal void Reset()
+                    {
+                        void Clear() { }
+                        Clear();
+                    }
+                }
+
+                interface IAuditable
+                {
+                    void Audit();
+                }
+            }
+        "#};
+
+        let functions = parser.parse_functions(content, "OrderService.cs")?;
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Place",
+                "OrderService",
+                "~OrderService",
+                "Reset",
+                "Clear",
+                "Audit"
+            ]
+        );
+
+        let place = &functions[0];
+        assert_eq!(place.kind, FunctionKind::Method);
+        assert_eq!(place.visibility, Visibility::Public);
+        assert_eq!(place.containing_type.as_deref(), Some("OrderService"));
+        let params: Vec<(&str, Option<&str>, Option<&str>)> = place
+            .parameters
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.type_annotation.as_deref(),
+                    p.default_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("sku", Some("string"), None),
+                ("quantity", Some("int"), Some("1")),
+                ("tags", Some("params string[]"), None),
+            ]
+        );
+        assert_eq!(
+            parser.extract_documentation(content, &place.location)?,
+            Some("Places an order.".to_string())
+        );
+
+        assert_eq!(functions[1].kind, FunctionKind::Constructor);
+        assert_eq!(functions[1].visibility, Visibility::Private);
+        assert_eq!(functions[3].visibility, Visibility::Protected);
+        assert_eq!(functions[4].kind, FunctionKind::Function);
+        assert_eq!(functions[4].containing_type, None);
+        assert_eq!(functions[5].visibility, Visibility::Public);
+        assert_eq!(functions[5].containing_type.as_deref(), Some("IAuditable"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_csharp_calls() -> Result<()> {
+        let mut parser = CSharpParser::new();
+        let content = indoc! {r#"
+            class Worker
+            {
+                void Run()
+                {
+                    Start();
+                    this.Stop();
+                    repository.Orders.Save(order);
+                    Parse<int>(text);
+                    Console.WriteLine(Format(value));
+                }
+            }
+        "#};
+
+        let calls = parser.parse_calls(content, "Worker.cs")?;
+        let paths: Vec<(&str, Option<&str>)> = calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.fully_qualified_name.as_deref()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("Start", Some("Start")),
+                ("Stop", Some("this.Stop")),
+                ("Save", Some("repository.Orders.Save")),
+                ("Parse", Some("Parse")),
+                ("WriteLine", Some("Console.WriteLine")),
+                ("Format", Some("Format")),
+            ]
+        );
+        assert!(calls
+            .iter()
+            .all(|c| c.caller_name.as_deref() == Some("Run")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_csharp_attributes_on_types_and_methods() -> Result<()> {
+        let mut parser = CSharpParser::new();
+        let content = indoc::indoc! {r#"
+            [Serializable]
+            public record Person(string Name);
+
+            [ApiController, Route("api/users")]
+            public class UsersController
+            {
+                [HttpGet("{id}")]
+                [Obsolete]
+                public void Run() {}
+            }
+        "#};
+
+        let types = parser.parse_types(content, "Users.cs")?;
+        let decorators: Vec<(&str, Vec<&Decorator>)> = types
+            .iter()
+            .map(|t| (t.name.as_str(), t.decorators.iter().collect()))
+            .collect();
+        assert_eq!(
+            decorators,
+            vec![
+                (
+                    "Person",
+                    vec![&Decorator {
+                        name: "Serializable".to_string(),
+                        arguments: Vec::new(),
+                    }]
+                ),
+                (
+                    "UsersController",
+                    vec![
+                        &Decorator {
+                            name: "ApiController".to_string(),
+                            arguments: Vec::new(),
+                        },
+                        &Decorator {
+                            name: "Route".to_string(),
+                            arguments: vec!["\"api/users\"".to_string()],
+                        },
+                    ]
+                ),
+            ]
+        );
+
+        let functions = parser.parse_functions(content, "Users.cs")?;
+        let run = functions.iter().find(|f| f.name == "Run").unwrap();
+        assert_eq!(
+            run.decorators,
+            vec![
+                Decorator {
+                    name: "HttpGet".to_string(),
+                    arguments: vec!["\"{id}\"".to_string()],
+                },
+                Decorator {
+                    name: "Obsolete".to_string(),
+                    arguments: Vec::new(),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_csharp_parser_empty_content() -> Result<()> {
+        let mut parser = CSharpParser::new();
+
+        assert!(parser.parse_functions("", "Empty.cs")?.is_empty());
+        assert!(parser.parse_calls("", "Empty.cs")?.is_empty());
+        assert!(parser.parse_types("", "Empty.cs")?.is_empty());
+
+        let module = parser.parse_modules("", "Empty.cs")?;
+        assert_eq!(module.name, "Empty.cs");
+        assert!(module.imports.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_csharp_types_and_properties() -> Result<()> {
+        let mut parser = CSharpParser::new();
+        let content = indoc! {r#"
+            namespace Shop;
+
+            /// An order placed by a customer.
+            [Table("orders")]
+            public class Order<T> : Entity, IComparable<Order<T>> where T : class, new()
+            {
+                /// The order number.
+                [Key, Required]
+                public int Id { get; set; }
+
+                public string? Note { get; init; } = "none";
+
+                public int Total => items.Count;
+
+                private static readonly int max = 10, min;
+
+                public void Submit() { }
+            }
+
+            public record Point(int X, int Y);
+
+            public record struct Size(int Width, int Height);
+
+            internal struct Money
+            {
+                decimal amount;
+            }
+
+            public interface IRepository
+            {
+                void Save();
+            }
+
+            enum Status { Open, Closed = 2 }
+        "#};
+
+        let types = parser.parse_types(content, "Order.cs")?;
+        let kinds: Vec<(&str, &TypeKind)> =
+            types.iter().map(|t| (t.name.as_str(), &t.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Order", &TypeKind::Class),
+                ("Point", &TypeKind::Class),
+                ("Size", &TypeKind::Struct),
+                ("Money", &TypeKind::Struct),
+                ("IRepository", &TypeKind::Interface),
+                ("Status", &TypeKind::Enum),
+            ]
+        );
+
+        let order = &types[0];
+        assert_eq!(order.visibility, Visibility::Public);
+        assert_eq!(order.super_types, vec!["Entity", "IComparable<Order<T>>"]);
+        assert_eq!(order.methods, vec!["Submit"]);
+        assert_eq!(
+            order.documentation.as_deref(),
+            Some("An order placed by a customer.")
+        );
+        assert_eq!(order.containing_entity_name.as_deref(), Some("Shop"));
+        assert_eq!(order.generic_params.len(), 1);
+        assert_eq!(order.generic_params[0].name, "T");
+        assert_eq!(order.generic_params[0].bounds, vec!["class", "new()"]);
+
+        let fields: Vec<&str> = order.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["Id", "Note", "Total", "max", "min"]);
+
+        let id = &order.fields[0];
+        assert_eq!(id.type_annotation.as_deref(), Some("int"));
+        assert_eq!(id.visibility, Visibility::Public);
+        assert_eq!(id.annotations, vec!["Key", "Required"]);
+        assert_eq!(id.documentation.as_deref(), Some("The order number."));
+
+        let note = &order.fields[1];
+        assert!(note.is_optional);
+        assert_eq!(note.default_value.as_deref(), Some("\"none\""));
+        assert_eq!(order.fields[2].default_value, None);
+
+        let max = &order.fields[3];
+        assert!(max.is_static);
+        assert_eq!(max.visibility, Visibility::Private);
+        assert_eq!(max.default_value.as_deref(), Some("10"));
+
+        let point: Vec<(&str, Option<&str>)> = types[1]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(point, vec![("X", Some("int")), ("Y", Some("int"))]);
+
+        assert_eq!(types[3].visibility, Visibility::Internal);
+        assert_eq!(types[3].fields[0].visibility, Visibility::Private);
+        assert_eq!(types[4].methods, vec!["Save"]);
+
+        let status: Vec<(&str, Option<&str>)> = types[5]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.default_value.as_deref()))
+            .collect();
+        assert_eq!(status, vec![("Open", None), ("Closed", Some("2"))]);
+        assert_eq!(types[5].visibility, Visibility::Internal);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_csharp_modules() -> Result<()> {
+        let mut parser = CSharpParser::new();
+        let content = indoc! {r#"
+            using System;
+            using System.Collections.Generic;
+            using static System.Math;
+            using IO = System.IO;
+
+            namespace Shop.Orders
+            {
+                public class Order { }
+
+                class Draft { }
+            }
+        "#};
+
+        let module = parser.parse_modules(content, "src/Order.cs")?;
+        assert_eq!(module.name, "Shop.Orders");
+        assert_eq!(module.exports, vec!["Order"]);
+
+        let imports: Vec<(&str, Vec<&str>)> = module
+            .imports
+            .iter()
+            .map(|i| {
+                (
+                    i.module_name.as_str(),
+                    i.imported_symbols.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("System", vec!["*"]),
+                ("System.Collections.Generic", vec!["*"]),
+                ("System.Math", vec!["*"]),
+                ("System.IO", vec!["IO"]),
+            ]
+        );
+        assert!(module.imports.iter().all(|i| !i.is_relative));
+
+        Ok(())
+    }
+}
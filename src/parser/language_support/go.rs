@@ -0,0 +1,1053 @@
+use super::*;
+use super::{node_to_location, traverse_node};
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::{Node, Parser, Tree};
+
+pub struct GoParser {
+    parser: Parser,
+}
+
+impl Default for GoParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GoParser {
+    /// Creates a new Go parser
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of the Go parser
+    ///
+    /// # Panics
+    /// This function will panic if the tree-sitter Go language cannot be loaded.
+    /// This should only happen in case of a build/linking issue with the tree-sitter library.
+    pub fn new() -> Self {
+        Self::try_new().expect("Failed to load Go grammar - this is a build configuration error")
+    }
+
+    /// Creates a new Go parser with error handling
+    ///
+    /// # Returns
+    /// * `Result<Self>` - A new instance of the Go parser or an error
+    pub fn try_new() -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_go::LANGUAGE.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load Go grammar: {}", e))?;
+        Ok(Self { parser })
+    }
+
+    fn parse_tree(&mut self, content: &str, file_path: &str, what: &str) -> Result<Tree> {
+        self.parser.parse(content, None).ok_or_else(|| {
+            let filename = Path::new(file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            anyhow::anyhow!(
+                "Failed to parse Go {} in file '{}' ({}B). The file may contain syntax errors.",
+                what,
+                filename,
+                content.len()
+            )
+        })
+    }
+
+    fn extract_parameters(&self, node: Node, content: &str) -> Vec<Parameter> {
+        let mut params = Vec::new();
+
+        let Some(param_list) = node.child_by_field_name("parameters") else {
+            return params;
+        };
+
+        let mut cursor = param_list.walk();
+        for param_node in param_list.named_children(&mut cursor) {
+            if !matches!(
+                param_node.kind(),
+                "parameter_declaration" | "variadic_parameter_declaration"
+            ) {
+                continue;
+            }
+
+            let type_annotation = param_node
+                .child_by_field_name("type")
+                .and_then(|t| t.utf8_text(content.as_bytes()).ok())
+                .map(|t| {
+                    if param_node.kind() == "variadic_parameter_declaration" {
+                        format!("...{}", t)
+                    } else {
+                        t.to_string()
+                    }
+                });
+
+            // `a, b int` declares several parameters sharing one type
+            let mut name_cursor = param_node.walk();
+            let names: Vec<&str> = param_node
+                .children_by_field_name("name", &mut name_cursor)
+                .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+                .collect();
+
+            if names.is_empty() {
+                params.push(Parameter {
+                    name: "_".to_string(),
+                    type_annotation,
+                    default_value: None,
+                });
+            } else {
+                params.extend(names.into_iter().map(|name| Parameter {
+                    name: name.to_string(),
+                    type_annotation: type_annotation.clone(),
+                    default_value: None,
+                }));
+            }
+        }
+
+        params
+    }
+
+    /// Name of the type a method's receiver refers to, e.g. `Server` for `(s *Server[T])`
+    fn extract_receiver_type(&self, node: Node, content: &str) -> Option<String> {
+        let receiver = node.child_by_field_name("receiver")?;
+        let mut cursor = receiver.walk();
+        let param = receiver
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "parameter_declaration")?;
+
+        let mut type_node = param.child_by_field_name("type")?;
+        loop {
+            type_node = match type_node.kind() {
+                "pointer_type" => type_node.named_child(0)?,
+                "generic_type" => type_node.child_by_field_name("type")?,
+                _ => break,
+            };
+        }
+
+        type_node
+            .utf8_text(content.as_bytes())
+            .ok()
+            .map(String::from)
+    }
+
+    fn extract_function_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<FunctionDefinition> {
+        let kind = match node.kind() {
+            "function_declaration" => FunctionKind::Function,
+            "method_declaration" => FunctionKind::Method,
+            _ => return None,
+        };
+
+        let name = node
+            .child_by_field_name("name")?
+            .utf8_text(content.as_bytes())
+            .ok()?
+            .to_string();
+        let containing_type = self.extract_receiver_type(node, content);
+
+        Some(FunctionDefinition {
+            visibility: go_visibility(&name),
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            location: node_to_location(node),
+            containing_type: containing_type.clone(),
+            parameters: self.extract_parameters(node, content),
+            // A single type or a parenthesised list, e.g. `(*User, error)`
+            return_type: node
+                .child_by_field_name("result")
+                .and_then(|result| result.utf8_text(content.as_bytes()).ok())
+                .map(String::from),
+            containing_entity_name: containing_type,
+            decorators: Vec::new(),
+        })
+    }
+
+    fn extract_call_name(&self, node: Node, content: &str) -> Option<(String, Option<String>)> {
+        match node.kind() {
+            "identifier" | "type_identifier" => {
+                let name = node.utf8_text(content.as_bytes()).ok()?.to_string();
+                Some((name.clone(), Some(name)))
+            }
+            "selector_expression" => {
+                let operand = node.child_by_field_name("operand")?;
+                let field = node.child_by_field_name("field")?;
+
+                let operand_text = operand.utf8_text(content.as_bytes()).ok()?;
+                let field_text = field.utf8_text(content.as_bytes()).ok()?;
+
+                Some((
+                    field_text.to_string(),
+                    Some(format!("{}.{}", operand_text, field_text)),
+                ))
+            }
+            "qualified_type" => {
+                let package = node.child_by_field_name("package")?;
+                let name = node.child_by_field_name("name")?;
+
+                let package_text = package.utf8_text(content.as_bytes()).ok()?;
+                let name_text = name.utf8_text(content.as_bytes()).ok()?;
+
+                Some((
+                    name_text.to_string(),
+                    Some(format!("{}.{}", package_text, name_text)),
+                ))
+            }
+            // Explicit instantiation of a generic function, e.g. `Map[int](xs)`
+            "index_expression" => {
+                self.extract_call_name(node.child_by_field_name("operand")?, content)
+            }
+            "generic_type" => self.extract_call_name(node.child_by_field_name("type")?, content),
+            _ => None,
+        }
+    }
+
+    fn extract_generic_parameters(&self, node: Node, content: &str) -> Vec<GenericParameter> {
+        let Some(type_params) = node.child_by_field_name("type_parameters") else {
+            return Vec::new();
+        };
+
+        let mut params = Vec::new();
+        let mut cursor = type_params.walk();
+        for param_node in type_params.named_children(&mut cursor) {
+            let constraint = param_node
+                .child_by_field_name("type")
+                .and_then(|t| t.utf8_text(content.as_bytes()).ok())
+                .map(String::from);
+
+            let mut name_cursor = param_node.walk();
+            for name in param_node.children_by_field_name("name", &mut name_cursor) {
+                if let Ok(name) = name.utf8_text(content.as_bytes()) {
+                    params.push(GenericParameter {
+                        name: name.to_string(),
+                        bounds: constraint.iter().cloned().collect(),
+                        default_type: None,
+                    });
+                }
+            }
+        }
+
+        params
+    }
+
+    /// Fields of a struct, with embedded types returned separately
+    fn extract_struct_fields(
+        &self,
+        struct_node: Node,
+        content: &str,
+    ) -> (Vec<FieldDefinition>, Vec<String>) {
+        let mut fields = Vec::new();
+        let mut embedded = Vec::new();
+
+        let mut cursor = struct_node.walk();
+        let Some(field_list) = struct_node
+            .named_children(&mut cursor)
+            .find(|n| n.kind() == "field_declaration_list")
+        else {
+            return (fields, embedded);
+        };
+
+        let mut field_cursor = field_list.walk();
+        for field in field_list.named_children(&mut field_cursor) {
+            if field.kind() != "field_declaration" {
+                continue;
+            }
+
+            let Some(type_node) = field.child_by_field_name("type") else {
+                continue;
+            };
+            let type_text = type_node.utf8_text(content.as_bytes()).ok();
+            let annotations: Vec<String> = field
+                .child_by_field_name("tag")
+                .and_then(|tag| tag.utf8_text(content.as_bytes()).ok())
+                .map(String::from)
+                .into_iter()
+                .collect();
+
+            let mut name_cursor = field.walk();
+            let names: Vec<&str> = field
+                .children_by_field_name("name", &mut name_cursor)
+                .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+                .collect();
+
+            // A field without a name embeds its type, promoting the type's
+            // fields and methods
+            if names.is_empty() {
+                embedded.extend(type_text.map(String::from));
+                continue;
+            }
+
+            for name in names {
+                fields.push(FieldDefinition {
+                    name: name.to_string(),
+                    type_annotation: type_text.map(String::from),
+                    visibility: go_visibility(name),
+                    location: node_to_location(field),
+                    annotations: annotations.clone(),
+                    ..Default::default()
+                });
+            }
+        }
+
+        (fields, embedded)
+    }
+
+    /// Method names of an interface, with embedded interfaces returned separately
+    fn extract_interface_methods(
+        &self,
+        interface_node: Node,
+        content: &str,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut methods = Vec::new();
+        let mut embedded = Vec::new();
+
+        let mut cursor = interface_node.walk();
+        for child in interface_node.named_children(&mut cursor) {
+            match child.kind() {
+                "method_spec" | "method_elem" => methods.extend(
+                    child
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        .map(String::from),
+                ),
+                "comment" => {}
+                // Embedded interfaces and type constraints such as `~int | ~string`
+                _ => embedded.extend(child.utf8_text(content.as_bytes()).ok().map(String::from)),
+            }
+        }
+
+        (methods, embedded)
+    }
+
+    fn extract_type_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<TypeDefinition> {
+        let name = node
+            .child_by_field_name("name")?
+            .utf8_text(content.as_bytes())
+            .ok()?
+            .to_string();
+        let type_node = node.child_by_field_name("type")?;
+
+        let (kind, fields, methods, super_types) = match (node.kind(), type_node.kind()) {
+            ("type_alias", _) => (TypeKind::TypeAlias, Vec::new(), Vec::new(), Vec::new()),
+            (_, "struct_type") => {
+                let (fields, embedded) = self.extract_struct_fields(type_node, content);
+                (TypeKind::Struct, fields, Vec::new(), embedded)
+            }
+            (_, "interface_type") => {
+                let (methods, embedded) = self.extract_interface_methods(type_node, content);
+                (TypeKind::Interface, Vec::new(), methods, embedded)
+            }
+            // A defined type such as `type Celsius float64`
+            _ => (TypeKind::TypeAlias, Vec::new(), Vec::new(), Vec::new()),
+        };
+
+        // Doc comments belong to the `type` declaration, not to the spec
+        let declaration = node
+            .parent()
+            .filter(|parent| parent.kind() == "type_declaration")
+            .unwrap_or(node);
+
+        Some(TypeDefinition {
+            visibility: go_visibility(&name),
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            location: node_to_location(node),
+            super_types,
+            fields,
+            methods,
+            documentation: leading_comments(content, declaration.start_position().row),
+            containing_entity_name: None,
+            generic_params: self.extract_generic_parameters(node, content),
+            mixins: Vec::new(),
+            decorators: Vec::new(),
+        })
+    }
+}
+
+/// Go exports identifiers that start with an upper case letter; everything
+/// else is visible only within its package
+fn go_visibility(name: &str) -> Visibility {
+    if name.starts_with(char::is_uppercase) {
+        Visibility::Public
+    } else {
+        Visibility::Package
+    }
+}
+
+/// The `//` comment lines directly above a line, without their markers
+fn leading_comments(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().take(line).collect();
+    let mut doc_lines: Vec<&str> = lines
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with("//"))
+        .map(|l| l.trim_start_matches("//").trim())
+        .collect();
+
+    if doc_lines.is_empty() {
+        return None;
+    }
+
+    doc_lines.reverse();
+    Some(doc_lines.join("\n"))
+}
+
+impl LanguageParser for GoParser {
+    /// Determines if this parser can handle a given file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to check
+    ///
+    /// # Returns
+    /// * `bool` - True if this parser can handle the file, false otherwise
+    fn can_handle(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| ext == "go")
+    }
+
+    /// Parses Go functions and methods from the source code.
+    ///
+    /// Methods have their receiver's type as `containing_type`, whether the
+    /// receiver is a value or a pointer.
+    ///
+    /// # Arguments
+    /// * `content` - The Go source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<FunctionDefinition>>` - List of extracted function definitions or an error
+    fn parse_functions(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<FunctionDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code")?;
+        let root_node = tree.root_node();
+
+        tracing::debug!(
+            "Parsed Go file '{}' ({} bytes) - AST has {} nodes",
+            file_path,
+            content.len(),
+            root_node.child_count()
+        );
+
+        let mut functions = Vec::new();
+        traverse_node(root_node, &mut |node| {
+            if let Some(func) = self.extract_function_details(node, content, file_path) {
+                functions.push(func);
+            }
+        });
+
+        Ok(functions)
+    }
+
+    /// Parses function and method calls from Go source code
+    ///
+    /// # Arguments
+    /// * `content` - The Go source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<CallReference>>` - List of extracted function call references or an error
+    fn parse_calls(&mut self, content: &str, file_path: &str) -> Result<Vec<CallReference>> {
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code for function calls")?;
+
+        tracing::debug!(
+            "Parsing function calls from Go file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut calls = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            let function = match node.kind() {
+                "call_expression" => node.child_by_field_name("function"),
+                // `Map[int](xs)` reads like a conversion to a generic type, but
+                // instantiates and calls a generic function far more often
+                "type_conversion_expression" => node
+                    .child_by_field_name("type")
+                    .filter(|t| t.kind() == "generic_type"),
+                _ => return,
+            };
+
+            let Some((name, full_path)) =
+                function.and_then(|function| self.extract_call_name(function, content))
+            else {
+                return;
+            };
+
+            calls.push(
+                CallReference::with_details(
+                    name,
+                    full_path,
+                    Some(node_to_location(node)),
+                    Some(file_path.to_string()),
+                    Vec::new(),
+                )
+                .with_caller(enclosing_function_name(node, |n| {
+                    self.extract_function_details(n, content, file_path)
+                        .map(|func| func.name)
+                })),
+            );
+        });
+
+        Ok(calls)
+    }
+
+    /// Parses Go structs, interfaces and defined types
+    ///
+    /// Embedded structs and interfaces are reported as `super_types`. Go
+    /// methods are declared outside their type, so struct `methods` are left
+    /// empty; the indexer matches them up through `containing_type`.
+    ///
+    /// # Arguments
+    /// * `content` - The Go source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<TypeDefinition>>` - List of extracted type definitions or an error
+    fn parse_types(&mut self, content: &str, file_path: &str) -> Result<Vec<TypeDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "types")?;
+
+        tracing::debug!(
+            "Parsing types from Go file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut types = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if matches!(node.kind(), "type_spec" | "type_alias") {
+                if let Some(type_def) = self.extract_type_details(node, content, file_path) {
+                    types.push(type_def);
+                }
+            }
+        });
+
+        Ok(types)
+    }
+
+    /// Parses the package clause and imports of a Go file
+    ///
+    /// The module is named after its package. Each import lists the name the
+    /// package is referred to by in this file: its alias, or the last element
+    /// of its path.
+    ///
+    /// # Arguments
+    /// * `content` - The Go source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<ModuleDefinition>` - Module definition or an error
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            tracing::debug!("Empty Go file content for module parsing: '{}'", file_path);
+            return Ok(module_def);
+        }
+
+        let tree = self.parse_tree(content, file_path, "module")?;
+        let root_node = tree.root_node();
+
+        let mut cursor = root_node.walk();
+        for node in root_node.named_children(&mut cursor) {
+            match node.kind() {
+                "package_clause" => {
+                    if let Some(name) = node
+                        .named_child(0)
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                    {
+                        module_def.name = name.to_string();
+                    }
+                    module_def.documentation = leading_comments(content, node.start_position().row);
+                }
+                "import_declaration" => traverse_node(node, &mut |spec| {
+                    if spec.kind() != "import_spec" {
+                        return;
+                    }
+
+                    let Some(path) = spec
+                        .child_by_field_name("path")
+                        .and_then(|p| p.utf8_text(content.as_bytes()).ok())
+                        .map(|p| p.trim_matches(|c| c == '"' || c == '`'))
+                    else {
+                        return;
+                    };
+
+                    let alias = spec
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok());
+                    let imported_symbols = match alias {
+                        // Dot imports bring every exported name into scope
+                        Some(".") => vec!["*".to_string()],
+                        // Blank imports are only for side effects
+                        Some("_") => Vec::new(),
+                        Some(alias) => vec![alias.to_string()],
+                        None => vec![path.rsplit('/').next().unwrap_or(path).to_string()],
+                    };
+
+                    module_def.imports.push(ImportDefinition {
+                        module_name: path.to_string(),
+                        imported_symbols,
+                        location: node_to_location(spec),
+                        is_relative: path.starts_with('.'),
+                    });
+                }),
+                "function_declaration" | "method_declaration" | "type_declaration" => {
+                    traverse_node(node, &mut |child| {
+                        let is_declared_name = child
+                            .parent()
+                            .and_then(|parent| parent.child_by_field_name("name"))
+                            .is_some_and(|name| name.id() == child.id())
+                            && matches!(
+                                child.parent().map(|p| p.kind()),
+                                Some(
+                                    "function_declaration"
+                                        | "method_declaration"
+                                        | "type_spec"
+                                        | "type_alias"
+                                )
+                            );
+                        if is_declared_name {
+                            if let Ok(name) = child.utf8_text(content.as_bytes()) {
+                                if go_visibility(name) == Visibility::Public {
+                                    module_def.exports.push(name.to_string());
+                                }
+                            }
+                        }
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(module_def)
+    }
+
+    /// Extracts the `//` comment block directly above a declaration
+    fn extract_documentation(&self, content: &str, location: &Location) -> Result<Option<String>> {
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(leading_comments(content, location.start.line))
+    }
+
+    /// Parses package-level `const` and `var` declarations, one variable per
+    /// name, so `var a, b = 1, 2` gives `a` the value `1` and `b` the value `2`
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "package variables")?;
+        let root = tree.root_node();
+        let text = |node: Node| node.utf8_text(content.as_bytes()).ok().map(String::from);
+
+        let mut variables = Vec::new();
+        let mut cursor = root.walk();
+        for declaration in root.children(&mut cursor) {
+            let is_const = match declaration.kind() {
+                "const_declaration" => true,
+                "var_declaration" => false,
+                _ => continue,
+            };
+            traverse_node(declaration, &mut |spec| {
+                if !matches!(spec.kind(), "const_spec" | "var_spec") {
+                    return;
+                }
+                let type_annotation = spec.child_by_field_name("type").and_then(text);
+                let values = spec.child_by_field_name("value");
+                let mut names = spec.walk();
+                for (index, name) in spec.children_by_field_name("name", &mut names).enumerate() {
+                    let Some(name) = text(name).filter(|name| name != "_") else {
+                        continue;
+                    };
+                    variables.push(VariableDefinition {
+                        visibility: go_visibility(&name),
+                        name,
+                        file_path: file_path.to_string(),
+                        type_annotation: type_annotation.clone(),
+                        initializer: values
+                            .and_then(|values| values.named_child(index))
+                            .and_then(text),
+                        location: node_to_location(spec),
+                        is_const,
+                        is_static: false,
+                        documentation: None,
+                    });
+                }
+            });
+        }
+
+        Ok(variables)
+    }
+
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variable references")?;
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
+        Box::new(GoParser::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_functions_and_methods() -> Result<()> {
+        let mut parser = GoParser::new();
+        let content = indoc! {r#"
+            package server
+
+            // New creates a server.
+            func New(name string, port int, opts ...Option) *Server {
+                return &Server{}
+            }
+
+            func (s *Server[T]) Start(a, b string) error {
+                return nil
+            }
+
+            func (Base) close() {}
+        "#};
+
+        let functions = parser.parse_functions(content, "server.go")?;
+        assert_eq!(functions.len(), 3);
+
+        let new = &functions[0];
+        assert_eq!(new.name, "New");
+        assert_eq!(new.kind, FunctionKind::Function);
+        assert_eq!(new.visibility, Visibility::Public);
+        assert_eq!(new.containing_type, None);
+        assert_eq!(new.return_type.as_deref(), Some("*Server"));
+        let params: Vec<(&str, Option<&str>)> = new
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("name", Some("string")),
+                ("port", Some("int")),
+                ("opts", Some("...Option"))
+            ]
+        );
+        assert_eq!(
+            parser.extract_documentation(content, &new.location)?,
+            Some("New creates a server.".to_string())
+        );
+
+        let start = &functions[1];
+        assert_eq!(start.kind, FunctionKind::Method);
+        assert_eq!(start.containing_type.as_deref(), Some("Server"));
+        assert_eq!(start.parameters.len(), 2);
+        assert_eq!(start.return_type.as_deref(), Some("error"));
+
+        let close = &functions[2];
+        assert_eq!(close.containing_type.as_deref(), Some("Base"));
+        assert_eq!(close.visibility, Visibility::Package);
+        assert_eq!(close.return_type, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_go_parser_empty_content() -> Result<()> {
+        let mut parser = GoParser::new();
+
+        assert!(parser.parse_functions("", "empty.go")?.is_empty());
+        assert!(parser.parse_types("", "empty.go")?.is_empty());
+        assert!(parser.parse_calls("", "empty.go")?.is_empty());
+
+        let module = parser.parse_modules("", "empty.go")?;
+        assert!(module.imports.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_structs_and_interfaces() -> Result<()> {
+        let mut parser = GoParser::new();
+        let content = indoc! {r#"
+            package store
+
+            // Store persists records.
+            type Store interface {
+                io.Closer
+                Get(id string) (Record, error)
+                Put(r Record) error
+            }
+
+            type Cache[K comparable, V any] struct {
+                Base
+                *sync.Mutex
+                Name, Path string `json:"name"`
+                entries map[K]V
+            }
+
+            type ID = string
+            type Celsius float64
+        "#};
+
+        let types = parser.parse_types(content, "store.go")?;
+        assert_eq!(types.len(), 4);
+
+        let store = &types[0];
+        assert_eq!(store.name, "Store");
+        assert_eq!(store.kind, TypeKind::Interface);
+        assert_eq!(store.methods, vec!["Get", "Put"]);
+        assert_eq!(store.super_types, vec!["io.Closer"]);
+        assert_eq!(
+            store.documentation.as_deref(),
+            Some("Store persists records.")
+        );
+
+        let cache = &types[1];
+        assert_eq!(cache.kind, TypeKind::Struct);
+        assert_eq!(cache.super_types, vec!["Base", "sync.Mutex"]);
+        let fields: Vec<(&str, Option<&str>)> = cache
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.type_annotation.as_deref()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("Name", Some("string")),
+                ("Path", Some("string")),
+                ("entries", Some("map[K]V"))
+            ]
+        );
+        assert_eq!(cache.fields[0].annotations, vec!["`json:\"name\"`"]);
+        assert_eq!(cache.fields[2].visibility, Visibility::Package);
+        let generics: Vec<(&str, &[String])> = cache
+            .generic_params
+            .iter()
+            .map(|g| (g.name.as_str(), g.bounds.as_slice()))
+            .collect();
+        assert_eq!(
+            generics,
+            vec![
+                ("K", &["comparable".to_string()][..]),
+                ("V", &["any".to_string()][..])
+            ]
+        );
+
+        assert_eq!(types[2].name, "ID");
+        assert_eq!(types[2].kind, TypeKind::TypeAlias);
+        assert_eq!(types[3].name, "Celsius");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_imports() -> Result<()> {
+        let mut parser = GoParser::new();
+        let content = indoc! {r#"
+            // Package api serves requests.
+            package api
+
+            import (
+                "fmt"
+                str "strings"
+                . "math"
+                _ "embed"
+                "github.com/acme/shop/internal/store"
+            )
+
+            func Serve() {}
+            func helper() {}
+            type Handler struct{}
+        "#};
+
+        let module = parser.parse_modules(content, "api/server.go")?;
+        assert_eq!(module.name, "api");
+        assert_eq!(
+            module.documentation.as_deref(),
+            Some("Package api serves requests.")
+        );
+
+        let imports: Vec<(&str, Vec<&str>)> = module
+            .imports
+            .iter()
+            .map(|i| {
+                (
+                    i.module_name.as_str(),
+                    i.imported_symbols.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("fmt", vec!["fmt"]),
+                ("strings", vec!["str"]),
+                ("math", vec!["*"]),
+                ("embed", vec![]),
+                ("github.com/acme/shop/internal/store", vec!["store"]),
+            ]
+        );
+        assert_eq!(module.exports, vec!["Serve", "Handler"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_variables() -> Result<()> {
+        let mut parser = GoParser::new();
+        let content = indoc! {r#"
+            package config
+
+            // MaxRetries bounds how often a request is retried.
+            const MaxRetries = 3
+
+            const (
+                modeA Mode = iota
+                modeB
+            )
+
+            var host, port = "localhost", 8080
+
+            func Address() string {
+                port++
+                return fmt.Sprintf("%s:%d", host, port)
+            }
+        "#};
+
+        let variables = parser.parse_variables(content, "config.go")?;
+        let summary: Vec<(&str, Option<&str>, Option<&str>, bool)> = variables
+            .iter()
+            .map(|v| {
+                (
+                    v.name.as_str(),
+                    v.type_annotation.as_deref(),
+                    v.initializer.as_deref(),
+                    v.is_const,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("MaxRetries", None, Some("3"), true),
+                ("modeA", Some("Mode"), Some("iota"), true),
+                ("modeB", None, None, true),
+                ("host", None, Some(r#""localhost""#), false),
+                ("port", None, Some("8080"), false),
+            ]
+        );
+        assert_eq!(variables[0].visibility, Visibility::Public);
+        assert_eq!(variables[1].visibility, Visibility::Package);
+        assert_eq!(
+            parser.extract_documentation(content, &variables[0].location)?,
+            Some("MaxRetries bounds how often a request is retried.".to_string())
+        );
+
+        let references = parser.parse_variable_references(content, "config.go")?;
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+        assert!(find("port").is_write);
+        assert!(!find("host").is_write);
+        assert_eq!(find("host").caller_name, "Address");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_calls() -> Result<()> {
+        let mut parser = GoParser::new();
+        let content = indoc! {r#"
+            package main
+
+            var handler = setup()
+
+            func (s *Server) Run() {
+                fmt.Println("running")
+                s.listen()
+                process[int](items)
+            }
+        "#};
+
+        let calls = parser.parse_calls(content, "main.go")?;
+        assert_eq!(calls.len(), 4);
+
+        assert_eq!(calls[0].callee_name, "setup");
+        assert_eq!(calls[0].caller_name, None);
+
+        assert_eq!(calls[1].callee_name, "Println");
+        assert_eq!(
+            calls[1].fully_qualified_name.as_deref(),
+            Some("fmt.Println")
+        );
+        assert_eq!(calls[1].caller_name.as_deref(), Some("Run"));
+
+        assert_eq!(calls[2].fully_qualified_name.as_deref(), Some("s.listen"));
+        assert_eq!(calls[3].callee_name, "process");
+
+        Ok(())
+    }
+}
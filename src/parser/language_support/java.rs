@@ -15,7 +15,9 @@ impl Default for JavaParser {
 impl JavaParser {
     pub fn new() -> Self {
         let mut parser = Parser::new();
-        parser.set_language(tree_sitter_java::language()).unwrap();
+        parser
+            .set_language(&tree_sitter_java::LANGUAGE.into())
+            .unwrap();
         Self { parser }
     }
 
@@ -48,7 +50,9 @@ impl JavaParser {
                                 location: self.extract_location(node),
                                 containing_type: Some(name.to_string()),
                                 parameters: self.extract_parameters(node, content),
+                                return_type: None,
                                 containing_entity_name: Some(name.to_string()),
+                                decorators: annotations(node, content),
                             });
                         }
                     }
@@ -84,13 +88,19 @@ impl JavaParser {
             location: self.extract_location(node),
             containing_type: containing_type.clone(),
             parameters: self.extract_parameters(node, content),
+            return_type: node
+                .child_by_field_name("type")
+                .and_then(|type_node| type_node.utf8_text(content.as_bytes()).ok())
+                .filter(|type_text| *type_text != "void")
+                .map(String::from),
             containing_entity_name: containing_type,
+            decorators: annotations(node, content),
         })
     }
 
     fn extract_visibility(&self, node: Node, content: &str) -> Visibility {
         // In Java, methods have modifiers like public, private, protected
-        if let Some(modifiers) = node.child_by_field_name("modifiers") {
+        if let Some(modifiers) = modifiers(node) {
             for i in 0..modifiers.child_count() {
                 if let Some(modifier) = modifiers.child(i) {
                     if let Ok(modifier_text) = modifier.utf8_text(content.as_bytes()) {
@@ -349,12 +359,14 @@ impl JavaParser {
             documentation,
             containing_entity_name,
             generic_params,
+            mixins: Vec::new(),
+            decorators: annotations(node, content),
         })
     }
 
     fn extract_type_visibility(&self, node: Node, content: &str) -> Visibility {
         // In Java, classes have modifiers like public, private, protected
-        if let Some(modifiers) = node.child_by_field_name("modifiers") {
+        if let Some(modifiers) = modifiers(node) {
             for i in 0..modifiers.child_count() {
                 if let Some(modifier) = modifiers.child(i) {
                     if let Ok(modifier_text) = modifier.utf8_text(content.as_bytes()) {
@@ -390,7 +402,7 @@ impl JavaParser {
 
                     // Check if field is static
                     let mut is_static = false;
-                    if let Some(modifiers) = n.child_by_field_name("modifiers") {
+                    if let Some(modifiers) = modifiers(n) {
                         for i in 0..modifiers.child_count() {
                             if let Some(modifier) = modifiers.child(i) {
                                 if let Ok(modifier_text) = modifier.utf8_text(content.as_bytes()) {
@@ -427,23 +439,17 @@ impl JavaParser {
                                     }
 
                                     let mut annotations = Vec::new();
-                                    if let Some(modifiers) = n.child_by_field_name("modifiers") {
+                                    if let Some(modifiers) = modifiers(n) {
                                         for i in 0..modifiers.child_count() {
                                             if let Some(modifier) = modifiers.child(i) {
+                                                // Keywords are anonymous; annotations are named
+                                                if !modifier.is_named() {
+                                                    continue;
+                                                }
                                                 if let Ok(modifier_text) =
                                                     modifier.utf8_text(content.as_bytes())
                                                 {
-                                                    if ![
-                                                        "public",
-                                                        "private",
-                                                        "protected",
-                                                        "static",
-                                                        "final",
-                                                    ]
-                                                    .contains(&modifier_text)
-                                                    {
-                                                        annotations.push(modifier_text.to_string());
-                                                    }
+                                                    annotations.push(modifier_text.to_string());
                                                 }
                                             }
                                         }
@@ -634,13 +640,22 @@ impl LanguageParser for JavaParser {
 
                         let location = node_to_location(node);
 
-                        calls.push(CallReference::with_details(
-                            name.to_string(),
-                            fully_qualified_name,
-                            Some(location),
-                            Some(file_path.to_string()),
-                            Vec::new(),
-                        ));
+                        calls.push(
+                            CallReference::with_details(
+                                name.to_string(),
+                                fully_qualified_name,
+                                Some(location),
+                                Some(file_path.to_string()),
+                                Vec::new(),
+                            )
+                            .with_caller(enclosing_function_name(
+                                node,
+                                |n| {
+                                    self.extract_function_details(n, content, file_path)
+                                        .map(|func| func.name)
+                                },
+                            )),
+                        );
                     }
                 }
             }
@@ -667,6 +682,8 @@ impl LanguageParser for JavaParser {
                 imports: Vec::new(),
                 exports: Vec::new(),
                 documentation: None,
+                submodules: Vec::new(),
+                bindings: Vec::new(),
             });
         }
 
@@ -706,48 +723,42 @@ impl LanguageParser for JavaParser {
             imports: Vec::new(),
             exports: Vec::new(),
             documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
         };
 
         // Extract package name
-        for i in 0..root_node.child_count() {
-            if let Some(child) = root_node.child(i) {
-                if child.kind() == "package_declaration" {
-                    if let Some(name_node) = child.child_by_field_name("name") {
-                        if let Ok(package_name) = name_node.utf8_text(content.as_bytes()) {
-                            module_def.name = package_name.to_string();
-                        }
-                    }
+        let mut cursor = root_node.walk();
+        for child in root_node.named_children(&mut cursor) {
+            if child.kind() == "package_declaration" {
+                if let Some(package_name) = qualified_name(child, content) {
+                    module_def.name = package_name;
                 }
             }
         }
 
-        // Extract imports
+        // Extract imports, including static and wildcard imports
         traverse_node(root_node, &mut |node| {
             if node.kind() == "import_declaration" {
-                if let Some(name_node) = node.child_by_field_name("name") {
-                    if let Ok(import_name) = name_node.utf8_text(content.as_bytes()) {
-                        let is_relative = import_name.starts_with(".");
-
-                        // Check if it's a wildcard impor
-                        let mut imported_symbols = Vec::new();
-                        if import_name.ends_with(".*") {
-                            imported_symbols.push("*".to_string());
-                        } else {
-                            // Extract the class name from the impor
-                            if let Some(last_dot) = import_name.rfind('.') {
-                                imported_symbols.push(import_name[(last_dot + 1)..].to_string());
-                            } else {
-                                imported_symbols.push(import_name.to_string());
-                            }
-                        }
-
-                        module_def.imports.push(ImportDefinition {
-                            module_name: import_name.to_string(),
-                            imported_symbols,
-                            location: self.extract_location(node),
-                            is_relative,
-                        });
-                    }
+                if let Some(name) = qualified_name(node, content) {
+                    let mut cursor = node.walk();
+                    let is_wildcard = node
+                        .named_children(&mut cursor)
+                        .any(|child| child.kind() == "asterisk");
+
+                    let (module_name, imported_symbols) = if is_wildcard {
+                        (format!("{}.*", name), vec!["*".to_string()])
+                    } else {
+                        let symbol = name.rsplit('.').next().unwrap_or(&name).to_string();
+                        (name, vec![symbol])
+                    };
+
+                    module_def.imports.push(ImportDefinition {
+                        module_name,
+                        imported_symbols,
+                        location: self.extract_location(node),
+                        is_relative: false,
+                    });
                 }
             }
         });
@@ -760,7 +771,7 @@ impl LanguageParser for JavaParser {
             {
                 // Check if the class is public
                 let mut is_public = false;
-                if let Some(modifiers) = node.child_by_field_name("modifiers") {
+                if let Some(modifiers) = modifiers(node) {
                     for i in 0..modifiers.child_count() {
                         if let Some(modifier) = modifiers.child(i) {
                             if let Ok(modifier_text) = modifier.utf8_text(content.as_bytes()) {
@@ -812,7 +823,7 @@ impl LanguageParser for JavaParser {
         let mut doc_end_line = 0;
 
         // Safe subset of lines to search
-        let search_range = if start_line > 10 { start_line - 10 } else { 0 }..start_line;
+        let search_range = start_line.saturating_sub(10)..start_line;
 
         // Iterate through previous lines to find documentation comments
         for i in (search_range).rev() {
@@ -855,11 +866,98 @@ impl LanguageParser for JavaParser {
         }
     }
 
+    /// Parses the names methods read or write. Java has no variables outside
+    /// classes, so these refer to fields, such as `static final` constants.
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse Java variable references in file '{}' ({}B). The file may contain syntax errors.",
+                file_path,
+                content.len()
+            )
+        })?;
+
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
     fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
         Box::new(JavaParser::new())
     }
 }
 
+/// The modifiers of a declaration: keywords such as `public` and its annotations
+fn modifiers(node: Node) -> Option<Node> {
+    let mut cursor = node.walk();
+    let modifiers = node
+        .named_children(&mut cursor)
+        .find(|child| child.kind() == "modifiers");
+    modifiers
+}
+
+/// The annotations of a declaration with their arguments, e.g.
+/// `@GetMapping("/users")` is `GetMapping` with the argument `"/users"`
+fn annotations(node: Node, content: &str) -> Vec<Decorator> {
+    let text = |node: Node| node.utf8_text(content.as_bytes()).ok().map(String::from);
+
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    children
+        .into_iter()
+        .flat_map(|child| {
+            if child.kind() == "modifiers" {
+                let mut cursor = child.walk();
+                child.named_children(&mut cursor).collect()
+            } else {
+                vec![child]
+            }
+        })
+        .filter(|child| matches!(child.kind(), "annotation" | "marker_annotation"))
+        .filter_map(|annotation| {
+            let arguments = annotation
+                .child_by_field_name("arguments")
+                .map(|arguments| {
+                    let mut cursor = arguments.walk();
+                    arguments
+                        .named_children(&mut cursor)
+                        .filter(|argument| !argument.kind().ends_with("comment"))
+                        .filter_map(text)
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(Decorator {
+                name: text(annotation.child_by_field_name("name")?)?,
+                arguments,
+            })
+        })
+        .collect()
+}
+
+/// The dotted name in a `package` or `import` declaration
+fn qualified_name(node: Node, content: &str) -> Option<String> {
+    let mut cursor = node.walk();
+    let name = node
+        .named_children(&mut cursor)
+        .find(|child| matches!(child.kind(), "identifier" | "scoped_identifier"))?;
+    name.utf8_text(content.as_bytes()).ok().map(String::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -906,6 +1004,34 @@ mod tests {
         assert!(!parser.can_handle(Path::new("test.js")));
     }
 
+    #[test]
+    fn test_java_call_caller() -> Result<()> {
+        let mut parser = JavaParser::new();
+        let java_code = indoc! {r#"
+            public class Service {
+                public Service() {
+                    init();
+                }
+
+                public void run() {
+                    helper.process();
+                }
+            }
+        "#};
+
+        let calls = parser.parse_calls(java_code, "Service.java")?;
+        assert_eq!(calls.len(), 2);
+
+        // Constructors are indexed under the class name
+        assert_eq!(calls[0].callee_name, "init");
+        assert_eq!(calls[0].caller_name.as_deref(), Some("Service"));
+
+        assert_eq!(calls[1].callee_name, "process");
+        assert_eq!(calls[1].caller_name.as_deref(), Some("run"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_java_parser_empty_content() {
         let mut parser = JavaParser::new();
@@ -1083,6 +1209,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_java_package_imports_and_annotations() -> Result<()> {
+        let mut parser = JavaParser::new();
+        let content = indoc! {r#"
+            package com.example.web;
+
+            import com.example.model.User;
+            import com.example.repo.*;
+            import static com.example.util.Strings.capitalize;
+
+            @RestController
+            @RequestMapping(value = "/users", produces = "application/json")
+            public class UserController {
+                @Autowired
+                private transient UserRepository repository;
+
+                @GetMapping("/{id}")
+                public User getUser(@PathVariable long id) {
+                    return repository.find(id);
+                }
+            }
+        "#};
+
+        let module = parser.parse_modules(content, "UserController.java")?;
+        assert_eq!(module.name, "com.example.web");
+        let imports: Vec<(&str, Vec<&str>)> = module
+            .imports
+            .iter()
+            .map(|import| {
+                (
+                    import.module_name.as_str(),
+                    import.imported_symbols.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("com.example.model.User", vec!["User"]),
+                ("com.example.repo.*", vec!["*"]),
+                ("com.example.util.Strings.capitalize", vec!["capitalize"]),
+            ]
+        );
+
+        let types = parser.parse_types(content, "UserController.java")?;
+        let controller = &types[0];
+        assert_eq!(controller.visibility, Visibility::Public);
+        assert_eq!(
+            controller.decorators,
+            vec![
+                Decorator {
+                    name: "RestController".to_string(),
+                    arguments: Vec::new(),
+                },
+                Decorator {
+                    name: "RequestMapping".to_string(),
+                    arguments: vec![
+                        "value = \"/users\"".to_string(),
+                        "produces = \"application/json\"".to_string(),
+                    ],
+                },
+            ]
+        );
+        assert_eq!(controller.fields[0].annotations, vec!["@Autowired"]);
+
+        let functions = parser.parse_functions(content, "UserController.java")?;
+        assert_eq!(functions[0].name, "getUser");
+        assert_eq!(
+            functions[0].decorators,
+            vec![Decorator {
+                name: "GetMapping".to_string(),
+                arguments: vec!["\"/{id}\"".to_string()],
+            }]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_java_generic_parameters() {
         let mut parser = JavaParser::new();
@@ -26,7 +26,7 @@ impl JavaScriptParser {
     pub fn new() -> Self {
         let mut parser = Parser::new();
         parser
-            .set_language(tree_sitter_javascript::language())
+            .set_language(&tree_sitter_javascript::LANGUAGE.into())
             .expect("Failed to load JavaScript grammar - this is a build configuration error");
         Self { parser }
     }
@@ -39,7 +39,7 @@ impl JavaScriptParser {
     pub fn try_new() -> Result<Self> {
         let mut parser = Parser::new();
         parser
-            .set_language(tree_sitter_javascript::language())
+            .set_language(&tree_sitter_javascript::LANGUAGE.into())
             .map_err(|e| anyhow::anyhow!("Failed to load JavaScript grammar: {}", e))?;
         Ok(Self { parser })
     }
@@ -106,7 +106,9 @@ impl JavaScriptParser {
                     location: node_to_location(node),
                     containing_type: None,
                     parameters: self.extract_parameters(node, content),
+                    return_type: None,
                     containing_entity_name: None,
+                    decorators: Vec::new(),
                 })
             }
             "method_definition" => {
@@ -152,7 +154,9 @@ impl JavaScriptParser {
                     location: node_to_location(node),
                     containing_type: containing_type.clone(),
                     parameters: self.extract_parameters(node, content),
+                    return_type: None,
                     containing_entity_name: containing_type,
+                    decorators: Vec::new(),
                 })
             }
             "arrow_function" => {
@@ -186,7 +190,9 @@ impl JavaScriptParser {
                     location: node_to_location(node),
                     containing_type: None,
                     parameters: self.extract_parameters(node, content),
+                    return_type: None,
                     containing_entity_name: None,
+                    decorators: Vec::new(),
                 })
             }
             _ => None,
@@ -271,7 +277,7 @@ impl LanguageParser for JavaScriptParser {
         file_path
             .extension()
             .and_then(|e| e.to_str())
-            .is_some_and(|ext| matches!(ext, "js" | "jsx" | "ts" | "tsx"))
+            .is_some_and(|ext| matches!(ext, "js" | "jsx" | "mjs" | "cjs"))
     }
 
     /// Parses JavaScript functions and methods from the source code.
@@ -373,18 +379,34 @@ impl LanguageParser for JavaScriptParser {
         traverse_node(root_node, &mut |node| {
             if node.kind() == "call_expression" {
                 if let Some(function) = node.child_by_field_name("function") {
+                    // `require()` is recorded as an import by `parse_modules`, and
+                    // so is `import()`, whose callee isn't a name
+                    let is_require = function.kind() == "identifier"
+                        && function.utf8_text(content.as_bytes()) == Ok("require");
+                    if is_require {
+                        return;
+                    }
                     if let Some((name, full_path)) = self.extract_call_name(function, content) {
                         // Create location
                         let location = node_to_location(node);
 
                         // Use helper method from CallReference
-                        calls.push(CallReference::with_details(
-                            name,
-                            full_path,
-                            Some(location),
-                            Some(file_path.to_string()),
-                            Vec::new(), // We'll add argument extraction later
-                        ));
+                        calls.push(
+                            CallReference::with_details(
+                                name,
+                                full_path,
+                                Some(location),
+                                Some(file_path.to_string()),
+                                Vec::new(), // We'll add argument extraction later
+                            )
+                            .with_caller(enclosing_function_name(
+                                node,
+                                |n| {
+                                    self.extract_function_details(n, content, file_path)
+                                        .map(|func| func.name)
+                                },
+                            )),
+                        );
                     }
                 }
             }
@@ -393,6 +415,120 @@ impl LanguageParser for JavaScriptParser {
         Ok(calls)
     }
 
+    /// Parses a module's imports and exports
+    ///
+    /// ES module `import` and `export` statements, dynamic `import()`,
+    /// CommonJS `require()` calls and assignments to `module.exports` or
+    /// `exports` are all recorded, along with the names each binds.
+    ///
+    /// # Arguments
+    /// * `content` - The JavaScript source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<ModuleDefinition>` - The module's imports, exports and bindings or an error
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            return Ok(module_def);
+        }
+
+        let tree = self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse JavaScript modules in file '{}' ({}B). The file may contain syntax errors.",
+                file_path,
+                content.len()
+            )
+        })?;
+
+        traverse_node(tree.root_node(), &mut |node| match node.kind() {
+            "import_statement" => parse_import_statement(node, content, &mut module_def),
+            "export_statement" => parse_export_statement(node, content, &mut module_def),
+            "call_expression" => parse_module_call(node, content, &mut module_def),
+            "assignment_expression" => parse_exports_assignment(node, content, &mut module_def),
+            _ => {}
+        });
+
+        for binding in &module_def.bindings {
+            if binding.is_export
+                && binding.name != "*"
+                && !module_def.exports.contains(&binding.name)
+            {
+                module_def.exports.push(binding.name.clone());
+            }
+        }
+
+        tracing::debug!(
+            "Parsed JavaScript module '{}' with {} imports and {} exports",
+            file_path,
+            module_def.imports.len(),
+            module_def.exports.len()
+        );
+
+        Ok(module_def)
+    }
+
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse JavaScript variables in file '{}' ({}B). The file may contain syntax errors.",
+                file_path,
+                content.len()
+            )
+        })?;
+
+        Ok(top_level_variables(tree.root_node(), content, file_path))
+    }
+
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse JavaScript variable references in file '{}' ({}B). The file may contain syntax errors.",
+                file_path,
+                content.len()
+            )
+        })?;
+
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier", "shorthand_property_identifier"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
     fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
         Box::new(JavaScriptParser::new())
     }
@@ -476,6 +612,8 @@ impl LanguageParser for JavaScriptParser {
                     documentation: None,
                     containing_entity_name: None, // No nesting info ye
                     generic_params,
+                    mixins: Vec::new(),
+                    decorators: Vec::new(),
                 });
             }
         });
@@ -484,6 +622,466 @@ impl LanguageParser for JavaScriptParser {
     }
 }
 
+/// Record an `import` statement: `import a, { b as c } from './m'`,
+/// `import * as ns from './m'` or `import './m'`
+fn parse_import_statement(node: Node, content: &str, module_def: &mut ModuleDefinition) {
+    let Some(module_name) = node
+        .child_by_field_name("source")
+        .and_then(|source| string_value(source, content))
+    else {
+        return;
+    };
+
+    let mut bindings = Vec::new();
+    traverse_node(node, &mut |child| match child.kind() {
+        // The default import: `import React from 'react'`
+        "identifier" if child.parent().is_some_and(|p| p.kind() == "import_clause") => {
+            bindings.extend(node_text(child, content).map(|name| (name, "default".to_string())));
+        }
+        "import_specifier" => bindings.extend(specifier_binding(child, content)),
+        "namespace_import" => {
+            let mut cursor = child.walk();
+            let name = child
+                .named_children(&mut cursor)
+                .find(|n| n.kind() == "identifier")
+                .and_then(|n| node_text(n, content));
+            bindings.extend(name.map(|name| (name, "*".to_string())));
+        }
+        _ => {}
+    });
+
+    add_import(module_def, node, module_name, bindings, false);
+}
+
+/// Record an `export` statement, either of the module's own definitions or
+/// re-exporting another module's: `export { a as b } from './m'`, `export * from './m'`
+fn parse_export_statement(node: Node, content: &str, module_def: &mut ModuleDefinition) {
+    let mut bindings = Vec::new();
+
+    if let Some(module_name) = node
+        .child_by_field_name("source")
+        .and_then(|source| string_value(source, content))
+    {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "export_clause" => {
+                    let mut clause_cursor = child.walk();
+                    bindings.extend(
+                        child
+                            .named_children(&mut clause_cursor)
+                            .filter(|n| n.kind() == "export_specifier")
+                            .filter_map(|n| specifier_binding(n, content)),
+                    );
+                }
+                // `export * as ns from './m'`
+                "namespace_export" => {
+                    let mut export_cursor = child.walk();
+                    let name = child
+                        .named_children(&mut export_cursor)
+                        .find_map(|n| node_text(n, content));
+                    bindings.extend(name.map(|name| (name, "*".to_string())));
+                }
+                "*" => bindings.push(("*".to_string(), "*".to_string())),
+                _ => {}
+            }
+        }
+
+        // Re-exports bind no names in the module itself
+        add_import(module_def, node, module_name, bindings, true);
+        return;
+    }
+
+    let is_default = {
+        let mut cursor = node.walk();
+        let found = node
+            .children(&mut cursor)
+            .any(|child| child.kind() == "default");
+        found
+    };
+    let exported = |name: String| {
+        if is_default {
+            ("default".to_string(), name)
+        } else {
+            (name.clone(), name)
+        }
+    };
+
+    if let Some(declaration) = node.child_by_field_name("declaration") {
+        match declaration.kind() {
+            "lexical_declaration" | "variable_declaration" => {
+                let mut cursor = declaration.walk();
+                bindings.extend(
+                    declaration
+                        .named_children(&mut cursor)
+                        .filter(|d| d.kind() == "variable_declarator")
+                        .filter_map(|d| d.child_by_field_name("name"))
+                        .filter(|name| name.kind() == "identifier")
+                        .filter_map(|name| node_text(name, content))
+                        .map(exported),
+                );
+            }
+            _ => bindings.extend(
+                declaration
+                    .child_by_field_name("name")
+                    .and_then(|name| node_text(name, content))
+                    .map(exported),
+            ),
+        }
+    } else if let Some(value) = node.child_by_field_name("value") {
+        // `export default name` or `export default function name() {}`
+        let name = match value.kind() {
+            "identifier" => node_text(value, content),
+            _ => value
+                .child_by_field_name("name")
+                .and_then(|name| node_text(name, content)),
+        };
+        bindings.push((
+            "default".to_string(),
+            name.unwrap_or_else(|| "default".to_string()),
+        ));
+    } else {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind() == "export_clause" {
+                let mut clause_cursor = child.walk();
+                bindings.extend(
+                    child
+                        .named_children(&mut clause_cursor)
+                        .filter(|n| n.kind() == "export_specifier")
+                        .filter_map(|n| specifier_binding(n, content)),
+                );
+            }
+        }
+    }
+
+    module_def.bindings.extend(
+        bindings
+            .into_iter()
+            .map(|(name, target)| BindingDefinition {
+                name,
+                target,
+                module_name: None,
+                is_export: true,
+            }),
+    );
+}
+
+/// Record a dynamic `import('./m')` or a CommonJS `require('./m')`, with the
+/// names a `require` is assigned to: `const m = require('./m')`,
+/// `const { a, b: c } = require('./m')` or `const a = require('./m').a`
+fn parse_module_call(node: Node, content: &str, module_def: &mut ModuleDefinition) {
+    let Some(function) = node.child_by_field_name("function") else {
+        return;
+    };
+    let is_import = function.kind() == "import";
+    let is_require = function.kind() == "identifier"
+        && node_text(function, content).as_deref() == Some("require");
+    if !is_import && !is_require {
+        return;
+    }
+
+    let Some(module_name) = node
+        .child_by_field_name("arguments")
+        .and_then(|args| args.named_child(0))
+        .and_then(|arg| string_value(arg, content))
+    else {
+        return;
+    };
+
+    let mut bindings = Vec::new();
+    if is_require {
+        // `require('./m').a` binds the export `a` rather than the whole module
+        let (target, assigned) = match node.parent() {
+            Some(parent)
+                if parent.kind() == "member_expression"
+                    && parent.child_by_field_name("object") == Some(node) =>
+            {
+                let property = parent
+                    .child_by_field_name("property")
+                    .and_then(|p| node_text(p, content));
+                (property, parent.parent())
+            }
+            parent => (Some("*".to_string()), parent),
+        };
+
+        let declared = assigned
+            .filter(|d| d.kind() == "variable_declarator")
+            .and_then(|d| d.child_by_field_name("name"));
+        match (declared, target) {
+            (Some(name), Some(target)) if name.kind() == "identifier" => {
+                bindings.extend(node_text(name, content).map(|name| (name, target)));
+            }
+            (Some(pattern), Some(target))
+                if pattern.kind() == "object_pattern" && target == "*" =>
+            {
+                let mut cursor = pattern.walk();
+                for property in pattern.named_children(&mut cursor) {
+                    match property.kind() {
+                        "shorthand_property_identifier_pattern" => {
+                            bindings.extend(
+                                node_text(property, content).map(|name| (name.clone(), name)),
+                            );
+                        }
+                        "pair_pattern" => {
+                            let key = property
+                                .child_by_field_name("key")
+                                .and_then(|key| node_text(key, content));
+                            let value = property
+                                .child_by_field_name("value")
+                                .filter(|value| value.kind() == "identifier")
+                                .and_then(|value| node_text(value, content));
+                            if let (Some(key), Some(value)) = (key, value) {
+                                bindings.push((value, key));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    add_import(module_def, node, module_name, bindings, false);
+}
+
+/// Record the names a CommonJS module exports: `module.exports = { a, b: c }`,
+/// `module.exports = name`, `module.exports = require('./m')` or `exports.a = b`
+fn parse_exports_assignment(node: Node, content: &str, module_def: &mut ModuleDefinition) {
+    let (Some(left), Some(right)) = (
+        node.child_by_field_name("left"),
+        node.child_by_field_name("right"),
+    ) else {
+        return;
+    };
+    let Some(left_text) = node_text(left, content) else {
+        return;
+    };
+    let right_name = || match right.kind() {
+        "identifier" => node_text(right, content),
+        _ => right
+            .child_by_field_name("name")
+            .and_then(|name| node_text(name, content)),
+    };
+
+    let mut bindings = Vec::new();
+    let mut module_name = None;
+    if left_text == "module.exports" {
+        match right.kind() {
+            "object" => {
+                let mut cursor = right.walk();
+                for property in right.named_children(&mut cursor) {
+                    match property.kind() {
+                        "shorthand_property_identifier" => {
+                            bindings.extend(
+                                node_text(property, content).map(|name| (name.clone(), name)),
+                            );
+                        }
+                        "pair" => {
+                            let Some(key) = property
+                                .child_by_field_name("key")
+                                .and_then(|key| node_text(key, content))
+                            else {
+                                continue;
+                            };
+                            let value = property
+                                .child_by_field_name("value")
+                                .filter(|value| value.kind() == "identifier")
+                                .and_then(|value| node_text(value, content));
+                            bindings.push((key.clone(), value.unwrap_or(key)));
+                        }
+                        "method_definition" => {
+                            bindings.extend(
+                                property
+                                    .child_by_field_name("name")
+                                    .and_then(|name| node_text(name, content))
+                                    .map(|name| (name.clone(), name)),
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // `module.exports = require('./m')` passes on all of another module's exports
+            "call_expression" => {
+                let required = right
+                    .child_by_field_name("function")
+                    .and_then(|f| node_text(f, content))
+                    .filter(|f| f == "require")
+                    .and(right.child_by_field_name("arguments"))
+                    .and_then(|args| args.named_child(0))
+                    .and_then(|arg| string_value(arg, content));
+                if required.is_some() {
+                    module_name = required;
+                    bindings.push(("*".to_string(), "*".to_string()));
+                }
+            }
+            _ => bindings.extend(right_name().map(|name| ("default".to_string(), name))),
+        }
+    } else if left.kind() == "member_expression" {
+        let object = left
+            .child_by_field_name("object")
+            .and_then(|object| node_text(object, content));
+        if matches!(object.as_deref(), Some("module.exports" | "exports")) {
+            if let Some(property) = left
+                .child_by_field_name("property")
+                .and_then(|p| node_text(p, content))
+            {
+                let target = right_name().unwrap_or_else(|| property.clone());
+                bindings.push((property, target));
+            }
+        }
+    }
+
+    module_def.bindings.extend(
+        bindings
+            .into_iter()
+            .map(|(name, target)| BindingDefinition {
+                name,
+                target,
+                module_name: module_name.clone(),
+                is_export: true,
+            }),
+    );
+}
+
+/// Record an import of a module along with the names it binds, as
+/// `(name, target)` pairs
+fn add_import(
+    module_def: &mut ModuleDefinition,
+    node: Node,
+    module_name: String,
+    bindings: Vec<(String, String)>,
+    is_export: bool,
+) {
+    module_def.imports.push(ImportDefinition {
+        is_relative: module_name.starts_with('.'),
+        module_name: module_name.clone(),
+        imported_symbols: if is_export {
+            Vec::new()
+        } else {
+            bindings.iter().map(|(name, _)| name.clone()).collect()
+        },
+        location: node_to_location(node),
+    });
+    module_def.bindings.extend(
+        bindings
+            .into_iter()
+            .map(|(name, target)| BindingDefinition {
+                name,
+                target,
+                module_name: Some(module_name.clone()),
+                is_export,
+            }),
+    );
+}
+
+/// The `(name, target)` pair of an import or export specifier, `a` or `a as b`
+fn specifier_binding(node: Node, content: &str) -> Option<(String, String)> {
+    let name = node
+        .child_by_field_name("name")
+        .and_then(|name| string_value(name, content).or_else(|| node_text(name, content)))?;
+    let alias = node
+        .child_by_field_name("alias")
+        .and_then(|alias| string_value(alias, content).or_else(|| node_text(alias, content)));
+    Some((alias.unwrap_or_else(|| name.clone()), name))
+}
+
+/// The value of a string literal without its quotes, if it has no substitutions
+fn string_value(node: Node, content: &str) -> Option<String> {
+    let mut cursor = node.walk();
+    let is_plain_template = node.kind() == "template_string"
+        && !node
+            .named_children(&mut cursor)
+            .any(|child| child.kind() == "template_substitution");
+    if node.kind() != "string" && !is_plain_template {
+        return None;
+    }
+    node_text(node, content).map(|text| text.trim_matches(['"', '\'', '`']).to_string())
+}
+
+fn node_text(node: Node, content: &str) -> Option<String> {
+    node.utf8_text(content.as_bytes()).ok().map(String::from)
+}
+
+/// The `const`, `let` and `var` declarations at the top level of a JavaScript
+/// or TypeScript program, exported or not
+///
+/// Names bound to functions and classes are left out, as they're parsed as
+/// functions and types, and so are names bound by destructuring patterns.
+pub(super) fn top_level_variables(
+    root: Node,
+    content: &str,
+    file_path: &str,
+) -> Vec<VariableDefinition> {
+    let mut variables = Vec::new();
+    let mut cursor = root.walk();
+    for statement in root.children(&mut cursor) {
+        let (declaration, visibility) = if statement.kind() == "export_statement" {
+            match statement.child_by_field_name("declaration") {
+                Some(declaration) => (declaration, Visibility::Public),
+                None => continue,
+            }
+        } else {
+            (statement, Visibility::Default)
+        };
+        let keyword = match declaration.kind() {
+            "lexical_declaration" => declaration
+                .child_by_field_name("kind")
+                .and_then(|kind| node_text(kind, content)),
+            "variable_declaration" => Some("var".to_string()),
+            _ => None,
+        };
+        let Some(keyword) = keyword else {
+            continue;
+        };
+
+        let mut declarators = declaration.walk();
+        for declarator in declaration.named_children(&mut declarators) {
+            if declarator.kind() != "variable_declarator" {
+                continue;
+            }
+            let Some(name) = declarator
+                .child_by_field_name("name")
+                .filter(|name| name.kind() == "identifier")
+                .and_then(|name| node_text(name, content))
+            else {
+                continue;
+            };
+            let value = declarator.child_by_field_name("value");
+            if value.is_some_and(|value| {
+                matches!(
+                    value.kind(),
+                    "arrow_function"
+                        | "function"
+                        | "function_expression"
+                        | "generator_function"
+                        | "class"
+                )
+            }) {
+                continue;
+            }
+
+            variables.push(VariableDefinition {
+                name,
+                file_path: file_path.to_string(),
+                type_annotation: declarator
+                    .child_by_field_name("type")
+                    .and_then(|t| node_text(t, content))
+                    .map(|t| t.trim_start_matches(':').trim().to_string()),
+                initializer: value.and_then(|value| node_text(value, content)),
+                visibility: visibility.clone(),
+                location: node_to_location(declarator),
+                is_const: keyword == "const",
+                is_static: false,
+                documentation: None,
+            });
+        }
+    }
+    variables
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -657,6 +1255,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_calls_caller() -> Result<()> {
+        let mut parser = JavaScriptParser::new();
+        let content = indoc! {r#"
+            init();
+
+            class Service {
+                start() {
+                    helper();
+                }
+            }
+
+            const handler = () => {
+                process();
+            };
+        "#};
+
+        let calls = parser.parse_calls(content, "test.js")?;
+        assert_eq!(calls.len(), 3);
+
+        assert_eq!(calls[0].callee_name, "init");
+        assert_eq!(calls[0].caller_name, None);
+
+        assert_eq!(calls[1].callee_name, "helper");
+        assert_eq!(calls[1].caller_name.as_deref(), Some("start"));
+
+        assert_eq!(calls[2].callee_name, "process");
+        assert_eq!(calls[2].caller_name.as_deref(), Some("arrow_handler"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_calls() -> Result<()> {
         let mut parser = JavaScriptParser::new();
@@ -665,6 +1295,8 @@ mod tests {
                 console.log('test');
                 someObject.method();
                 helper();
+                const fs = require('fs');
+                import('./lazy');
             }
         "#};
 
@@ -702,6 +1334,189 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_modules() -> Result<()> {
+        let mut parser = JavaScriptParser::new();
+        let content = indoc! {r#"
+            import React, { useState as useLocalState } from 'react';
+            import * as utils from './utils.js';
+            import './polyfills';
+            const fs = require('fs');
+            const { join, resolve: resolvePath } = require('path');
+            const helper = require('./helper').helper;
+
+            export function render() {}
+            export const VERSION = '1.0', NAME = 'app';
+            export default class App {}
+            export { useLocalState as useState, utils };
+            export { Button as PrimaryButton } from './components/button';
+            export * from './components';
+
+            async function load() {
+                return import(`./pages/home.js`);
+            }
+        "#};
+
+        let module = parser.parse_modules(content, "src/app.js")?;
+
+        let imports: Vec<(&str, Vec<&str>)> = module
+            .imports
+            .iter()
+            .map(|import| {
+                (
+                    import.module_name.as_str(),
+                    import.imported_symbols.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("react", vec!["React", "useLocalState"]),
+                ("./utils.js", vec!["utils"]),
+                ("./polyfills", vec![]),
+                ("fs", vec!["fs"]),
+                ("path", vec!["join", "resolvePath"]),
+                ("./helper", vec!["helper"]),
+                ("./components/button", vec![]),
+                ("./components", vec![]),
+                ("./pages/home.js", vec![]),
+            ]
+        );
+        assert!(module.imports[1].is_relative);
+        assert!(!module.imports[0].is_relative);
+
+        assert_eq!(
+            module.exports,
+            vec![
+                "render",
+                "VERSION",
+                "NAME",
+                "default",
+                "useState",
+                "utils",
+                "PrimaryButton"
+            ]
+        );
+
+        let bindings: Vec<(&str, &str, Option<&str>, bool)> = module
+            .bindings
+            .iter()
+            .map(|b| {
+                (
+                    b.name.as_str(),
+                    b.target.as_str(),
+                    b.module_name.as_deref(),
+                    b.is_export,
+                )
+            })
+            .collect();
+        for expected in [
+            ("React", "default", Some("react"), false),
+            ("useLocalState", "useState", Some("react"), false),
+            ("utils", "*", Some("./utils.js"), false),
+            ("resolvePath", "resolve", Some("path"), false),
+            ("helper", "helper", Some("./helper"), false),
+            ("default", "App", None, true),
+            ("useState", "useLocalState", None, true),
+            ("PrimaryButton", "Button", Some("./components/button"), true),
+            ("*", "*", Some("./components"), true),
+        ] {
+            assert!(
+                bindings.contains(&expected),
+                "missing binding {:?}",
+                expected
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_commonjs_exports() -> Result<()> {
+        let mut parser = JavaScriptParser::new();
+        let content = indoc! {r#"
+            function parse() {}
+            const format = () => {};
+            module.exports = { parse, stringify: format, check() {} };
+            module.exports.VERSION = '2';
+            exports.tokenize = tokenize;
+        "#};
+
+        let module = parser.parse_modules(content, "lib/index.cjs")?;
+        assert!(module.imports.is_empty());
+        assert_eq!(
+            module.exports,
+            vec!["parse", "stringify", "check", "VERSION", "tokenize"]
+        );
+        let stringify = module
+            .bindings
+            .iter()
+            .find(|b| b.name == "stringify")
+            .expect("stringify binding");
+        assert_eq!(stringify.target, "format");
+        assert!(stringify.is_export);
+
+        let module = parser.parse_modules("module.exports = require('./impl');\n", "index.js")?;
+        assert_eq!(module.imports[0].module_name, "./impl");
+        assert_eq!(module.bindings.len(), 1);
+        assert_eq!(module.bindings[0].module_name.as_deref(), Some("./impl"));
+        assert_eq!(module.bindings[0].target, "*");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_variables() -> Result<()> {
+        let mut parser = JavaScriptParser::new();
+        let content = indoc! {r#"
+            export const API_URL = 'https://example.com';
+            let retries = 3, { debug } = options;
+            var cache;
+            const handler = () => {};
+            const Model = class {};
+
+            function request() {
+                retries -= 1;
+                return fetch(API_URL, { cache });
+            }
+        "#};
+
+        let variables = parser.parse_variables(content, "client.js")?;
+        let summary: Vec<(&str, Option<&str>, bool, Visibility)> = variables
+            .iter()
+            .map(|v| {
+                (
+                    v.name.as_str(),
+                    v.initializer.as_deref(),
+                    v.is_const,
+                    v.visibility.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "API_URL",
+                    Some("'https://example.com'"),
+                    true,
+                    Visibility::Public
+                ),
+                ("retries", Some("3"), false, Visibility::Default),
+                ("cache", None, false, Visibility::Default),
+            ]
+        );
+
+        let references = parser.parse_variable_references(content, "client.js")?;
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+        assert!(find("retries").is_write);
+        assert!(!find("API_URL").is_write);
+        assert_eq!(find("cache").caller_name, "request");
+
+        Ok(())
+    }
+
     #[test]
     fn test_typescript_generic_parameters() -> Result<()> {
         let mut parser = JavaScriptParser::new();
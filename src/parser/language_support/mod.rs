@@ -1,19 +1,31 @@
 use anyhow::Result;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{hash_map::Entry, HashMap};
 use std::path::Path;
 use std::sync::Mutex;
 
 // Re-export these types for use by language parsers
-pub use crate::graph::entity::{Location, Parameter, Position, Visibility};
+pub use crate::graph::entity::{Decorator, Location, Parameter, Position, Visibility};
 
+pub mod cpp;
+pub mod csharp;
+pub mod go;
 pub mod java;
 pub mod javascript;
+pub mod php;
 pub mod python;
+pub mod query_pack;
+pub mod ruby;
 pub mod rust;
+pub mod typescript;
 
 /// List of supported source file extensions.
-pub const SUPPORTED_EXTENSIONS: &[&str] = &["rs", "py", "js", "java"];
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "jsx", "mjs", "cjs", "ts", "tsx", "java", "go", "c", "h", "cc", "cpp", "hpp",
+    "cs", "rb", "php",
+];
 
 /// Recursively traverses a node and its children, applying a callback function to each node.
 ///
@@ -40,6 +52,138 @@ where
     }
 }
 
+/// Finds the name of the nearest function enclosing a node.
+///
+/// Ancestors are visited from the innermost outwards and `function_name` is asked
+/// for the name of each one; the first name returned wins.
+pub fn enclosing_function_name<F>(node: tree_sitter::Node, function_name: F) -> Option<String>
+where
+    F: FnMut(tree_sitter::Node) -> Option<String>,
+{
+    std::iter::successors(node.parent(), |n| n.parent()).find_map(function_name)
+}
+
+/// Fields holding what a member or path expression is accessed on, across grammars
+const QUALIFIER_FIELDS: &[&str] = &[
+    "object",
+    "path",
+    "scope",
+    "operand",
+    "expression",
+    "receiver",
+    "value",
+];
+
+/// Collects the names read or written inside functions: every node of one of
+/// the identifier `kinds`, once per enclosing function and qualifier.
+///
+/// `function_name` names the functions the same way `enclosing_function_name`
+/// does. Names used outside any function are skipped.
+pub fn variable_references<F>(
+    root: tree_sitter::Node,
+    content: &str,
+    kinds: &[&str],
+    mut function_name: F,
+) -> Vec<VariableReference>
+where
+    F: FnMut(tree_sitter::Node) -> Option<String>,
+{
+    // Walked in source order, so each reference is located at its first use
+    let mut names = Vec::new();
+    let mut pending = vec![root];
+    while let Some(node) = pending.pop() {
+        if kinds.contains(&node.kind()) {
+            names.push(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.children(&mut cursor).collect();
+        pending.extend(children.into_iter().rev());
+    }
+
+    collect_variable_references(names, content, |node| {
+        // Functions are told apart by node, as several may share a name
+        std::iter::successors(node.parent(), |n| n.parent())
+            .find_map(|n| function_name(n).map(|name| (n.id(), name)))
+    })
+}
+
+/// Turns the nodes naming variables into references, once per enclosing
+/// function and qualifier
+///
+/// `enclosing_function` gives the node ID and name of the function a node is
+/// used in, or None outside functions.
+pub fn collect_variable_references<'a, F>(
+    names: impl IntoIterator<Item = tree_sitter::Node<'a>>,
+    content: &str,
+    mut enclosing_function: F,
+) -> Vec<VariableReference>
+where
+    F: FnMut(tree_sitter::Node<'a>) -> Option<(usize, String)>,
+{
+    let mut references: Vec<VariableReference> = Vec::new();
+    let mut seen: HashMap<(usize, Option<String>, String), usize> = HashMap::new();
+
+    for node in names {
+        let Some((function_id, caller_name)) = enclosing_function(node) else {
+            continue;
+        };
+        let Ok(name) = node.utf8_text(content.as_bytes()) else {
+            continue;
+        };
+
+        let qualifier = node.parent().and_then(|parent| {
+            QUALIFIER_FIELDS
+                .iter()
+                .filter_map(|field| parent.child_by_field_name(field))
+                .find(|object| object.end_byte() <= node.start_byte())
+                .and_then(|object| object.utf8_text(content.as_bytes()).ok())
+                .map(String::from)
+        });
+        let is_write = is_assigned(node);
+
+        match seen.entry((function_id, qualifier.clone(), name.to_string())) {
+            Entry::Occupied(entry) => {
+                references[*entry.get()].is_write |= is_write;
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(references.len());
+                references.push(VariableReference {
+                    name: name.to_string(),
+                    qualifier,
+                    caller_name,
+                    location: node_to_location(node),
+                    is_write,
+                });
+            }
+        }
+    }
+
+    references
+}
+
+/// Whether a node is the target of the nearest assignment or increment
+/// enclosing it
+fn is_assigned(node: tree_sitter::Node) -> bool {
+    let Some(assignment) = std::iter::successors(node.parent(), |n| n.parent()).find(|n| {
+        let kind = n.kind();
+        kind.contains("assignment")
+            || matches!(
+                kind,
+                "update_expression" | "inc_statement" | "dec_statement"
+            )
+    }) else {
+        return false;
+    };
+
+    let target = ["left", "argument"]
+        .iter()
+        .find_map(|field| assignment.child_by_field_name(field))
+        .or_else(|| assignment.named_child(0));
+    target.is_some_and(|target| {
+        target.start_byte() <= node.start_byte() && node.end_byte() <= target.end_byte()
+    })
+}
+
 /// Converts a tree-sitter Node to a Location struct.
 ///
 /// # Arguments
@@ -67,11 +211,37 @@ pub fn node_to_location(node: tree_sitter::Node) -> Location {
     }
 }
 
-/// Checks if the given path is a supported source file based on its extension.
+/// Returns the name of the language a source file is written in, based on its extension.
+pub fn language_for_file(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?;
+    if let Some(pack) = query_pack::pack_for_extension(extension) {
+        return Some(&pack.name);
+    }
+
+    match extension {
+        "rs" => Some("rust"),
+        "py" => Some("python"),
+        "js" | "jsx" | "mjs" | "cjs" => Some("javascript"),
+        "ts" | "tsx" => Some("typescript"),
+        "java" => Some("java"),
+        "go" => Some("go"),
+        "c" | "h" => Some("c"),
+        "cc" | "cpp" | "hpp" => Some("cpp"),
+        "cs" => Some("csharp"),
+        "rb" => Some("ruby"),
+        "php" => Some("php"),
+        _ => None,
+    }
+}
+
+/// Checks if the given path is a supported source file based on its extension,
+/// including the extensions of loaded query packs.
 pub fn is_supported_source_file(path: &Path) -> bool {
     path.extension()
         .and_then(|ext| ext.to_str())
-        .map(|ext| SUPPORTED_EXTENSIONS.contains(&ext))
+        .map(|ext| {
+            SUPPORTED_EXTENSIONS.contains(&ext) || query_pack::pack_for_extension(ext).is_some()
+        })
         .unwrap_or(false)
 }
 
@@ -86,7 +256,11 @@ pub struct FunctionDefinition {
     pub containing_type: Option<String>, // Kept for backward compatibility
     pub parameters: Vec<Parameter>,
     #[serde(default)]
+    pub return_type: Option<String>, // Declared return type as written, e.g. `Vec<User>`
+    #[serde(default)]
     pub containing_entity_name: Option<String>, // Name of the parent entity (could be a type, module, or function)
+    #[serde(default)]
+    pub decorators: Vec<Decorator>, // Decorators or annotations, e.g. Python `@app.route`
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -114,6 +288,10 @@ pub struct TypeDefinition {
     pub containing_entity_name: Option<String>, // Name of the parent entity (if nested)
     #[serde(default)]
     pub generic_params: Vec<GenericParameter>, // Generic type parameters
+    #[serde(default)]
+    pub mixins: Vec<String>, // Mixed-in modules or traits (e.g. Ruby `include`, PHP `use`)
+    #[serde(default)]
+    pub decorators: Vec<Decorator>, // Decorators or annotations, e.g. Java `@Entity`
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -146,6 +324,38 @@ pub struct FieldDefinition {
     pub documentation: Option<String>,
 }
 
+/// Variable or constant declared at module level, such as a Rust `static`,
+/// a Python global or an exported JavaScript `const`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableDefinition {
+    pub name: String,
+    pub file_path: String,
+    pub type_annotation: Option<String>,
+    pub initializer: Option<String>, // Source text of the initial value, e.g. `30` for `TIMEOUT = 30`
+    pub visibility: Visibility,
+    pub location: Location,
+    pub is_const: bool,
+    pub is_static: bool,
+    #[serde(default)]
+    pub documentation: Option<String>,
+}
+
+/// Name read or written inside a function, which may refer to a
+/// module-level variable or constant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableReference {
+    pub name: String,
+    /// What the name is accessed on, e.g. `config` in `config.TIMEOUT`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qualifier: Option<String>,
+    /// Name of the enclosing function or method, as reported by `parse_functions`
+    pub caller_name: String,
+    /// Location of the first access in the function
+    pub location: Location,
+    /// Whether the function assigns to the name rather than only reading it
+    pub is_write: bool,
+}
+
 /// Module or file representation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleDefinition {
@@ -154,6 +364,44 @@ pub struct ModuleDefinition {
     pub imports: Vec<ImportDefinition>,
     pub exports: Vec<String>,
     pub documentation: Option<String>,
+    #[serde(default)]
+    pub submodules: Vec<SubmoduleDefinition>, // Modules declared in the file, e.g. Rust `mod`
+    #[serde(default)]
+    pub bindings: Vec<BindingDefinition>, // Names imported or exported, e.g. JavaScript `import { a as b }`
+}
+
+/// Name a module binds to an export of another module, or exports to other
+/// modules, such as JavaScript's `import { a as b } from './m'` or `export { a as b }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingDefinition {
+    /// Name the binding has in the module, or the name it's exported as
+    pub name: String,
+    /// Name the binding refers to: an export of `module_name`, `*` for that
+    /// whole module, or a name defined in the module when there's no `module_name`
+    pub target: String,
+    /// Module the binding comes from, as written in the source
+    pub module_name: Option<String>,
+    /// Whether the binding is exported rather than only imported
+    pub is_export: bool,
+}
+
+/// Module declared inside a file, such as Rust's `mod name;` or `mod name { ... }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmoduleDefinition {
+    /// Path from the file's own module, e.g. `outer::inner` for `mod inner` inside `mod outer`
+    pub name: String,
+    pub location: Location,
+    /// Whether the module's body is in the declaring file rather than a file of its own
+    pub is_inline: bool,
+}
+
+/// Declaration that a type implements a trait or interface, made apart from
+/// the type's definition, such as Rust's `impl Trait for Type`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImplementationDefinition {
+    pub type_name: String,
+    pub trait_name: String,
+    pub location: Location,
 }
 
 /// Generic type parameter definition
@@ -183,14 +431,38 @@ pub struct DomainConcept {
     pub confidence: f32,
 }
 
-static LANGUAGE_PARSERS: Lazy<Mutex<Vec<Box<dyn LanguageParser + Send>>>> = Lazy::new(|| {
-    Mutex::new(vec![
+/// Create one parser for every supported language
+fn create_parsers() -> Vec<Box<dyn LanguageParser + Send>> {
+    let builtin: Vec<Box<dyn LanguageParser + Send>> = vec![
         Box::new(rust::RustParser::new()),
         Box::new(python::PythonParser::new()),
         Box::new(javascript::JavaScriptParser::new()),
+        Box::new(typescript::TypeScriptParser::new()),
         Box::new(java::JavaParser::new()),
-    ])
-});
+        Box::new(go::GoParser::new()),
+        Box::new(cpp::CppParser::new()),
+        Box::new(csharp::CSharpParser::new()),
+        Box::new(ruby::RubyParser::new()),
+        Box::new(php::PhpParser::new()),
+    ];
+
+    // Parsers are tried in order, so query packs override built-in parsers
+    query_pack::registered_parsers()
+        .into_iter()
+        .map(|parser| Box::new(parser) as Box<dyn LanguageParser + Send>)
+        .chain(builtin)
+        .collect()
+}
+
+static LANGUAGE_PARSERS: Lazy<Mutex<Vec<Box<dyn LanguageParser + Send>>>> =
+    Lazy::new(|| Mutex::new(create_parsers()));
+
+thread_local! {
+    // Each worker thread keeps its own parsers so parallel parsing never
+    // contends on the `LANGUAGE_PARSERS` lock
+    static THREAD_PARSERS: RefCell<Vec<Box<dyn LanguageParser + Send>>> =
+        RefCell::new(create_parsers());
+}
 
 /// Standardized language parser trait with consistent interface and error handling
 pub trait LanguageParser: Send {
@@ -302,9 +574,74 @@ pub trait LanguageParser: Send {
             imports: Vec::new(),
             exports: Vec::new(),
             documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
         })
     }
 
+    /// Parses declarations that a type implements a trait or interface made
+    /// outside the type's definition
+    ///
+    /// # Arguments
+    /// * `content` - The source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<ImplementationDefinition>>` - List of implementations or an error
+    fn parse_implementations(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<ImplementationDefinition>> {
+        // Default implementation returns empty lis
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+        }
+        Ok(Vec::new())
+    }
+
+    /// Parses variables and constants declared at module level, outside any
+    /// function or type
+    ///
+    /// # Arguments
+    /// * `content` - The source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<VariableDefinition>>` - List of variables or an error
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        // Default implementation returns empty lis
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+        }
+        Ok(Vec::new())
+    }
+
+    /// Parses the names functions and methods read or write, which may refer
+    /// to module-level variables and constants
+    ///
+    /// # Arguments
+    /// * `content` - The source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<VariableReference>>` - One reference per name and function, or an error
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        // Default implementation returns empty lis
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+        }
+        Ok(Vec::new())
+    }
+
     /// Infers domain concepts from code
     ///
     /// # Arguments
@@ -398,6 +735,11 @@ pub struct CallReference {
     /// Arguments passed to the function (if available)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub arguments: Vec<String>,
+
+    /// Name of the function or method enclosing the call site, as reported by
+    /// `parse_functions` (None for calls made at module level)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caller_name: Option<String>,
 }
 
 impl CallReference {
@@ -415,8 +757,15 @@ impl CallReference {
             location,
             file_path,
             arguments,
+            caller_name: None,
         }
     }
+
+    /// Sets the name of the function or method that makes this call
+    pub fn with_caller(mut self, caller_name: Option<String>) -> Self {
+        self.caller_name = caller_name;
+        self
+    }
 }
 
 /// Get the appropriate parser for a given file
@@ -450,3 +799,21 @@ pub fn get_parser_for_file(file_path: &Path) -> Result<Option<Box<dyn LanguagePa
             |parser| Ok(Some(parser)),
         )
 }
+
+/// Run `f` with the current thread's parser for the given file
+///
+/// Unlike [`get_parser_for_file`], the parser is neither cloned nor shared:
+/// each thread creates its parsers once and reuses them for every file.
+///
+/// # Returns
+/// * `Option<R>` - The result of `f`, or `None` if no parser handles the file
+pub fn with_thread_parser<R>(
+    file_path: &Path,
+    f: impl FnOnce(&mut dyn LanguageParser) -> R,
+) -> Option<R> {
+    THREAD_PARSERS.with(|parsers| {
+        let mut parsers = parsers.borrow_mut();
+        let parser = parsers.iter_mut().find(|p| p.can_handle(file_path))?;
+        Some(f(parser.as_mut()))
+    })
+}
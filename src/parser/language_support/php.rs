@@ -0,0 +1,1190 @@
+use super::*;
+use super::{node_to_location, traverse_node};
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::{Node, Parser, Tree};
+
+/// Declarations that introduce a PHP type
+const TYPE_DECLARATIONS: &[&str] = &[
+    "class_declaration",
+    "interface_declaration",
+    "trait_declaration",
+    "enum_declaration",
+];
+
+/// `require`, `include` and their `_once` variants
+const INCLUDE_EXPRESSIONS: &[&str] = &[
+    "require_expression",
+    "require_once_expression",
+    "include_expression",
+    "include_once_expression",
+];
+
+pub struct PhpParser {
+    parser: Parser,
+}
+
+impl Default for PhpParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhpParser {
+    /// Creates a new PHP parser
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of the PHP parser
+    ///
+    /// # Panics
+    /// This function will panic if the tree-sitter PHP language cannot be loaded.
+    /// This should only happen in case of a build/linking issue with the tree-sitter library.
+    pub fn new() -> Self {
+        Self::try_new().expect("Failed to load PHP grammar - this is a build configuration error")
+    }
+
+    /// Creates a new PHP parser with error handling
+    ///
+    /// # Returns
+    /// * `Result<Self>` - A new instance of the PHP parser or an error
+    pub fn try_new() -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_php::LANGUAGE_PHP.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load PHP grammar: {}", e))?;
+        Ok(Self { parser })
+    }
+
+    fn parse_tree(&mut self, content: &str, file_path: &str, what: &str) -> Result<Tree> {
+        self.parser.parse(content, None).ok_or_else(|| {
+            let filename = Path::new(file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            anyhow::anyhow!(
+                "Failed to parse PHP {} in file '{}' ({}B). The file may contain syntax errors.",
+                what,
+                filename,
+                content.len()
+            )
+        })
+    }
+
+    fn extract_parameters(&self, param_list: Node, content: &str) -> Vec<Parameter> {
+        let mut params = Vec::new();
+        let mut cursor = param_list.walk();
+        for param_node in param_list.named_children(&mut cursor) {
+            let prefix = match param_node.kind() {
+                "simple_parameter" | "property_promotion_parameter" => "",
+                "variadic_parameter" => "...",
+                _ => continue,
+            };
+            let Some(name) = variable_name(param_node, content) else {
+                continue;
+            };
+
+            params.push(Parameter {
+                name,
+                type_annotation: text_of(param_node, "type", content)
+                    .map(|t| format!("{}{}", prefix, t)),
+                default_value: text_of(param_node, "default_value", content),
+            });
+        }
+
+        params
+    }
+
+    fn extract_function_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<FunctionDefinition> {
+        let name = text_of(node, "name", content)?;
+
+        let (kind, containing_type, visibility) = match node.kind() {
+            "function_definition" => (FunctionKind::Function, None, Visibility::Public),
+            "method_declaration" => {
+                let kind = if name == "__construct" {
+                    FunctionKind::Constructor
+                } else {
+                    FunctionKind::Method
+                };
+                let visibility = visibility_from_modifiers(&modifiers(node, content));
+                (kind, enclosing_type(node, content), visibility)
+            }
+            _ => return None,
+        };
+
+        Some(FunctionDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility,
+            location: node_to_location(node),
+            containing_entity_name: containing_type
+                .clone()
+                .or_else(|| enclosing_namespace(node, content)),
+            containing_type,
+            parameters: node
+                .child_by_field_name("parameters")
+                .map(|params| self.extract_parameters(params, content))
+                .unwrap_or_default(),
+            return_type: text_of(node, "return_type", content).filter(|t| t != "void"),
+            decorators: Vec::new(),
+        })
+    }
+
+    fn extract_call_name(&self, node: Node, content: &str) -> Option<(String, Option<String>)> {
+        let text = |n: Node| n.utf8_text(content.as_bytes()).ok();
+
+        match node.kind() {
+            // `format($x)` or `\App\helper()`
+            "function_call_expression" => {
+                let function = node.child_by_field_name("function")?;
+                if !matches!(function.kind(), "name" | "qualified_name") {
+                    return None;
+                }
+                let path = text(function)?.trim_start_matches('\\');
+                let name = path.rsplit('\\').next()?.to_string();
+                Some((name, Some(path.to_string())))
+            }
+            // `$this->save()` or `$order?->total()`
+            "member_call_expression" | "nullsafe_member_call_expression" => {
+                let name = text_of(node, "name", content)?;
+                let object = node.child_by_field_name("object")?;
+
+                // Receivers such as `$this->build()->finish()` can't be resolved statically
+                let receiver = match object.kind() {
+                    "variable_name" | "member_access_expression" => text(object)?
+                        .replace("?->", ".")
+                        .replace("->", ".")
+                        .replace('$', ""),
+                    _ => return Some((name.clone(), Some(name))),
+                };
+                Some((name.clone(), Some(format!("{}.{}", receiver, name))))
+            }
+            // `Str::slug()`, `parent::__construct()` or `static::create()`
+            "scoped_call_expression" => {
+                let name = text_of(node, "name", content)?;
+                let scope = node.child_by_field_name("scope")?;
+                let scope = match text(scope)? {
+                    "static" => "self",
+                    scope => scope.trim_start_matches('\\'),
+                };
+                Some((name.clone(), Some(format!("{}::{}", scope, name))))
+            }
+            // `new Invoice()` runs `Invoice::__construct`
+            "object_creation_expression" => {
+                let class = node
+                    .named_child(0)
+                    .filter(|class| matches!(class.kind(), "name" | "qualified_name"))?;
+                let class = text(class)?.trim_start_matches('\\');
+                Some((
+                    "__construct".to_string(),
+                    Some(format!("{}::__construct", class)),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Properties, constants and enum cases declared in a type body, and the
+    /// names of its methods and traits
+    fn extract_members(
+        &self,
+        body: Node,
+        content: &str,
+    ) -> (Vec<FieldDefinition>, Vec<String>, Vec<String>) {
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+        let mut mixins = Vec::new();
+
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            match member.kind() {
+                "method_declaration" => {
+                    methods.extend(text_of(member, "name", content));
+
+                    // Constructor parameters such as `private Repo $repo` are properties too
+                    if text_of(member, "name", content).as_deref() == Some("__construct") {
+                        fields.extend(self.extract_promoted_properties(member, content));
+                    }
+                }
+                // `use HasFactory, SoftDeletes;`
+                "use_declaration" => {
+                    let mut use_cursor = member.walk();
+                    mixins.extend(
+                        member
+                            .named_children(&mut use_cursor)
+                            .filter(|n| matches!(n.kind(), "name" | "qualified_name"))
+                            .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+                            .map(|name| name.trim_start_matches('\\').to_string()),
+                    );
+                }
+                "property_declaration" => {
+                    let modifiers = modifiers(member, content);
+                    let visibility = visibility_from_modifiers(&modifiers);
+                    let type_annotation = text_of(member, "type", content);
+
+                    // `public $a, $b = 1;` declares several properties sharing modifiers
+                    let mut element_cursor = member.walk();
+                    for element in member
+                        .named_children(&mut element_cursor)
+                        .filter(|n| n.kind() == "property_element")
+                    {
+                        let Some(name) = variable_name(element, content) else {
+                            continue;
+                        };
+
+                        fields.push(FieldDefinition {
+                            name,
+                            type_annotation: type_annotation.clone(),
+                            visibility: visibility.clone(),
+                            location: node_to_location(element),
+                            is_static: modifiers.contains(&"static"),
+                            default_value: text_of(element, "default_value", content),
+                            is_optional: type_annotation
+                                .as_deref()
+                                .is_some_and(|t| t.starts_with('?')),
+                            annotations: attributes(member, content),
+                            documentation: doc_comments(content, member.start_position().row),
+                        });
+                    }
+                }
+                // `public const TAX = 0.2;`
+                "const_declaration" => {
+                    let visibility = visibility_from_modifiers(&modifiers(member, content));
+
+                    let mut element_cursor = member.walk();
+                    for element in member
+                        .named_children(&mut element_cursor)
+                        .filter(|n| n.kind() == "const_element")
+                    {
+                        // The name is the element's first child and its value the last
+                        let Some(name) = element
+                            .named_child(0)
+                            .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        else {
+                            continue;
+                        };
+
+                        fields.push(FieldDefinition {
+                            name: name.to_string(),
+                            visibility: visibility.clone(),
+                            location: node_to_location(element),
+                            is_static: true,
+                            default_value: (element.named_child_count() > 1)
+                                .then(|| element.named_child(element.named_child_count() - 1))
+                                .flatten()
+                                .and_then(|value| value.utf8_text(content.as_bytes()).ok())
+                                .map(String::from),
+                            annotations: attributes(member, content),
+                            documentation: doc_comments(content, member.start_position().row),
+                            ..Default::default()
+                        });
+                    }
+                }
+                "enum_case" => {
+                    let Some(name) = text_of(member, "name", content) else {
+                        continue;
+                    };
+
+                    fields.push(FieldDefinition {
+                        name,
+                        visibility: Visibility::Public,
+                        location: node_to_location(member),
+                        is_static: true,
+                        default_value: text_of(member, "value", content),
+                        annotations: attributes(member, content),
+                        documentation: doc_comments(content, member.start_position().row),
+                        ..Default::default()
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        (fields, methods, mixins)
+    }
+
+    fn extract_promoted_properties(
+        &self,
+        constructor: Node,
+        content: &str,
+    ) -> Vec<FieldDefinition> {
+        let Some(params) = constructor.child_by_field_name("parameters") else {
+            return Vec::new();
+        };
+
+        let mut cursor = params.walk();
+        params
+            .named_children(&mut cursor)
+            .filter(|n| n.kind() == "property_promotion_parameter")
+            .filter_map(|param| {
+                let visibility = param
+                    .child_by_field_name("visibility")
+                    .and_then(|v| v.utf8_text(content.as_bytes()).ok())
+                    .map(|v| visibility_from_modifiers(&[v]))
+                    .unwrap_or(Visibility::Public);
+
+                Some(FieldDefinition {
+                    name: variable_name(param, content)?,
+                    type_annotation: text_of(param, "type", content),
+                    visibility,
+                    location: node_to_location(param),
+                    default_value: text_of(param, "default_value", content),
+                    annotations: attributes(param, content),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn extract_type_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<TypeDefinition> {
+        let kind = match node.kind() {
+            "class_declaration" => TypeKind::Class,
+            "interface_declaration" => TypeKind::Interface,
+            "trait_declaration" => TypeKind::Trait,
+            "enum_declaration" => TypeKind::Enum,
+            _ => return None,
+        };
+        let name = text_of(node, "name", content)?;
+
+        // `extends` and `implements` both name super types, as they do in Java
+        let mut super_types = Vec::new();
+        let mut cursor = node.walk();
+        for clause in node
+            .named_children(&mut cursor)
+            .filter(|n| matches!(n.kind(), "base_clause" | "class_interface_clause"))
+        {
+            let mut clause_cursor = clause.walk();
+            super_types.extend(
+                clause
+                    .named_children(&mut clause_cursor)
+                    .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+                    .map(|name| name.trim_start_matches('\\').to_string()),
+            );
+        }
+
+        let (fields, methods, mixins) = node
+            .child_by_field_name("body")
+            .map(|body| self.extract_members(body, content))
+            .unwrap_or_default();
+
+        Some(TypeDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility: Visibility::Public,
+            location: node_to_location(node),
+            super_types,
+            fields,
+            methods,
+            documentation: doc_comments(content, node.start_position().row),
+            containing_entity_name: enclosing_namespace(node, content),
+            generic_params: Vec::new(),
+            mixins,
+            decorators: Vec::new(),
+        })
+    }
+}
+
+fn text_of(node: Node, field: &str, content: &str) -> Option<String> {
+    node.child_by_field_name(field)?
+        .utf8_text(content.as_bytes())
+        .ok()
+        .map(String::from)
+}
+
+/// Name of a parameter or property without its `$`
+fn variable_name(node: Node, content: &str) -> Option<String> {
+    text_of(node, "name", content).map(|name| name.trim_start_matches('$').to_string())
+}
+
+fn modifiers<'a>(node: Node, content: &'a str) -> Vec<&'a str> {
+    let mut cursor = node.walk();
+    let modifiers = node
+        .named_children(&mut cursor)
+        .filter(|n| n.kind().ends_with("_modifier"))
+        .filter_map(|n| n.utf8_text(content.as_bytes()).ok())
+        .collect();
+    modifiers
+}
+
+/// Members are public unless declared otherwise
+fn visibility_from_modifiers(modifiers: &[&str]) -> Visibility {
+    if modifiers.contains(&"private") {
+        Visibility::Private
+    } else if modifiers.contains(&"protected") {
+        Visibility::Protected
+    } else {
+        Visibility::Public
+    }
+}
+
+/// The attributes applied to a declaration, e.g. `Column` for `#[Column]`
+fn attributes(node: Node, content: &str) -> Vec<String> {
+    let Some(list) = node.child_by_field_name("attributes") else {
+        return Vec::new();
+    };
+
+    let mut attributes = Vec::new();
+    traverse_node(list, &mut |n| {
+        if n.kind() == "attribute" {
+            attributes.extend(n.utf8_text(content.as_bytes()).ok().map(String::from));
+        }
+    });
+    attributes
+}
+
+/// Name of the type whose body declares a node
+fn enclosing_type(node: Node, content: &str) -> Option<String> {
+    let body = node
+        .parent()
+        .filter(|parent| matches!(parent.kind(), "declaration_list" | "enum_declaration_list"))?;
+    let declaration = body
+        .parent()
+        .filter(|parent| TYPE_DECLARATIONS.contains(&parent.kind()))?;
+    text_of(declaration, "name", content)
+}
+
+/// Namespace a node is declared in, whether in a `namespace` block or after
+/// a `namespace` statement
+fn enclosing_namespace(node: Node, content: &str) -> Option<String> {
+    if let Some(block) = std::iter::successors(node.parent(), |n| n.parent())
+        .find(|n| n.kind() == "namespace_definition" && n.child_by_field_name("body").is_some())
+    {
+        return text_of(block, "name", content);
+    }
+
+    let root = std::iter::successors(Some(node), |n| n.parent()).last()?;
+    let mut cursor = root.walk();
+    let namespace = root
+        .named_children(&mut cursor)
+        .take_while(|n| n.start_byte() <= node.start_byte())
+        .filter(|n| n.kind() == "namespace_definition")
+        .last();
+    namespace.and_then(|namespace| text_of(namespace, "name", content))
+}
+
+/// The `/** */` docblock directly above a line, without its markers
+fn doc_comments(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().take(line).collect();
+    if !lines.last()?.trim().ends_with("*/") {
+        return None;
+    }
+
+    let start = lines.iter().rposition(|l| l.trim().starts_with("/**"))?;
+    let doc_lines: Vec<&str> = lines[start..]
+        .iter()
+        .map(|l| {
+            l.trim()
+                .trim_start_matches("/**")
+                .trim_end_matches("*/")
+                .trim_start_matches('*')
+                .trim()
+        })
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    if doc_lines.is_empty() {
+        return None;
+    }
+
+    Some(doc_lines.join("\n"))
+}
+
+impl LanguageParser for PhpParser {
+    /// Determines if this parser can handle a given file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to check
+    ///
+    /// # Returns
+    /// * `bool` - True if this parser can handle the file, false otherwise
+    fn can_handle(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| ext == "php")
+    }
+
+    /// Parses PHP functions and methods
+    ///
+    /// # Arguments
+    /// * `content` - The PHP source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<FunctionDefinition>>` - List of extracted function definitions or an error
+    fn parse_functions(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<FunctionDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code")?;
+        let root_node = tree.root_node();
+
+        tracing::debug!(
+            "Parsed PHP file '{}' ({} bytes) - AST has {} nodes",
+            file_path,
+            content.len(),
+            root_node.child_count()
+        );
+
+        let mut functions = Vec::new();
+        traverse_node(root_node, &mut |node| {
+            if let Some(func) = self.extract_function_details(node, content, file_path) {
+                functions.push(func);
+            }
+        });
+
+        Ok(functions)
+    }
+
+    /// Parses function, method and static calls and `new` expressions from PHP source code
+    ///
+    /// # Arguments
+    /// * `content` - The PHP source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<CallReference>>` - List of extracted function call references or an error
+    fn parse_calls(&mut self, content: &str, file_path: &str) -> Result<Vec<CallReference>> {
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code for calls")?;
+
+        tracing::debug!(
+            "Parsing calls from PHP file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut calls = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            let Some((name, full_path)) = self.extract_call_name(node, content) else {
+                return;
+            };
+
+            calls.push(
+                CallReference::with_details(
+                    name,
+                    full_path,
+                    Some(node_to_location(node)),
+                    Some(file_path.to_string()),
+                    Vec::new(),
+                )
+                .with_caller(enclosing_function_name(node, |n| {
+                    self.extract_function_details(n, content, file_path)
+                        .map(|func| func.name)
+                })),
+            );
+        });
+
+        Ok(calls)
+    }
+
+    /// Parses PHP classes, interfaces, traits and enums
+    ///
+    /// Properties, including those promoted from constructor parameters,
+    /// constants and enum cases are reported as fields, and traits brought in
+    /// with `use` as mixins.
+    ///
+    /// # Arguments
+    /// * `content` - The PHP source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<TypeDefinition>>` - List of extracted type definitions or an error
+    fn parse_types(&mut self, content: &str, file_path: &str) -> Result<Vec<TypeDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "types")?;
+
+        tracing::debug!(
+            "Parsing types from PHP file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut types = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if let Some(type_def) = self.extract_type_details(node, content, file_path) {
+                types.push(type_def);
+            }
+        });
+
+        Ok(types)
+    }
+
+    /// Parses the namespace, `use` imports and `require`/`include` statements of a PHP file
+    ///
+    /// The module is named after the file's first namespace. Each `use`
+    /// imports its alias or the last segment of the imported name.
+    ///
+    /// # Arguments
+    /// * `content` - The PHP source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<ModuleDefinition>` - Module definition or an error
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            tracing::debug!("Empty PHP file content for module parsing: '{}'", file_path);
+            return Ok(module_def);
+        }
+
+        let tree = self.parse_tree(content, file_path, "module")?;
+        let root_node = tree.root_node();
+
+        let mut namespace_found = false;
+        traverse_node(root_node, &mut |node| match node.kind() {
+            "namespace_definition" if !namespace_found => {
+                if let Some(name) = text_of(node, "name", content) {
+                    module_def.name = name;
+                    namespace_found = true;
+                }
+            }
+            "namespace_use_declaration" => {
+                // `use App\Contracts\{Payable, Loggable as Log};` shares a prefix
+                let mut cursor = node.walk();
+                let prefix = node
+                    .named_children(&mut cursor)
+                    .find(|n| n.kind() == "namespace_name")
+                    .and_then(|n| n.utf8_text(content.as_bytes()).ok());
+
+                let group = node.child_by_field_name("body").unwrap_or(node);
+                let mut clause_cursor = group.walk();
+                let clauses: Vec<Node> = group
+                    .named_children(&mut clause_cursor)
+                    .filter(|n| n.kind() == "namespace_use_clause")
+                    .collect();
+
+                for clause in clauses {
+                    let Some(name) = clause
+                        .named_child(0)
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        .map(|name| name.trim_start_matches('\\'))
+                    else {
+                        continue;
+                    };
+                    let module_name = match prefix {
+                        Some(prefix) => format!("{}\\{}", prefix, name),
+                        None => name.to_string(),
+                    };
+                    let symbol = text_of(clause, "alias", content)
+                        .or_else(|| module_name.rsplit('\\').next().map(String::from))
+                        .unwrap_or_default();
+
+                    module_def.imports.push(ImportDefinition {
+                        module_name,
+                        imported_symbols: vec![symbol],
+                        location: node_to_location(clause),
+                        is_relative: false,
+                    });
+                }
+            }
+            // `require_once __DIR__ . '/helpers.php';` names its file in the last string
+            kind if INCLUDE_EXPRESSIONS.contains(&kind) => {
+                let mut path = None;
+                traverse_node(node, &mut |n| {
+                    if n.kind() == "string_content" {
+                        path = n.utf8_text(content.as_bytes()).ok();
+                    }
+                });
+                let Some(path) = path else {
+                    return;
+                };
+
+                let expression = node.utf8_text(content.as_bytes()).unwrap_or_default();
+                module_def.imports.push(ImportDefinition {
+                    module_name: path.to_string(),
+                    imported_symbols: vec!["*".to_string()],
+                    location: node_to_location(node),
+                    is_relative: expression.contains("__DIR__") || path.starts_with('.'),
+                });
+            }
+            kind if (TYPE_DECLARATIONS.contains(&kind) || kind == "function_definition")
+                && enclosing_type(node, content).is_none() =>
+            {
+                module_def.exports.extend(text_of(node, "name", content));
+            }
+            _ => {}
+        });
+
+        Ok(module_def)
+    }
+
+    /// Extracts the `/** */` docblock directly above a declaration
+    fn extract_documentation(&self, content: &str, location: &Location) -> Result<Option<String>> {
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(doc_comments(content, location.start.line))
+    }
+
+    /// Parses constants declared with `const` or `define()` and variables
+    /// assigned at the top level of a file or namespace
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variables")?;
+        let text = |node: Node| node.utf8_text(content.as_bytes()).ok().map(String::from);
+        let constant = |name: String, value: Option<String>, node: Node| VariableDefinition {
+            name,
+            file_path: file_path.to_string(),
+            type_annotation: None,
+            initializer: value,
+            visibility: Visibility::Public,
+            location: node_to_location(node),
+            is_const: true,
+            is_static: false,
+            documentation: None,
+        };
+
+        // Statements of the file, and of namespaces declared with braces
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let statements: Vec<Node> = root
+            .named_children(&mut cursor)
+            .flat_map(|statement| {
+                match statement
+                    .child_by_field_name("body")
+                    .filter(|_| statement.kind() == "namespace_definition")
+                {
+                    Some(body) => {
+                        let mut cursor = body.walk();
+                        body.named_children(&mut cursor).collect()
+                    }
+                    None => vec![statement],
+                }
+            })
+            .collect();
+
+        let mut variables = Vec::new();
+        for statement in statements {
+            match statement.kind() {
+                // `const MAX = 3, MIN = 1;`
+                "const_declaration" => {
+                    let mut cursor = statement.walk();
+                    for element in statement.named_children(&mut cursor) {
+                        if element.kind() != "const_element" {
+                            continue;
+                        }
+                        if let Some(name) = element.named_child(0).and_then(text) {
+                            let value = element.named_child(1).and_then(text);
+                            variables.push(constant(name, value, element));
+                        }
+                    }
+                }
+                "expression_statement" => {
+                    let Some(expression) = statement.named_child(0) else {
+                        continue;
+                    };
+                    match expression.kind() {
+                        // `define('DEBUG', true);`
+                        "function_call_expression"
+                            if text_of(expression, "function", content).as_deref()
+                                == Some("define") =>
+                        {
+                            let Some(arguments) = expression.child_by_field_name("arguments")
+                            else {
+                                continue;
+                            };
+                            let mut cursor = arguments.walk();
+                            let arguments: Vec<Node> = arguments
+                                .named_children(&mut cursor)
+                                .filter(|argument| argument.kind() == "argument")
+                                .collect();
+                            let name = arguments
+                                .first()
+                                .copied()
+                                .and_then(text)
+                                .map(|name| name.trim_matches(['\'', '"']).to_string());
+                            if let Some(name) = name {
+                                let value = arguments.get(1).copied().and_then(text);
+                                variables.push(constant(name, value, expression));
+                            }
+                        }
+                        // `$config = [...];`
+                        "assignment_expression" => {
+                            let Some(name) = expression
+                                .child_by_field_name("left")
+                                .filter(|left| left.kind() == "variable_name")
+                                .and_then(text)
+                            else {
+                                continue;
+                            };
+                            variables.push(VariableDefinition {
+                                is_const: false,
+                                ..constant(name, text_of(expression, "right", content), expression)
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(variables)
+    }
+
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variable references")?;
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["name", "variable_name"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
+        Box::new(PhpParser::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_php_functions_and_methods() -> Result<()> {
+        let mut parser = PhpParser::new();
+        let content = indoc! {r#"
+            <?php
+            namespace App\Models;
+
+            class Invoice
+            {
+                /**
+                 * Creates an invoice.
+                 */
+                public function __construct(private Repo $repo, int $total = 0, string ...$tags) {}
+
+                protected static function build($x) {}
+
+                function total(): int { return 0; }
+            }
+
+            function helper() {}
+        "#};
+
+        let functions = parser.parse_functions(content, "Invoice.php")?;
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["__construct", "build", "total", "helper"]);
+
+        let constructor = &functions[0];
+        assert_eq!(constructor.kind, FunctionKind::Constructor);
+        assert_eq!(constructor.containing_type.as_deref(), Some("Invoice"));
+        let params: Vec<(&str, Option<&str>, Option<&str>)> = constructor
+            .parameters
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.type_annotation.as_deref(),
+                    p.default_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("repo", Some("Repo"), None),
+                ("total", Some("int"), Some("0")),
+                ("tags", Some("...string"), None),
+            ]
+        );
+        assert_eq!(
+            parser.extract_documentation(content, &constructor.location)?,
+            Some("Creates an invoice.".to_string())
+        );
+
+        assert_eq!(functions[1].visibility, Visibility::Protected);
+        assert_eq!(functions[2].visibility, Visibility::Public);
+        assert_eq!(functions[3].kind, FunctionKind::Function);
+        assert_eq!(
+            functions[3].containing_entity_name.as_deref(),
+            Some("App\\Models")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_php_calls() -> Result<()> {
+        let mut parser = PhpParser::new();
+        let content = indoc! {r#"
+            <?php
+            function run() {
+                format($x);
+                \App\helper();
+                $this->repo?->save();
+                $order->total();
+                Str::slug("a");
+                static::create();
+                parent::__construct();
+                $i = new \App\Invoice(1);
+            }
+        "#};
+
+        let calls = parser.parse_calls(content, "run.php")?;
+        let paths: Vec<(&str, Option<&str>)> = calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.fully_qualified_name.as_deref()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("format", Some("format")),
+                ("helper", Some("App\\helper")),
+                ("save", Some("this.repo.save")),
+                ("total", Some("order.total")),
+                ("slug", Some("Str::slug")),
+                ("create", Some("self::create")),
+                ("__construct", Some("parent::__construct")),
+                ("__construct", Some("App\\Invoice::__construct")),
+            ]
+        );
+        assert!(calls
+            .iter()
+            .all(|c| c.caller_name.as_deref() == Some("run")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_php_parser_empty_content() -> Result<()> {
+        let mut parser = PhpParser::new();
+
+        assert!(parser.parse_functions("", "empty.php")?.is_empty());
+        assert!(parser.parse_calls("", "empty.php")?.is_empty());
+        assert!(parser.parse_types("", "empty.php")?.is_empty());
+        assert_eq!(parser.parse_modules("", "empty.php")?.name, "empty.php");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_php_types_and_traits() -> Result<()> {
+        let mut parser = PhpParser::new();
+        let content = indoc! {r#"
+            <?php
+            namespace App\Models;
+
+            /** An invoice. */
+            final class Invoice extends Model implements Payable, \Countable
+            {
+                use HasFactory, SoftDeletes;
+
+                public const TAX = 0.2;
+
+                #[Column]
+                protected ?int $total = 0;
+
+                private static array $lines;
+
+                public function __construct(private Repo $repo) {}
+            }
+
+            interface Payable extends Billable { public function pay(): void; }
+
+            trait HasFactory { public function factory() {} }
+
+            enum Status: string { case Open = 'open'; case Closed = 'closed'; }
+        "#};
+
+        let types = parser.parse_types(content, "Invoice.php")?;
+        let kinds: Vec<(&str, &TypeKind)> =
+            types.iter().map(|t| (t.name.as_str(), &t.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Invoice", &TypeKind::Class),
+                ("Payable", &TypeKind::Interface),
+                ("HasFactory", &TypeKind::Trait),
+                ("Status", &TypeKind::Enum),
+            ]
+        );
+
+        let invoice = &types[0];
+        assert_eq!(invoice.super_types, vec!["Model", "Payable", "Countable"]);
+        assert_eq!(invoice.mixins, vec!["HasFactory", "SoftDeletes"]);
+        assert_eq!(invoice.methods, vec!["__construct"]);
+        assert_eq!(invoice.documentation.as_deref(), Some("An invoice."));
+        assert_eq!(
+            invoice.containing_entity_name.as_deref(),
+            Some("App\\Models")
+        );
+
+        let fields: Vec<&str> = invoice.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["TAX", "total", "lines", "repo"]);
+
+        let tax = &invoice.fields[0];
+        assert!(tax.is_static);
+        assert_eq!(tax.default_value.as_deref(), Some("0.2"));
+
+        let total = &invoice.fields[1];
+        assert_eq!(total.visibility, Visibility::Protected);
+        assert_eq!(total.type_annotation.as_deref(), Some("?int"));
+        assert!(total.is_optional);
+        assert_eq!(total.annotations, vec!["Column"]);
+
+        assert!(invoice.fields[2].is_static);
+        assert_eq!(invoice.fields[3].visibility, Visibility::Private);
+        assert_eq!(invoice.fields[3].type_annotation.as_deref(), Some("Repo"));
+
+        assert_eq!(types[1].super_types, vec!["Billable"]);
+        let cases: Vec<(&str, Option<&str>)> = types[3]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.default_value.as_deref()))
+            .collect();
+        assert_eq!(
+            cases,
+            vec![("Open", Some("'open'")), ("Closed", Some("'closed'"))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_php_modules() -> Result<()> {
+        let mut parser = PhpParser::new();
+        let content = indoc! {r#"
+            <?php
+            namespace App\Http;
+
+            use Illuminate\Support\Str;
+            use App\Contracts\{Payable, Loggable as Log};
+            use function App\helpers\format;
+            require_once __DIR__ . '/helpers.php';
+
+            class Controller {}
+
+            function route() {}
+        "#};
+
+        let module = parser.parse_modules(content, "Controller.php")?;
+        assert_eq!(module.name, "App\\Http");
+        assert_eq!(module.exports, vec!["Controller", "route"]);
+
+        let imports: Vec<(&str, Vec<&str>, bool)> = module
+            .imports
+            .iter()
+            .map(|i| {
+                (
+                    i.module_name.as_str(),
+                    i.imported_symbols.iter().map(String::as_str).collect(),
+                    i.is_relative,
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("Illuminate\\Support\\Str", vec!["Str"], false),
+                ("App\\Contracts\\Payable", vec!["Payable"], false),
+                ("App\\Contracts\\Loggable", vec!["Log"], false),
+                ("App\\helpers\\format", vec!["format"], false),
+                ("/helpers.php", vec!["*"], true),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_php_variables() -> Result<()> {
+        let mut parser = PhpParser::new();
+        let content = indoc! {r#"
+            <?php
+            namespace App;
+
+            const MAX_USERS = 100, MIN_USERS = 1;
+            define('DEBUG', true);
+            $config = ['env' => 'prod'];
+
+            function boot() {
+                global $config;
+                $config = load();
+                return MAX_USERS;
+            }
+        "#};
+
+        let variables = parser.parse_variables(content, "boot.php")?;
+        let summary: Vec<(&str, Option<&str>, bool)> = variables
+            .iter()
+            .map(|v| (v.name.as_str(), v.initializer.as_deref(), v.is_const))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("MAX_USERS", Some("100"), true),
+                ("MIN_USERS", Some("1"), true),
+                ("DEBUG", Some("true"), true),
+                ("$config", Some("['env' => 'prod']"), false),
+            ]
+        );
+
+        let references = parser.parse_variable_references(content, "boot.php")?;
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+        assert!(find("$config").is_write);
+        assert!(!find("MAX_USERS").is_write);
+        assert_eq!(find("MAX_USERS").caller_name, "boot");
+
+        Ok(())
+    }
+}
@@ -15,7 +15,9 @@ impl Default for PythonParser {
 impl PythonParser {
     pub fn new() -> Self {
         let mut parser = Parser::new();
-        parser.set_language(tree_sitter_python::language()).unwrap();
+        parser
+            .set_language(&tree_sitter_python::LANGUAGE.into())
+            .unwrap();
         Self { parser }
     }
 
@@ -223,7 +225,12 @@ impl PythonParser {
             },
             containing_type,
             parameters,
+            return_type: node
+                .child_by_field_name("return_type")
+                .and_then(|type_node| type_node.utf8_text(content.as_bytes()).ok())
+                .map(String::from),
             containing_entity_name: None,
+            decorators: decorators(node, content),
         })
     }
 
@@ -302,6 +309,13 @@ impl PythonParser {
             }
         }
 
+        // Class attributes are annotated on the assignment: `x: int = 5`
+        if let Some(type_node) = assign.child_by_field_name("type") {
+            if let Ok(typ) = type_node.utf8_text(content.as_bytes()) {
+                type_annotation = Some(typ.to_string());
+            }
+        }
+
         // Get the right side (default value)
         if let Some(right) = assign.child_by_field_name("right") {
             // Add bounds checking to prevent panics
@@ -554,6 +568,8 @@ impl PythonParser {
             documentation: None,
             containing_entity_name,
             generic_params,
+            mixins: Vec::new(),
+            decorators: decorators(node, content),
         })
     }
 }
@@ -791,13 +807,22 @@ impl LanguageParser for PythonParser {
                         };
 
                         // Use helper method to create the call reference
-                        calls.push(CallReference::with_details(
-                            name.to_string(),
-                            fully_qualified_name,
-                            Some(location),
-                            Some(file_path.to_string()),
-                            Vec::new(), // We'll add argument extraction later
-                        ));
+                        calls.push(
+                            CallReference::with_details(
+                                name.to_string(),
+                                fully_qualified_name,
+                                Some(location),
+                                Some(file_path.to_string()),
+                                Vec::new(), // We'll add argument extraction later
+                            )
+                            .with_caller(enclosing_function_name(
+                                node,
+                                |n| {
+                                    self.extract_function_details(n, content, file_path)
+                                        .map(|func| func.name)
+                                },
+                            )),
+                        );
                     }
                 }
             }
@@ -806,11 +831,240 @@ impl LanguageParser for PythonParser {
         Ok(calls)
     }
 
+    /// Parses `import` and `from ... import` statements
+    ///
+    /// Each module imported by an `import` statement is a separate import,
+    /// with its alias as the imported symbol if it has one. A `from` import
+    /// imports its names, or their aliases, from one module; relative modules
+    /// keep their leading dots, e.g. `..models`.
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+        if content.is_empty() {
+            return Ok(module_def);
+        }
+
+        let tree = self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse Python imports in file '{}' ({}B). The file may contain syntax errors.",
+                file_path,
+                content.len()
+            )
+        })?;
+
+        let text = |node: Node| node.utf8_text(content.as_bytes()).ok().map(String::from);
+        // A name as imported: `a.b`, or `a.b as c` bound to `c`
+        let imported_name = |node: Node| match node.kind() {
+            "aliased_import" => Some((
+                text(node.child_by_field_name("name")?)?,
+                node.child_by_field_name("alias").and_then(text),
+            )),
+            _ => Some((text(node)?, None)),
+        };
+
+        traverse_node(tree.root_node(), &mut |node| {
+            let mut cursor = node.walk();
+            match node.kind() {
+                "import_statement" => {
+                    for name in node.children_by_field_name("name", &mut cursor) {
+                        let Some((module_name, alias)) = imported_name(name) else {
+                            continue;
+                        };
+                        module_def.imports.push(ImportDefinition {
+                            module_name,
+                            imported_symbols: alias.into_iter().collect(),
+                            location: node_to_location(node),
+                            is_relative: false,
+                        });
+                    }
+                }
+                "import_from_statement" => {
+                    let Some(module_name) = node.child_by_field_name("module_name").and_then(text)
+                    else {
+                        return;
+                    };
+                    let mut imported_symbols: Vec<String> = node
+                        .children_by_field_name("name", &mut cursor)
+                        .filter_map(imported_name)
+                        .map(|(name, alias)| alias.unwrap_or(name))
+                        .collect();
+                    let mut cursor = node.walk();
+                    if node
+                        .children(&mut cursor)
+                        .any(|child| child.kind() == "wildcard_import")
+                    {
+                        imported_symbols.push("*".to_string());
+                    }
+
+                    module_def.imports.push(ImportDefinition {
+                        is_relative: module_name.starts_with('.'),
+                        module_name,
+                        imported_symbols,
+                        location: node_to_location(node),
+                    });
+                }
+                _ => {}
+            }
+        });
+
+        Ok(module_def)
+    }
+
+    /// Parses names assigned at module level. Names in upper case, such as
+    /// `MAX_RETRIES`, and names annotated `Final` are constants by convention,
+    /// and names with a leading underscore are private.
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse Python module variables in file '{}' ({}B). The file may contain syntax errors.",
+                file_path,
+                content.len()
+            )
+        })?;
+
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let variables = root
+            .children(&mut cursor)
+            .filter(|statement| statement.kind() == "expression_statement")
+            .filter_map(|statement| statement.named_child(0))
+            .filter(|assignment| assignment.kind() == "assignment")
+            .filter_map(|assignment| {
+                let text = |field: &str| {
+                    assignment
+                        .child_by_field_name(field)
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        .map(String::from)
+                };
+                let name = assignment
+                    .child_by_field_name("left")
+                    .filter(|left| left.kind() == "identifier")?
+                    .utf8_text(content.as_bytes())
+                    .ok()?
+                    .to_string();
+                let type_annotation = text("type");
+                let is_const = name.chars().any(char::is_alphabetic)
+                    && !name.chars().any(char::is_lowercase)
+                    || type_annotation
+                        .as_deref()
+                        .is_some_and(|t| t == "Final" || t.starts_with("Final["));
+
+                Some(VariableDefinition {
+                    visibility: if name.starts_with('_') {
+                        Visibility::Private
+                    } else {
+                        Visibility::Public
+                    },
+                    name,
+                    file_path: file_path.to_string(),
+                    type_annotation,
+                    initializer: text("right"),
+                    location: node_to_location(assignment),
+                    is_const,
+                    is_static: false,
+                    documentation: None,
+                })
+            })
+            .collect();
+
+        Ok(variables)
+    }
+
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse Python variable references in file '{}' ({}B). The file may contain syntax errors.",
+                file_path,
+                content.len()
+            )
+        })?;
+
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
     fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
         Box::new(PythonParser::new())
     }
 }
 
+/// The decorators applied to a function, e.g. `app.route` with the argument
+/// `"/users"` for `@app.route("/users")`
+fn decorators(node: Node, content: &str) -> Vec<Decorator> {
+    let Some(definition) = node
+        .parent()
+        .filter(|parent| parent.kind() == "decorated_definition")
+    else {
+        return Vec::new();
+    };
+    let text = |node: Node| node.utf8_text(content.as_bytes()).ok().map(String::from);
+
+    let mut cursor = definition.walk();
+    definition
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "decorator")
+        .filter_map(|decorator| decorator.named_child(0))
+        .filter_map(|expression| {
+            if expression.kind() != "call" {
+                return Some(Decorator {
+                    name: text(expression)?,
+                    arguments: Vec::new(),
+                });
+            }
+
+            let arguments = expression
+                .child_by_field_name("arguments")
+                .map(|arguments| {
+                    let mut cursor = arguments.walk();
+                    arguments
+                        .named_children(&mut cursor)
+                        .filter(|argument| argument.kind() != "comment")
+                        .filter_map(text)
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(Decorator {
+                name: text(expression.child_by_field_name("function")?)?,
+                arguments,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -823,7 +1077,7 @@ mod tests {
                 return a + b
 
             class TestClass:
-                def method_with_self(self, param1, param2: int):
+                def method_with_self(self, param1, param2: int) -> Optional["User"]:
                     return param1 + param2
 
                 @classmethod
@@ -878,6 +1132,7 @@ mod tests {
             assert_eq!(method.containing_entity_name, Some("TestClass".to_string()));
             assert_eq!(method.parameters.len(), 3);
             assert_eq!(method.parameters[0].name, "self");
+            assert_eq!(method.return_type.as_deref(), Some(r#"Optional["User"]"#));
         }
 
         if let Some(args_func) = function_with_args {
@@ -1027,15 +1282,188 @@ mod tests {
             assert_eq!(class_def.name, "TestClass");
             assert_eq!(class_def.kind, TypeKind::Class);
 
-            // For now, check if we have any fields
-            if !class_def.fields.is_empty() {
-                // Test a single field
-                let field = &class_def.fields[0];
-                assert!(!field.name.is_empty(), "Field name is empty");
-            }
+            let field_type = |name: &str| {
+                class_def
+                    .fields
+                    .iter()
+                    .find(|f| f.name == name)
+                    .and_then(|f| f.type_annotation.as_deref())
+            };
+            assert_eq!(field_type("public_field"), None);
+            assert_eq!(field_type("typed_field"), Some("str"));
+            assert_eq!(field_type("optional_field"), Some("Optional[int]"));
         }
     }
 
+    #[test]
+    fn test_python_call_caller() -> Result<()> {
+        let mut parser = PythonParser::new();
+        let content = indoc! {r#"
+            setup()
+
+            class Service:
+                def run(self):
+                    self.helper()
+
+            def outer():
+                def inner():
+                    process()
+                inner()
+        "#};
+
+        let calls = parser.parse_calls(content, "test.py")?;
+        let caller_of = |callee: &str| {
+            calls
+                .iter()
+                .find(|call| call.callee_name == callee)
+                .and_then(|call| call.caller_name.clone())
+        };
+
+        assert_eq!(caller_of("setup"), None);
+        assert_eq!(caller_of("self.helper").as_deref(), Some("run"));
+        assert_eq!(caller_of("process").as_deref(), Some("inner"));
+        assert_eq!(caller_of("inner").as_deref(), Some("outer"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_python_imports() -> Result<()> {
+        let mut parser = PythonParser::new();
+        let content = indoc! {r#"
+            import os, pkg.sub as s
+            from .models import User, make_user as mk
+            from .. import views
+            from helpers import *
+        "#};
+
+        let module = parser.parse_modules(content, "app/api.py")?;
+        let imports: Vec<(&str, Vec<&str>, bool)> = module
+            .imports
+            .iter()
+            .map(|import| {
+                (
+                    import.module_name.as_str(),
+                    import.imported_symbols.iter().map(String::as_str).collect(),
+                    import.is_relative,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            imports,
+            vec![
+                ("os", vec![], false),
+                ("pkg.sub", vec!["s"], false),
+                (".models", vec!["User", "mk"], true),
+                ("..", vec!["views"], true),
+                ("helpers", vec!["*"], false),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_python_decorators() -> Result<()> {
+        let mut parser = PythonParser::new();
+        let content = indoc! {r#"
+            @app.route("/users", methods=["GET"])
+            @login_required
+            def list_users():
+                pass
+
+            class Service:
+                @staticmethod
+                def create():
+                    pass
+
+            def plain():
+                pass
+        "#};
+
+        let functions = parser.parse_functions(content, "views.py")?;
+        let decorators_of = |name: &str| {
+            functions
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| f.decorators.clone())
+                .unwrap()
+        };
+
+        assert_eq!(
+            decorators_of("list_users"),
+            vec![
+                Decorator {
+                    name: "app.route".to_string(),
+                    arguments: vec![r#""/users""#.to_string(), r#"methods=["GET"]"#.to_string()],
+                },
+                Decorator {
+                    name: "login_required".to_string(),
+                    arguments: Vec::new(),
+                },
+            ]
+        );
+        assert_eq!(decorators_of("create")[0].name, "staticmethod");
+        assert!(decorators_of("plain").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_python_module_variables() -> Result<()> {
+        let mut parser = PythonParser::new();
+        let content = indoc! {r#"
+            import settings
+
+            MAX_RETRIES = 3
+            timeout: float = 2.5
+            _cache = {}
+            VERSION: Final = "1.0"
+            a, b = 1, 2
+
+            def fetch():
+                global _cache
+                _cache = {}
+                return MAX_RETRIES * settings.TIMEOUT
+
+            class Client:
+                retries = 5
+        "#};
+
+        let variables = parser.parse_variables(content, "client.py")?;
+        let summary: Vec<(&str, Option<&str>, Option<&str>, bool)> = variables
+            .iter()
+            .map(|v| {
+                (
+                    v.name.as_str(),
+                    v.type_annotation.as_deref(),
+                    v.initializer.as_deref(),
+                    v.is_const,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("MAX_RETRIES", None, Some("3"), true),
+                ("timeout", Some("float"), Some("2.5"), false),
+                ("_cache", None, Some("{}"), false),
+                ("VERSION", Some("Final"), Some(r#""1.0""#), true),
+            ]
+        );
+        assert_eq!(variables[2].visibility, Visibility::Private);
+
+        let references = parser.parse_variable_references(content, "client.py")?;
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+        assert!(find("_cache").is_write);
+        assert!(!find("MAX_RETRIES").is_write);
+        assert_eq!(find("MAX_RETRIES").caller_name, "fetch");
+        assert_eq!(find("TIMEOUT").qualifier.as_deref(), Some("settings"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_python_nested_entities() {
         let python_code = indoc! {r#"
@@ -0,0 +1,1268 @@
+//! Language parsers driven by tree-sitter queries
+//!
+//! A query pack pairs a grammar built into ummon with a `.scm` query file
+//! whose captures name what to extract, so that a language can be added or
+//! adjusted without writing a parser. Packs are directories holding a
+//! `pack.toml` and any number of `.scm` files:
+//!
+//! ```toml
+//! grammar = "ruby"          # a grammar built into ummon, see `grammar`
+//! extensions = ["rb"]
+//! doc_comments = ["#"]      # line prefixes of documentation comments
+//! ```
+//!
+//! The captures recognized are:
+//!
+//! | Capture | Meaning |
+//! |---------|---------|
+//! | `@function.definition` | A function or method; `#set! kind` to `function`, `method`, `constructor` or `lambda` |
+//! | `@function.name`, `@function.parameter`, `@function.visibility` | Its name, parameter names and visibility modifier |
+//! | `@function.return_type` | Its declared return type |
+//! | `@class.definition` | A type; `#set! kind` to `class`, `struct`, `interface`, `trait`, `enum`, `union` or `alias` |
+//! | `@class.name`, `@class.super`, `@class.visibility` | Its name, supertypes and visibility modifier |
+//! | `@scope.definition`, `@scope.name` | A block whose functions are methods of the named type, like a Rust `impl` |
+//! | `@field.definition`, `@field.name`, `@field.type` | A field of the enclosing type |
+//! | `@call.expression`, `@call.callee` | A call and the called name or path |
+//! | `@call.receiver` | The object a method is called on, joined to the callee with `.` |
+//! | `@import.definition`, `@import.module`, `@import.symbol` | An import, the imported module and names; `*` when no name is captured |
+//! | `@variable.definition` | A module-level variable; `#set! kind` to `constant`, `static` or `variable` |
+//! | `@variable.name`, `@variable.type`, `@variable.value`, `@variable.visibility` | Its name, type, initial value and visibility modifier |
+//! | `@reference.name` | A name read or written, which may refer to a variable |
+//! | `@module.name`, `@export.name` | The module's name and exported names |
+//!
+//! Captures of one entity can come from several patterns: they are grouped
+//! by their `definition` node. A variable captured on the same node as a
+//! function, such as a JavaScript arrow function, is the function.
+
+use super::*;
+use super::{node_to_location, Location};
+use anyhow::{Context, Result};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor, StreamingIterator, Tree};
+
+/// Name of the file describing a query pack
+pub const PACK_FILE: &str = "pack.toml";
+
+/// Query packs loaded at startup, which take precedence over built-in parsers
+static QUERY_PACKS: OnceCell<Vec<CompiledPack>> = OnceCell::new();
+
+/// A pack with its queries compiled once, shared by every thread's parser
+struct CompiledPack {
+    pack: Arc<QueryPack>,
+    language: Language,
+    query: Arc<Query>,
+}
+
+/// A grammar, the file extensions it parses and the queries to run on them
+#[derive(Debug, Clone)]
+pub struct QueryPack {
+    /// Language name, used by the `languages` setting; the pack's directory name
+    pub name: String,
+    pub grammar: String,
+    pub extensions: Vec<String>,
+    pub doc_comments: Vec<String>,
+    pub queries: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackManifest {
+    grammar: String,
+    extensions: Vec<String>,
+    #[serde(default)]
+    doc_comments: Vec<String>,
+}
+
+impl QueryPack {
+    /// Create a pack from the contents of its `pack.toml` and queries
+    pub fn from_manifest(name: &str, manifest: &str, queries: String) -> Result<Self> {
+        let manifest: PackManifest = toml::from_str(manifest)
+            .with_context(|| format!("Invalid {} of query pack '{}'", PACK_FILE, name))?;
+
+        if grammar(&manifest.grammar).is_none() {
+            anyhow::bail!(
+                "Query pack '{}' uses unknown grammar '{}'",
+                name,
+                manifest.grammar
+            );
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            grammar: manifest.grammar,
+            extensions: manifest.extensions,
+            doc_comments: manifest.doc_comments,
+            queries,
+        })
+    }
+
+    /// Load a pack from a directory holding a `pack.toml` and `.scm` files
+    pub fn load(dir: &Path) -> Result<Self> {
+        let name = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("Invalid query pack directory {}", dir.display()))?;
+        let manifest = std::fs::read_to_string(dir.join(PACK_FILE))
+            .with_context(|| format!("Failed to read {}", dir.join(PACK_FILE).display()))?;
+
+        let mut query_files: Vec<_> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "scm"))
+            .collect();
+        query_files.sort();
+
+        let mut queries = String::new();
+        for path in query_files {
+            queries.push_str(
+                &std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            );
+            queries.push('\n');
+        }
+
+        Self::from_manifest(name, &manifest, queries)
+    }
+
+    fn handles(&self, extension: &str) -> bool {
+        self.extensions.iter().any(|ext| ext == extension)
+    }
+}
+
+/// The grammars query packs can be written against
+pub fn grammar(name: &str) -> Option<Language> {
+    let language = match name {
+        "rust" => tree_sitter_rust::LANGUAGE,
+        "python" => tree_sitter_python::LANGUAGE,
+        "javascript" => tree_sitter_javascript::LANGUAGE,
+        "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+        "tsx" => tree_sitter_typescript::LANGUAGE_TSX,
+        "java" => tree_sitter_java::LANGUAGE,
+        "go" => tree_sitter_go::LANGUAGE,
+        "c" => tree_sitter_c::LANGUAGE,
+        "cpp" => tree_sitter_cpp::LANGUAGE,
+        "csharp" => tree_sitter_c_sharp::LANGUAGE,
+        "ruby" => tree_sitter_ruby::LANGUAGE,
+        "php" => tree_sitter_php::LANGUAGE_PHP,
+        _ => return None,
+    };
+    Some(language.into())
+}
+
+/// The packs shipped with ummon, equivalent to its Rust, Python, JavaScript
+/// and Java parsers, to start new packs from
+pub fn builtin_packs() -> Vec<QueryPack> {
+    [
+        (
+            "rust",
+            include_str!("../../../queries/rust/pack.toml"),
+            include_str!("../../../queries/rust/queries.scm"),
+        ),
+        (
+            "python",
+            include_str!("../../../queries/python/pack.toml"),
+            include_str!("../../../queries/python/queries.scm"),
+        ),
+        (
+            "javascript",
+            include_str!("../../../queries/javascript/pack.toml"),
+            include_str!("../../../queries/javascript/queries.scm"),
+        ),
+        (
+            "java",
+            include_str!("../../../queries/java/pack.toml"),
+            include_str!("../../../queries/java/queries.scm"),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, manifest, queries)| {
+        QueryPack::from_manifest(name, manifest, queries.to_string())
+            .expect("built-in query packs are valid")
+    })
+    .collect()
+}
+
+/// Load every query pack in a directory and use them for the rest of the run
+///
+/// Each subdirectory with a `pack.toml` is a pack. Packs are checked to
+/// compile before any is used. This can only be done once, before any file
+/// is parsed.
+///
+/// # Returns
+/// * `Result<usize>` - The number of packs loaded
+pub fn register_query_packs(dir: &Path) -> Result<usize> {
+    let mut packs = Vec::new();
+    let mut pack_dirs: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read query pack directory {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(PACK_FILE).is_file())
+        .collect();
+    pack_dirs.sort();
+
+    for pack_dir in pack_dirs {
+        let parser = QueryParser::new(Arc::new(QueryPack::load(&pack_dir)?))?;
+        tracing::debug!(
+            "Loaded query pack '{}' for {:?}",
+            parser.pack.name,
+            parser.pack.extensions
+        );
+        packs.push(CompiledPack {
+            pack: parser.pack,
+            language: parser.language,
+            query: parser.query,
+        });
+    }
+
+    let count = packs.len();
+    QUERY_PACKS
+        .set(packs)
+        .map_err(|_| anyhow::anyhow!("Query packs have already been loaded"))?;
+    Ok(count)
+}
+
+fn compiled_packs() -> &'static [CompiledPack] {
+    QUERY_PACKS.get().map_or(&[], Vec::as_slice)
+}
+
+/// The query packs loaded with [`register_query_packs`]
+pub fn registered_query_packs() -> impl Iterator<Item = &'static QueryPack> {
+    compiled_packs()
+        .iter()
+        .map(|compiled| compiled.pack.as_ref())
+}
+
+/// The loaded query pack handling a file extension, if any
+pub fn pack_for_extension(extension: &str) -> Option<&'static QueryPack> {
+    registered_query_packs().find(|pack| pack.handles(extension))
+}
+
+/// A parser for each loaded query pack
+pub fn registered_parsers() -> Vec<QueryParser> {
+    compiled_packs()
+        .iter()
+        .map(|compiled| {
+            QueryParser::with_query(
+                compiled.pack.clone(),
+                compiled.language.clone(),
+                compiled.query.clone(),
+            )
+            .expect("the grammar loaded when the pack was registered")
+        })
+        .collect()
+}
+
+/// Captures of one function, type, field or import, from every pattern
+/// that matched it
+#[derive(Default)]
+struct Entity<'a> {
+    node: Option<Node<'a>>,
+    captures: HashMap<&'a str, Vec<Node<'a>>>,
+    kind: Option<String>,
+}
+
+impl<'a> Entity<'a> {
+    fn first(&self, capture: &str) -> Option<Node<'a>> {
+        self.captures.get(capture)?.first().copied()
+    }
+
+    fn all(&self, capture: &str) -> &[Node<'a>] {
+        self.captures.get(capture).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Everything the pack's queries captured in a file
+#[derive(Default)]
+struct Captures<'a> {
+    functions: Vec<Entity<'a>>,
+    classes: Vec<Entity<'a>>,
+    scopes: Vec<Entity<'a>>,
+    fields: Vec<Entity<'a>>,
+    calls: Vec<Entity<'a>>,
+    imports: Vec<Entity<'a>>,
+    variables: Vec<Entity<'a>>,
+    module_names: Vec<Node<'a>>,
+    exports: Vec<Node<'a>>,
+    references: Vec<Node<'a>>,
+}
+
+/// Which kind of entity a capture belongs to, and the capture's role in it
+fn split_capture(name: &str) -> Option<(&str, &str)> {
+    name.split_once('.')
+}
+
+pub struct QueryParser {
+    pack: Arc<QueryPack>,
+    language: Language,
+    parser: Parser,
+    query: Arc<Query>,
+}
+
+impl QueryParser {
+    /// Create a parser running a pack's queries
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The parser, or an error if the queries don't compile
+    pub fn new(pack: Arc<QueryPack>) -> Result<Self> {
+        let language = grammar(&pack.grammar)
+            .with_context(|| format!("Unknown grammar '{}'", pack.grammar))?;
+        let query = Query::new(&language, &pack.queries).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid queries in query pack '{}' at line {}: {}",
+                pack.name,
+                e.row + 1,
+                e.message
+            )
+        })?;
+
+        Self::with_query(pack, language, Arc::new(query))
+    }
+
+    fn with_query(pack: Arc<QueryPack>, language: Language, query: Arc<Query>) -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&language)
+            .map_err(|e| anyhow::anyhow!("Failed to load {} grammar: {}", pack.grammar, e))?;
+
+        Ok(Self {
+            pack,
+            language,
+            parser,
+            query,
+        })
+    }
+
+    fn parse_tree(&mut self, content: &str, file_path: &str) -> Result<Tree> {
+        self.parser.parse(content, None).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Failed to parse {} file '{}' ({}B). The file may contain syntax errors.",
+                self.pack.name,
+                file_path,
+                content.len()
+            )
+        })
+    }
+
+    /// Run the pack's queries over a tree, grouping captures by entity
+    fn collect<'a>(&'a self, tree: &'a Tree, content: &str) -> Captures<'a> {
+        // Entities are keyed by kind and definition node, in the order first matched
+        let mut entities: Vec<(&str, Entity<'a>)> = Vec::new();
+        let mut index: HashMap<(&str, usize), usize> = HashMap::new();
+        let mut captures = Captures::default();
+
+        let capture_names = self.query.capture_names();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&self.query, tree.root_node(), content.as_bytes());
+        while let Some(query_match) = matches.next() {
+            let kind_property = self
+                .query
+                .property_settings(query_match.pattern_index)
+                .iter()
+                .find(|property| &*property.key == "kind")
+                .and_then(|property| property.value.as_deref().map(String::from));
+
+            // Group the match's captures by the entity they describe
+            let mut by_entity: HashMap<&str, Vec<(&str, Node<'a>)>> = HashMap::new();
+            for capture in query_match.captures {
+                let name = capture_names[capture.index as usize];
+                let Some((entity, role)) = split_capture(name) else {
+                    continue;
+                };
+                match (entity, role) {
+                    ("module", "name") => captures.module_names.push(capture.node),
+                    ("export", "name") => captures.exports.push(capture.node),
+                    ("reference", "name") => captures.references.push(capture.node),
+                    _ => by_entity
+                        .entry(entity)
+                        .or_default()
+                        .push((role, capture.node)),
+                }
+            }
+
+            for (entity, roles) in by_entity {
+                let Some(key_node) = roles
+                    .iter()
+                    .find(|(role, _)| *role == "definition" || *role == "expression")
+                    .or_else(|| roles.first())
+                    .map(|(_, node)| *node)
+                else {
+                    continue;
+                };
+                let key_entity = match entity {
+                    "function" | "class" | "scope" | "field" | "call" | "import" | "variable" => {
+                        entity
+                    }
+                    _ => continue,
+                };
+
+                let position = *index.entry((key_entity, key_node.id())).or_insert_with(|| {
+                    entities.push((key_entity, Entity::default()));
+                    entities.len() - 1
+                });
+                let target = &mut entities[position].1;
+                if target.kind.is_none() {
+                    target.kind = kind_property.clone();
+                }
+                for (role, node) in roles {
+                    if role == "definition" || role == "expression" {
+                        target.node = Some(node);
+                    } else {
+                        let nodes = target.captures.entry(role).or_default();
+                        if !nodes.iter().any(|n| n.id() == node.id()) {
+                            nodes.push(node);
+                        }
+                    }
+                }
+                if target.node.is_none() {
+                    target.node = Some(key_node);
+                }
+            }
+        }
+
+        for (kind, entity) in entities {
+            match kind {
+                "function" => captures.functions.push(entity),
+                "class" => captures.classes.push(entity),
+                "scope" => captures.scopes.push(entity),
+                "field" => captures.fields.push(entity),
+                "call" => captures.calls.push(entity),
+                "variable" => captures.variables.push(entity),
+                _ => captures.imports.push(entity),
+            }
+        }
+
+        captures
+    }
+
+    fn doc_comments(&self, content: &str, line: usize) -> Option<String> {
+        if self.pack.doc_comments.is_empty() {
+            return None;
+        }
+
+        let lines: Vec<&str> = content.lines().take(line).collect();
+        let mut doc_lines: Vec<&str> = lines
+            .iter()
+            .rev()
+            .map(|l| l.trim())
+            .map_while(|l| {
+                self.pack
+                    .doc_comments
+                    .iter()
+                    .find(|prefix| l.starts_with(prefix.as_str()))
+                    .map(|prefix| l[prefix.len()..].trim())
+            })
+            .collect();
+
+        doc_lines.reverse();
+        // Drop the blank lines left by delimiters such as `/**` and `*/`
+        let start = doc_lines.iter().position(|l| !l.is_empty())?;
+        let end = doc_lines.iter().rposition(|l| !l.is_empty())?;
+        Some(doc_lines[start..=end].join("\n"))
+    }
+}
+
+fn text<'a>(node: Node, content: &'a str) -> &'a str {
+    node.utf8_text(content.as_bytes()).unwrap_or_default()
+}
+
+fn visibility(node: Option<Node>, content: &str) -> Visibility {
+    let Some(node) = node else {
+        return Visibility::Default;
+    };
+    let modifier = text(node, content);
+
+    if modifier.contains("pub") || modifier.contains("export") {
+        Visibility::Public
+    } else if modifier.contains("private") {
+        Visibility::Private
+    } else if modifier.contains("protected") {
+        Visibility::Protected
+    } else if modifier.contains("internal") {
+        Visibility::Internal
+    } else {
+        Visibility::Default
+    }
+}
+
+fn type_kind(kind: Option<&str>) -> TypeKind {
+    match kind {
+        None | Some("class") => TypeKind::Class,
+        Some("struct") => TypeKind::Struct,
+        Some("interface") => TypeKind::Interface,
+        Some("trait") => TypeKind::Trait,
+        Some("enum") => TypeKind::Enum,
+        Some("union") => TypeKind::Union,
+        Some("alias") => TypeKind::TypeAlias,
+        Some(_) => TypeKind::Unknown,
+    }
+}
+
+/// The last segment of a called path such as `Foo::new`, `self.save` or `$this->run`
+fn last_segment(path: &str) -> &str {
+    path.rsplit(['.', ':', '\\', '>']).next().unwrap_or(path)
+}
+
+/// Names of the definitions enclosing each node, innermost first
+struct Enclosing<'tree> {
+    names: HashMap<usize, (Node<'tree>, String)>,
+}
+
+impl<'tree> Enclosing<'tree> {
+    fn new<'a>(entities: impl Iterator<Item = &'a Entity<'tree>>, content: &str) -> Self
+    where
+        'tree: 'a,
+    {
+        let names = entities
+            .filter_map(|entity| {
+                let node = entity.node?;
+                let name = text(entity.first("name")?, content).to_string();
+                Some((node.id(), (node, name)))
+            })
+            .collect();
+        Self { names }
+    }
+
+    /// The nearest definition strictly enclosing a node
+    fn of(&self, node: Node<'tree>) -> Option<&(Node<'tree>, String)> {
+        std::iter::successors(node.parent(), |n| n.parent()).find_map(|n| self.names.get(&n.id()))
+    }
+}
+
+impl LanguageParser for QueryParser {
+    /// Determines if this parser can handle a given file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to check
+    ///
+    /// # Returns
+    /// * `bool` - True if the pack lists the file's extension, false otherwise
+    fn can_handle(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.pack.handles(ext))
+    }
+
+    /// Extracts the `@function.definition` captures of the pack's queries
+    ///
+    /// A function inside a `@class.definition` or `@scope.definition` is a
+    /// method of that type unless its pattern sets another `kind`.
+    fn parse_functions(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<FunctionDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path)?;
+        let captures = self.collect(&tree, content);
+        let types = Enclosing::new(captures.classes.iter().chain(&captures.scopes), content);
+
+        let functions = captures
+            .functions
+            .iter()
+            .filter_map(|function| {
+                let node = function.node?;
+                let name = text(function.first("name")?, content).to_string();
+                let containing_type = types.of(node).map(|(_, name)| name.clone());
+
+                let kind = match function.kind.as_deref() {
+                    Some("function") => FunctionKind::Function,
+                    Some("method") => FunctionKind::Method,
+                    Some("constructor") => FunctionKind::Constructor,
+                    Some("lambda") => FunctionKind::Lambda,
+                    _ if containing_type.is_some() => FunctionKind::Method,
+                    _ => FunctionKind::Function,
+                };
+
+                Some(FunctionDefinition {
+                    name,
+                    file_path: file_path.to_string(),
+                    kind,
+                    visibility: visibility(function.first("visibility"), content),
+                    location: node_to_location(node),
+                    containing_entity_name: containing_type.clone(),
+                    containing_type,
+                    parameters: function
+                        .all("parameter")
+                        .iter()
+                        .map(|param| Parameter {
+                            name: text(*param, content).to_string(),
+                            type_annotation: None,
+                            default_value: None,
+                        })
+                        .collect(),
+                    return_type: function
+                        .first("return_type")
+                        .map(|node| text(node, content).to_string()),
+                    decorators: Vec::new(),
+                })
+            })
+            .collect();
+
+        Ok(functions)
+    }
+
+    /// Extracts the `@call.callee` captures of the pack's queries
+    fn parse_calls(&mut self, content: &str, file_path: &str) -> Result<Vec<CallReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path)?;
+        let captures = self.collect(&tree, content);
+        let functions = Enclosing::new(captures.functions.iter(), content);
+
+        let calls = captures
+            .calls
+            .iter()
+            .filter_map(|call| {
+                let node = call.node?;
+                let callee = text(call.first("callee")?, content);
+                let path = match call.first("receiver") {
+                    Some(receiver) => format!("{}.{}", text(receiver, content), callee),
+                    None => callee.to_string(),
+                };
+                let name = last_segment(&path).to_string();
+
+                Some(
+                    CallReference::with_details(
+                        name,
+                        Some(path),
+                        Some(node_to_location(node)),
+                        Some(file_path.to_string()),
+                        Vec::new(),
+                    )
+                    .with_caller(functions.of(node).map(|(_, name)| name.clone())),
+                )
+            })
+            .collect();
+
+        Ok(calls)
+    }
+
+    /// Extracts the `@class.definition` captures of the pack's queries, with
+    /// the `@field.definition` captures and functions inside them
+    fn parse_types(&mut self, content: &str, file_path: &str) -> Result<Vec<TypeDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path)?;
+        let captures = self.collect(&tree, content);
+        let types = Enclosing::new(captures.classes.iter(), content);
+        let scopes = Enclosing::new(captures.classes.iter().chain(&captures.scopes), content);
+
+        let mut definitions: Vec<(usize, TypeDefinition)> = captures
+            .classes
+            .iter()
+            .filter_map(|class| {
+                let node = class.node?;
+                let name = text(class.first("name")?, content).to_string();
+
+                Some((
+                    node.id(),
+                    TypeDefinition {
+                        name,
+                        file_path: file_path.to_string(),
+                        kind: type_kind(class.kind.as_deref()),
+                        visibility: visibility(class.first("visibility"), content),
+                        location: node_to_location(node),
+                        super_types: class
+                            .all("super")
+                            .iter()
+                            .map(|super_type| text(*super_type, content).to_string())
+                            .collect(),
+                        fields: Vec::new(),
+                        methods: Vec::new(),
+                        documentation: self.doc_comments(content, node.start_position().row),
+                        containing_entity_name: types.of(node).map(|(_, name)| name.clone()),
+                        generic_params: Vec::new(),
+                        mixins: Vec::new(),
+                        decorators: Vec::new(),
+                    },
+                ))
+            })
+            .collect();
+
+        for field in &captures.fields {
+            let (Some(node), Some(name)) = (field.node, field.first("name")) else {
+                continue;
+            };
+            let Some((owner, _)) = types.of(node) else {
+                continue;
+            };
+            if let Some((_, definition)) = definitions.iter_mut().find(|(id, _)| *id == owner.id())
+            {
+                definition.fields.push(FieldDefinition {
+                    name: text(name, content).to_string(),
+                    type_annotation: field.first("type").map(|t| text(t, content).to_string()),
+                    visibility: visibility(field.first("visibility"), content),
+                    location: node_to_location(node),
+                    documentation: self.doc_comments(content, node.start_position().row),
+                    ..Default::default()
+                });
+            }
+        }
+
+        // Methods are listed on the type whose body declares them
+        for function in &captures.functions {
+            let (Some(node), Some(name)) = (function.node, function.first("name")) else {
+                continue;
+            };
+            let Some((scope, _)) = scopes.of(node) else {
+                continue;
+            };
+            if let Some((_, definition)) = definitions.iter_mut().find(|(id, _)| *id == scope.id())
+            {
+                definition.methods.push(text(name, content).to_string());
+            }
+        }
+
+        Ok(definitions
+            .into_iter()
+            .map(|(_, definition)| definition)
+            .collect())
+    }
+
+    /// Extracts the `@import`, `@module.name` and `@export.name` captures of
+    /// the pack's queries
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            return Ok(module_def);
+        }
+
+        let tree = self.parse_tree(content, file_path)?;
+        let captures = self.collect(&tree, content);
+
+        if let Some(name) = captures.module_names.first() {
+            module_def.name = text(*name, content).to_string();
+        }
+        module_def.exports = captures
+            .exports
+            .iter()
+            .map(|export| text(*export, content).to_string())
+            .collect();
+
+        for import in &captures.imports {
+            let (Some(node), Some(module)) = (import.node, import.first("module")) else {
+                continue;
+            };
+            let module_name = text(module, content).trim_matches(['"', '\'', '`']);
+
+            let mut imported_symbols: Vec<String> = import
+                .all("symbol")
+                .iter()
+                .map(|symbol| text(*symbol, content).to_string())
+                .collect();
+            if imported_symbols.is_empty() {
+                imported_symbols.push("*".to_string());
+            }
+
+            module_def.imports.push(ImportDefinition {
+                module_name: module_name.to_string(),
+                imported_symbols,
+                location: node_to_location(node),
+                is_relative: module_name.starts_with('.'),
+            });
+        }
+
+        Ok(module_def)
+    }
+
+    /// Extracts the `@variable.definition` captures of the pack's queries
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path)?;
+        let captures = self.collect(&tree, content);
+
+        let variables = captures
+            .variables
+            .iter()
+            .filter_map(|variable| {
+                let node = variable.node?;
+                if captures
+                    .functions
+                    .iter()
+                    .any(|function| function.node.is_some_and(|f| f.id() == node.id()))
+                {
+                    return None;
+                }
+                let capture_text = |capture: &str| {
+                    variable
+                        .first(capture)
+                        .map(|node| text(node, content).to_string())
+                };
+
+                Some(VariableDefinition {
+                    name: capture_text("name")?,
+                    file_path: file_path.to_string(),
+                    type_annotation: capture_text("type")
+                        .map(|t| t.trim_start_matches(':').trim().to_string()),
+                    initializer: capture_text("value"),
+                    visibility: visibility(variable.first("visibility"), content),
+                    location: node_to_location(node),
+                    is_const: variable.kind.as_deref() == Some("constant"),
+                    is_static: variable.kind.as_deref() == Some("static"),
+                    documentation: None,
+                })
+            })
+            .collect();
+
+        Ok(variables)
+    }
+
+    /// Extracts the `@reference.name` captures of the pack's queries made
+    /// inside functions
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path)?;
+        let captures = self.collect(&tree, content);
+        let functions = Enclosing::new(captures.functions.iter(), content);
+
+        Ok(collect_variable_references(
+            captures.references.iter().copied(),
+            content,
+            |node| {
+                functions
+                    .of(node)
+                    .map(|(function, name)| (function.id(), name.clone()))
+            },
+        ))
+    }
+
+    /// Extracts the comment lines directly above a definition that start
+    /// with one of the pack's `doc_comments` prefixes
+    fn extract_documentation(&self, content: &str, location: &Location) -> Result<Option<String>> {
+        Ok(self.doc_comments(content, location.start.line))
+    }
+
+    fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
+        Box::new(
+            Self::with_query(self.pack.clone(), self.language.clone(), self.query.clone())
+                .expect("the grammar loaded when the parser was created"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use tempfile::tempdir;
+
+    const RUST_SAMPLE: &str = r#"
+use crate::graph::{Entity, Relationship};
+
+/// A point.
+pub struct Point<T> { pub x: i32, y: T }
+
+pub enum Shape { Circle, Square(i32) }
+
+pub trait Draw { fn draw(&self); }
+
+impl<T> Point<T> {
+    /// Creates a point.
+    pub fn new(x: i32, y: T) -> Self { Point { x, y } }
+    fn len(&self) -> i32 { helper(self.x) }
+}
+
+impl Draw for Point<i32> { fn draw(&self) { Self::new(1, 2); self.len(); } }
+
+fn helper(v: i32) -> i32 { v.abs(); crate::util::run(); inner::go::<u8>() }
+
+pub const MAX: i32 = 10;
+static COUNT: u32 = 0;
+mod limits { pub const LIMIT: u8 = 1; }
+"#;
+
+    const PYTHON_SAMPLE: &str = r#"
+import os.path as osp
+from .models import User
+
+class Shape(Base):
+    sides: int = 0
+
+    def __init__(self, name, *args):
+        super().__init__()
+
+    @staticmethod
+    def area(x: int = 1) -> float:
+        return helper(x)
+
+    def draw(self):
+        self.area()
+        os.path.join("a", "b")
+
+def helper(v):
+    def inner():
+        pass
+    return inner()
+
+MAX_SIDES = 8
+_cache = {}
+"#;
+
+    const JAVASCRIPT_SAMPLE: &str = r#"
+import React, { useState as useS } from 'react';
+
+/**
+ * A widget.
+ */
+export class Widget extends Base {
+  count = 0;
+  constructor(props) { super(props); this.init(); }
+  render() { return utils.format(this.count); }
+}
+
+export function helper(a, b = 2) { console.log(a); return run(); }
+
+const double = (x) => x * 2;
+const LIMIT = 3;
+let counter = 0;
+var legacy;
+"#;
+
+    const JAVA_SAMPLE: &str = r#"
+package com.example.shapes;
+
+import java.util.List;
+import java.util.*;
+
+public class Shape extends Base implements Drawable {
+    private int sides;
+
+    public Shape(int sides) { this.sides = sides; init(); }
+
+    public int area(int scale) { return Math.max(sides, scale); }
+
+    private static void helper() { System.out.println("hi"); }
+
+    class Inner { void run() { helper(); } }
+}
+
+interface Drawable { void draw(); }
+
+enum Color { RED, GREEN }
+"#;
+
+    fn builtin_parser(name: &str) -> QueryParser {
+        let pack = builtin_packs()
+            .into_iter()
+            .find(|pack| pack.name == name)
+            .unwrap();
+        QueryParser::new(Arc::new(pack)).unwrap()
+    }
+
+    type Functions = BTreeSet<(String, String, Option<String>)>;
+
+    /// Functions by name, kind and, for methods, the name of their type
+    /// without generic arguments
+    fn function_keys(functions: Vec<FunctionDefinition>) -> Functions {
+        functions
+            .into_iter()
+            .map(|function| {
+                // The JavaScript parser prefixes arrow functions with `arrow_`
+                let name = match function.kind {
+                    FunctionKind::Lambda => function.name.trim_start_matches("arrow_").to_string(),
+                    _ => function.name,
+                };
+                let containing_type = match function.kind {
+                    FunctionKind::Method | FunctionKind::Constructor => function
+                        .containing_type
+                        .map(|name| name.split('<').next().unwrap_or_default().to_string()),
+                    _ => None,
+                };
+                (name, format!("{:?}", function.kind), containing_type)
+            })
+            .collect()
+    }
+
+    fn assert_parity(name: &str, file_path: &str, content: &str) {
+        let mut builtin = get_parser_for_file(Path::new(file_path)).unwrap().unwrap();
+        let mut pack = builtin_parser(name);
+
+        assert_eq!(
+            function_keys(pack.parse_functions(content, file_path).unwrap()),
+            function_keys(builtin.parse_functions(content, file_path).unwrap()),
+            "functions of {}",
+            name
+        );
+
+        let type_keys = |types: Vec<TypeDefinition>| -> BTreeSet<(String, String)> {
+            types
+                .into_iter()
+                .map(|t| (t.name, format!("{:?}", t.kind)))
+                .collect()
+        };
+        assert_eq!(
+            type_keys(pack.parse_types(content, file_path).unwrap()),
+            type_keys(builtin.parse_types(content, file_path).unwrap()),
+            "types of {}",
+            name
+        );
+
+        // Packs may find calls the parsers miss, but no fewer
+        let call_keys = |calls: Vec<CallReference>| -> BTreeSet<(String, Option<String>)> {
+            calls
+                .into_iter()
+                .map(|call| {
+                    (
+                        last_segment(&call.callee_name).to_string(),
+                        call.caller_name,
+                    )
+                })
+                .collect()
+        };
+        let pack_calls = call_keys(pack.parse_calls(content, file_path).unwrap());
+        let builtin_calls = call_keys(builtin.parse_calls(content, file_path).unwrap());
+        assert!(
+            builtin_calls.is_subset(&pack_calls),
+            "calls of {} missing from its pack: {:?}",
+            name,
+            builtin_calls.difference(&pack_calls).collect::<Vec<_>>()
+        );
+
+        let variable_keys = |variables: Vec<VariableDefinition>| -> BTreeSet<(String, bool)> {
+            variables
+                .into_iter()
+                .map(|variable| (variable.name, variable.is_const))
+                .collect()
+        };
+        assert_eq!(
+            variable_keys(pack.parse_variables(content, file_path).unwrap()),
+            variable_keys(builtin.parse_variables(content, file_path).unwrap()),
+            "variables of {}",
+            name
+        );
+
+        let reference_keys = |references: Vec<VariableReference>| -> BTreeSet<(String, String)> {
+            references
+                .into_iter()
+                .map(|reference| {
+                    let caller = reference.caller_name.trim_start_matches("arrow_");
+                    (reference.name, caller.to_string())
+                })
+                .collect()
+        };
+        let pack_references =
+            reference_keys(pack.parse_variable_references(content, file_path).unwrap());
+        let builtin_references = reference_keys(
+            builtin
+                .parse_variable_references(content, file_path)
+                .unwrap(),
+        );
+        assert!(
+            builtin_references.is_subset(&pack_references),
+            "references of {} missing from its pack: {:?}",
+            name,
+            builtin_references
+                .difference(&pack_references)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_builtin_packs_match_parsers() {
+        assert_parity("rust", "sample.rs", RUST_SAMPLE);
+        assert_parity("python", "sample.py", PYTHON_SAMPLE);
+        assert_parity("javascript", "sample.js", JAVASCRIPT_SAMPLE);
+        assert_parity("java", "Sample.java", JAVA_SAMPLE);
+    }
+
+    #[test]
+    fn test_rust_pack_details() {
+        let mut parser = builtin_parser("rust");
+
+        let types = parser.parse_types(RUST_SAMPLE, "sample.rs").unwrap();
+        let point = types.iter().find(|t| t.name == "Point").unwrap();
+        assert_eq!(point.visibility, Visibility::Public);
+        assert_eq!(point.documentation.as_deref(), Some("A point."));
+        assert_eq!(
+            point
+                .fields
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["x", "y"]
+        );
+
+        let functions = parser.parse_functions(RUST_SAMPLE, "sample.rs").unwrap();
+        let return_type = |name: &str| {
+            functions
+                .iter()
+                .find(|f| f.name == name)
+                .and_then(|f| f.return_type.as_deref())
+        };
+        assert_eq!(return_type("new"), Some("Self"));
+        assert_eq!(return_type("draw"), None);
+
+        let calls = parser.parse_calls(RUST_SAMPLE, "sample.rs").unwrap();
+        let run = calls.iter().find(|c| c.callee_name == "run").unwrap();
+        assert_eq!(
+            run.fully_qualified_name.as_deref(),
+            Some("crate::util::run")
+        );
+
+        let module = parser.parse_modules(RUST_SAMPLE, "sample.rs").unwrap();
+        assert_eq!(module.imports.len(), 1);
+        assert_eq!(module.imports[0].module_name, "crate::graph");
+        assert_eq!(
+            module.imports[0].imported_symbols,
+            vec!["Entity", "Relationship"]
+        );
+    }
+
+    #[test]
+    fn test_javascript_pack_details() {
+        let mut parser = builtin_parser("javascript");
+
+        let functions = parser
+            .parse_functions(JAVASCRIPT_SAMPLE, "sample.js")
+            .unwrap();
+        let helper = functions.iter().find(|f| f.name == "helper").unwrap();
+        assert_eq!(helper.visibility, Visibility::Public);
+        assert_eq!(
+            helper
+                .parameters
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        let types = parser.parse_types(JAVASCRIPT_SAMPLE, "sample.js").unwrap();
+        assert_eq!(types[0].super_types, vec!["Base"]);
+        assert_eq!(types[0].documentation.as_deref(), Some("A widget."));
+
+        let module = parser
+            .parse_modules(JAVASCRIPT_SAMPLE, "sample.js")
+            .unwrap();
+        assert_eq!(module.exports, vec!["Widget", "helper"]);
+        assert_eq!(module.imports[0].module_name, "react");
+        assert_eq!(module.imports[0].imported_symbols, vec!["React", "useS"]);
+    }
+
+    #[test]
+    fn test_java_pack_details() {
+        let mut parser = builtin_parser("java");
+
+        let types = parser.parse_types(JAVA_SAMPLE, "Sample.java").unwrap();
+        let shape = types.iter().find(|t| t.name == "Shape").unwrap();
+        assert_eq!(shape.super_types, vec!["Base", "Drawable"]);
+        let color = types.iter().find(|t| t.name == "Color").unwrap();
+        assert_eq!(color.fields.len(), 2);
+
+        let calls = parser.parse_calls(JAVA_SAMPLE, "Sample.java").unwrap();
+        let println = calls.iter().find(|c| c.callee_name == "println").unwrap();
+        assert_eq!(
+            println.fully_qualified_name.as_deref(),
+            Some("System.out.println")
+        );
+
+        let module = parser.parse_modules(JAVA_SAMPLE, "Sample.java").unwrap();
+        assert_eq!(module.name, "com.example.shapes");
+        assert_eq!(module.imports[0].module_name, "java.util");
+        assert_eq!(module.imports[0].imported_symbols, vec!["List"]);
+        assert_eq!(module.imports[1].imported_symbols, vec!["*"]);
+    }
+
+    #[test]
+    fn test_load_pack_from_directory() {
+        let dir = tempdir().unwrap();
+        let pack_dir = dir.path().join("golite");
+        std::fs::create_dir(&pack_dir).unwrap();
+        std::fs::write(
+            pack_dir.join(PACK_FILE),
+            "grammar = \"go\"\nextensions = [\"go\"]\ndoc_comments = [\"//\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            pack_dir.join("functions.scm"),
+            "(function_declaration name: (identifier) @function.name) @function.definition\n",
+        )
+        .unwrap();
+        std::fs::write(
+            pack_dir.join("calls.scm"),
+            "(call_expression function: (_) @call.callee) @call.expression\n",
+        )
+        .unwrap();
+
+        let pack = QueryPack::load(&pack_dir).unwrap();
+        assert_eq!(pack.name, "golite");
+        let mut parser = QueryParser::new(Arc::new(pack)).unwrap();
+        assert!(parser.can_handle(Path::new("main.go")));
+        assert!(!parser.can_handle(Path::new("main.rs")));
+
+        let content = "package main\n\n// Entry point\nfunc main() { fmt.Println(run()) }\n\nfunc run() int { return 1 }\n";
+        let functions = parser.parse_functions(content, "main.go").unwrap();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(
+            parser
+                .extract_documentation(content, &functions[0].location)
+                .unwrap()
+                .as_deref(),
+            Some("Entry point")
+        );
+
+        let mut cloned = parser.clone_box();
+        let calls = cloned.parse_calls(content, "main.go").unwrap();
+        let names: Vec<_> = calls.iter().map(|c| c.callee_name.as_str()).collect();
+        assert_eq!(names, vec!["Println", "run"]);
+        assert!(calls
+            .iter()
+            .all(|c| c.caller_name.as_deref() == Some("main")));
+    }
+
+    #[test]
+    fn test_invalid_packs_are_rejected() {
+        let unknown = QueryPack::from_manifest(
+            "cobol",
+            "grammar = \"cobol\"\nextensions = [\"cob\"]\n",
+            String::new(),
+        );
+        assert!(unknown.unwrap_err().to_string().contains("unknown grammar"));
+
+        let pack = QueryPack::from_manifest(
+            "broken",
+            "grammar = \"rust\"\nextensions = [\"rs\"]\n",
+            "(function_item\n  nme: (identifier) @function.name)\n".to_string(),
+        )
+        .unwrap();
+        let error = QueryParser::new(Arc::new(pack)).err().unwrap().to_string();
+        assert!(error.contains("'broken'"), "{}", error);
+        assert!(error.contains("line 2"), "{}", error);
+    }
+}
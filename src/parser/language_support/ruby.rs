@@ -0,0 +1,1090 @@
+use super::*;
+use super::{node_to_location, traverse_node};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tree_sitter::{Node, Parser, Tree};
+
+/// Calls in a class body that declare something rather than run code
+const DECLARATION_CALLS: &[&str] = &[
+    "require",
+    "require_relative",
+    "include",
+    "extend",
+    "prepend",
+    "attr_accessor",
+    "attr_reader",
+    "attr_writer",
+    "private",
+    "protected",
+    "public",
+    "module_function",
+];
+
+/// Calls that mix a module into a class or module
+const MIXIN_CALLS: &[&str] = &["include", "extend", "prepend"];
+
+const ATTRIBUTE_CALLS: &[&str] = &["attr_accessor", "attr_reader", "attr_writer"];
+
+pub struct RubyParser {
+    parser: Parser,
+}
+
+impl Default for RubyParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RubyParser {
+    /// Creates a new Ruby parser
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of the Ruby parser
+    ///
+    /// # Panics
+    /// This function will panic if the tree-sitter Ruby language cannot be loaded.
+    /// This should only happen in case of a build/linking issue with the tree-sitter library.
+    pub fn new() -> Self {
+        Self::try_new().expect("Failed to load Ruby grammar - this is a build configuration error")
+    }
+
+    /// Creates a new Ruby parser with error handling
+    ///
+    /// # Returns
+    /// * `Result<Self>` - A new instance of the Ruby parser or an error
+    pub fn try_new() -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_ruby::LANGUAGE.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load Ruby grammar: {}", e))?;
+        Ok(Self { parser })
+    }
+
+    fn parse_tree(&mut self, content: &str, file_path: &str, what: &str) -> Result<Tree> {
+        self.parser.parse(content, None).ok_or_else(|| {
+            let filename = Path::new(file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            anyhow::anyhow!(
+                "Failed to parse Ruby {} in file '{}' ({}B). The file may contain syntax errors.",
+                what,
+                filename,
+                content.len()
+            )
+        })
+    }
+
+    fn extract_parameters(&self, param_list: Node, content: &str) -> Vec<Parameter> {
+        let mut params = Vec::new();
+        let mut cursor = param_list.walk();
+        for param_node in param_list.named_children(&mut cursor) {
+            let (prefix, name_node) = match param_node.kind() {
+                "identifier" => ("", Some(param_node)),
+                "optional_parameter" | "keyword_parameter" => {
+                    ("", param_node.child_by_field_name("name"))
+                }
+                "splat_parameter" => ("*", param_node.child_by_field_name("name")),
+                "hash_splat_parameter" => ("**", param_node.child_by_field_name("name")),
+                "block_parameter" => ("&", param_node.child_by_field_name("name")),
+                _ => continue,
+            };
+            let Some(name) = name_node.and_then(|n| n.utf8_text(content.as_bytes()).ok()) else {
+                continue;
+            };
+
+            params.push(Parameter {
+                name: format!("{}{}", prefix, name),
+                type_annotation: None,
+                default_value: text_of(param_node, "value", content),
+            });
+        }
+
+        params
+    }
+
+    fn extract_function_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<FunctionDefinition> {
+        if !matches!(node.kind(), "method" | "singleton_method") {
+            return None;
+        }
+
+        let name = text_of(node, "name", content)?;
+        let containing_type = enclosing_type(node, content);
+        let kind = match &containing_type {
+            Some(_) if name == "initialize" => FunctionKind::Constructor,
+            Some(_) => FunctionKind::Method,
+            None => FunctionKind::Function,
+        };
+
+        Some(FunctionDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility: method_visibility(node, content),
+            location: node_to_location(node),
+            containing_entity_name: containing_type.clone(),
+            containing_type,
+            parameters: node
+                .child_by_field_name("parameters")
+                .map(|params| self.extract_parameters(params, content))
+                .unwrap_or_default(),
+            return_type: None,
+            decorators: Vec::new(),
+        })
+    }
+
+    fn extract_call_name(&self, node: Node, content: &str) -> Option<(String, Option<String>)> {
+        let method = node.child_by_field_name("method")?;
+        if !matches!(method.kind(), "identifier" | "constant") {
+            // `super` and `.()` calls name no method
+            return None;
+        }
+        let name = method.utf8_text(content.as_bytes()).ok()?.to_string();
+
+        let Some(receiver) = node.child_by_field_name("receiver") else {
+            if DECLARATION_CALLS.contains(&name.as_str()) {
+                return None;
+            }
+            return Some((name.clone(), Some(name)));
+        };
+
+        // Receivers such as `build(x).finish` can't be resolved statically
+        if !matches!(
+            receiver.kind(),
+            "identifier" | "constant" | "scope_resolution" | "self" | "instance_variable"
+        ) {
+            return Some((name.clone(), Some(name)));
+        }
+
+        // `Billing::Invoice.new` is a dotted path, like calls of other languages
+        let receiver = receiver
+            .utf8_text(content.as_bytes())
+            .ok()?
+            .replace("::", ".");
+
+        // `Invoice.new` runs `Invoice#initialize`
+        let name = if name == "new" && receiver.starts_with(char::is_uppercase) {
+            "initialize".to_string()
+        } else {
+            name
+        };
+
+        Some((name.clone(), Some(format!("{}.{}", receiver, name))))
+    }
+
+    fn extract_type_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<TypeDefinition> {
+        // Modules are namespaces as well as mixins, and reported like traits
+        // so that including one implements it
+        let kind = match node.kind() {
+            "class" => TypeKind::Class,
+            "module" => TypeKind::Trait,
+            _ => return None,
+        };
+        let name = type_name(node, content)?;
+
+        let super_types = node
+            .child_by_field_name("superclass")
+            .and_then(|superclass| superclass.named_child(0))
+            .and_then(|superclass| superclass.utf8_text(content.as_bytes()).ok())
+            .map(String::from)
+            .into_iter()
+            .collect();
+
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+        let mut mixins = Vec::new();
+        if let Some(body) = node.child_by_field_name("body") {
+            for member in body_members(body) {
+                match member.kind() {
+                    "method" | "singleton_method" => {
+                        methods.extend(text_of(member, "name", content));
+                    }
+                    // `TAX = 0.2`
+                    "assignment" => {
+                        let Some(constant) = member
+                            .child_by_field_name("left")
+                            .filter(|left| left.kind() == "constant")
+                        else {
+                            continue;
+                        };
+
+                        let Ok(name) = constant.utf8_text(content.as_bytes()) else {
+                            continue;
+                        };
+
+                        fields.push(FieldDefinition {
+                            name: name.to_string(),
+                            visibility: Visibility::Public,
+                            location: node_to_location(member),
+                            is_static: true,
+                            default_value: text_of(member, "right", content),
+                            documentation: doc_comments(content, member.start_position().row),
+                            ..Default::default()
+                        });
+                    }
+                    "call" if member.child_by_field_name("receiver").is_none() => {
+                        let Some(method) = text_of(member, "method", content) else {
+                            continue;
+                        };
+                        let arguments = call_arguments(member);
+
+                        if MIXIN_CALLS.contains(&method.as_str()) {
+                            mixins.extend(
+                                arguments
+                                    .iter()
+                                    .filter(|arg| {
+                                        matches!(arg.kind(), "constant" | "scope_resolution")
+                                    })
+                                    .filter_map(|arg| arg.utf8_text(content.as_bytes()).ok())
+                                    .map(String::from),
+                            );
+                        } else if ATTRIBUTE_CALLS.contains(&method.as_str()) {
+                            // `attr_accessor :total, :lines`
+                            fields.extend(
+                                arguments
+                                    .iter()
+                                    .filter(|arg| arg.kind() == "simple_symbol")
+                                    .filter_map(|arg| arg.utf8_text(content.as_bytes()).ok())
+                                    .map(|symbol| FieldDefinition {
+                                        name: symbol.trim_start_matches(':').to_string(),
+                                        visibility: Visibility::Public,
+                                        location: node_to_location(member),
+                                        documentation: doc_comments(
+                                            content,
+                                            member.start_position().row,
+                                        ),
+                                        ..Default::default()
+                                    }),
+                            );
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Some(TypeDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility: Visibility::Public,
+            location: node_to_location(node),
+            super_types,
+            fields,
+            methods,
+            documentation: doc_comments(content, node.start_position().row),
+            containing_entity_name: enclosing_type(node, content),
+            generic_params: Vec::new(),
+            mixins,
+            decorators: Vec::new(),
+        })
+    }
+}
+
+fn text_of(node: Node, field: &str, content: &str) -> Option<String> {
+    node.child_by_field_name(field)?
+        .utf8_text(content.as_bytes())
+        .ok()
+        .map(String::from)
+}
+
+/// Name of a class or module, the last segment of `class Billing::Invoice`
+fn type_name(node: Node, content: &str) -> Option<String> {
+    let name = node.child_by_field_name("name")?;
+    let name = match name.kind() {
+        "scope_resolution" => name.child_by_field_name("name")?,
+        _ => name,
+    };
+    name.utf8_text(content.as_bytes()).ok().map(String::from)
+}
+
+/// Name of the class or module a node is declared in
+fn enclosing_type(node: Node, content: &str) -> Option<String> {
+    std::iter::successors(node.parent(), |n| n.parent())
+        .find(|n| matches!(n.kind(), "class" | "module"))
+        .and_then(|n| type_name(n, content))
+}
+
+/// Statements of a class body, including those of `class << self` and
+/// methods wrapped in a modifier such as `private def`
+fn body_members(body: Node) -> Vec<Node> {
+    let mut members = Vec::new();
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        match member.kind() {
+            "singleton_class" => {
+                if let Some(singleton_body) = member.child_by_field_name("body") {
+                    members.extend(body_members(singleton_body));
+                }
+            }
+            "call" => {
+                let wrapped = call_arguments(member)
+                    .into_iter()
+                    .filter(|arg| matches!(arg.kind(), "method" | "singleton_method"))
+                    .collect::<Vec<_>>();
+                if wrapped.is_empty() {
+                    members.push(member);
+                } else {
+                    members.extend(wrapped);
+                }
+            }
+            _ => members.push(member),
+        }
+    }
+    members
+}
+
+/// Methods each class or module of a file can call without a receiver: its
+/// own and those of the modules it mixes in that are defined in the file
+fn callable_methods(types: &[TypeDefinition]) -> HashMap<&str, HashSet<&str>> {
+    let mut callable: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for type_def in types {
+        let methods = callable.entry(type_def.name.as_str()).or_default();
+        let mut pending = vec![type_def.name.as_str()];
+        let mut seen = HashSet::new();
+        while let Some(name) = pending.pop() {
+            if !seen.insert(name) {
+                continue;
+            }
+            // A class may be reopened, so gather every definition of it
+            for definition in types.iter().filter(|t| t.name == name) {
+                methods.extend(definition.methods.iter().map(String::as_str));
+                pending.extend(
+                    definition
+                        .mixins
+                        .iter()
+                        .filter_map(|mixin| mixin.rsplit("::").next()),
+                );
+            }
+        }
+    }
+    callable
+}
+
+/// The method a bare identifier calls when it stands as a statement of its
+/// own, such as `total` in `def finish; total; end`
+fn bare_call_name(
+    node: Node,
+    content: &str,
+    callable: &HashMap<&str, HashSet<&str>>,
+) -> Option<(String, Option<String>)> {
+    let is_statement = node.parent().is_some_and(|parent| match parent.kind() {
+        "body_statement" | "then" | "else" | "block_body" | "begin" | "ensure" | "do" => true,
+        // `audit if dirty`
+        "if_modifier" | "unless_modifier" | "while_modifier" | "until_modifier" => {
+            parent.child_by_field_name("body") == Some(node)
+        }
+        _ => false,
+    });
+    if !is_statement {
+        return None;
+    }
+
+    let name = node.utf8_text(content.as_bytes()).ok()?;
+    let method = std::iter::successors(node.parent(), |n| n.parent())
+        .find(|n| matches!(n.kind(), "method" | "singleton_method"))?;
+    let methods = callable.get(enclosing_type(node, content)?.as_str())?;
+    if !methods.contains(name) || is_local(method, name, content) {
+        return None;
+    }
+
+    Some((name.to_string(), Some(name.to_string())))
+}
+
+/// Whether a name is a parameter or assigned local variable of a method,
+/// including those of the blocks within it
+fn is_local(method: Node, name: &str, content: &str) -> bool {
+    let mut local = false;
+    traverse_node(method, &mut |node| {
+        if local || node.kind() != "identifier" {
+            return;
+        }
+        let Some(parent) = node.parent() else {
+            return;
+        };
+        let declares = parent.kind().ends_with("parameters")
+            || parent.kind().ends_with("_parameter")
+            || (matches!(parent.kind(), "assignment" | "operator_assignment")
+                && parent.child_by_field_name("left") == Some(node));
+        local = declares && node.utf8_text(content.as_bytes()) == Ok(name);
+    });
+    local
+}
+
+fn call_arguments(call: Node) -> Vec<Node> {
+    let Some(arguments) = call.child_by_field_name("arguments") else {
+        return Vec::new();
+    };
+    let mut cursor = arguments.walk();
+    let args = arguments.named_children(&mut cursor).collect();
+    args
+}
+
+fn visibility_keyword(keyword: &str) -> Option<Visibility> {
+    match keyword {
+        "private" => Some(Visibility::Private),
+        "protected" => Some(Visibility::Protected),
+        "public" => Some(Visibility::Public),
+        _ => None,
+    }
+}
+
+/// Visibility of a method, from a `private def` modifier or the last bare
+/// `private`, `protected` or `public` before it in the class body
+fn method_visibility(node: Node, content: &str) -> Visibility {
+    // `private def secret`
+    let modifier = node
+        .parent()
+        .filter(|parent| parent.kind() == "argument_list")
+        .and_then(|arguments| arguments.parent())
+        .filter(|call| call.kind() == "call");
+    if let Some(call) = modifier {
+        if let Some(visibility) =
+            text_of(call, "method", content).and_then(|method| visibility_keyword(&method))
+        {
+            return visibility;
+        }
+    }
+
+    std::iter::successors(node.prev_named_sibling(), |n| n.prev_named_sibling())
+        .filter(|n| n.kind() == "identifier")
+        .find_map(|n| visibility_keyword(n.utf8_text(content.as_bytes()).ok()?))
+        .unwrap_or(Visibility::Public)
+}
+
+/// The `#` comment lines directly above a line, without their markers
+fn doc_comments(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().take(line).collect();
+    let mut doc_lines: Vec<&str> = lines
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .take_while(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches('#').trim())
+        .collect();
+
+    if doc_lines.is_empty() {
+        return None;
+    }
+
+    doc_lines.reverse();
+    Some(doc_lines.join("\n"))
+}
+
+impl LanguageParser for RubyParser {
+    /// Determines if this parser can handle a given file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to check
+    ///
+    /// # Returns
+    /// * `bool` - True if this parser can handle the file, false otherwise
+    fn can_handle(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| ext == "rb")
+    }
+
+    /// Parses Ruby methods, including singleton methods such as `def self.build`
+    ///
+    /// # Arguments
+    /// * `content` - The Ruby source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<FunctionDefinition>>` - List of extracted function definitions or an error
+    fn parse_functions(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<FunctionDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code")?;
+        let root_node = tree.root_node();
+
+        tracing::debug!(
+            "Parsed Ruby file '{}' ({} bytes) - AST has {} nodes",
+            file_path,
+            content.len(),
+            root_node.child_count()
+        );
+
+        let mut functions = Vec::new();
+        traverse_node(root_node, &mut |node| {
+            if let Some(func) = self.extract_function_details(node, content, file_path) {
+                functions.push(func);
+            }
+        });
+
+        Ok(functions)
+    }
+
+    /// Parses method calls from Ruby source code
+    ///
+    /// A call with no receiver, arguments or parentheses parses like a local
+    /// variable, so one standing as a statement of its own is only reported
+    /// when it names a method of the enclosing class or module, or of a
+    /// module it mixes in that's defined in the same file, and isn't shadowed
+    /// by a parameter or local variable.
+    ///
+    /// # Arguments
+    /// * `content` - The Ruby source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<CallReference>>` - List of extracted function call references or an error
+    fn parse_calls(&mut self, content: &str, file_path: &str) -> Result<Vec<CallReference>> {
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code for method calls")?;
+
+        tracing::debug!(
+            "Parsing method calls from Ruby file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut types = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            types.extend(self.extract_type_details(node, content, file_path));
+        });
+        let callable = callable_methods(&types);
+
+        let mut calls = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            let call = match node.kind() {
+                "call" => self.extract_call_name(node, content),
+                "identifier" => bare_call_name(node, content, &callable),
+                _ => None,
+            };
+            let Some((name, full_path)) = call else {
+                return;
+            };
+
+            calls.push(
+                CallReference::with_details(
+                    name,
+                    full_path,
+                    Some(node_to_location(node)),
+                    Some(file_path.to_string()),
+                    Vec::new(),
+                )
+                .with_caller(enclosing_function_name(node, |n| {
+                    self.extract_function_details(n, content, file_path)
+                        .map(|func| func.name)
+                })),
+            );
+        });
+
+        Ok(calls)
+    }
+
+    /// Parses Ruby classes and modules
+    ///
+    /// Constants and `attr_*` accessors are reported as fields, and modules
+    /// brought in with `include`, `extend` or `prepend` as mixins.
+    ///
+    /// # Arguments
+    /// * `content` - The Ruby source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<TypeDefinition>>` - List of extracted type definitions or an error
+    fn parse_types(&mut self, content: &str, file_path: &str) -> Result<Vec<TypeDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "types")?;
+
+        tracing::debug!(
+            "Parsing types from Ruby file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut types = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if let Some(type_def) = self.extract_type_details(node, content, file_path) {
+                types.push(type_def);
+            }
+        });
+
+        Ok(types)
+    }
+
+    /// Parses the `require` and `require_relative` statements of a Ruby file
+    ///
+    /// The module is named after the file's first top-level module, if it
+    /// has one, and exports its top-level classes and modules.
+    ///
+    /// # Arguments
+    /// * `content` - The Ruby source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<ModuleDefinition>` - Module definition or an error
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            tracing::debug!(
+                "Empty Ruby file content for module parsing: '{}'",
+                file_path
+            );
+            return Ok(module_def);
+        }
+
+        let tree = self.parse_tree(content, file_path, "module")?;
+        let root_node = tree.root_node();
+
+        let mut cursor = root_node.walk();
+        let mut module_found = false;
+        for node in root_node.named_children(&mut cursor) {
+            match node.kind() {
+                "module" | "class" => {
+                    let Some(name) = type_name(node, content) else {
+                        continue;
+                    };
+                    if node.kind() == "module" && !module_found {
+                        module_def.name = name.clone();
+                        module_found = true;
+                    }
+                    module_def.exports.push(name);
+                }
+                "call" => {
+                    let Some(method) = text_of(node, "method", content) else {
+                        continue;
+                    };
+                    if !matches!(method.as_str(), "require" | "require_relative") {
+                        continue;
+                    }
+
+                    let Some(path) = call_arguments(node)
+                        .into_iter()
+                        .find(|arg| arg.kind() == "string")
+                        .and_then(|arg| arg.utf8_text(content.as_bytes()).ok())
+                    else {
+                        continue;
+                    };
+
+                    module_def.imports.push(ImportDefinition {
+                        module_name: path.trim_matches(['\'', '"']).to_string(),
+                        imported_symbols: vec!["*".to_string()],
+                        location: node_to_location(node),
+                        is_relative: method == "require_relative",
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(module_def)
+    }
+
+    /// Extracts the `#` comment lines directly above a definition
+    fn extract_documentation(&self, content: &str, location: &Location) -> Result<Option<String>> {
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(doc_comments(content, location.start.line))
+    }
+
+    /// Parses constants and `$global` variables assigned at the top level.
+    /// Constants assigned in a class or module body are its fields.
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variables")?;
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        let variables = root
+            .named_children(&mut cursor)
+            .filter(|statement| statement.kind() == "assignment")
+            .filter_map(|assignment| {
+                let left = assignment
+                    .child_by_field_name("left")
+                    .filter(|left| matches!(left.kind(), "constant" | "global_variable"))?;
+                Some(VariableDefinition {
+                    name: left.utf8_text(content.as_bytes()).ok()?.to_string(),
+                    file_path: file_path.to_string(),
+                    type_annotation: None,
+                    initializer: text_of(assignment, "right", content),
+                    visibility: Visibility::Public,
+                    location: node_to_location(assignment),
+                    is_const: left.kind() == "constant",
+                    is_static: false,
+                    documentation: None,
+                })
+            })
+            .collect();
+
+        Ok(variables)
+    }
+
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variable references")?;
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier", "constant", "global_variable"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
+        Box::new(RubyParser::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_ruby_methods() -> Result<()> {
+        let mut parser = RubyParser::new();
+        let content = indoc! {r#"
+            module Billing
+              class Invoice
+                # Creates an invoice.
+                def initialize(total, rate = 0.2, *rest, currency: "EUR", **opts, &blk)
+                end
+
+                def self.build; end
+
+                class << self
+                  def parse(text); end
+                end
+
+                private
+
+                def secret; end
+
+                public def shown; end
+              end
+            end
+
+            def helper; end
+        "#};
+
+        let functions = parser.parse_functions(content, "invoice.rb")?;
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["initialize", "build", "parse", "secret", "shown", "helper"]
+        );
+
+        let initialize = &functions[0];
+        assert_eq!(initialize.kind, FunctionKind::Constructor);
+        assert_eq!(initialize.containing_type.as_deref(), Some("Invoice"));
+        let params: Vec<(&str, Option<&str>)> = initialize
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.default_value.as_deref()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("total", None),
+                ("rate", Some("0.2")),
+                ("*rest", None),
+                ("currency", Some("\"EUR\"")),
+                ("**opts", None),
+                ("&blk", None),
+            ]
+        );
+        assert_eq!(
+            parser.extract_documentation(content, &initialize.location)?,
+            Some("Creates an invoice.".to_string())
+        );
+
+        assert_eq!(functions[1].kind, FunctionKind::Method);
+        assert_eq!(functions[2].containing_type.as_deref(), Some("Invoice"));
+        assert_eq!(functions[2].visibility, Visibility::Public);
+        assert_eq!(functions[3].visibility, Visibility::Private);
+        assert_eq!(functions[4].visibility, Visibility::Public);
+        assert_eq!(functions[5].kind, FunctionKind::Function);
+        assert_eq!(functions[5].containing_type, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ruby_calls() -> Result<()> {
+        let mut parser = RubyParser::new();
+        let content = indoc! {r#"
+            class Invoice
+              include Comparable
+
+              def finish
+                Billing::Invoice.new(1)
+                self.total
+                Helper.format(x)
+                @repo.save(self)
+                build(1).run
+                puts "done"
+                super
+              end
+            end
+        "#};
+
+        let calls = parser.parse_calls(content, "invoice.rb")?;
+        let paths: Vec<(&str, Option<&str>)> = calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.fully_qualified_name.as_deref()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("initialize", Some("Billing.Invoice.initialize")),
+                ("total", Some("self.total")),
+                ("format", Some("Helper.format")),
+                ("save", Some("@repo.save")),
+                ("run", Some("run")),
+                ("build", Some("build")),
+                ("puts", Some("puts")),
+            ]
+        );
+        assert!(calls
+            .iter()
+            .all(|c| c.caller_name.as_deref() == Some("finish")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ruby_bare_method_calls() -> Result<()> {
+        let mut parser = RubyParser::new();
+        let content = indoc! {r#"
+            module Formatting
+              def pad
+              end
+            end
+
+            class Invoice
+              include Formatting
+
+              def finish(lines)
+                subtotal = 0
+                total
+                pad
+                lines
+                subtotal
+                [1].each { |tax| tax }
+                audit if lines
+              end
+
+              def audit
+                total
+              end
+
+              def total
+              end
+            end
+        "#};
+
+        let calls = parser.parse_calls(content, "invoice.rb")?;
+        let names: Vec<(&str, Option<&str>)> = calls
+            .iter()
+            .map(|c| (c.callee_name.as_str(), c.caller_name.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("total", Some("finish")),
+                ("pad", Some("finish")),
+                ("each", Some("finish")),
+                ("audit", Some("finish")),
+                ("total", Some("audit")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ruby_parser_empty_content() -> Result<()> {
+        let mut parser = RubyParser::new();
+
+        assert!(parser.parse_functions("", "empty.rb")?.is_empty());
+        assert!(parser.parse_calls("", "empty.rb")?.is_empty());
+        assert!(parser.parse_types("", "empty.rb")?.is_empty());
+        assert_eq!(parser.parse_modules("", "empty.rb")?.name, "empty.rb");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ruby_classes_and_mixins() -> Result<()> {
+        let mut parser = RubyParser::new();
+        let content = indoc! {r#"
+            # Billing code.
+            module Billing
+              # An invoice.
+              class Invoice < Base::Record
+                include Comparable
+                extend Forwardable, Billing::Helpers
+                attr_accessor :total, :lines
+                TAX = 0.2
+
+                def total; end
+                private def secret; end
+              end
+            end
+        "#};
+
+        let types = parser.parse_types(content, "invoice.rb")?;
+        assert_eq!(types.len(), 2);
+
+        let billing = &types[0];
+        assert_eq!(billing.name, "Billing");
+        assert_eq!(billing.kind, TypeKind::Trait);
+        assert_eq!(billing.documentation.as_deref(), Some("Billing code."));
+
+        let invoice = &types[1];
+        assert_eq!(invoice.name, "Invoice");
+        assert_eq!(invoice.kind, TypeKind::Class);
+        assert_eq!(invoice.super_types, vec!["Base::Record"]);
+        assert_eq!(
+            invoice.mixins,
+            vec!["Comparable", "Forwardable", "Billing::Helpers"]
+        );
+        assert_eq!(invoice.methods, vec!["total", "secret"]);
+        assert_eq!(invoice.containing_entity_name.as_deref(), Some("Billing"));
+        assert_eq!(invoice.documentation.as_deref(), Some("An invoice."));
+
+        let fields: Vec<(&str, bool, Option<&str>)> = invoice
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.is_static, f.default_value.as_deref()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("total", false, None),
+                ("lines", false, None),
+                ("TAX", true, Some("0.2")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ruby_modules() -> Result<()> {
+        let mut parser = RubyParser::new();
+        let content = indoc! {r#"
+            require 'json'
+            require_relative "../lib/helper"
+
+            module Billing
+            end
+
+            class Report; end
+        "#};
+
+        let module = parser.parse_modules(content, "billing.rb")?;
+        assert_eq!(module.name, "Billing");
+        assert_eq!(module.exports, vec!["Billing", "Report"]);
+
+        let imports: Vec<(&str, bool)> = module
+            .imports
+            .iter()
+            .map(|i| (i.module_name.as_str(), i.is_relative))
+            .collect();
+        assert_eq!(imports, vec![("json", false), ("../lib/helper", true)]);
+        assert!(module.imports.iter().all(|i| i.imported_symbols == ["*"]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ruby_variables() -> Result<()> {
+        let mut parser = RubyParser::new();
+        let content = indoc! {r#"
+            MAX_ITEMS = 50
+            $verbose = false
+
+            module Billing
+              TAX = 0.2
+
+              def self.total(items)
+                $verbose = true
+                items.take(MAX_ITEMS).sum * (1 + Billing::TAX)
+              end
+            end
+        "#};
+
+        let variables = parser.parse_variables(content, "billing.rb")?;
+        let summary: Vec<(&str, Option<&str>, bool)> = variables
+            .iter()
+            .map(|v| (v.name.as_str(), v.initializer.as_deref(), v.is_const))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("MAX_ITEMS", Some("50"), true),
+                ("$verbose", Some("false"), false)
+            ]
+        );
+
+        let references = parser.parse_variable_references(content, "billing.rb")?;
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+        assert!(find("$verbose").is_write);
+        assert_eq!(find("MAX_ITEMS").caller_name, "total");
+        assert_eq!(find("TAX").qualifier.as_deref(), Some("Billing"));
+
+        Ok(())
+    }
+}
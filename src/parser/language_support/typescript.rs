@@ -0,0 +1,1261 @@
+use super::*;
+use super::{node_to_location, traverse_node};
+use anyhow::Result;
+use std::path::Path;
+use tree_sitter::{Node, Parser, Tree};
+
+/// Parser for TypeScript and TSX files
+///
+/// The two dialects use separate grammars, since TSX gives `<` a different
+/// meaning, so one tree-sitter parser is kept for each and picked by extension.
+pub struct TypeScriptParser {
+    parser: Parser,
+    tsx_parser: Parser,
+}
+
+impl Default for TypeScriptParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeScriptParser {
+    /// Creates a new TypeScript parser
+    ///
+    /// # Returns
+    /// * `Self` - A new instance of the TypeScript parser
+    ///
+    /// # Panics
+    /// This function will panic if the tree-sitter TypeScript or TSX language cannot be loaded.
+    /// This should only happen in case of a build/linking issue with the tree-sitter library.
+    pub fn new() -> Self {
+        Self::try_new()
+            .expect("Failed to load TypeScript grammars - this is a build configuration error")
+    }
+
+    /// Creates a new TypeScript parser with error handling
+    ///
+    /// # Returns
+    /// * `Result<Self>` - A new instance of the TypeScript parser or an error
+    pub fn try_new() -> Result<Self> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load TypeScript grammar: {}", e))?;
+
+        let mut tsx_parser = Parser::new();
+        tsx_parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TSX.into())
+            .map_err(|e| anyhow::anyhow!("Failed to load TSX grammar: {}", e))?;
+
+        Ok(Self { parser, tsx_parser })
+    }
+
+    /// Parse a file with the grammar matching its extension
+    fn parse_tree(&mut self, content: &str, file_path: &str, what: &str) -> Result<Tree> {
+        let is_tsx = Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| ext == "tsx");
+        let parser = if is_tsx {
+            &mut self.tsx_parser
+        } else {
+            &mut self.parser
+        };
+
+        parser.parse(content, None).ok_or_else(|| {
+            let filename = Path::new(file_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            anyhow::anyhow!(
+                "Failed to parse TypeScript {} in file '{}' ({}B). The file may contain syntax errors.",
+                what,
+                filename,
+                content.len()
+            )
+        })
+    }
+
+    fn extract_parameters(&self, node: Node, content: &str) -> Vec<Parameter> {
+        // `x => x` has a single bare parameter rather than a parameter list
+        if let Some(param) = node.child_by_field_name("parameter") {
+            return param
+                .utf8_text(content.as_bytes())
+                .map(|name| {
+                    vec![Parameter {
+                        name: name.to_string(),
+                        type_annotation: None,
+                        default_value: None,
+                    }]
+                })
+                .unwrap_or_default();
+        }
+
+        let mut params = Vec::new();
+
+        if let Some(param_list) = node.child_by_field_name("parameters") {
+            let mut cursor = param_list.walk();
+            for param_node in param_list.named_children(&mut cursor) {
+                if !matches!(
+                    param_node.kind(),
+                    "required_parameter" | "optional_parameter"
+                ) {
+                    continue;
+                }
+
+                let Some(name) = param_node
+                    .child_by_field_name("pattern")
+                    .and_then(|pattern| pattern.utf8_text(content.as_bytes()).ok())
+                else {
+                    continue;
+                };
+
+                params.push(Parameter {
+                    name: name.to_string(),
+                    type_annotation: type_annotation(param_node, content),
+                    default_value: param_node
+                        .child_by_field_name("value")
+                        .and_then(|v| v.utf8_text(content.as_bytes()).ok())
+                        .map(String::from),
+                });
+            }
+        }
+
+        params
+    }
+
+    fn extract_function_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<FunctionDefinition> {
+        match node.kind() {
+            "function_declaration" | "generator_function_declaration" => {
+                let name = node
+                    .child_by_field_name("name")?
+                    .utf8_text(content.as_bytes())
+                    .ok()?
+                    .to_string();
+
+                Some(FunctionDefinition {
+                    name,
+                    file_path: file_path.to_string(),
+                    kind: FunctionKind::Function,
+                    visibility: export_visibility(node),
+                    location: node_to_location(node),
+                    containing_type: None,
+                    parameters: self.extract_parameters(node, content),
+                    return_type: return_type(node, content),
+                    containing_entity_name: None,
+                    decorators: Vec::new(),
+                })
+            }
+            "method_definition" => {
+                let name = node
+                    .child_by_field_name("name")?
+                    .utf8_text(content.as_bytes())
+                    .ok()?
+                    .to_string();
+
+                let containing_type = containing_class_name(node, content);
+                let visibility = member_visibility(node, &name, content);
+                let kind = if name == "constructor" {
+                    FunctionKind::Constructor
+                } else {
+                    FunctionKind::Method
+                };
+
+                Some(FunctionDefinition {
+                    name,
+                    file_path: file_path.to_string(),
+                    kind,
+                    visibility,
+                    location: node_to_location(node),
+                    containing_type: containing_type.clone(),
+                    parameters: self.extract_parameters(node, content),
+                    return_type: return_type(node, content),
+                    containing_entity_name: containing_type,
+                    decorators: Vec::new(),
+                })
+            }
+            "arrow_function" => {
+                let name = std::iter::successors(node.parent(), |n| n.parent())
+                    .find(|parent| parent.kind() == "variable_declarator")
+                    .and_then(|declarator| declarator.child_by_field_name("name"))
+                    .and_then(|name_node| name_node.utf8_text(content.as_bytes()).ok())
+                    .map(|name| format!("arrow_{}", name))
+                    .unwrap_or_else(|| {
+                        format!(
+                            "arrow_{}_{}",
+                            node.start_position().row,
+                            node.start_position().column
+                        )
+                    });
+
+                Some(FunctionDefinition {
+                    name,
+                    file_path: file_path.to_string(),
+                    kind: FunctionKind::Lambda,
+                    visibility: Visibility::Default,
+                    location: node_to_location(node),
+                    containing_type: None,
+                    parameters: self.extract_parameters(node, content),
+                    return_type: return_type(node, content),
+                    containing_entity_name: None,
+                    decorators: Vec::new(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn extract_call_name(&self, node: Node, content: &str) -> Option<(String, Option<String>)> {
+        match node.kind() {
+            "identifier" => {
+                let name = node.utf8_text(content.as_bytes()).ok()?.to_string();
+                Some((name.clone(), Some(name)))
+            }
+            "member_expression" => {
+                let object = node.child_by_field_name("object")?;
+                let property = node.child_by_field_name("property")?;
+
+                let obj_text = object.utf8_text(content.as_bytes()).ok()?;
+                let prop_text = property.utf8_text(content.as_bytes()).ok()?;
+
+                Some((
+                    prop_text.to_string(),
+                    Some(format!("{}.{}", obj_text, prop_text)),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn extract_generic_parameters(&self, node: Node, content: &str) -> Vec<GenericParameter> {
+        let Some(type_params) = node.child_by_field_name("type_parameters") else {
+            return Vec::new();
+        };
+
+        let mut cursor = type_params.walk();
+        type_params
+            .named_children(&mut cursor)
+            .filter(|param| param.kind() == "type_parameter")
+            .map(|param_node| {
+                // `extends Base` and `= Base` wrap the type we're after
+                let inner_text = |field: &str| {
+                    param_node
+                        .child_by_field_name(field)
+                        .and_then(|n| n.named_child(0))
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        .map(String::from)
+                };
+
+                GenericParameter {
+                    name: param_node
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        .unwrap_or_default()
+                        .to_string(),
+                    bounds: inner_text("constraint").into_iter().collect(),
+                    default_type: inner_text("value"),
+                }
+            })
+            .collect()
+    }
+
+    /// Names of the classes or interfaces a type extends or implements
+    fn extract_super_types(&self, node: Node, content: &str) -> Vec<String> {
+        let mut super_types = Vec::new();
+        let mut cursor = node.walk();
+
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "class_heritage" => {
+                    let mut heritage_cursor = child.walk();
+                    for clause in child.named_children(&mut heritage_cursor) {
+                        match clause.kind() {
+                            "extends_clause" => super_types.extend(
+                                clause
+                                    .child_by_field_name("value")
+                                    .and_then(|v| v.utf8_text(content.as_bytes()).ok())
+                                    .map(String::from),
+                            ),
+                            "implements_clause" => {
+                                let mut types_cursor = clause.walk();
+                                super_types.extend(
+                                    clause
+                                        .named_children(&mut types_cursor)
+                                        .filter_map(|t| t.utf8_text(content.as_bytes()).ok())
+                                        .map(String::from),
+                                );
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                "extends_type_clause" => {
+                    let mut types_cursor = child.walk();
+                    super_types.extend(
+                        child
+                            .named_children(&mut types_cursor)
+                            .filter_map(|t| t.utf8_text(content.as_bytes()).ok())
+                            .map(String::from),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        super_types
+    }
+
+    /// Fields and method names declared in a class, interface or enum body
+    fn extract_members(&self, node: Node, content: &str) -> (Vec<FieldDefinition>, Vec<String>) {
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+
+        let Some(body) = node.child_by_field_name("body") else {
+            return (fields, methods);
+        };
+
+        let text = |n: Node| n.utf8_text(content.as_bytes()).ok().map(String::from);
+
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            match member.kind() {
+                "public_field_definition" | "property_signature" => {
+                    let Some(name) = member.child_by_field_name("name").and_then(text) else {
+                        continue;
+                    };
+                    let visibility = member_visibility(member, &name, content);
+
+                    fields.push(FieldDefinition {
+                        name,
+                        type_annotation: type_annotation(member, content),
+                        visibility,
+                        location: node_to_location(member),
+                        is_static: has_token(member, "static"),
+                        default_value: member.child_by_field_name("value").and_then(text),
+                        is_optional: has_token(member, "?"),
+                        ..Default::default()
+                    });
+                }
+                "method_definition" => {
+                    if let Some(name) = member.child_by_field_name("name").and_then(text) {
+                        // Constructor parameters such as `private x: number`
+                        // declare fields too
+                        if name == "constructor" {
+                            fields.extend(self.parameter_properties(member, content));
+                        }
+                        methods.push(name);
+                    }
+                }
+                "method_signature" | "abstract_method_signature" => {
+                    methods.extend(member.child_by_field_name("name").and_then(text));
+                }
+                // Enum members
+                "property_identifier" => fields.extend(text(member).map(|name| FieldDefinition {
+                    name,
+                    visibility: Visibility::Public,
+                    location: node_to_location(member),
+                    is_static: true,
+                    ..Default::default()
+                })),
+                "enum_assignment" => {
+                    if let Some(name) = member.child_by_field_name("name").and_then(text) {
+                        fields.push(FieldDefinition {
+                            name,
+                            visibility: Visibility::Public,
+                            location: node_to_location(member),
+                            is_static: true,
+                            default_value: member.child_by_field_name("value").and_then(text),
+                            ..Default::default()
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (fields, methods)
+    }
+
+    /// Fields declared by constructor parameters with an accessibility modifier
+    fn parameter_properties(&self, constructor: Node, content: &str) -> Vec<FieldDefinition> {
+        let Some(param_list) = constructor.child_by_field_name("parameters") else {
+            return Vec::new();
+        };
+
+        let mut cursor = param_list.walk();
+        param_list
+            .named_children(&mut cursor)
+            .filter_map(|param| {
+                let visibility = accessibility(param, content)?;
+                let name = param
+                    .child_by_field_name("pattern")?
+                    .utf8_text(content.as_bytes())
+                    .ok()?
+                    .to_string();
+
+                Some(FieldDefinition {
+                    name,
+                    type_annotation: type_annotation(param, content),
+                    visibility,
+                    location: node_to_location(param),
+                    is_optional: param.kind() == "optional_parameter",
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn extract_type_details(
+        &self,
+        node: Node,
+        content: &str,
+        file_path: &str,
+    ) -> Option<TypeDefinition> {
+        let kind = match node.kind() {
+            "class_declaration" | "abstract_class_declaration" => TypeKind::Class,
+            "interface_declaration" => TypeKind::Interface,
+            "type_alias_declaration" => TypeKind::TypeAlias,
+            "enum_declaration" => TypeKind::Enum,
+            _ => return None,
+        };
+
+        let name = node
+            .child_by_field_name("name")?
+            .utf8_text(content.as_bytes())
+            .ok()?
+            .to_string();
+        let (fields, methods) = self.extract_members(node, content);
+
+        Some(TypeDefinition {
+            name,
+            file_path: file_path.to_string(),
+            kind,
+            visibility: export_visibility(node),
+            location: node_to_location(node),
+            super_types: self.extract_super_types(node, content),
+            fields,
+            methods,
+            documentation: None,
+            containing_entity_name: None,
+            generic_params: self.extract_generic_parameters(node, content),
+            mixins: Vec::new(),
+            decorators: Vec::new(),
+        })
+    }
+
+    /// Import declaration for an `import` statement or an `export ... from` re-export
+    fn extract_import(&self, node: Node, content: &str) -> Option<ImportDefinition> {
+        let source = node.child_by_field_name("source")?;
+        let module_name = source
+            .utf8_text(content.as_bytes())
+            .ok()?
+            .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+            .to_string();
+
+        let mut imported_symbols = Vec::new();
+        traverse_node(node, &mut |child| match child.kind() {
+            "import_specifier" | "export_specifier" => {
+                imported_symbols.extend(
+                    child
+                        .child_by_field_name("name")
+                        .and_then(|n| n.utf8_text(content.as_bytes()).ok())
+                        .map(String::from),
+                );
+            }
+            // A default import: `import React from 'react'`
+            "identifier" if child.parent().is_some_and(|p| p.kind() == "import_clause") => {
+                imported_symbols.extend(child.utf8_text(content.as_bytes()).ok().map(String::from));
+            }
+            "namespace_import" | "namespace_export" => imported_symbols.push("*".to_string()),
+            "*" if child
+                .parent()
+                .is_some_and(|p| p.kind() == "export_statement") =>
+            {
+                imported_symbols.push("*".to_string())
+            }
+            _ => {}
+        });
+
+        Some(ImportDefinition {
+            is_relative: module_name.starts_with('.'),
+            module_name,
+            imported_symbols,
+            location: node_to_location(node),
+        })
+    }
+
+    /// Names an `export` statement makes visible to other modules
+    fn extract_exports(&self, node: Node, content: &str) -> Vec<String> {
+        let text = |n: Node| n.utf8_text(content.as_bytes()).ok().map(String::from);
+
+        if let Some(declaration) = node.child_by_field_name("declaration") {
+            return match declaration.kind() {
+                "lexical_declaration" | "variable_declaration" => {
+                    let mut cursor = declaration.walk();
+                    declaration
+                        .named_children(&mut cursor)
+                        .filter(|d| d.kind() == "variable_declarator")
+                        .filter_map(|d| d.child_by_field_name("name").and_then(text))
+                        .collect()
+                }
+                _ => match declaration.child_by_field_name("name").and_then(text) {
+                    Some(name) => vec![name],
+                    None => vec!["default".to_string()],
+                },
+            };
+        }
+
+        let mut exports = Vec::new();
+        traverse_node(node, &mut |child| {
+            if child.kind() == "export_specifier" {
+                exports.extend(
+                    child
+                        .child_by_field_name("alias")
+                        .or_else(|| child.child_by_field_name("name"))
+                        .and_then(text),
+                );
+            }
+        });
+
+        // `export default <expression>`
+        if exports.is_empty() && has_token(node, "default") {
+            exports.push("default".to_string());
+        }
+
+        exports
+    }
+}
+
+/// The text of a node's type annotation, without the leading colon
+fn type_annotation(node: Node, content: &str) -> Option<String> {
+    node.child_by_field_name("type")
+        .and_then(|t| t.utf8_text(content.as_bytes()).ok())
+        .map(|t| t.trim_start_matches(':').trim().to_string())
+}
+
+/// Declared return type of a function, without its colon, unless it's `void`
+fn return_type(node: Node, content: &str) -> Option<String> {
+    node.child_by_field_name("return_type")
+        .and_then(|t| t.utf8_text(content.as_bytes()).ok())
+        .map(|t| t.trim_start_matches(':').trim().to_string())
+        .filter(|t| t != "void")
+}
+
+/// Whether a node has an anonymous child token such as `static` or `?`
+fn has_token(node: Node, token: &str) -> bool {
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .any(|child| !child.is_named() && child.kind() == token);
+    found
+}
+
+/// The visibility given by a `public`, `private` or `protected` modifier
+fn accessibility(node: Node, content: &str) -> Option<Visibility> {
+    let mut cursor = node.walk();
+    let modifier = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "accessibility_modifier")?;
+
+    match modifier.utf8_text(content.as_bytes()).ok()? {
+        "private" => Some(Visibility::Private),
+        "protected" => Some(Visibility::Protected),
+        _ => Some(Visibility::Public),
+    }
+}
+
+/// Class members are public unless marked otherwise or named with `#`
+fn member_visibility(node: Node, name: &str, content: &str) -> Visibility {
+    if name.starts_with('#') {
+        Visibility::Private
+    } else {
+        accessibility(node, content).unwrap_or(Visibility::Public)
+    }
+}
+
+/// Declarations are public when exported and module-private otherwise
+fn export_visibility(node: Node) -> Visibility {
+    if node
+        .parent()
+        .is_some_and(|parent| parent.kind() == "export_statement")
+    {
+        Visibility::Public
+    } else {
+        Visibility::Default
+    }
+}
+
+fn containing_class_name(node: Node, content: &str) -> Option<String> {
+    std::iter::successors(node.parent(), |n| n.parent())
+        .find(|parent| {
+            matches!(
+                parent.kind(),
+                "class_declaration" | "abstract_class_declaration"
+            )
+        })
+        .and_then(|class| class.child_by_field_name("name"))
+        .and_then(|name| name.utf8_text(content.as_bytes()).ok())
+        .map(String::from)
+}
+
+impl LanguageParser for TypeScriptParser {
+    /// Determines if this parser can handle a given file
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the file to check
+    ///
+    /// # Returns
+    /// * `bool` - True if this parser can handle the file, false otherwise
+    fn can_handle(&self, file_path: &Path) -> bool {
+        file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| matches!(ext, "ts" | "tsx"))
+    }
+
+    /// Parses TypeScript functions, methods and arrow functions from the source code.
+    ///
+    /// # Arguments
+    /// * `content` - The TypeScript source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<FunctionDefinition>>` - List of extracted function definitions or an error
+    fn parse_functions(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<FunctionDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code")?;
+        let root_node = tree.root_node();
+
+        tracing::debug!(
+            "Parsed TypeScript file '{}' ({} bytes) - AST has {} nodes",
+            file_path,
+            content.len(),
+            root_node.child_count()
+        );
+
+        let mut functions = Vec::new();
+        traverse_node(root_node, &mut |node| {
+            if let Some(func) = self.extract_function_details(node, content, file_path) {
+                functions.push(func);
+            }
+        });
+
+        Ok(functions)
+    }
+
+    /// Parses function calls from TypeScript source code
+    ///
+    /// # Arguments
+    /// * `content` - The TypeScript source code as a string
+    /// * `file_path` - Path to the source file (for error reporting and context)
+    ///
+    /// # Returns
+    /// * `Result<Vec<CallReference>>` - List of extracted function call references or an error
+    fn parse_calls(&mut self, content: &str, file_path: &str) -> Result<Vec<CallReference>> {
+        if content.is_empty() {
+            tracing::debug!("Empty file content for '{}'", file_path);
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "code for function calls")?;
+
+        tracing::debug!(
+            "Parsing function calls from TypeScript file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut calls = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if node.kind() != "call_expression" {
+                return;
+            }
+
+            let Some((name, full_path)) = node
+                .child_by_field_name("function")
+                .and_then(|function| self.extract_call_name(function, content))
+            else {
+                return;
+            };
+
+            calls.push(
+                CallReference::with_details(
+                    name,
+                    full_path,
+                    Some(node_to_location(node)),
+                    Some(file_path.to_string()),
+                    Vec::new(),
+                )
+                .with_caller(enclosing_function_name(node, |n| {
+                    self.extract_function_details(n, content, file_path)
+                        .map(|func| func.name)
+                })),
+            );
+        });
+
+        Ok(calls)
+    }
+
+    /// Parses TypeScript classes, interfaces, type aliases and enums
+    ///
+    /// # Arguments
+    /// * `content` - The TypeScript source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<Vec<TypeDefinition>>` - List of extracted type definitions or an error
+    fn parse_types(&mut self, content: &str, file_path: &str) -> Result<Vec<TypeDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "types")?;
+
+        tracing::debug!(
+            "Parsing types from TypeScript file '{}' ({} bytes)",
+            file_path,
+            content.len()
+        );
+
+        let mut types = Vec::new();
+        traverse_node(tree.root_node(), &mut |node| {
+            if let Some(type_def) = self.extract_type_details(node, content, file_path) {
+                types.push(type_def);
+            }
+        });
+
+        Ok(types)
+    }
+
+    /// Parses the imports and exports of a TypeScript module
+    ///
+    /// # Arguments
+    /// * `content` - The TypeScript source code as a string
+    /// * `file_path` - Path to the source file
+    ///
+    /// # Returns
+    /// * `Result<ModuleDefinition>` - Module definition or an error
+    fn parse_modules(&mut self, content: &str, file_path: &str) -> Result<ModuleDefinition> {
+        let mut module_def = ModuleDefinition {
+            name: Path::new(file_path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: file_path.to_string(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+            documentation: None,
+            submodules: Vec::new(),
+            bindings: Vec::new(),
+        };
+
+        if content.is_empty() {
+            tracing::debug!(
+                "Empty TypeScript file content for module parsing: '{}'",
+                file_path
+            );
+            return Ok(module_def);
+        }
+
+        let tree = self.parse_tree(content, file_path, "module")?;
+        let root_node = tree.root_node();
+
+        let mut cursor = root_node.walk();
+        for node in root_node.named_children(&mut cursor) {
+            match node.kind() {
+                "import_statement" => module_def
+                    .imports
+                    .extend(self.extract_import(node, content)),
+                "export_statement" => {
+                    module_def
+                        .imports
+                        .extend(self.extract_import(node, content));
+                    module_def
+                        .exports
+                        .extend(self.extract_exports(node, content));
+                }
+                _ => {}
+            }
+        }
+
+        tracing::debug!(
+            "Parsed TypeScript module '{}' with {} imports and {} exports",
+            file_path,
+            module_def.imports.len(),
+            module_def.exports.len()
+        );
+
+        Ok(module_def)
+    }
+
+    fn parse_variables(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableDefinition>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variables")?;
+        Ok(super::javascript::top_level_variables(
+            tree.root_node(),
+            content,
+            file_path,
+        ))
+    }
+
+    fn parse_variable_references(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> Result<Vec<VariableReference>> {
+        if content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = self.parse_tree(content, file_path, "variable references")?;
+        Ok(variable_references(
+            tree.root_node(),
+            content,
+            &["identifier", "shorthand_property_identifier"],
+            |n| {
+                self.extract_function_details(n, content, file_path)
+                    .map(|func| func.name)
+            },
+        ))
+    }
+
+    fn clone_box(&self) -> Box<dyn LanguageParser + Send> {
+        Box::new(TypeScriptParser::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_function_declaration() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            export function hello(name: string, greeting = "Hello"): void {
+                console.log(`${greeting}, ${name}!`);
+            }
+
+            function internal(count?: number): Promise<User[]> {}
+        "#};
+
+        let functions = parser.parse_functions(content, "test.ts")?;
+        assert_eq!(functions.len(), 2);
+
+        let func = &functions[0];
+        assert_eq!(func.name, "hello");
+        assert_eq!(func.kind, FunctionKind::Function);
+        assert_eq!(func.visibility, Visibility::Public);
+        assert_eq!(func.parameters.len(), 2);
+        assert_eq!(func.parameters[0].name, "name");
+        assert_eq!(
+            func.parameters[0].type_annotation.as_deref(),
+            Some("string")
+        );
+        assert_eq!(
+            func.parameters[1].default_value.as_deref(),
+            Some("\"Hello\"")
+        );
+
+        assert_eq!(func.return_type, None);
+
+        let internal = &functions[1];
+        assert_eq!(internal.visibility, Visibility::Default);
+        assert_eq!(internal.return_type.as_deref(), Some("Promise<User[]>"));
+        assert_eq!(internal.parameters[0].name, "count");
+        assert_eq!(
+            internal.parameters[0].type_annotation.as_deref(),
+            Some("number")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typescript_parser_empty_content() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+
+        let functions = parser.parse_functions("", "empty.ts")?;
+        assert!(
+            functions.is_empty(),
+            "Empty content should yield empty functions result"
+        );
+
+        let types = parser.parse_types("", "empty.ts")?;
+        assert!(
+            types.is_empty(),
+            "Empty content should yield empty types result"
+        );
+
+        let calls = parser.parse_calls("", "empty.ts")?;
+        assert!(
+            calls.is_empty(),
+            "Empty content should yield empty calls result"
+        );
+
+        let module = parser.parse_modules("", "empty.ts")?;
+        assert!(module.imports.is_empty() && module.exports.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typescript_parser_invalid_content() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let invalid_code = "This isn't even valid TypeScript syntax @#$%^&*()";
+
+        // Tree-sitter recovers from errors, so this must not fail or crash
+        parser.parse_functions(invalid_code, "invalid.ts")?;
+        parser.parse_types(invalid_code, "invalid.ts")?;
+        parser.parse_calls(invalid_code, "invalid.ts")?;
+        parser.parse_modules(invalid_code, "invalid.ts")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_class_method() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            class Example {
+                constructor(private name: string) {}
+
+                greet(): void {
+                    console.log(`Hello, ${this.name}!`);
+                }
+
+                protected reset() {}
+
+                #privateMethod() {
+                    return 'private';
+                }
+            }
+        "#};
+
+        let functions = parser.parse_functions(content, "test.ts")?;
+        assert_eq!(functions.len(), 4);
+
+        let constructor = &functions[0];
+        assert_eq!(constructor.name, "constructor");
+        assert_eq!(constructor.kind, FunctionKind::Constructor);
+
+        let method = &functions[1];
+        assert_eq!(method.name, "greet");
+        assert_eq!(method.kind, FunctionKind::Method);
+        assert_eq!(method.containing_type.as_deref(), Some("Example"));
+        assert_eq!(method.visibility, Visibility::Public);
+
+        assert_eq!(functions[2].visibility, Visibility::Protected);
+
+        let private_method = &functions[3];
+        assert_eq!(private_method.name, "#privateMethod");
+        assert_eq!(private_method.visibility, Visibility::Private);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_arrow_function() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            const greet = (name: string): string => `Hello, ${name}!`;
+            const double = x => x * 2;
+        "#};
+
+        let functions = parser.parse_functions(content, "test.ts")?;
+        assert_eq!(functions.len(), 2);
+
+        assert_eq!(functions[0].kind, FunctionKind::Lambda);
+        assert_eq!(functions[0].name, "arrow_greet");
+        assert_eq!(
+            functions[0].parameters[0].type_annotation.as_deref(),
+            Some("string")
+        );
+        assert_eq!(functions[1].name, "arrow_double");
+        assert_eq!(functions[1].parameters[0].name, "x");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_calls_caller() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            init();
+
+            class Service {
+                start(): void {
+                    this.helper();
+                }
+            }
+
+            const handler = (): void => {
+                process();
+            };
+        "#};
+
+        let calls = parser.parse_calls(content, "test.ts")?;
+        assert_eq!(calls.len(), 3);
+
+        assert_eq!(calls[0].callee_name, "init");
+        assert_eq!(calls[0].caller_name, None);
+
+        assert_eq!(calls[1].callee_name, "helper");
+        assert_eq!(
+            calls[1].fully_qualified_name.as_deref(),
+            Some("this.helper")
+        );
+        assert_eq!(calls[1].caller_name.as_deref(), Some("start"));
+
+        assert_eq!(calls[2].callee_name, "process");
+        assert_eq!(calls[2].caller_name.as_deref(), Some("arrow_handler"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_class_type() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            export class Repository<T extends Entity, K = string> extends Base implements Store<T>, Disposable {
+                private readonly items: Map<K, T> = new Map();
+                static instances?: number;
+
+                constructor(public name: string, db: Database) {
+                    super();
+                }
+
+                find(id: K): T | undefined {
+                    return this.items.get(id);
+                }
+            }
+
+            abstract class Shape {
+                abstract area(): number;
+            }
+        "#};
+
+        let types = parser.parse_types(content, "repository.ts")?;
+        assert_eq!(types.len(), 2);
+
+        let repo = &types[0];
+        assert_eq!(repo.name, "Repository");
+        assert_eq!(repo.kind, TypeKind::Class);
+        assert_eq!(repo.visibility, Visibility::Public);
+        assert_eq!(repo.super_types, vec!["Base", "Store<T>", "Disposable"]);
+        assert_eq!(repo.methods, vec!["constructor", "find"]);
+
+        assert_eq!(repo.generic_params.len(), 2);
+        assert_eq!(repo.generic_params[0].name, "T");
+        assert_eq!(repo.generic_params[0].bounds, vec!["Entity"]);
+        assert_eq!(
+            repo.generic_params[1].default_type.as_deref(),
+            Some("string")
+        );
+
+        let field_names: Vec<&str> = repo.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(field_names, vec!["items", "instances", "name"]);
+        assert_eq!(repo.fields[0].visibility, Visibility::Private);
+        assert_eq!(repo.fields[0].type_annotation.as_deref(), Some("Map<K, T>"));
+        assert!(repo.fields[1].is_static && repo.fields[1].is_optional);
+        assert_eq!(repo.fields[2].visibility, Visibility::Public);
+
+        let shape = &types[1];
+        assert_eq!(shape.kind, TypeKind::Class);
+        assert_eq!(shape.visibility, Visibility::Default);
+        assert_eq!(shape.methods, vec!["area"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_interfaces_aliases_and_enums() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            export interface Shape<T> extends Named, Sized {
+                readonly id?: T;
+                area(): number;
+            }
+
+            type Id = string | number;
+
+            export enum Color { Red, Green = "green" }
+        "#};
+
+        let types = parser.parse_types(content, "shapes.ts")?;
+        assert_eq!(types.len(), 3);
+
+        let shape = &types[0];
+        assert_eq!(shape.kind, TypeKind::Interface);
+        assert_eq!(shape.super_types, vec!["Named", "Sized"]);
+        assert_eq!(shape.methods, vec!["area"]);
+        assert_eq!(shape.fields[0].name, "id");
+        assert!(shape.fields[0].is_optional);
+        assert_eq!(shape.generic_params[0].name, "T");
+
+        let alias = &types[1];
+        assert_eq!(alias.name, "Id");
+        assert_eq!(alias.kind, TypeKind::TypeAlias);
+        assert_eq!(alias.visibility, Visibility::Default);
+
+        let color = &types[2];
+        assert_eq!(color.kind, TypeKind::Enum);
+        let members: Vec<&str> = color.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(members, vec!["Red", "Green"]);
+        assert_eq!(color.fields[1].default_value.as_deref(), Some("\"green\""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_imports_and_exports() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            import React, { useState as useLocalState, Component } from 'react';
+            import * as utils from "./utils";
+            import type { Config } from '../config';
+            import './styles.css';
+
+            export { helper, format as formatValue } from './format';
+            export * from './types';
+
+            export const VERSION = "1.0", NAME = "app";
+            export function render() {}
+            export default class App {}
+            export { React };
+        "#};
+
+        let module = parser.parse_modules(content, "src/app.ts")?;
+
+        let imports: Vec<(&str, Vec<&str>, bool)> = module
+            .imports
+            .iter()
+            .map(|import| {
+                (
+                    import.module_name.as_str(),
+                    import.imported_symbols.iter().map(String::as_str).collect(),
+                    import.is_relative,
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("react", vec!["React", "useState", "Component"], false),
+                ("./utils", vec!["*"], true),
+                ("../config", vec!["Config"], true),
+                ("./styles.css", vec![], true),
+                ("./format", vec!["helper", "format"], true),
+                ("./types", vec!["*"], true),
+            ]
+        );
+
+        assert_eq!(
+            module.exports,
+            vec![
+                "helper",
+                "formatValue",
+                "VERSION",
+                "NAME",
+                "render",
+                "App",
+                "React"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_variables() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            export const TIMEOUT_MS: number = 5000;
+            let current: Session | null = null;
+            const log = (msg: string) => console.log(msg);
+
+            function connect(): void {
+                current = open(TIMEOUT_MS);
+            }
+        "#};
+
+        let variables = parser.parse_variables(content, "session.ts")?;
+        let summary: Vec<(&str, Option<&str>, Option<&str>, bool)> = variables
+            .iter()
+            .map(|v| {
+                (
+                    v.name.as_str(),
+                    v.type_annotation.as_deref(),
+                    v.initializer.as_deref(),
+                    v.is_const,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("TIMEOUT_MS", Some("number"), Some("5000"), true),
+                ("current", Some("Session | null"), Some("null"), false),
+            ]
+        );
+
+        let references = parser.parse_variable_references(content, "session.ts")?;
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+        assert!(find("current").is_write);
+        assert!(!find("TIMEOUT_MS").is_write);
+        assert_eq!(find("TIMEOUT_MS").caller_name, "connect");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_tsx() -> Result<()> {
+        let mut parser = TypeScriptParser::new();
+        let content = indoc! {r#"
+            interface Props { title: string }
+
+            export const Header = ({ title }: Props) => {
+                const label = format<string>(title);
+                return <h1 className="header">{label}</h1>;
+            };
+        "#};
+
+        let types = parser.parse_types(content, "header.tsx")?;
+        assert_eq!(types.len(), 1);
+        assert_eq!(types[0].name, "Props");
+
+        let functions = parser.parse_functions(content, "header.tsx")?;
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "arrow_Header");
+
+        let calls = parser.parse_calls(content, "header.tsx")?;
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].callee_name, "format");
+        assert_eq!(calls[0].caller_name.as_deref(), Some("arrow_Header"));
+
+        Ok(())
+    }
+}
@@ -1,2 +1,10 @@
+pub mod call_resolver;
 pub mod domain_model;
+pub mod header_merge;
+pub mod implicit_interfaces;
+pub mod js_modules;
 pub mod language_support;
+pub mod pipeline;
+pub mod python_modules;
+pub mod rust_modules;
+pub mod type_usage;
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use rayon::prelude::*;
+use std::path::Path;
+
+use crate::parser::language_support::{
+    is_supported_source_file, with_thread_parser, CallReference, DomainConcept, FunctionDefinition,
+    ImplementationDefinition, LanguageParser, ModuleDefinition, TypeDefinition, VariableDefinition,
+    VariableReference,
+};
+
+/// A function definition together with its extracted documentation
+#[derive(Debug, Clone)]
+pub struct ParsedFunction {
+    pub definition: FunctionDefinition,
+    pub documentation: Option<String>,
+}
+
+/// Everything the indexer needs from a single source file
+#[derive(Debug, Clone)]
+pub struct ParsedFile {
+    pub path: String,
+    pub module: ModuleDefinition,
+    pub functions: Vec<ParsedFunction>,
+    pub types: Vec<TypeDefinition>,
+    pub calls: Vec<CallReference>,
+    pub implementations: Vec<ImplementationDefinition>,
+    pub variables: Vec<VariableDefinition>,
+    pub variable_references: Vec<VariableReference>,
+    pub domain_concepts: Vec<DomainConcept>,
+}
+
+/// Parse a single source file with the current thread's parser
+///
+/// # Returns
+/// * `Result<Option<ParsedFile>>` - `None` if the path isn't a supported source file
+pub fn parse_file(file_path: &str) -> Result<Option<ParsedFile>> {
+    let path = Path::new(file_path);
+
+    if !path.is_file() || !is_supported_source_file(path) {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    with_thread_parser(path, |parser| parse_content(parser, &content, file_path)).transpose()
+}
+
+fn parse_content(
+    parser: &mut dyn LanguageParser,
+    content: &str,
+    file_path: &str,
+) -> Result<ParsedFile> {
+    let module = parser.parse_modules(content, file_path)?;
+
+    let functions = parser
+        .parse_functions(content, file_path)?
+        .into_iter()
+        .map(|definition| {
+            let documentation = parser.extract_documentation(content, &definition.location)?;
+            Ok(ParsedFunction {
+                definition,
+                documentation,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let variables = parser
+        .parse_variables(content, file_path)?
+        .into_iter()
+        .map(|mut variable| {
+            if variable.documentation.is_none() {
+                variable.documentation =
+                    parser.extract_documentation(content, &variable.location)?;
+            }
+            Ok(variable)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(ParsedFile {
+        path: file_path.to_string(),
+        module,
+        functions,
+        types: parser.parse_types(content, file_path)?,
+        calls: parser.parse_calls(content, file_path)?,
+        implementations: parser.parse_implementations(content, file_path)?,
+        variables,
+        variable_references: parser.parse_variable_references(content, file_path)?,
+        domain_concepts: parser.infer_domain_concepts(content, file_path)?,
+    })
+}
+
+/// Parse source files on a pool of `jobs` worker threads
+///
+/// Results come back in the order of `file_paths`, whatever the number of
+/// threads, so merging them into the graph is deterministic. Files that no
+/// parser handles are skipped.
+///
+/// # Arguments
+/// * `file_paths` - Files to parse
+/// * `jobs` - Number of worker threads, or 0 for one per available CPU
+pub fn parse_files(file_paths: &[String], jobs: usize) -> Result<Vec<ParsedFile>> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+
+    let parsed = pool.install(|| {
+        file_paths
+            .par_iter()
+            .map(|file_path| parse_file(file_path))
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    Ok(parsed.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_files_is_deterministic() {
+        let dir = tempdir().unwrap();
+        let mut files = Vec::new();
+        for i in 0..24 {
+            let (name, content) = match i % 3 {
+                0 => (
+                    format!("file_{:02}.rs", i),
+                    format!("fn rust_{i}() {{ helper(); }}\nstruct Type{i} {{ x: i32 }}\n"),
+                ),
+                1 => (
+                    format!("file_{:02}.py", i),
+                    format!("def python_{i}():\n    helper()\n"),
+                ),
+                _ => (
+                    format!("file_{:02}.js", i),
+                    format!("function js_{i}() {{ helper(); }}\n"),
+                ),
+            };
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            files.push(path.to_string_lossy().to_string());
+        }
+        files.push(dir.path().join("notes.txt").to_string_lossy().to_string());
+
+        let summarize = |parsed: Vec<ParsedFile>| -> Vec<(String, Vec<String>, usize)> {
+            parsed
+                .into_iter()
+                .map(|file| {
+                    let names = file
+                        .functions
+                        .iter()
+                        .map(|f| f.definition.name.clone())
+                        .collect();
+                    (file.path, names, file.calls.len())
+                })
+                .collect()
+        };
+
+        let serial = summarize(parse_files(&files, 1).unwrap());
+        let parallel = summarize(parse_files(&files, 4).unwrap());
+
+        assert_eq!(serial.len(), 24);
+        assert_eq!(serial, parallel);
+        assert_eq!(serial[0].1, vec!["rust_0"]);
+        assert_eq!(serial[0].2, 1);
+        assert!(serial
+            .iter()
+            .map(|(path, _, _)| path)
+            .eq(files.iter().take(24)));
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::Path;
+
+/// File marking a directory as a Python package
+const PACKAGE_INIT: &str = "__init__.py";
+
+/// The dotted name of a Python module in a package, derived from the
+/// `__init__.py` files of the directories above it
+///
+/// `app/models.py` is `app.models` when `app/__init__.py` exists, and
+/// `app/__init__.py` is the package `app` itself. Modules outside any package,
+/// such as scripts, have no name.
+pub fn module_name(file_path: &str) -> Option<String> {
+    let path = Path::new(file_path);
+    let dir = path.parent()?;
+    if !is_package(dir) {
+        return None;
+    }
+
+    let mut segments: Vec<String> = dir
+        .ancestors()
+        .take_while(|dir| is_package(dir))
+        .filter_map(|dir| dir.file_name()?.to_str().map(String::from))
+        .collect();
+    segments.reverse();
+    if path.file_name()? != PACKAGE_INIT {
+        segments.push(path.file_stem()?.to_str()?.to_string());
+    }
+    Some(segments.join("."))
+}
+
+/// The ID of a Python file's module entity: its dotted name if it's in a
+/// package, otherwise its path
+pub fn module_id(file_path: &str) -> String {
+    module_name(file_path).unwrap_or_else(|| file_path.to_string())
+}
+
+/// The file defining a module imported by a Python file, if it exists
+///
+/// Relative modules such as `.models` or `..` are found from the importing
+/// file's package. Absolute modules are looked up from the directory holding
+/// the importing file's top-level package, or the file itself when it isn't in
+/// a package; installed packages aren't found.
+pub fn resolve_import(file_path: &str, module: &str) -> Option<String> {
+    let dir = Path::new(file_path).parent()?;
+    let relative = module.trim_start_matches('.');
+    let levels = module.len() - relative.len();
+
+    let base = if levels > 0 {
+        dir.ancestors().nth(levels - 1)?.to_path_buf()
+    } else {
+        dir.ancestors()
+            .find(|dir| !is_package(dir))
+            .unwrap_or(dir)
+            .to_path_buf()
+    };
+    let path = relative
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+        .fold(base, |path, segment| path.join(segment));
+
+    module_file(&path)
+}
+
+/// The file defining a submodule of a package, for names imported from one,
+/// as in `from app import models`
+pub fn submodule_file(package_file: &str, name: &str) -> Option<String> {
+    let path = Path::new(package_file);
+    if path.file_name()? != PACKAGE_INIT {
+        return None;
+    }
+    module_file(&path.parent()?.join(name))
+}
+
+/// The file defining the module at a path without extension: `path.py` or
+/// `path/__init__.py`
+fn module_file(path: &Path) -> Option<String> {
+    [path.with_extension("py"), path.join(PACKAGE_INIT)]
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+        .map(|candidate| candidate.to_string_lossy().to_string())
+}
+
+fn is_package(dir: &Path) -> bool {
+    dir.join(PACKAGE_INIT).is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_module_names_and_imports() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "app/__init__.py",
+            "app/models.py",
+            "app/api/__init__.py",
+            "app/api/views.py",
+            "scripts/run.py",
+            "scripts/helpers.py",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let path = |file: &str| root.join(file).to_string_lossy().to_string();
+
+        assert_eq!(
+            module_name(&path("app/__init__.py")).as_deref(),
+            Some("app")
+        );
+        assert_eq!(
+            module_name(&path("app/api/views.py")).as_deref(),
+            Some("app.api.views")
+        );
+        assert_eq!(module_name(&path("scripts/run.py")), None);
+        assert_eq!(module_id(&path("scripts/run.py")), path("scripts/run.py"));
+
+        let views = path("app/api/views.py");
+        assert_eq!(
+            resolve_import(&views, "app.models"),
+            Some(path("app/models.py"))
+        );
+        assert_eq!(
+            resolve_import(&views, "..models"),
+            Some(path("app/models.py"))
+        );
+        assert_eq!(
+            resolve_import(&views, "."),
+            Some(path("app/api/__init__.py"))
+        );
+        assert_eq!(resolve_import(&views, "app"), Some(path("app/__init__.py")));
+        assert_eq!(resolve_import(&views, "os.path"), None);
+        assert_eq!(
+            resolve_import(&path("scripts/run.py"), "helpers"),
+            Some(path("scripts/helpers.py"))
+        );
+
+        assert_eq!(
+            submodule_file(&path("app/__init__.py"), "models"),
+            Some(path("app/models.py"))
+        );
+        assert_eq!(submodule_file(&path("app/models.py"), "User"), None);
+    }
+}
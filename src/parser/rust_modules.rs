@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::parser::language_support::{
+    language_for_file, with_thread_parser, ModuleDefinition, SubmoduleDefinition,
+};
+use crate::parser::pipeline::ParsedFile;
+
+/// Crate name used when a crate has no `Cargo.toml`
+const ANONYMOUS_CRATE: &str = "crate";
+
+/// Where Rust source files sit in the module trees of their crates
+///
+/// A crate's tree is walked from its root, `src/lib.rs`, `src/main.rs` or a
+/// binary in `src/bin`, following `mod` declarations to the files defining
+/// them. Modules are named by their full path starting with the crate's name,
+/// such as `ummon::graph::entity`. A package's `src/main.rs` is named
+/// `{package}_main` when the package also has a library of the same name.
+/// Files no crate reaches have no module path.
+#[derive(Debug, Default)]
+pub struct RustModules {
+    /// Module path of each file in a crate's module tree
+    files: HashMap<String, String>,
+}
+
+impl RustModules {
+    /// Walk the module trees of the crates the parsed Rust files belong to
+    ///
+    /// Files of those crates that weren't parsed, as happens during an
+    /// incremental update, are read from disk for their `mod` declarations.
+    pub fn build(parsed_files: &[ParsedFile]) -> Self {
+        let parsed_modules: HashMap<&str, &ModuleDefinition> = parsed_files
+            .iter()
+            .map(|parsed| (parsed.path.as_str(), &parsed.module))
+            .collect();
+
+        let mut modules = Self::default();
+        let mut walked_roots = HashSet::new();
+        for parsed in parsed_files {
+            if language_for_file(Path::new(&parsed.path)) != Some("rust")
+                || modules.files.contains_key(&parsed.path)
+            {
+                continue;
+            }
+
+            for (root, crate_name) in crate_roots(Path::new(&parsed.path)) {
+                if walked_roots.insert(root.clone()) {
+                    modules.walk(&root, crate_name, true, &parsed_modules);
+                }
+            }
+        }
+
+        modules
+    }
+
+    /// The module path of a file, e.g. `ummon::graph::entity` for `src/graph/entity.rs`
+    pub fn module_path(&self, file_path: &str) -> Option<&str> {
+        self.files.get(file_path).map(String::as_str)
+    }
+
+    /// Record a module file and walk the modules it declares
+    ///
+    /// `owns_directory` is true for crate roots and `mod.rs` files, whose
+    /// submodules are files next to them rather than in a directory named
+    /// after them.
+    fn walk(
+        &mut self,
+        file: &Path,
+        module_path: String,
+        owns_directory: bool,
+        parsed: &HashMap<&str, &ModuleDefinition>,
+    ) {
+        let key = file.to_string_lossy().to_string();
+        if self.files.contains_key(&key) {
+            return;
+        }
+        self.files.insert(key.clone(), module_path.clone());
+
+        let submodules = match parsed.get(key.as_str()) {
+            Some(module) => module.submodules.clone(),
+            None => read_submodules(file),
+        };
+
+        let Some(parent) = file.parent() else {
+            return;
+        };
+        let child_dir = match file.file_stem() {
+            Some(stem) if !owns_directory => parent.join(stem),
+            _ => parent.to_path_buf(),
+        };
+
+        for submodule in submodules {
+            if submodule.is_inline {
+                continue;
+            }
+
+            // `mod a { mod b; }` is defined in `a/b.rs` or `a/b/mod.rs`
+            let relative: PathBuf = submodule.name.split("::").collect();
+            let candidates = [
+                (child_dir.join(relative.with_extension("rs")), false),
+                (child_dir.join(&relative).join("mod.rs"), true),
+            ];
+            if let Some((child, owns_directory)) =
+                candidates.into_iter().find(|(path, _)| path.is_file())
+            {
+                let path = format!("{}::{}", module_path, submodule.name);
+                self.walk(&child, path, owns_directory, parsed);
+            }
+        }
+    }
+}
+
+/// Read the `mod` declarations of a file that wasn't parsed
+fn read_submodules(file: &Path) -> Vec<SubmoduleDefinition> {
+    let file_path = file.to_string_lossy();
+    let submodules = std::fs::read_to_string(file)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| {
+            with_thread_parser(file, |parser| parser.parse_modules(&content, &file_path))
+                .transpose()
+        });
+
+    match submodules {
+        Ok(module) => module.map(|module| module.submodules).unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("Failed to read modules of {}: {}", file_path, e);
+            Vec::new()
+        }
+    }
+}
+
+/// The roots of the crates a Rust file may belong to, with the crates' names
+///
+/// The crates are those of the package whose `Cargo.toml` is nearest to the
+/// file. Without one, `lib.rs` and `main.rs` in the nearest directory having
+/// either are roots of an anonymous crate.
+fn crate_roots(file: &Path) -> Vec<(PathBuf, String)> {
+    let Some(package_dir) = file
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").is_file())
+    else {
+        return file
+            .ancestors()
+            .skip(1)
+            .map(|dir| {
+                ["lib.rs", "main.rs"]
+                    .iter()
+                    .map(|root| (dir.join(root), ANONYMOUS_CRATE.to_string()))
+                    .filter(|(root, _)| root.is_file())
+                    .collect::<Vec<_>>()
+            })
+            .find(|roots| !roots.is_empty())
+            .unwrap_or_default();
+    };
+
+    let Some((package_name, lib_name)) = read_manifest(&package_dir.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let src = package_dir.join("src");
+    let lib_name = lib_name.unwrap_or(package_name.clone());
+
+    // A binary named like the package's library would share its module paths
+    let bin_name = if lib_name == package_name && src.join("lib.rs").is_file() {
+        format!("{}_main", package_name)
+    } else {
+        package_name
+    };
+    let mut roots = vec![
+        (src.join("lib.rs"), lib_name),
+        (src.join("main.rs"), bin_name),
+    ];
+
+    // Binaries in `src/bin` are named after their file or directory
+    let mut binaries: Vec<(PathBuf, String)> = std::fs::read_dir(src.join("bin"))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.replace('-', "_");
+            if path.is_dir() {
+                Some((path.join("main.rs"), name))
+            } else {
+                (path.extension()? == "rs").then_some((path, name))
+            }
+        })
+        .collect();
+    binaries.sort();
+    roots.extend(binaries);
+
+    roots.retain(|(root, _)| root.is_file());
+    roots
+}
+
+/// The package name and library name, if renamed, from a `Cargo.toml`, as
+/// they're written in paths, with dashes replaced by underscores
+fn read_manifest(manifest: &Path) -> Option<(String, Option<String>)> {
+    let content = std::fs::read_to_string(manifest).ok()?;
+    let manifest: toml::Table = toml::from_str(&content)
+        .map_err(|e| tracing::warn!("Invalid {}: {}", manifest.display(), e))
+        .ok()?;
+
+    let name = |section: &str| {
+        manifest
+            .get(section)?
+            .get("name")?
+            .as_str()
+            .map(|name| name.replace('-', "_"))
+    };
+    Some((name("package")?, name("lib")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::pipeline::parse_files;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write(root: &Path, files: &[(&str, &str)]) {
+        for (file, content) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_module_tree() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            &[
+                ("Cargo.toml", "[package]\nname = \"my-app\"\n"),
+                (
+                    "src/lib.rs",
+                    "pub mod graph;\nmod util;\nmod tests { mod fixtures; }\n",
+                ),
+                ("src/graph/mod.rs", "pub mod entity;\n"),
+                ("src/graph/entity.rs", "pub struct Entity;\n"),
+                ("src/util.rs", "mod fmt;\n"),
+                ("src/util/fmt.rs", "pub fn pretty() {}\n"),
+                ("src/tests/fixtures.rs", ""),
+                ("src/main.rs", "mod cli;\nfn main() {}\n"),
+                ("src/cli.rs", ""),
+                ("src/bin/tool.rs", "fn main() {}\n"),
+                ("src/orphan.rs", ""),
+            ],
+        );
+
+        // Only some files are parsed; the rest are read from disk
+        let path = |file: &str| root.join(file).to_string_lossy().to_string();
+        let parsed = parse_files(
+            &[
+                path("src/graph/entity.rs"),
+                path("src/util/fmt.rs"),
+                path("src/bin/tool.rs"),
+                path("src/orphan.rs"),
+            ],
+            1,
+        )
+        .unwrap();
+        let modules = RustModules::build(&parsed);
+
+        let module_of = |file: &str| modules.module_path(&path(file));
+        assert_eq!(module_of("src/lib.rs"), Some("my_app"));
+        assert_eq!(module_of("src/graph/mod.rs"), Some("my_app::graph"));
+        assert_eq!(
+            module_of("src/graph/entity.rs"),
+            Some("my_app::graph::entity")
+        );
+        assert_eq!(module_of("src/util/fmt.rs"), Some("my_app::util::fmt"));
+        assert_eq!(
+            module_of("src/tests/fixtures.rs"),
+            Some("my_app::tests::fixtures")
+        );
+        assert_eq!(module_of("src/main.rs"), Some("my_app_main"));
+        assert_eq!(module_of("src/cli.rs"), Some("my_app_main::cli"));
+        assert_eq!(module_of("src/bin/tool.rs"), Some("tool"));
+        assert_eq!(module_of("src/orphan.rs"), None);
+    }
+
+    #[test]
+    fn test_crate_without_manifest() {
+        let dir = tempdir().unwrap();
+        write(
+            dir.path(),
+            &[("lib.rs", "mod a;\n"), ("a.rs", "fn f() {}\n")],
+        );
+
+        let file = dir.path().join("a.rs").to_string_lossy().to_string();
+        let parsed = parse_files(std::slice::from_ref(&file), 1).unwrap();
+        let modules = RustModules::build(&parsed);
+
+        assert_eq!(modules.module_path(&file), Some("crate::a"));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::parser::language_support::{FieldDefinition, FunctionDefinition};
+
+/// Keywords and modifiers that appear in type annotations without naming a
+/// type, such as Rust's `&mut` and `dyn`, TypeScript's `keyof` and Go's `chan`
+const TYPE_KEYWORDS: &[&str] = &[
+    "as",
+    "asserts",
+    "chan",
+    "class",
+    "const",
+    "dyn",
+    "enum",
+    "extends",
+    "extern",
+    "final",
+    "fn",
+    "for",
+    "func",
+    "impl",
+    "in",
+    "infer",
+    "interface",
+    "is",
+    "keyof",
+    "mut",
+    "out",
+    "params",
+    "readonly",
+    "ref",
+    "signed",
+    "struct",
+    "super",
+    "typename",
+    "typeof",
+    "union",
+    "unique",
+    "unsafe",
+    "unsigned",
+    "volatile",
+    "where",
+];
+
+/// Where a type is named
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TypeRole {
+    /// The type of a function's parameter
+    Param,
+    /// A function's return type
+    Return,
+    /// The type of a field
+    Field,
+}
+
+impl TypeRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TypeRole::Param => "param",
+            TypeRole::Return => "return",
+            TypeRole::Field => "field",
+        }
+    }
+}
+
+/// Type names in a function's parameter and return types, with where each is named
+pub fn signature_types(func: &FunctionDefinition) -> Vec<(TypeRole, &str)> {
+    let params = func
+        .parameters
+        .iter()
+        .filter_map(|param| param.type_annotation.as_deref())
+        .flat_map(type_names)
+        .map(|name| (TypeRole::Param, name));
+    let returns = func
+        .return_type
+        .as_deref()
+        .into_iter()
+        .flat_map(type_names)
+        .map(|name| (TypeRole::Return, name));
+
+    params.chain(returns).collect()
+}
+
+/// Type names in a field's type
+pub fn field_types(field: &FieldDefinition) -> Vec<(TypeRole, &str)> {
+    field
+        .type_annotation
+        .as_deref()
+        .into_iter()
+        .flat_map(type_names)
+        .map(|name| (TypeRole::Field, name))
+        .collect()
+}
+
+/// The type names in a type annotation, including its generic arguments
+///
+/// `Vec<User>` names `Vec` and `User`, `Optional["User"]` names `Optional`
+/// and `User`, and `map[string]*models.User` names `map`, `string` and
+/// `models.User`. Paths keep their `::`, `.` or `\` separators, and each name
+/// is listed once, in order. Rust lifetimes, keywords such as `mut` or `dyn`,
+/// and the names bound in `Iterator<Item = User>` are skipped.
+pub fn type_names(annotation: &str) -> Vec<&str> {
+    let is_name_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '\\');
+
+    let mut names: Vec<&str> = Vec::new();
+    let mut rest = annotation;
+    while let Some(start) = rest.find(is_name_char) {
+        let token = &rest[start..];
+        let end = token.find(|c| !is_name_char(c)).unwrap_or(token.len());
+        let is_lifetime = rest[..start].ends_with('\'');
+        let name = token[..end].trim_matches(['.', ':', '\\']);
+        rest = &token[end..];
+
+        let rest_trimmed = rest.trim_start();
+        let is_binding = rest_trimmed.starts_with('=')
+            && !rest_trimmed.starts_with("==")
+            && !rest_trimmed.starts_with("=>");
+
+        if is_lifetime
+            || is_binding
+            || name.is_empty()
+            || name.starts_with(|c: char| c.is_ascii_digit())
+            || TYPE_KEYWORDS.contains(&name)
+        {
+            continue;
+        }
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_names() {
+        assert_eq!(type_names("Vec<User>"), ["Vec", "User"]);
+        assert_eq!(type_names("Optional[\"User\"]"), ["Optional", "User"]);
+        assert_eq!(
+            type_names("HashMap<String, crate::db::Order>"),
+            ["HashMap", "String", "crate::db::Order"]
+        );
+        assert_eq!(type_names("&'a mut User"), ["User"]);
+        assert_eq!(
+            type_names("impl Iterator<Item = User>"),
+            ["Iterator", "User"]
+        );
+        assert_eq!(
+            type_names("Box<dyn Fn(Order) -> bool>"),
+            ["Box", "Fn", "Order", "bool"]
+        );
+        assert_eq!(type_names("readonly User[]"), ["User"]);
+        assert_eq!(type_names("chan<- *Event"), ["Event"]);
+        assert_eq!(
+            type_names("map[string]*models.User"),
+            ["map", "string", "models.User"]
+        );
+        assert_eq!(type_names("?\\App\\Models\\User"), ["App\\Models\\User"]);
+        assert_eq!(
+            type_names("Promise<User | null>[]"),
+            ["Promise", "User", "null"]
+        );
+        assert_eq!(type_names("[u8; 32]"), ["u8"]);
+        assert_eq!(type_names("...Order"), ["Order"]);
+        assert!(type_names("()").is_empty());
+    }
+}
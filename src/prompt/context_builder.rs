@@ -9,13 +9,14 @@ pub fn build_context(db: &Database, instruction: &str) -> String {
     context.push_str("\n\nKnown Functions:\n");
 
     // Get function entities directly from the database
-    let function_entities = match db.query_entities_by_type(&EntityType::Function, None, vec![]) {
-        Ok(entities) => entities,
-        Err(e) => {
-            tracing::error!("Failed to query functions from database: {}", e);
-            vec![] // Return empty list on error
-        }
-    };
+    let function_entities =
+        match db.query_entities_by_type(&EntityType::Function, None, vec![], None) {
+            Ok(entities) => entities,
+            Err(e) => {
+                tracing::error!("Failed to query functions from database: {}", e);
+                vec![] // Return empty list on error
+            }
+        };
 
     for func in function_entities {
         context.push_str("- ");
@@ -28,7 +29,7 @@ pub fn build_context(db: &Database, instruction: &str) -> String {
     }
 
     // Also include method entities
-    let method_entities = match db.query_entities_by_type(&EntityType::Method, None, vec![]) {
+    let method_entities = match db.query_entities_by_type(&EntityType::Method, None, vec![], None) {
         Ok(entities) => entities,
         Err(e) => {
             tracing::error!("Failed to query methods from database: {}", e);
@@ -1,7 +1,5 @@
 use anyhow::Result;
 use std::collections::HashMap;
-use std::env;
-use std::str::FromStr;
 
 use crate::parser::domain_model::{
     AttributeType, DomainEntity, DomainModelBuilder, EntityType, RelationType, Relationship,
@@ -13,45 +11,15 @@ pub struct LlmModelExtractor {
     pub config: LlmConfig,
 }
 
-impl Default for LlmModelExtractor {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 impl LlmModelExtractor {
-    pub fn new() -> Self {
-        // Create default config
-        let mut config = LlmConfig::default();
-
-        // Try to determine the provider and API key from environment variables
-        if let Ok(provider_str) = env::var("LLM_PROVIDER") {
-            if let Ok(provider) = LlmProvider::from_str(&provider_str) {
-                config.provider = provider;
-                tracing::info!("Using LLM provider: {:?}", provider);
-            } else {
-                tracing::warn!("Unknown LLM provider '{}', using default", provider_str);
-            }
-        }
-
-        // Check for API keys based on the provider
-        let api_key = match config.provider {
-            LlmProvider::OpenRouter => env::var("OPENROUTER_API_KEY").ok(),
-            LlmProvider::OpenAI => env::var("OPENAI_API_KEY").ok(),
-            LlmProvider::Anthropic => env::var("ANTHROPIC_API_KEY").ok(),
-            LlmProvider::GoogleVertexAI => env::var("GOOGLE_API_KEY").ok(),
-            LlmProvider::Ollama => Some(String::new()), // Ollama doesn't need an API key
-        };
-
-        // Set the API key if found
-        if let Some(key) = api_key {
-            if !key.is_empty() || config.provider == LlmProvider::Ollama {
-                tracing::info!(
-                    "Found API key for {:?}, LLM domain extraction enabled",
-                    config.provider
-                );
-                config.api_key = key;
-            }
+    pub fn new(config: LlmConfig) -> Self {
+        let has_api_key = !config.api_key.is_empty() || config.provider == LlmProvider::Ollama;
+        if has_api_key {
+            tracing::info!(
+                "Using {:?} with model {} for LLM domain extraction",
+                config.provider,
+                config.model
+            );
         } else {
             tracing::warn!(
                 "{:?} API key not set, LLM domain extraction disabled",
@@ -59,22 +27,6 @@ impl LlmModelExtractor {
             );
         }
 
-        // Check for custom endpoint
-        if let Ok(endpoint) = env::var("LLM_ENDPOINT") {
-            if !endpoint.is_empty() {
-                config.endpoint_url = Some(endpoint);
-                tracing::info!("Using custom LLM endpoint");
-            }
-        }
-
-        // Check for model override
-        if let Ok(model) = env::var("LLM_MODEL") {
-            if !model.is_empty() {
-                config.model = model;
-                tracing::info!("Using custom LLM model: {}", config.model);
-            }
-        }
-
         Self { config }
     }
 }
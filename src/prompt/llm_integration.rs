@@ -4,6 +4,8 @@ use serde_json::Value;
 use std::str::FromStr;
 use std::time::Duration;
 
+use crate::config::LlmSettings;
+
 /// Enum representing different LLM API providers
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LlmProvider {
@@ -53,13 +55,21 @@ impl Default for LlmConfig {
     }
 }
 
-/// Get LLM configuration based on CLI arguments and environment variables
-pub fn get_llm_config(cli_provider: Option<&str>, cli_model: Option<&str>) -> LlmConfig {
-    let provider_str = cli_provider
-        .map(|s| s.to_string())
-        .or_else(|| std::env::var("LLM_PROVIDER").ok())
-        .unwrap_or_else(|| "openrouter".to_string());
-    let provider = LlmProvider::from_str(&provider_str).unwrap_or(LlmProvider::OpenRouter);
+/// Build the LLM configuration for a request from the merged settings
+///
+/// Provider, model, endpoint and sampling settings come from `settings`, which
+/// already combine `ummon.toml`, the environment and command-line flags. API
+/// keys are only ever read from the environment.
+pub fn get_llm_config(settings: &LlmSettings) -> LlmConfig {
+    let defaults = LlmConfig::default();
+
+    let provider = match settings.provider.as_deref() {
+        Some(name) => LlmProvider::from_str(name).unwrap_or_else(|e| {
+            tracing::warn!("{}, using OpenRouter", e);
+            LlmProvider::OpenRouter
+        }),
+        None => LlmProvider::OpenRouter,
+    };
 
     let api_key = match provider {
         LlmProvider::OpenRouter => std::env::var("OPENROUTER_API_KEY").unwrap_or_default(),
@@ -69,26 +79,21 @@ pub fn get_llm_config(cli_provider: Option<&str>, cli_model: Option<&str>) -> Ll
         LlmProvider::Ollama => String::new(), // Ollama doesn't need an API key
     };
 
-    let model = cli_model
-        .map(|s| s.to_string())
-        .or_else(|| std::env::var("LLM_MODEL").ok())
-        .unwrap_or_else(|| match provider {
-            LlmProvider::OpenRouter => "anthropic/claude-3-5-haiku-20241022".to_string(),
-            LlmProvider::OpenAI => "gpt-4-turbo".to_string(),
-            LlmProvider::Anthropic => "claude-3-5-haiku-20241022".to_string(),
-            LlmProvider::GoogleVertexAI => "gemini-1.5-pro".to_string(),
-            LlmProvider::Ollama => "llama3".to_string(),
-        });
-
-    let endpoint_url = std::env::var("LLM_ENDPOINT").ok();
+    let model = settings.model.clone().unwrap_or_else(|| match provider {
+        LlmProvider::OpenRouter => "anthropic/claude-3-5-haiku-20241022".to_string(),
+        LlmProvider::OpenAI => "gpt-4-turbo".to_string(),
+        LlmProvider::Anthropic => "claude-3-5-haiku-20241022".to_string(),
+        LlmProvider::GoogleVertexAI => "gemini-1.5-pro".to_string(),
+        LlmProvider::Ollama => "llama3".to_string(),
+    });
 
     LlmConfig {
         provider,
         api_key,
         model,
-        temperature: 0.2,
-        max_tokens: 1500,
-        endpoint_url,
+        temperature: settings.temperature.unwrap_or(defaults.temperature),
+        max_tokens: settings.max_tokens.unwrap_or(defaults.max_tokens),
+        endpoint_url: settings.endpoint.clone(),
     }
 }
 
@@ -377,6 +382,26 @@ mod tests {
         assert!(LlmProvider::from_str("unknown_provider").is_err());
     }
 
+    #[test]
+    fn test_get_llm_config_from_settings() {
+        let settings = LlmSettings {
+            provider: Some("ollama".to_string()),
+            endpoint: Some("http://localhost:11434".to_string()),
+            temperature: Some(0.7),
+            ..Default::default()
+        };
+
+        let config = get_llm_config(&settings);
+        assert_eq!(config.provider, LlmProvider::Ollama);
+        assert_eq!(config.model, "llama3");
+        assert_eq!(config.temperature, 0.7);
+        assert_eq!(config.max_tokens, 1500);
+        assert_eq!(
+            config.endpoint_url.as_deref(),
+            Some("http://localhost:11434")
+        );
+    }
+
     #[test]
     fn test_llm_config_default() {
         let config = LlmConfig::default();
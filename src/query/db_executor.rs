@@ -1,148 +1,263 @@
 use anyhow::{anyhow, Result};
 use rusqlite::types::ToSql;
 
-use crate::db::Database;
-use crate::graph::entity::{Entity, EntityId};
+use crate::db::{direction_join_condition, Database, Pagination};
+use crate::graph::entity::{Entity, EntityType};
 use crate::graph::relationship::RelationshipType;
 
-use super::parser::{ConditionNode, Operator, QueryType, SelectQuery, TraversalQuery, Value};
+use super::parser::{
+    entity_type_from_name, ConditionNode, Operator, QueryType, SelectQuery, TraversalQuery, Value,
+};
 
 /// List of allowed column names for safe attribute access
 const ALLOWED_COLUMNS: [&str; 4] = ["name", "file_path", "documentation", "id"];
 
+/// Attributes matching the names of an entity's decorators or annotations,
+/// which are kept in the entity's data rather than in a column of their own
+const DECORATOR_ATTRIBUTES: [&str; 2] = ["decorator", "annotation"];
+
+/// Maximum relationship depth followed by traversal queries
+const MAX_TRAVERSAL_DEPTH: usize = 10;
+
 /// SQL query with parameters, used to avoid SQL injection
 pub struct SafeQuery {
     pub sql: String,
     pub params: Vec<Box<dyn ToSql>>,
 }
 
+impl SafeQuery {
+    /// Combine several where clauses with AND, returning None if there are none
+    fn and_all(queries: Vec<SafeQuery>) -> Option<SafeQuery> {
+        queries.into_iter().reduce(|left, right| {
+            let mut params = left.params;
+            params.extend(right.params);
+            SafeQuery {
+                sql: format!("({}) AND ({})", left.sql, right.sql),
+                params,
+            }
+        })
+    }
+}
+
+/// One page of query results
+pub struct QueryPage {
+    pub entities: Vec<Box<dyn Entity>>,
+    /// Number of entities matching the query across all pages
+    pub total: usize,
+    /// Offset of the next page, if more results remain
+    pub next_offset: Option<usize>,
+}
+
+/// Result filters applied on top of a query's own conditions
+#[derive(Debug, Clone, Default)]
+pub struct QueryFilters {
+    /// Only return entities of this type
+    pub entity_type: Option<EntityType>,
+    /// Only return entities whose file path matches this pattern
+    pub path: Option<String>,
+    /// Match names and paths exactly instead of as patterns
+    pub exact: bool,
+}
+
+impl QueryFilters {
+    /// Build filters from command line values, validating the type name
+    pub fn new(type_filter: Option<&str>, path: Option<&str>, exact: bool) -> Result<Self> {
+        let entity_type = match type_filter {
+            Some(name) => Some(
+                entity_type_from_name(name)
+                    .ok_or_else(|| anyhow!("Unknown entity type filter: '{}'", name))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            entity_type,
+            path: path.map(|p| p.to_string()),
+            exact,
+        })
+    }
+
+    /// Convert the filters to a where clause. Paths match as a substring unless
+    /// they already contain a `%` wildcard or `exact` is set.
+    fn to_sql(&self) -> Option<SafeQuery> {
+        let mut clauses = Vec::new();
+
+        if let Some(entity_type) = &self.entity_type {
+            clauses.push(SafeQuery {
+                sql: "entity_type = ?".to_string(),
+                params: vec![Box::new(entity_type.to_string())],
+            });
+        }
+
+        if let Some(path) = &self.path {
+            let clause = if self.exact {
+                SafeQuery {
+                    sql: "file_path = ?".to_string(),
+                    params: vec![Box::new(path.clone())],
+                }
+            } else {
+                let pattern = if path.contains('%') {
+                    path.clone()
+                } else {
+                    format!("%{}%", path)
+                };
+                SafeQuery {
+                    sql: "file_path LIKE ?".to_string(),
+                    params: vec![Box::new(pattern)],
+                }
+            };
+            clauses.push(clause);
+        }
+
+        SafeQuery::and_all(clauses)
+    }
+}
+
 /// Executes a parsed query against the SQLite database directly
 pub struct DbQueryExecutor<'a> {
     db: &'a Database,
+    filters: QueryFilters,
 }
 
 impl<'a> DbQueryExecutor<'a> {
     pub fn new(db: &'a Database) -> Self {
-        Self { db }
+        Self {
+            db,
+            filters: QueryFilters::default(),
+        }
     }
 
-    /// Execute a parsed query and return matching entities
-    pub fn execute(&self, query: QueryType) -> Result<Vec<Box<dyn Entity>>> {
-        match query {
-            QueryType::Select(select) => self.execute_select(&select),
-            QueryType::Traversal(traversal) => self.execute_traversal(&traversal),
-        }
+    /// Apply result filters to every query run by this executor
+    pub fn with_filters(mut self, filters: QueryFilters) -> Self {
+        self.filters = filters;
+        self
     }
 
-    /// Execute a select query using direct SQL
-    fn execute_select(&self, query: &SelectQuery) -> Result<Vec<Box<dyn Entity>>> {
-        let entity_type = &query.entity_type.entity_type;
+    /// Execute a parsed query and return all matching entities
+    pub fn execute(&self, query: QueryType) -> Result<Vec<Box<dyn Entity>>> {
+        let (entity_type, clause) = self.build_query(&query)?;
+        self.query_entities(&entity_type, clause, None)
+    }
 
-        let safe_query = match &query.conditions {
-            Some(condition) => Some(self.condition_to_sql(condition)?),
-            None => None,
+    /// Execute a parsed query and return one page of matching entities along with
+    /// the total number of matches
+    pub fn execute_page(&self, query: QueryType, page: Pagination) -> Result<QueryPage> {
+        let (entity_type, clause) = self.build_query(&query)?;
+        let total = match clause {
+            Some(sq) => self
+                .db
+                .count_entities_by_type(&entity_type, Some(&sq.sql), sq.params)?,
+            None => self.db.count_entities_by_type(&entity_type, None, vec![])?,
         };
 
-        // Unpack the safe query into condition and parameters
-        match safe_query {
+        // Parameters are consumed by each statement, so build the clause again
+        let (_, clause) = self.build_query(&query)?;
+        let entities = self.query_entities(&entity_type, clause, Some(page))?;
+
+        Ok(QueryPage {
+            entities,
+            total,
+            next_offset: page.next_offset(total),
+        })
+    }
+
+    fn query_entities(
+        &self,
+        entity_type: &EntityType,
+        clause: Option<SafeQuery>,
+        page: Option<Pagination>,
+    ) -> Result<Vec<Box<dyn Entity>>> {
+        match clause {
             Some(sq) => self
                 .db
-                .query_entities_by_type(entity_type, Some(&sq.sql), sq.params),
-            None => self.db.query_entities_by_type(entity_type, None, vec![]),
+                .query_entities_by_type(entity_type, Some(&sq.sql), sq.params, page),
+            None => self
+                .db
+                .query_entities_by_type(entity_type, None, vec![], page),
         }
     }
 
-    /// Execute a traversal query using the database's find_paths method
-    fn execute_traversal(&self, query: &TraversalQuery) -> Result<Vec<Box<dyn Entity>>> {
-        let source_entities =
-            self.db
-                .query_entities_by_type(&query.source_type.entity_type, None, vec![])?;
-
-        let mut result_entities = Vec::new();
-
-        for source_entity in source_entities {
-            let direction = match query.relationship.relationship_type {
-                RelationshipType::Calls => "outbound",
-                RelationshipType::Contains => "outbound",
-                RelationshipType::Imports => "outbound",
-                RelationshipType::Inherits => "outbound",
-                RelationshipType::Implements => "outbound",
-                RelationshipType::References => "outbound",
-                RelationshipType::Defines => "outbound",
-                RelationshipType::Uses => "outbound",
-                RelationshipType::Depends => "outbound",
-                RelationshipType::RepresentedBy => "outbound",
-                RelationshipType::RelatesTo => "both",
-                RelationshipType::DependsOn => "outbound",
-                RelationshipType::Other(_) => "both",
-            };
-
-            let paths = self.db.find_paths(
-                source_entity.id(),
-                None, // to_id is None because we're looking for any target of the correct type
-                Some(&query.target_type.entity_type),
-                Some(&query.relationship.relationship_type),
-                10, // reasonable max depth
-                direction,
-            )?;
-
-            if !paths.is_empty() {
-                if let Some(ref condition) = query.conditions {
-                    let has_valid_target = self.check_traversal_targets(&paths, condition)?;
-                    if has_valid_target {
-                        result_entities.push(source_entity);
-                    }
-                } else {
-                    result_entities.push(source_entity);
-                }
-            }
+    /// Determine the entity type a query returns and the where clause selecting it
+    fn build_query(&self, query: &QueryType) -> Result<(EntityType, Option<SafeQuery>)> {
+        match query {
+            QueryType::Select(select) => Ok((
+                select.entity_type.entity_type.clone(),
+                self.select_clause(select)?,
+            )),
+            QueryType::Traversal(traversal) => Ok((
+                traversal.source_type.entity_type.clone(),
+                Some(self.traversal_clause(traversal)?),
+            )),
         }
-
-        Ok(result_entities)
     }
 
-    /// Check if any traversal target meets the conditions
-    fn check_traversal_targets(
-        &self,
-        paths: &[(EntityId, usize)],
-        condition: &ConditionNode,
-    ) -> Result<bool> {
-        // Only consider entities that are targets (not the source) - depth > 0
-        let target_ids: Vec<&EntityId> = paths
-            .iter()
-            .filter(|(_, depth)| *depth > 0)
-            .map(|(id, _)| id)
-            .collect();
-
-        if target_ids.is_empty() {
-            return Ok(false);
+    /// Build the where clause for a select query
+    fn select_clause(&self, query: &SelectQuery) -> Result<Option<SafeQuery>> {
+        let mut clauses = Vec::new();
+        if let Some(condition) = &query.conditions {
+            clauses.push(self.condition_to_sql(condition)?);
         }
+        clauses.extend(self.filters.to_sql());
 
-        for target_id in target_ids {
-            if let Some(entity) = self.db.load_entity(target_id)? {
-                let entity_type = entity.entity_type();
+        Ok(SafeQuery::and_all(clauses))
+    }
 
-                let mut safe_query = self.condition_to_sql(condition)?;
+    /// Build the where clause for a traversal query: source entities that reach a
+    /// target of the requested type (meeting the conditions) through the relationship.
+    /// The traversal runs as a single recursive CTE so paging happens in SQL.
+    fn traversal_clause(&self, query: &TraversalQuery) -> Result<SafeQuery> {
+        let direction = match query.relationship.relationship_type {
+            RelationshipType::RelatesTo | RelationshipType::Other(_) => "both",
+            _ => "outbound",
+        };
 
-                let id_condition = "id = ?".to_string();
-                let combined_sql = format!("{} AND ({})", id_condition, safe_query.sql);
+        let mut params: Vec<Box<dyn ToSql>> =
+            vec![Box::new(query.source_type.entity_type.to_string())];
 
-                let mut combined_params: Vec<Box<dyn ToSql>> =
-                    vec![Box::new(target_id.as_str().to_string())];
-                combined_params.append(&mut safe_query.params);
-                let matching_entities = self.db.query_entities_by_type(
-                    &entity_type,
-                    Some(&combined_sql),
-                    combined_params,
-                )?;
+        let source_filter = match self.filters.to_sql() {
+            Some(sq) => {
+                params.extend(sq.params);
+                format!(" AND ({})", sq.sql)
+            }
+            None => String::new(),
+        };
 
-                if !matching_entities.is_empty() {
-                    return Ok(true);
-                }
+        params.push(Box::new(query.relationship.relationship_type.to_string()));
+        params.push(Box::new(query.target_type.entity_type.to_string()));
+
+        let target_condition = match &query.conditions {
+            Some(condition) => {
+                let sq = self.condition_to_sql(condition)?;
+                params.extend(sq.params);
+                format!(" AND ({})", sq.sql)
             }
-        }
+            None => String::new(),
+        };
+
+        let sql = format!(
+            "id IN (
+                WITH RECURSIVE traverse(origin, id, depth) AS (
+                    SELECT id, id, 0 FROM entities WHERE entity_type = ?{}
+                    UNION
+                    SELECT
+                        t.origin,
+                        CASE WHEN r.source_id = t.id THEN r.target_id ELSE r.source_id END,
+                        t.depth + 1
+                    FROM relationships r
+                    JOIN traverse t ON {}
+                    WHERE r.relationship_type = ? AND t.depth < {}
+                )
+                SELECT origin FROM traverse
+                WHERE depth > 0
+                  AND id IN (SELECT id FROM entities WHERE entity_type = ?{})
+            )",
+            source_filter,
+            direction_join_condition(direction),
+            MAX_TRAVERSAL_DEPTH,
+            target_condition
+        );
 
-        Ok(false)
+        Ok(SafeQuery { sql, params })
     }
 
     /// Convert a condition to SQL where clause with parameterized values
@@ -181,6 +296,12 @@ impl<'a> DbQueryExecutor<'a> {
                     params: inner_query.params,
                 })
             }
+            ConditionNode::HasAttribute(attr) if DECORATOR_ATTRIBUTES.contains(&attr.as_str()) => {
+                Ok(SafeQuery {
+                    sql: "json_array_length(data, '$.decorators') > 0".to_string(),
+                    params: vec![],
+                })
+            }
             ConditionNode::HasAttribute(attr) => {
                 let attr_name = self.validate_attribute_name(attr)?;
 
@@ -209,9 +330,6 @@ impl<'a> DbQueryExecutor<'a> {
                 operator,
                 value,
             } => {
-                // Validate attribute name against allowed columns
-                let attr_name = self.validate_attribute_name(attribute)?;
-
                 let sql_op = match operator {
                     Operator::Equal => "=",
                     Operator::NotEqual => "!=",
@@ -219,11 +337,23 @@ impl<'a> DbQueryExecutor<'a> {
                     Operator::LessThan => "<",
                     Operator::GreaterThanOrEqual => ">=",
                     Operator::LessThanOrEqual => "<=",
+                    // Exact matching disables wildcard patterns
+                    Operator::Like if self.filters.exact => "=",
                     Operator::Like => "LIKE",
                 };
 
                 // Create parameterized query with placeholder
-                let sql = format!("{} {} ?", attr_name, sql_op);
+                let sql = if DECORATOR_ATTRIBUTES.contains(&attribute.as_str()) {
+                    format!(
+                        "EXISTS (SELECT 1 FROM json_each(data, '$.decorators') \
+                         WHERE json_extract(value, '$.name') {} ?)",
+                        sql_op
+                    )
+                } else {
+                    // Validate attribute name against allowed columns
+                    let attr_name = self.validate_attribute_name(attribute)?;
+                    format!("{} {} ?", attr_name, sql_op)
+                };
 
                 // Convert value to SQL parameter
                 let param: Box<dyn ToSql> = match value {
@@ -256,7 +386,9 @@ impl<'a> DbQueryExecutor<'a> {
 mod tests {
     use super::*;
     use crate::db::get_database;
-    use crate::graph::entity::{BaseEntity, EntityId, EntityType, FunctionEntity, Visibility};
+    use crate::graph::entity::{
+        BaseEntity, Decorator, EntityId, EntityType, FunctionEntity, TypeEntity, Visibility,
+    };
     use crate::query::parser::parse_query;
     use tempfile::tempdir;
 
@@ -283,6 +415,11 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: vec![Decorator {
+                name: "app.route".to_string(),
+                arguments: vec!["\"/login\"".to_string()],
+            }],
+            containing_type: None,
         };
 
         let func2_id = EntityId::new("func2");
@@ -301,11 +438,32 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
+        };
+
+        let class = TypeEntity {
+            base: BaseEntity::new(
+                EntityId::new("type::com.example.User"),
+                "User".to_string(),
+                EntityType::Class,
+                Some("src/User.java".to_string()),
+            ),
+            fields: vec![],
+            methods: vec![],
+            supertypes: vec![],
+            visibility: Visibility::Public,
+            is_abstract: false,
+            decorators: vec![Decorator {
+                name: "Entity".to_string(),
+                arguments: Vec::new(),
+            }],
         };
 
         // Save entities to database
         db.save_entity(&func1).unwrap();
         db.save_entity(&func2).unwrap();
+        db.save_entity(&class).unwrap();
 
         // Create a relationship
         let rel_id = crate::graph::relationship::RelationshipId::new("calls_rel");
@@ -345,6 +503,27 @@ mod tests {
         assert_eq!(results[0].name(), "auth_login");
     }
 
+    #[test]
+    fn test_execute_select_by_decorator() {
+        let db = create_test_db();
+        let executor = DbQueryExecutor::new(&db);
+
+        let query = parse_query("select functions where decorator like 'app.%'").unwrap();
+        let results = executor.execute(query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name(), "auth_login");
+
+        let query = parse_query("select functions where has decorator").unwrap();
+        let results = executor.execute(query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name(), "auth_login");
+
+        let query = parse_query("select classes where annotation = 'Entity'").unwrap();
+        let results = executor.execute(query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name(), "User");
+    }
+
     #[test]
     fn test_condition_to_sql_parameterization() {
         use super::super::parser::{ConditionNode, Operator, Value};
@@ -381,4 +560,90 @@ mod tests {
         assert_eq!(result.sql, "(name LIKE ?) AND (file_path LIKE ?)");
         assert_eq!(result.params.len(), 2);
     }
+
+    #[test]
+    fn test_select_with_filters() {
+        let db = create_test_db();
+
+        let run = |query: &str, filters: QueryFilters| {
+            DbQueryExecutor::new(&db)
+                .with_filters(filters)
+                .execute(parse_query(query).unwrap())
+                .unwrap()
+        };
+
+        let by_type = QueryFilters::new(Some("function"), None, false).unwrap();
+        assert_eq!(run("select functions", by_type).len(), 2);
+
+        let other_type = QueryFilters::new(Some("methods"), None, false).unwrap();
+        assert!(run("select functions", other_type).is_empty());
+
+        let by_path = QueryFilters::new(None, Some("src/auth"), false).unwrap();
+        assert_eq!(run("select functions", by_path).len(), 2);
+
+        let other_path = QueryFilters::new(None, Some("src/billing"), false).unwrap();
+        assert!(run("select functions", other_path).is_empty());
+
+        // Exact mode turns patterns into literal comparisons
+        let pattern = "select functions where name like 'auth%'";
+        assert_eq!(run(pattern, QueryFilters::default()).len(), 1);
+        let exact = QueryFilters::new(None, Some("src/auth.rs"), true).unwrap();
+        assert!(run(pattern, exact).is_empty());
+
+        assert!(QueryFilters::new(Some("widgets"), None, false).is_err());
+    }
+
+    #[test]
+    fn test_traversal_with_filters() {
+        let db = create_test_db();
+        let query = "functions calls functions";
+
+        let results = DbQueryExecutor::new(&db)
+            .with_filters(QueryFilters::new(None, Some("auth"), false).unwrap())
+            .execute(parse_query(query).unwrap())
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name(), "auth_login");
+
+        let results = DbQueryExecutor::new(&db)
+            .with_filters(QueryFilters::new(None, Some("src/billing"), false).unwrap())
+            .execute(parse_query(query).unwrap())
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_execute_page() {
+        let db = create_test_db();
+        let executor = DbQueryExecutor::new(&db);
+
+        let page = executor
+            .execute_page(
+                parse_query("select functions").unwrap(),
+                Pagination::new(Some(1), 0),
+            )
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.next_offset, Some(1));
+        assert_eq!(page.entities.len(), 1);
+        assert_eq!(page.entities[0].name(), "auth_login");
+
+        let page = executor
+            .execute_page(
+                parse_query("select functions").unwrap(),
+                Pagination::new(Some(1), 1),
+            )
+            .unwrap();
+        assert_eq!(page.next_offset, None);
+        assert_eq!(page.entities[0].name(), "validate_token");
+
+        let page = executor
+            .execute_page(
+                parse_query("functions calls functions where name = 'validate_token'").unwrap(),
+                Pagination::new(Some(10), 0),
+            )
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.entities[0].name(), "auth_login");
+    }
 }
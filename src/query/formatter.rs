@@ -368,6 +368,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         // Create another function with different metadata
@@ -392,6 +394,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         // Add entities and relationship
@@ -432,6 +436,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         entities.push(Box::new(func) as Box<dyn Entity>);
@@ -458,6 +464,8 @@ mod tests {
             is_static: false,
             is_constructor: false,
             is_abstract: false,
+            decorators: Vec::new(),
+            containing_type: None,
         };
 
         entities.push(Box::new(func2) as Box<dyn Entity>);
@@ -3,30 +3,42 @@ mod formatter;
 mod nl_translator;
 mod parser;
 
-pub use db_executor::DbQueryExecutor;
+pub use db_executor::{DbQueryExecutor, QueryFilters, QueryPage};
 pub use formatter::{OutputFormat, ResultFormatter};
 pub use nl_translator::NaturalLanguageTranslator;
 pub use parser::parse_query;
 
+use crate::config::LlmSettings;
+use crate::db::Pagination;
 use crate::prompt::llm_integration::get_llm_config;
 use anyhow::Result;
 
-/// Process a query directly using the database without loading everything into memory
+/// Formatted query results along with paging information
+pub struct QueryOutput {
+    /// Results rendered in the requested output format
+    pub output: String,
+    /// Number of results in this page
+    pub returned: usize,
+    /// Number of results matching the query across all pages
+    pub total: usize,
+    /// Offset of the next page, if more results remain
+    pub next_offset: Option<usize>,
+}
+
+/// Process a query directly using the database without loading everything into memory.
+/// The limit and offset are applied in SQL, so every output format is paged.
 pub async fn process_query_with_db(
     db: &crate::db::Database,
     query_str: &str,
-    format_str: &str,
-    natural: bool,
-    llm_provider: Option<&str>,
-    llm_model: Option<&str>,
-) -> Result<String> {
+    options: &QueryOptions,
+) -> Result<QueryOutput> {
     // Set up the formatter with SQLite mode (modified formatter that handles boxed entities)
-    let format = format_str.parse().unwrap_or(OutputFormat::Text);
+    let format = options.format.parse().unwrap_or(OutputFormat::Text);
     let formatter = ResultFormatter::new_for_boxed_entities(format);
 
     // If natural language is enabled, translate query first
-    let query_to_execute = if natural {
-        let config = get_llm_config(llm_provider, llm_model);
+    let query_to_execute = if options.natural {
+        let config = get_llm_config(&options.llm);
         let translator = NaturalLanguageTranslator::new(config);
         let (translated, confidence) = translator.translate(query_str).await?;
 
@@ -43,21 +55,29 @@ pub async fn process_query_with_db(
     // Parse the query
     let parsed_query = parse_query(&query_to_execute)?;
 
-    // Execute the query directly with the database
-    let executor = DbQueryExecutor::new(db);
-    let results = executor.execute(parsed_query)?;
+    // Execute the query directly with the database, a limit of 0 meaning no limit
+    let executor = DbQueryExecutor::new(db).with_filters(options.filters.clone());
+    let limit = (options.limit > 0).then_some(options.limit);
+    let page = executor.execute_page(parsed_query, Pagination::new(limit, options.offset))?;
 
     // Format and return the results
-    formatter.format_boxed_entities(&results)
+    Ok(QueryOutput {
+        output: formatter.format_boxed_entities(&page.entities)?,
+        returned: page.entities.len(),
+        total: page.total,
+        next_offset: page.next_offset,
+    })
 }
 
 /// Options for refining query execution and output
 pub struct QueryOptions {
     pub format: String,
     pub natural: bool,
-    pub llm_provider: Option<String>,
-    pub llm_model: Option<String>,
+    pub filters: QueryFilters,
+    /// LLM used to translate natural language queries
+    pub llm: LlmSettings,
     pub limit: usize,
+    pub offset: usize,
 }
 
 impl Default for QueryOptions {
@@ -65,45 +85,19 @@ impl Default for QueryOptions {
         Self {
             format: "text".to_string(),
             natural: false,
-            llm_provider: None,
-            llm_model: None,
+            filters: QueryFilters::default(),
+            llm: LlmSettings::default(),
             limit: 100,
+            offset: 0,
         }
     }
 }
 
 /// Convenience function to execute a query with options
-pub async fn execute_query(query_str: &str, options: QueryOptions) -> Result<String> {
-    // Connect to the database
-    let db = crate::db::get_database("ummon.db")?;
+pub async fn execute_query(query_str: &str, options: QueryOptions) -> Result<QueryOutput> {
+    // Connect to the project's database
+    let db = crate::db::open_database(None)?;
 
     // Use the direct database query approach
-    let result = process_query_with_db(
-        &db,
-        query_str,
-        &options.format,
-        options.natural,
-        options.llm_provider.as_deref(),
-        options.llm_model.as_deref(),
-    )
-    .await?;
-
-    // Apply limit if needed
-    if options.limit > 0 && options.format == "text" {
-        // Only apply limit to text format to avoid breaking JSON/CSV structure
-        let lines: Vec<&str> = result.lines().collect();
-        let total_count = lines.len();
-
-        if total_count > options.limit {
-            let limited = lines.into_iter().take(options.limit).collect::<Vec<_>>();
-            return Ok(format!(
-                "{}\n(Limited to {} results, total: {})",
-                limited.join("\n"),
-                options.limit,
-                total_count
-            ));
-        }
-    }
-
-    Ok(result)
+    process_query_with_db(&db, query_str, &options).await
 }
@@ -21,7 +21,7 @@ traversal_query = { entity_type ~ relationship ~ entity_type ~ ("where" ~ condit
 entity_type = { 
     "functions" | "methods" | "classes" | "interfaces" | "traits" | 
     "structs" | "enums" | "modules" | "files" | "variables" | 
-    "fields" | "constants" | "domain_concepts" | "types"
+    "fields" | "constants" | "domain_concepts" | "types" | "externals"
 }
 
 // Relationship types
@@ -239,27 +239,37 @@ fn parse_traversal_query(pair: Pair<Rule>) -> Result<QueryType> {
 
 fn parse_entity_type(pair: Pair<Rule>) -> Result<EntityTypeSelector> {
     let entity_type_str = pair.as_str();
-    let entity_type = match entity_type_str {
-        "functions" => EntityType::Function,
-        "methods" => EntityType::Method,
-        "classes" => EntityType::Class,
-        "interfaces" => EntityType::Interface,
-        "traits" => EntityType::Trait,
-        "structs" => EntityType::Struct,
-        "enums" => EntityType::Enum,
-        "modules" => EntityType::Module,
-        "files" => EntityType::File,
-        "variables" => EntityType::Variable,
-        "fields" => EntityType::Field,
-        "constants" => EntityType::Constant,
-        "domain_concepts" => EntityType::DomainConcept,
-        "types" => EntityType::Type,
-        _ => return Err(anyhow!("Unknown entity type: '{}'", entity_type_str)),
-    };
+    let entity_type = entity_type_from_name(entity_type_str)
+        .ok_or_else(|| anyhow!("Unknown entity type: '{}'", entity_type_str))?;
 
     Ok(EntityTypeSelector { entity_type })
 }
 
+/// Map an entity type name such as "functions" or "Class" to its `EntityType`.
+/// Singular and plural forms are accepted, case-insensitively.
+pub fn entity_type_from_name(name: &str) -> Option<EntityType> {
+    let entity_type = match name.to_lowercase().as_str() {
+        "functions" | "function" => EntityType::Function,
+        "methods" | "method" => EntityType::Method,
+        "classes" | "class" => EntityType::Class,
+        "interfaces" | "interface" => EntityType::Interface,
+        "traits" | "trait" => EntityType::Trait,
+        "structs" | "struct" => EntityType::Struct,
+        "enums" | "enum" => EntityType::Enum,
+        "modules" | "module" => EntityType::Module,
+        "files" | "file" => EntityType::File,
+        "variables" | "variable" => EntityType::Variable,
+        "fields" | "field" => EntityType::Field,
+        "constants" | "constant" => EntityType::Constant,
+        "domain_concepts" | "domain_concept" | "domainconcept" => EntityType::DomainConcept,
+        "types" | "type" => EntityType::Type,
+        "externals" | "external" => EntityType::External,
+        _ => return None,
+    };
+
+    Some(entity_type)
+}
+
 fn parse_relationship(pair: Pair<Rule>) -> Result<RelationshipSelector> {
     let rel_str = pair.as_str();
     let relationship_type = match rel_str {
@@ -431,6 +441,16 @@ mod tests {
         } else {
             panic!("Expected Select query");
         }
+
+        assert!(matches!(
+            parse_query("select externals"),
+            Ok(QueryType::Select(SelectQuery {
+                entity_type: EntityTypeSelector {
+                    entity_type: EntityType::External
+                },
+                ..
+            }))
+        ));
     }
 
     #[test]